@@ -103,16 +103,20 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 		default_heap_pages: None,
 		offchain_worker: OffchainWorkerConfig { enabled: true, indexing_enabled: false },
 		force_authoring: false,
+		proof_size_limit: None,
 		disable_grandpa: false,
 		dev_key_seed: Some(Sr25519Keyring::Alice.to_seed()),
 		tracing_targets: None,
 		tracing_receiver: Default::default(),
 		max_runtime_instances: 8,
 		runtime_cache_size: 2,
+		precompile_runtime_upgrades: false,
+		wasmtime_artifact_cache: None,
 		announce_block: true,
 		base_path: Some(base_path),
 		informant_output_format: Default::default(),
 		wasm_runtime_overrides: None,
+		wasm_runtime_debug_substitutes: Vec::new(),
 	};
 
 	node_cli::service::new_full_base(config, false, |_, _| ())
@@ -97,4 +97,7 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Check that the host functions a runtime imports are provided by this node.
+	CheckHostFunctions(sc_cli::CheckHostFunctionsCmd),
 }
@@ -27,6 +27,7 @@ use kitchensink_runtime::{ExistentialDeposit, RuntimeApi};
 use node_executor::ExecutorDispatch;
 use node_primitives::Block;
 use sc_cli::{ChainSpec, Result, RuntimeVersion, SubstrateCli};
+use sc_executor::NativeExecutionDispatch;
 use sc_service::PartialComponents;
 use sp_keyring::Sr25519Keyring;
 
@@ -247,5 +248,7 @@ pub fn run() -> Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run::<Block>(&config))
 		},
+		Some(Subcommand::CheckHostFunctions(cmd)) =>
+			cmd.run::<<ExecutorDispatch as NativeExecutionDispatch>::ExtendHostFunctions>(),
 	}
 }
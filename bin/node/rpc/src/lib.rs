@@ -39,7 +39,8 @@ use sc_client_api::AuxStore;
 use sc_consensus_babe::{BabeConfiguration, Epoch};
 use sc_consensus_epochs::SharedEpochChanges;
 use sc_finality_grandpa::{
-	FinalityProofProvider, GrandpaJustificationStream, SharedAuthoritySet, SharedVoterState,
+	AncestryProofProvider, FinalityProofProvider, GrandpaJustificationStream, SharedAuthoritySet,
+	SharedVoterState,
 };
 use sc_rpc::SubscriptionTaskExecutor;
 pub use sc_rpc_api::DenyUnsafe;
@@ -101,6 +102,7 @@ pub fn create_full<C, P, SC, B>(
 where
 	C: ProvideRuntimeApi<Block>
 		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::ProofProvider<Block>
 		+ HeaderBackend<Block>
 		+ AuxStore
 		+ HeaderMetadata<Block, Error = BlockChainError>
@@ -124,7 +126,9 @@ where
 	use pallet_mmr_rpc::{Mmr, MmrApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use sc_consensus_babe_rpc::{Babe, BabeApiServer};
-	use sc_finality_grandpa_rpc::{Grandpa, GrandpaApiServer};
+	use sc_finality_grandpa_rpc::{
+		Grandpa, GrandpaAncestry, GrandpaAncestryApiServer, GrandpaApiServer,
+	};
 	use sc_rpc::dev::{Dev, DevApiServer};
 	use sc_rpc_spec_v2::chain_spec::{ChainSpec, ChainSpecApiServer};
 	use sc_sync_state_rpc::{SyncState, SyncStateApiServer};
@@ -175,6 +179,14 @@ where
 		)
 		.into_rpc(),
 	)?;
+	io.merge(
+		GrandpaAncestry::new(AncestryProofProvider::<_, _, Block, Hash>::new_for_service(
+			backend.clone(),
+			client.clone(),
+			sp_finality_grandpa_ancestry::mmr_root_storage_key(b"Mmr"),
+		))
+		.into_rpc(),
+	)?;
 
 	io.merge(
 		SyncState::new(chain_spec, client.clone(), shared_authority_set, shared_epoch_changes)?
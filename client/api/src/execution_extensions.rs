@@ -24,6 +24,7 @@
 
 use codec::Decode;
 use parking_lot::RwLock;
+use sc_executor::{CallContext, CallContextExt};
 use sc_transaction_pool_api::OffchainSubmitTransaction;
 use sp_core::{
 	offchain::{self, OffchainDbExt, OffchainWorkerExt, TransactionPoolExt},
@@ -187,6 +188,10 @@ impl<Block: traits::Block> ExecutionExtensions<Block> {
 			}
 		}
 
+		if let ExecutionContext::OffchainCall(_) = context {
+			extensions.register(CallContextExt(CallContext::Offchain));
+		}
+
 		if let ExecutionContext::OffchainCall(Some(ext)) = context {
 			extensions.register(OffchainWorkerExt::new(offchain::LimitedExternalities::new(
 				capabilities,
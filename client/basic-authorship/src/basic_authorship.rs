@@ -83,6 +83,10 @@ pub struct ProposerFactory<A, B, C, PR> {
 	telemetry: Option<TelemetryHandle>,
 	/// When estimating the block size, should the proof be included?
 	include_proof_in_block_size_estimation: bool,
+	/// The storage proof size limit in bytes.
+	///
+	/// Only enforced when proof recording is enabled.
+	proof_size_limit: Option<usize>,
 	/// phantom member to pin the `Backend`/`ProofRecording` type.
 	_phantom: PhantomData<(B, PR)>,
 }
@@ -108,6 +112,7 @@ impl<A, B, C> ProposerFactory<A, B, C, DisableProofRecording> {
 			telemetry,
 			client,
 			include_proof_in_block_size_estimation: false,
+			proof_size_limit: None,
 			_phantom: PhantomData,
 		}
 	}
@@ -136,6 +141,7 @@ impl<A, B, C> ProposerFactory<A, B, C, EnableProofRecording> {
 			soft_deadline_percent: DEFAULT_SOFT_DEADLINE_PERCENT,
 			telemetry,
 			include_proof_in_block_size_estimation: true,
+			proof_size_limit: None,
 			_phantom: PhantomData,
 		}
 	}
@@ -173,6 +179,21 @@ impl<A, B, C, PR> ProposerFactory<A, B, C, PR> {
 	pub fn set_soft_deadline(&mut self, percent: Percent) {
 		self.soft_deadline_percent = percent;
 	}
+
+	/// Set the storage proof size limit in bytes.
+	///
+	/// No more transactions are pushed to the block once the recorded storage proof reaches this
+	/// size. As the size of the proof generated by a transaction is only known after applying it,
+	/// the final proof may exceed the limit by the proof of the last pushed transaction.
+	///
+	/// Setting a limit enables storage proof recording during block production, even for a
+	/// factory created without proof recording. The proof is then only used for measuring and is
+	/// not returned with the proposal.
+	///
+	/// By default there is no limit.
+	pub fn set_proof_size_limit(&mut self, limit: usize) {
+		self.proof_size_limit = Some(limit);
+	}
 }
 
 impl<B, Block, C, A, PR> ProposerFactory<A, B, C, PR>
@@ -213,6 +234,7 @@ where
 			telemetry: self.telemetry.clone(),
 			_phantom: PhantomData,
 			include_proof_in_block_size_estimation: self.include_proof_in_block_size_estimation,
+			proof_size_limit: self.proof_size_limit,
 		};
 
 		proposer
@@ -254,6 +276,7 @@ pub struct Proposer<B, Block: BlockT, C, A: TransactionPool, PR> {
 	metrics: PrometheusMetrics,
 	default_block_size_limit: usize,
 	include_proof_in_block_size_estimation: bool,
+	proof_size_limit: Option<usize>,
 	soft_deadline_percent: Percent,
 	telemetry: Option<TelemetryHandle>,
 	_phantom: PhantomData<(B, PR)>,
@@ -343,8 +366,11 @@ where
 	) -> Result<Proposal<Block, backend::TransactionFor<B, Block>, PR::Proof>, sp_blockchain::Error>
 	{
 		let propose_with_start = time::Instant::now();
-		let mut block_builder =
-			self.client.new_block_at(&self.parent_id, inherent_digests, PR::ENABLED)?;
+		let mut block_builder = self.client.new_block_at(
+			&self.parent_id,
+			inherent_digests,
+			PR::ENABLED || self.proof_size_limit.is_some(),
+		)?;
 
 		let create_inherents_start = time::Instant::now();
 		let inherents = block_builder.create_inherents(inherent_data)?;
@@ -453,6 +479,15 @@ where
 				}
 			}
 
+			let proof_size = block_builder.estimate_proof_size().unwrap_or(0);
+			if self.proof_size_limit.map_or(false, |limit| proof_size >= limit) {
+				debug!(
+					"Reached proof size limit of `{:?}`, proceeding with proposing.",
+					self.proof_size_limit,
+				);
+				break EndProposingReason::HitProofSizeLimit
+			}
+
 			trace!("[{:?}] Pushing to the block.", pending_tx_hash);
 			match sc_block_builder::BlockBuilder::push(&mut block_builder, pending_tx_data) {
 				Ok(()) => {
@@ -935,6 +970,66 @@ mod tests {
 		assert_eq!(block.extrinsics().len(), 1);
 	}
 
+	#[test]
+	fn should_cease_building_block_when_proof_size_limit_is_reached() {
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let txpool = BasicPool::new_full(
+			Default::default(),
+			true.into(),
+			None,
+			spawner.clone(),
+			client.clone(),
+		);
+		let genesis_header = client
+			.header(&BlockId::Number(0u64))
+			.expect("header get error")
+			.expect("there should be header");
+
+		let extrinsics_num = 4;
+		let extrinsics = std::iter::once(
+			Transfer {
+				from: AccountKeyring::Alice.into(),
+				to: AccountKeyring::Bob.into(),
+				amount: 100,
+				nonce: 0,
+			}
+			.into_signed_tx(),
+		)
+		.chain((0..extrinsics_num - 1).map(|v| Extrinsic::IncludeData(vec![v as u8; 10])))
+		.collect::<Vec<_>>();
+		block_on(txpool.submit_at(&BlockId::number(0), SOURCE, extrinsics)).unwrap();
+		block_on(txpool.maintain(chain_event(genesis_header.clone())));
+
+		let mut proposer_factory = ProposerFactory::with_proof_recording(
+			spawner.clone(),
+			client.clone(),
+			txpool.clone(),
+			None,
+			None,
+		);
+
+		let deadline = time::Duration::from_secs(300);
+		let proposer = block_on(proposer_factory.init(&genesis_header)).unwrap();
+		let block =
+			block_on(proposer.propose(Default::default(), Default::default(), deadline, None))
+				.map(|r| r.block)
+				.unwrap();
+
+		// Without a proof size limit we should include all of them.
+		assert_eq!(block.extrinsics().len(), extrinsics_num);
+
+		proposer_factory.set_proof_size_limit(1);
+		let proposer = block_on(proposer_factory.init(&genesis_header)).unwrap();
+		let block =
+			block_on(proposer.propose(Default::default(), Default::default(), deadline, None))
+				.map(|r| r.block)
+				.unwrap();
+
+		// Only the `Transfer` reads from the storage, after pushing it the proof exceeds the limit.
+		assert_eq!(block.extrinsics().len(), 1);
+	}
+
 	#[test]
 	fn should_keep_adding_transactions_after_exhausts_resources_before_soft_deadline() {
 		// given
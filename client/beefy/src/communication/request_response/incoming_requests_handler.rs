@@ -26,11 +26,12 @@ use log::{debug, trace};
 use sc_client_api::BlockBackend;
 use sc_network::{config as netconfig, config::RequestResponseConfig, PeerId, ReputationChange};
 use sc_network_common::protocol::ProtocolName;
+use sp_blockchain::HeaderBackend;
 use sp_runtime::traits::Block;
 use std::{marker::PhantomData, sync::Arc};
 
 use crate::communication::request_response::{
-	on_demand_justifications_protocol_config, Error, JustificationRequest,
+	cost, on_demand_justifications_protocol_config, Error, JustificationRequest,
 };
 
 /// A request coming in, including a sender for sending responses.
@@ -125,7 +126,7 @@ pub struct BeefyJustifsRequestHandler<B, Client> {
 impl<B, Client> BeefyJustifsRequestHandler<B, Client>
 where
 	B: Block,
-	Client: BlockBackend<B> + Send + Sync,
+	Client: BlockBackend<B> + HeaderBackend<B> + Send + Sync,
 {
 	/// Create a new [`BeefyJustifsRequestHandler`].
 	pub fn new<Hash: AsRef<[u8]>>(
@@ -147,8 +148,7 @@ where
 
 	// Sends back justification response if justification found in client backend.
 	fn handle_request(&self, request: IncomingRequest<B>) -> Result<(), Error> {
-		// TODO (issue #12293): validate `request` and change peer reputation for invalid requests.
-
+		let mut reputation_changes = Vec::new();
 		let maybe_encoded_proof = if let Some(hash) =
 			self.client.block_hash(request.payload.begin).map_err(Error::Client)?
 		{
@@ -159,6 +159,11 @@ where
 				// No BEEFY justification present.
 				.ok_or(())
 		} else {
+			// Requests for blocks beyond our best block are fine, we might just not have imported
+			// them yet. Requests for blocks we should know of but don't are invalid.
+			if request.payload.begin <= self.client.info().best_number {
+				reputation_changes.push(cost::UNKNOWN_BLOCK);
+			}
 			Err(())
 		};
 
@@ -166,7 +171,7 @@ where
 			.pending_response
 			.send(netconfig::OutgoingResponse {
 				result: maybe_encoded_proof,
-				reputation_changes: Vec::new(),
+				reputation_changes,
 				sent_feedback: None,
 			})
 			.map_err(|_| Error::SendResponse)
@@ -176,7 +181,15 @@ where
 	pub async fn run(mut self) {
 		trace!(target: "beefy::sync", "🥩 Running BeefyJustifsRequestHandler");
 
-		while let Ok(request) = self.request_receiver.recv(|| vec![]).await {
+		loop {
+			let request = match self.request_receiver.recv(|| vec![cost::CANNOT_DECODE]).await {
+				Ok(request) => request,
+				Err(Error::RequestChannelExhausted) => break,
+				Err(e) => {
+					debug!(target: "beefy::sync", "🥩 Failed to decode justification request: {}", e);
+					continue
+				},
+			};
 			let peer = request.peer;
 			match self.handle_request(request) {
 				Ok(()) => {
@@ -186,7 +199,6 @@ where
 					)
 				},
 				Err(e) => {
-					// TODO (issue #12293): apply reputation changes here based on error type.
 					debug!(
 						target: "beefy::sync",
 						"🥩 Failed to handle BEEFY justification request from {:?}: {}", peer, e,
@@ -196,3 +208,68 @@ where
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::executor::block_on;
+	use sc_block_builder::BlockBuilderProvider;
+	use sp_consensus::BlockOrigin;
+	use sp_runtime::Justifications;
+	use substrate_test_runtime_client::{
+		runtime::Block as TestBlock, ClientBlockImportExt, ClientExt, TestClient,
+	};
+
+	// Builds a chain of `n` blocks, with a BEEFY justification for the last one.
+	fn handler_with_blocks(n: usize) -> BeefyJustifsRequestHandler<TestBlock, TestClient> {
+		let mut client = substrate_test_runtime_client::new();
+		for i in 1..=n {
+			let block = client.new_block(Default::default()).unwrap().build().unwrap().block;
+			if i == n {
+				let justifs = Justifications::from((BEEFY_ENGINE_ID, vec![1, 2, 3]));
+				block_on(client.import_justified(BlockOrigin::Own, block, justifs)).unwrap();
+			} else {
+				block_on(client.import(BlockOrigin::Own, block)).unwrap();
+			}
+		}
+		let genesis_hash = client.genesis_hash();
+		BeefyJustifsRequestHandler::new(genesis_hash, None, Arc::new(client)).0
+	}
+
+	fn request(
+		begin: u64,
+	) -> (IncomingRequest<TestBlock>, oneshot::Receiver<netconfig::OutgoingResponse>) {
+		let (tx, rx) = oneshot::channel();
+		let payload = JustificationRequest { begin };
+		(IncomingRequest::new(PeerId::random(), payload, tx), rx)
+	}
+
+	#[test]
+	fn responds_with_known_justification() {
+		let handler = handler_with_blocks(3);
+
+		let (req, mut rx) = request(3);
+		handler.handle_request(req).unwrap();
+		let response = rx.try_recv().unwrap().unwrap();
+		assert_eq!(response.result, Ok(vec![1, 2, 3]));
+		assert!(response.reputation_changes.is_empty());
+
+		// Known block without BEEFY justification.
+		let (req, mut rx) = request(2);
+		handler.handle_request(req).unwrap();
+		let response = rx.try_recv().unwrap().unwrap();
+		assert_eq!(response.result, Err(()));
+		assert!(response.reputation_changes.is_empty());
+	}
+
+	#[test]
+	fn does_not_penalize_requests_for_future_blocks() {
+		let handler = handler_with_blocks(3);
+
+		let (req, mut rx) = request(10);
+		handler.handle_request(req).unwrap();
+		let response = rx.try_recv().unwrap().unwrap();
+		assert_eq!(response.result, Err(()));
+		assert!(response.reputation_changes.is_empty());
+	}
+}
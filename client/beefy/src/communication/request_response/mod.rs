@@ -40,6 +40,16 @@ const JUSTIF_CHANNEL_SIZE: usize = 10;
 const MAX_RESPONSE_SIZE: u64 = 1024 * 1024;
 const JUSTIF_REQUEST_TIMEOUT: Duration = Duration::from_secs(3);
 
+/// Reputation changes applied to peers making invalid justification requests.
+pub(crate) mod cost {
+	use sc_network::ReputationChange as Rep;
+
+	/// Peer sent a request that could not be decoded.
+	pub(crate) const CANNOT_DECODE: Rep = Rep::new(-(1 << 12), "BEEFY: Bad request payload");
+	/// Peer requested a justification for a block we don't know of.
+	pub(crate) const UNKNOWN_BLOCK: Rep = Rep::new(-(1 << 10), "BEEFY: Request for unknown block");
+}
+
 /// Get the configuration for the BEEFY justifications Request/response protocol.
 ///
 /// Returns a receiver for messages received on this protocol and the requested
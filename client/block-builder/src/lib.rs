@@ -301,11 +301,18 @@ where
 		let size = self.estimated_header_size + self.extrinsics.encoded_size();
 
 		if include_proof {
-			size + self.api.proof_recorder().map(|pr| pr.estimate_encoded_size()).unwrap_or(0)
+			size + self.estimate_proof_size().unwrap_or(0)
 		} else {
 			size
 		}
 	}
+
+	/// Estimate the size of the storage proof recorded so far.
+	///
+	/// Returns `None` if proof recording is disabled.
+	pub fn estimate_proof_size(&self) -> Option<usize> {
+		self.api.proof_recorder().map(|pr| pr.estimate_encoded_size())
+	}
 }
 
 #[cfg(test)]
@@ -185,6 +185,15 @@ pub enum CryptoScheme {
 	Sr25519,
 	/// Use
 	Ecdsa,
+	/// Use BLS12-377 (experimental).
+	#[cfg(feature = "bls-experimental")]
+	Bls377,
+	/// Use BLS12-381 (experimental).
+	#[cfg(feature = "bls-experimental")]
+	Bls381,
+	/// Use Bandersnatch (experimental).
+	#[cfg(feature = "bandersnatch-experimental")]
+	Bandersnatch,
 }
 
 /// The type of the output format.
@@ -0,0 +1,274 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Implementation of the `check-host-functions` subcommand
+
+use crate::error::{self, Error};
+use clap::Parser;
+use sc_executor_common::runtime_blob::RuntimeBlob;
+use sp_wasm_interface::{HostFunctions, Signature};
+use std::{collections::BTreeMap, fmt, fs, path::PathBuf};
+
+/// The `check-host-functions` command
+#[derive(Debug, Clone, Parser)]
+#[command(
+	name = "check-host-functions",
+	about = "Check that the host functions a runtime imports are provided by this node"
+)]
+pub struct CheckHostFunctionsCmd {
+	/// Path to the runtime wasm blob, compressed or not.
+	#[arg(value_name = "WASM_FILE")]
+	wasm_file: PathBuf,
+
+	/// Only print problems, not the full list of imported host functions.
+	#[arg(long)]
+	quiet: bool,
+}
+
+impl CheckHostFunctionsCmd {
+	/// Run the command.
+	///
+	/// The node is assumed to provide [`sp_io::SubstrateHostFunctions`] and the given
+	/// `ExtendHostFunctions`, just like a `NativeElseWasmExecutor` does.
+	///
+	/// Returns `Err` if the runtime imports a host function the node doesn't provide or imports
+	/// it with a different signature.
+	pub fn run<ExtendHostFunctions: HostFunctions>(&self) -> error::Result<()> {
+		let code = fs::read(&self.wasm_file)?;
+		let blob = RuntimeBlob::uncompress_if_needed(&code)
+			.map_err(|e| Error::Input(format!("Invalid runtime wasm: {}", e)))?;
+		let imported = blob
+			.imported_host_functions()
+			.map_err(|e| Error::Input(format!("Invalid runtime wasm: {}", e)))?;
+
+		let report = HostFunctionsReport::new(
+			imported,
+			<(sp_io::SubstrateHostFunctions, ExtendHostFunctions)>::host_functions()
+				.into_iter()
+				.map(|f| (f.name().to_owned(), f.signature())),
+		);
+
+		if !self.quiet {
+			println!("Host functions imported by the runtime:");
+			for (name, signature) in &report.imported {
+				println!("  {}: {}", VersionedName(name), DisplaySignature(signature));
+			}
+			println!();
+			println!("Host functions provided by the node but not imported by the runtime:");
+			for name in &report.unused {
+				println!("  {}", VersionedName(name));
+			}
+			println!();
+		}
+
+		for (name, other_versions) in &report.missing {
+			print!("Missing: {}", VersionedName(name));
+			if !other_versions.is_empty() {
+				let other_versions =
+					other_versions.iter().map(u32::to_string).collect::<Vec<_>>().join(", ");
+				print!(" (the node provides version {})", other_versions);
+			}
+			println!();
+		}
+		for (name, imported, provided) in &report.mismatched {
+			println!(
+				"Signature mismatch: {}: the runtime imports {}, the node provides {}",
+				VersionedName(name),
+				DisplaySignature(imported),
+				DisplaySignature(provided),
+			);
+		}
+
+		if report.missing.is_empty() && report.mismatched.is_empty() {
+			println!(
+				"All {} host functions imported by the runtime are provided.",
+				report.imported.len()
+			);
+			Ok(())
+		} else {
+			Err(Error::Input(format!(
+				"The runtime imports {} host functions the node doesn't provide and {} with a \
+				 mismatching signature",
+				report.missing.len(),
+				report.mismatched.len(),
+			)))
+		}
+	}
+}
+
+/// The result of comparing the host functions imported by a runtime with those provided by a
+/// node.
+#[derive(Debug, Default, PartialEq)]
+struct HostFunctionsReport {
+	/// All host functions imported by the runtime.
+	imported: BTreeMap<String, Signature>,
+	/// Host functions imported by the runtime, but not provided by the node, together with the
+	/// other versions of the same host function the node provides.
+	missing: Vec<(String, Vec<u32>)>,
+	/// Host functions provided by the node with a different signature than imported by the
+	/// runtime, as `(name, imported, provided)`.
+	mismatched: Vec<(String, Signature, Signature)>,
+	/// Host functions provided by the node, but not imported by the runtime.
+	unused: Vec<String>,
+}
+
+impl HostFunctionsReport {
+	fn new(
+		imported: impl IntoIterator<Item = (String, Signature)>,
+		provided: impl IntoIterator<Item = (String, Signature)>,
+	) -> Self {
+		let imported = imported.into_iter().collect::<BTreeMap<_, _>>();
+		// Later host functions override earlier ones with the same name.
+		let provided = provided.into_iter().collect::<BTreeMap<_, _>>();
+
+		let mut report = HostFunctionsReport::default();
+		for (name, signature) in &imported {
+			match provided.get(name) {
+				Some(provided) if provided == signature => (),
+				Some(provided) =>
+					report.mismatched.push((name.clone(), signature.clone(), provided.clone())),
+				None => {
+					let (base_name, _) = split_version(name);
+					let other_versions = provided
+						.keys()
+						.filter_map(|name| match split_version(name) {
+							(other_base_name, Some(version)) if other_base_name == base_name =>
+								Some(version),
+							_ => None,
+						})
+						.collect();
+					report.missing.push((name.clone(), other_versions));
+				},
+			}
+		}
+		report.unused = provided.into_keys().filter(|name| !imported.contains_key(name)).collect();
+		report.imported = imported;
+
+		report
+	}
+}
+
+/// Splits the name of a host function into its base name and version, as generated by
+/// `#[runtime_interface]`, e.g. `ext_hashing_blake2_256_version_1`.
+fn split_version(name: &str) -> (&str, Option<u32>) {
+	name.rsplit_once("_version_")
+		.and_then(|(base_name, version)| Some((base_name, Some(version.parse().ok()?))))
+		.unwrap_or((name, None))
+}
+
+struct VersionedName<'a>(&'a str);
+
+impl fmt::Display for VersionedName<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match split_version(self.0) {
+			(base_name, Some(version)) => write!(f, "{} (version {})", base_name, version),
+			(name, None) => write!(f, "{}", name),
+		}
+	}
+}
+
+struct DisplaySignature<'a>(&'a Signature);
+
+impl fmt::Display for DisplaySignature<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let args = self.0.args.iter().map(|arg| format!("{:?}", arg)).collect::<Vec<_>>();
+		write!(f, "({})", args.join(", "))?;
+		match self.0.return_value {
+			Some(return_value) => write!(f, " -> {:?}", return_value),
+			None => Ok(()),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_wasm_interface::ValueType;
+
+	fn signature(args: &[ValueType], return_value: Option<ValueType>) -> Signature {
+		Signature::new(args.to_vec(), return_value)
+	}
+
+	#[test]
+	fn split_version_works() {
+		assert_eq!(
+			split_version("ext_hashing_blake2_256_version_1"),
+			("ext_hashing_blake2_256", Some(1))
+		);
+		assert_eq!(split_version("ext_foo_version_bar"), ("ext_foo_version_bar", None));
+		assert_eq!(split_version("ext_foo"), ("ext_foo", None));
+	}
+
+	#[test]
+	fn report_lists_missing_mismatched_and_unused_host_functions() {
+		let imported = vec![
+			("ext_a_version_1".to_owned(), signature(&[ValueType::I32], None)),
+			("ext_b_version_2".to_owned(), signature(&[], Some(ValueType::I64))),
+			("ext_c_version_1".to_owned(), signature(&[ValueType::I64], None)),
+			("ext_d_version_1".to_owned(), signature(&[], None)),
+		];
+		let provided = vec![
+			("ext_a_version_1".to_owned(), signature(&[ValueType::I32], None)),
+			("ext_b_version_1".to_owned(), signature(&[], Some(ValueType::I64))),
+			("ext_c_version_1".to_owned(), signature(&[ValueType::I32], None)),
+			("ext_e_version_1".to_owned(), signature(&[], None)),
+		];
+
+		let report = HostFunctionsReport::new(imported.clone(), provided);
+
+		assert_eq!(report.imported, imported.into_iter().collect());
+		assert_eq!(
+			report.missing,
+			vec![("ext_b_version_2".to_owned(), vec![1]), ("ext_d_version_1".to_owned(), vec![])]
+		);
+		assert_eq!(
+			report.mismatched,
+			vec![(
+				"ext_c_version_1".to_owned(),
+				signature(&[ValueType::I64], None),
+				signature(&[ValueType::I32], None)
+			)]
+		);
+		assert_eq!(report.unused, vec!["ext_b_version_1".to_owned(), "ext_e_version_1".to_owned()]);
+	}
+
+	#[test]
+	fn check_host_functions_of_runtime() {
+		let dir = tempfile::tempdir().unwrap();
+		let wasm_file = dir.path().join("runtime.wasm");
+
+		let wat = |import: &str| {
+			format!(
+				r#"(module (import "env" "ext_hashing_blake2_256_version_1" {}) (memory 1))"#,
+				import
+			)
+		};
+
+		fs::write(&wasm_file, wat::parse_str(wat("(func (param i64) (result i32))")).unwrap())
+			.unwrap();
+		let cmd = CheckHostFunctionsCmd::parse_from(&[
+			"check-host-functions",
+			wasm_file.to_str().unwrap(),
+		]);
+		assert!(cmd.run::<()>().is_ok());
+
+		fs::write(&wasm_file, wat::parse_str(wat("(func (param i32) (result i32))")).unwrap())
+			.unwrap();
+		assert!(cmd.run::<()>().is_err());
+	}
+}
@@ -80,4 +80,20 @@ mod tests {
 		let generate = GenerateCmd::parse_from(&["generate", "--password", "12345"]);
 		assert!(generate.run().is_ok())
 	}
+
+	#[test]
+	#[cfg(feature = "bls-experimental")]
+	fn generate_bls() {
+		for scheme in ["bls377", "bls381"] {
+			let generate = GenerateCmd::parse_from(&["generate", "--scheme", scheme]);
+			assert!(generate.run().is_ok())
+		}
+	}
+
+	#[test]
+	#[cfg(feature = "bandersnatch-experimental")]
+	fn generate_bandersnatch() {
+		let generate = GenerateCmd::parse_from(&["generate", "--scheme", "bandersnatch"]);
+		assert!(generate.run().is_ok())
+	}
 }
@@ -180,6 +180,33 @@ mod tests {
 		assert!(inspect.run().is_ok());
 	}
 
+	#[test]
+	#[cfg(feature = "bls-experimental")]
+	fn inspect_bls_key() {
+		let seed = "0xad1fb77243b536b90cfe5f0d351ab1b1ac40e3890b41dc64f766ee56340cfca5";
+		let public = format!(
+			"0x{}",
+			array_bytes::bytes2hex(
+				"",
+				sp_core::bls::bls381::Pair::from_string(seed, None).unwrap().public().as_slice()
+			)
+		);
+
+		let inspect = InspectKeyCmd::parse_from(&[
+			"inspect-key",
+			"--scheme",
+			"bls381",
+			"--expect-public",
+			&public,
+			seed,
+		]);
+		assert!(inspect.run().is_ok());
+
+		let inspect =
+			InspectKeyCmd::parse_from(&["inspect-key", "--scheme", "bls381", "--public", &public]);
+		assert!(inspect.run().is_ok());
+	}
+
 	#[test]
 	fn inspect_with_expected_public_key() {
 		let check_cmd = |seed, expected_public, success| {
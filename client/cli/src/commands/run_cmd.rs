@@ -18,6 +18,7 @@
 
 use crate::{
 	arg_enums::RpcMethods,
+	config::DEFAULT_WASMTIME_ARTIFACTS_PATH,
 	error::{Error, Result},
 	params::{
 		ImportParams, KeystoreParams, NetworkParams, OffchainWorkerParams, SharedParams,
@@ -32,7 +33,10 @@ use sc_service::{
 	ChainSpec, Role,
 };
 use sc_telemetry::TelemetryEndpoints;
-use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::{
+	net::{IpAddr, Ipv4Addr, SocketAddr},
+	path::PathBuf,
+};
 
 /// The `run` command used to run a node.
 #[derive(Debug, Clone, Parser)]
@@ -239,6 +243,13 @@ pub struct RunCmd {
 	#[arg(long)]
 	pub force_authoring: bool,
 
+	/// Stop adding transactions to authored blocks once their storage proof reaches this size in
+	/// bytes.
+	///
+	/// By default the storage proof size of authored blocks is not limited.
+	#[arg(long, value_name = "BYTES")]
+	pub proof_size_limit: Option<usize>,
+
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub keystore_params: KeystoreParams,
@@ -253,6 +264,18 @@ pub struct RunCmd {
 	#[arg(long, default_value_t = 2)]
 	pub runtime_cache_size: u8,
 
+	/// Don't compile upgraded and pending runtimes in the background after block import.
+	///
+	/// The first block executed with a new runtime then has to wait for it to be compiled.
+	#[arg(long)]
+	pub no_runtime_precompilation: bool,
+
+	/// Persist the artifacts of the runtimes compiled with wasmtime in the chain's data directory.
+	///
+	/// The runtimes are then not recompiled after a restart of the node.
+	#[arg(long)]
+	pub wasmtime_artifact_cache: bool,
+
 	/// Run a temporary node.
 	///
 	/// A temporary directory will be created to store the configuration and will be deleted
@@ -369,6 +392,10 @@ impl CliConfiguration for RunCmd {
 		Ok(self.shared_params.dev || self.force_authoring)
 	}
 
+	fn proof_size_limit(&self) -> Result<Option<usize>> {
+		Ok(self.proof_size_limit)
+	}
+
 	fn prometheus_config(
 		&self,
 		default_listen_port: u16,
@@ -481,6 +508,16 @@ impl CliConfiguration for RunCmd {
 		Ok(self.runtime_cache_size)
 	}
 
+	fn precompile_runtime_upgrades(&self) -> Result<bool> {
+		Ok(!self.no_runtime_precompilation)
+	}
+
+	fn wasmtime_artifact_cache(&self, config_dir: &PathBuf) -> Result<Option<PathBuf>> {
+		Ok(self
+			.wasmtime_artifact_cache
+			.then(|| config_dir.join(DEFAULT_WASMTIME_ARTIFACTS_PATH)))
+	}
+
 	fn base_path(&self) -> Result<Option<BasePath>> {
 		Ok(if self.tmp {
 			Some(BasePath::new_temp_dir()?)
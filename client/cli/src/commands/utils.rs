@@ -30,7 +30,7 @@ use sp_core::{
 	hexdisplay::HexDisplay,
 	Pair,
 };
-use sp_runtime::{traits::IdentifyAccount, MultiSigner};
+use sp_runtime::{traits::IdentifyAccount, AccountId32, MultiSigner};
 use std::{io::Read, path::PathBuf};
 
 /// Public key type for Runtime
@@ -38,6 +38,48 @@ pub type PublicFor<P> = <P as sp_core::Pair>::Public;
 /// Seed type for Runtime
 pub type SeedFor<P> = <P as sp_core::Pair>::Seed;
 
+/// A public key of one of the crypto schemes supported by the key subcommands.
+pub trait SchemePublic: sp_core::Public {
+	/// Returns the account id of the public key or `None` if the crypto scheme can't be used for
+	/// accounts.
+	fn account_id(&self) -> Option<AccountId32>;
+}
+
+macro_rules! impl_scheme_public_for_account {
+	( $( $public:ty ),* ) => {
+		$(
+			impl SchemePublic for $public {
+				fn account_id(&self) -> Option<AccountId32> {
+					Some(MultiSigner::from(self.clone()).into_account())
+				}
+			}
+		)*
+	};
+}
+
+impl_scheme_public_for_account!(
+	sp_core::ed25519::Public,
+	sp_core::sr25519::Public,
+	sp_core::ecdsa::Public
+);
+
+#[cfg(feature = "bls-experimental")]
+impl<T> SchemePublic for sp_core::bls::Public<T>
+where
+	sp_core::bls::Public<T>: sp_core::Public,
+{
+	fn account_id(&self) -> Option<AccountId32> {
+		None
+	}
+}
+
+#[cfg(feature = "bandersnatch-experimental")]
+impl SchemePublic for sp_core::bandersnatch::Public {
+	fn account_id(&self) -> Option<AccountId32> {
+		None
+	}
+}
+
 /// helper method to fetch uri from `Option<String>` either as a file or read from stdin
 pub fn read_uri(uri: Option<&String>) -> error::Result<String> {
 	let uri = if let Some(uri) = uri {
@@ -70,7 +112,7 @@ pub fn print_from_uri<Pair>(
 	output: OutputType,
 ) where
 	Pair: sp_core::Pair,
-	Pair::Public: Into<MultiSigner>,
+	Pair::Public: SchemePublic,
 {
 	let password = password.as_ref().map(|s| s.expose_secret().as_str());
 	let network_id = String::from(unwrap_or_default_ss58_version(network_override));
@@ -86,8 +128,8 @@ pub fn print_from_uri<Pair>(
 					"secretSeed": format_seed::<Pair>(seed),
 					"publicKey": format_public_key::<Pair>(public_key.clone()),
 					"ss58PublicKey": public_key.to_ss58check_with_version(network_override),
-					"accountId": format_account_id::<Pair>(public_key),
-					"ss58Address": pair.public().into().into_account().to_ss58check_with_version(network_override),
+					"accountId": format_account_id::<Pair>(public_key.clone()),
+					"ss58Address": format_ss58_address(&public_key, network_override),
 				});
 				println!(
 					"{}",
@@ -109,7 +151,7 @@ pub fn print_from_uri<Pair>(
 					format_public_key::<Pair>(public_key.clone()),
 					format_account_id::<Pair>(public_key.clone()),
 					public_key.to_ss58check_with_version(network_override),
-					pair.public().into().into_account().to_ss58check_with_version(network_override),
+					format_ss58_address(&public_key, network_override),
 				);
 			},
 		}
@@ -125,8 +167,8 @@ pub fn print_from_uri<Pair>(
 					"secretSeed": if let Some(seed) = seed { format_seed::<Pair>(seed) } else { "n/a".into() },
 					"publicKey": format_public_key::<Pair>(public_key.clone()),
 					"ss58PublicKey": public_key.to_ss58check_with_version(network_override),
-					"accountId": format_account_id::<Pair>(public_key),
-					"ss58Address": pair.public().into().into_account().to_ss58check_with_version(network_override),
+					"accountId": format_account_id::<Pair>(public_key.clone()),
+					"ss58Address": format_ss58_address(&public_key, network_override),
 				});
 				println!(
 					"{}",
@@ -148,7 +190,7 @@ pub fn print_from_uri<Pair>(
 					format_public_key::<Pair>(public_key.clone()),
 					format_account_id::<Pair>(public_key.clone()),
 					public_key.to_ss58check_with_version(network_override),
-					pair.public().into().into_account().to_ss58check_with_version(network_override),
+					format_ss58_address(&public_key, network_override),
 				);
 			},
 		}
@@ -201,7 +243,7 @@ pub fn print_from_public<Pair>(
 ) -> Result<(), Error>
 where
 	Pair: sp_core::Pair,
-	Pair::Public: Into<MultiSigner>,
+	Pair::Public: SchemePublic,
 {
 	let public = array_bytes::hex2bytes(public_str)?;
 
@@ -268,9 +310,23 @@ fn format_public_key<P: sp_core::Pair>(public_key: PublicFor<P>) -> String {
 /// formats public key as accountId as hex
 fn format_account_id<P: sp_core::Pair>(public_key: PublicFor<P>) -> String
 where
-	PublicFor<P>: Into<MultiSigner>,
+	PublicFor<P>: SchemePublic,
 {
-	format!("0x{}", HexDisplay::from(&public_key.into().into_account().as_ref()))
+	match public_key.account_id() {
+		Some(account_id) => format!("0x{}", HexDisplay::from(&account_id.as_ref())),
+		None => "n/a".into(),
+	}
+}
+
+/// formats the account id of public key as ss58 address
+fn format_ss58_address<Public: SchemePublic>(
+	public_key: &Public,
+	network_override: Ss58AddressFormat,
+) -> String {
+	match public_key.account_id() {
+		Some(account_id) => account_id.to_ss58check_with_version(network_override),
+		None => "n/a".into(),
+	}
 }
 
 /// checks if message is Some, otherwise reads message from stdin and optionally decodes hex
@@ -313,6 +369,65 @@ macro_rules! with_crypto_scheme {
 			$crate::CryptoScheme::Ed25519 => {
 				$method::<sp_core::ed25519::Pair, $($generics),*>($($params),*)
 			}
+			#[allow(unreachable_patterns)]
+			scheme => $crate::__with_bls_crypto_scheme!(scheme, $method<$($generics),*>($($params),*)),
 		}
 	};
 }
+
+// The experimental crypto schemes are dispatched by the following macros, since a `#[cfg]` in
+// `with_crypto_scheme!` would be evaluated against the features of the calling crate.
+
+#[cfg(feature = "bls-experimental")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __with_bls_crypto_scheme {
+	($scheme:expr, $method:ident<$($generics:ty),*>( $( $params:expr ),* )) => {
+		match $scheme {
+			$crate::CryptoScheme::Bls377 => {
+				$method::<sp_core::bls::bls377::Pair, $($generics),*>($($params),*)
+			}
+			$crate::CryptoScheme::Bls381 => {
+				$method::<sp_core::bls::bls381::Pair, $($generics),*>($($params),*)
+			}
+			#[allow(unreachable_patterns)]
+			scheme => {
+				$crate::__with_bandersnatch_crypto_scheme!(scheme, $method<$($generics),*>($($params),*))
+			}
+		}
+	};
+}
+
+#[cfg(not(feature = "bls-experimental"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __with_bls_crypto_scheme {
+	($scheme:expr, $method:ident<$($generics:ty),*>( $( $params:expr ),* )) => {
+		$crate::__with_bandersnatch_crypto_scheme!($scheme, $method<$($generics),*>($($params),*))
+	};
+}
+
+#[cfg(feature = "bandersnatch-experimental")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __with_bandersnatch_crypto_scheme {
+	($scheme:expr, $method:ident<$($generics:ty),*>( $( $params:expr ),* )) => {
+		match $scheme {
+			$crate::CryptoScheme::Bandersnatch => {
+				$method::<sp_core::bandersnatch::Pair, $($generics),*>($($params),*)
+			}
+			#[allow(unreachable_patterns)]
+			_ => unreachable!("All crypto schemes are handled by `with_crypto_scheme!`; qed"),
+		}
+	};
+}
+
+#[cfg(not(feature = "bandersnatch-experimental"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __with_bandersnatch_crypto_scheme {
+	($scheme:expr, $method:ident<$($generics:ty),*>( $( $params:expr ),* )) => {{
+		let _ = $scheme;
+		unreachable!("All crypto schemes are handled by `with_crypto_scheme!`; qed")
+	}};
+}
@@ -24,8 +24,7 @@ use crate::{
 use clap::Parser;
 use rand::{rngs::OsRng, RngCore};
 use sp_core::crypto::{unwrap_or_default_ss58_version, Ss58AddressFormat, Ss58Codec};
-use sp_runtime::traits::IdentifyAccount;
-use utils::print_from_uri;
+use utils::{print_from_uri, SchemePublic};
 
 /// The `vanity` command
 #[derive(Debug, Clone, Parser)]
@@ -79,8 +78,7 @@ fn generate_key<Pair>(
 ) -> Result<String, &'static str>
 where
 	Pair: sp_core::Pair,
-	Pair::Public: IdentifyAccount,
-	<Pair::Public as IdentifyAccount>::AccountId: Ss58Codec,
+	Pair::Public: SchemePublic,
 {
 	println!("Generating key containing pattern '{}'", desired);
 
@@ -97,7 +95,11 @@ where
 		}
 
 		let p = Pair::from_seed(&seed);
-		let ss58 = p.public().into_account().to_ss58check_with_version(network_override);
+		// Schemes without accounts match the pattern against the public key.
+		let ss58 = match p.public().account_id() {
+			Some(account_id) => account_id.to_ss58check_with_version(network_override),
+			None => p.public().to_ss58check_with_version(network_override),
+		};
 		let score = calculate_score(desired, &ss58);
 		if score > best || desired.len() < 2 {
 			best = score;
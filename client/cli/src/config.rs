@@ -30,6 +30,7 @@ use sc_service::{
 		BasePath, Configuration, DatabaseSource, KeystoreConfig, NetworkConfiguration,
 		NodeKeyConfig, OffchainWorkerConfig, PrometheusConfig, PruningMode, Role, RpcMethods,
 		TelemetryEndpoints, TransactionPoolOptions, WasmExecutionMethod,
+		WasmRuntimeDebugSubstitute,
 	},
 	BlocksPruning, ChainSpec, TracingReceiver,
 };
@@ -42,6 +43,9 @@ pub(crate) const NODE_NAME_MAX_LENGTH: usize = 64;
 /// Default sub directory to store network config.
 pub(crate) const DEFAULT_NETWORK_CONFIG_PATH: &str = "network";
 
+/// Default sub directory to store the artifacts of the runtimes compiled with wasmtime.
+pub(crate) const DEFAULT_WASMTIME_ARTIFACTS_PATH: &str = "wasmtime-artifacts";
+
 /// The recommended open file descriptor limit to be configured for the process.
 const RECOMMENDED_OPEN_FILE_DESCRIPTOR_LIMIT: u64 = 10_000;
 
@@ -287,6 +291,15 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		self.import_params().map(|x| x.wasm_runtime_overrides()).unwrap_or_default()
 	}
 
+	/// Get the local WASM runtimes that replace the on-chain runtime for ranges of blocks.
+	///
+	/// By default this is retrieved from `ImportParams` if it is available. Otherwise it's empty.
+	fn wasm_runtime_debug_substitutes(&self) -> Vec<WasmRuntimeDebugSubstitute> {
+		self.import_params()
+			.map(|x| x.wasm_runtime_debug_substitutes())
+			.unwrap_or_default()
+	}
+
 	/// Get the execution strategies.
 	///
 	/// By default this is retrieved from `ImportParams` if it is available. Otherwise its
@@ -414,6 +427,13 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(Default::default())
 	}
 
+	/// Get the storage proof size limit in bytes for authored blocks
+	///
+	/// By default this is `None`.
+	fn proof_size_limit(&self) -> Result<Option<usize>> {
+		Ok(Default::default())
+	}
+
 	/// Returns `Ok(true)` if grandpa should be disabled
 	///
 	/// By default this is `false`.
@@ -468,6 +488,21 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(2)
 	}
 
+	/// Compile upgraded and pending runtimes in the background after block import.
+	///
+	/// By default this is `true`.
+	fn precompile_runtime_upgrades(&self) -> Result<bool> {
+		Ok(true)
+	}
+
+	/// Get the directory in which the artifacts of the runtimes compiled with wasmtime are
+	/// persisted.
+	///
+	/// By default this is `None`, i.e. the artifacts are not persisted.
+	fn wasmtime_artifact_cache(&self, _config_dir: &PathBuf) -> Result<Option<PathBuf>> {
+		Ok(None)
+	}
+
 	/// Activate or not the automatic announcing of blocks after import
 	///
 	/// By default this is `false`.
@@ -532,6 +567,7 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			blocks_pruning: self.blocks_pruning()?,
 			wasm_method: self.wasm_method()?,
 			wasm_runtime_overrides: self.wasm_runtime_overrides(),
+			wasm_runtime_debug_substitutes: self.wasm_runtime_debug_substitutes(),
 			execution_strategies: self.execution_strategies(is_dev, is_validator)?,
 			rpc_http: self.rpc_http(DCV::rpc_http_listen_port())?,
 			rpc_ws: self.rpc_ws(DCV::rpc_ws_listen_port())?,
@@ -551,6 +587,7 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			default_heap_pages: self.default_heap_pages()?,
 			offchain_worker: self.offchain_worker(&role)?,
 			force_authoring: self.force_authoring()?,
+			proof_size_limit: self.proof_size_limit()?,
 			disable_grandpa: self.disable_grandpa()?,
 			dev_key_seed: self.dev_key_seed(is_dev)?,
 			tracing_targets: self.tracing_targets()?,
@@ -562,6 +599,8 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			base_path: Some(base_path),
 			informant_output_format: Default::default(),
 			runtime_cache_size,
+			precompile_runtime_upgrades: self.precompile_runtime_upgrades()?,
+			wasmtime_artifact_cache: self.wasmtime_artifact_cache(&config_dir)?,
 		})
 	}
 
@@ -590,6 +629,20 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(self.shared_params().disable_log_color())
 	}
 
+	/// Should the host function profiler be enabled?
+	///
+	/// By default this is retrieved from `SharedParams`.
+	fn profile_host_functions(&self) -> Result<bool> {
+		Ok(self.shared_params().profile_host_functions())
+	}
+
+	/// Get the file to write the host function profile to when the node exits.
+	///
+	/// By default this is retrieved from `SharedParams`.
+	fn host_function_profile_file(&self) -> Result<Option<PathBuf>> {
+		Ok(self.shared_params().host_function_profile_file())
+	}
+
 	/// Initialize substrate. This must be done only once per process.
 	///
 	/// This method:
@@ -598,6 +651,7 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 	/// 2. Optionally customize logger/profiling
 	/// 2. Initializes the logger
 	/// 3. Raises the FD limit
+	/// 4. Optionally enables the host function profiler
 	///
 	/// The `logger_hook` closure is executed before the logger is constructed
 	/// and initialized. It is useful for setting up a custom profiler.
@@ -659,6 +713,10 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			}
 		}
 
+		if self.profile_host_functions()? {
+			sc_executor_common::profiler::set_enabled(true);
+		}
+
 		Ok(())
 	}
 }
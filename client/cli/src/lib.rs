@@ -200,7 +200,8 @@ pub trait SubstrateCli: Sized {
 		let config = command.create_configuration(self, tokio_runtime.handle().clone())?;
 
 		command.init(&Self::support_url(), &Self::impl_version(), |_, _| {}, &config)?;
-		Runner::new(config, tokio_runtime)
+		Ok(Runner::new(config, tokio_runtime)?
+			.with_host_function_profile_file(command.host_function_profile_file()?))
 	}
 
 	/// Create a runner for the command provided in argument. The `logger_hook` can be used to setup
@@ -234,7 +235,8 @@ pub trait SubstrateCli: Sized {
 		let config = command.create_configuration(self, tokio_runtime.handle().clone())?;
 
 		command.init(&Self::support_url(), &Self::impl_version(), logger_hook, &config)?;
-		Runner::new(config, tokio_runtime)
+		Ok(Runner::new(config, tokio_runtime)?
+			.with_host_function_profile_file(command.host_function_profile_file()?))
 	}
 	/// Native runtime version.
 	fn native_runtime_version(chain_spec: &Box<dyn ChainSpec>) -> &'static RuntimeVersion;
@@ -28,6 +28,7 @@ use crate::{
 };
 use clap::Args;
 use sc_client_api::execution_extensions::ExecutionStrategies;
+use sc_service::config::WasmRuntimeDebugSubstitute;
 use std::path::PathBuf;
 
 /// Parameters for block import.
@@ -78,6 +79,17 @@ pub struct ImportParams {
 	#[arg(long, value_name = "PATH")]
 	pub wasm_runtime_overrides: Option<PathBuf>,
 
+	/// Execute a range of blocks with a local WASM runtime, e.g. a build with logging enabled.
+	///
+	/// Expects `FROM-TO=PATH` or `NUMBER=PATH`. The runtime is only used if its spec version
+	/// matches the on-chain one. Can be given multiple times.
+	#[arg(
+		long,
+		value_name = "BLOCKS=PATH",
+		value_parser = parse_wasm_runtime_debug_substitute,
+	)]
+	pub wasm_runtime_debug_substitute: Vec<WasmRuntimeDebugSubstitute>,
+
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub execution_strategies: ExecutionStrategiesParams,
@@ -120,6 +132,11 @@ impl ImportParams {
 		self.wasm_runtime_overrides.clone()
 	}
 
+	/// Get the local WASM runtimes that replace the on-chain runtime for ranges of blocks.
+	pub fn wasm_runtime_debug_substitutes(&self) -> Vec<WasmRuntimeDebugSubstitute> {
+		self.wasm_runtime_debug_substitute.clone()
+	}
+
 	/// Get execution strategies for the parameters
 	pub fn execution_strategies(&self, is_dev: bool, is_validator: bool) -> ExecutionStrategies {
 		let exec = &self.execution_strategies;
@@ -151,6 +168,27 @@ impl ImportParams {
 	}
 }
 
+/// Parse a `FROM-TO=PATH` or `NUMBER=PATH` debug substitute.
+fn parse_wasm_runtime_debug_substitute(s: &str) -> Result<WasmRuntimeDebugSubstitute, String> {
+	let (blocks, path) = s
+		.split_once('=')
+		.ok_or_else(|| format!("Expected `BLOCKS=PATH`, got `{}`", s))?;
+	let parse_number =
+		|n: &str| n.trim().parse::<u32>().map_err(|e| format!("Invalid block `{}`: {}", n, e));
+	let (start, end) = match blocks.split_once('-') {
+		Some((start, end)) => (parse_number(start)?, parse_number(end)?),
+		None => {
+			let number = parse_number(blocks)?;
+			(number, number)
+		},
+	};
+	if start > end {
+		return Err(format!("Invalid block range `{}`: {} is greater than {}", blocks, start, end))
+	}
+
+	Ok(WasmRuntimeDebugSubstitute { blocks: start..=end, path: PathBuf::from(path) })
+}
+
 /// Execution strategies parameters.
 #[derive(Debug, Clone, Args)]
 pub struct ExecutionStrategiesParams {
@@ -193,3 +231,23 @@ pub struct ExecutionStrategiesParams {
 	)]
 	pub execution: Option<ExecutionStrategy>,
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse_wasm_runtime_debug_substitute_works() {
+		let substitute = parse_wasm_runtime_debug_substitute("10-20=debug.wasm").unwrap();
+		assert_eq!(substitute.blocks, 10..=20);
+		assert_eq!(substitute.path, PathBuf::from("debug.wasm"));
+
+		let substitute = parse_wasm_runtime_debug_substitute("7=/tmp/debug.wasm").unwrap();
+		assert_eq!(substitute.blocks, 7..=7);
+		assert_eq!(substitute.path, PathBuf::from("/tmp/debug.wasm"));
+
+		assert!(parse_wasm_runtime_debug_substitute("debug.wasm").is_err());
+		assert!(parse_wasm_runtime_debug_substitute("20-10=debug.wasm").is_err());
+		assert!(parse_wasm_runtime_debug_substitute("a-10=debug.wasm").is_err());
+	}
+}
@@ -78,6 +78,20 @@ pub struct SharedParams {
 	/// Receiver to process tracing messages.
 	#[arg(long, value_name = "RECEIVER", value_enum, ignore_case = true, default_value_t = TracingReceiver::Log)]
 	pub tracing_receiver: TracingReceiver,
+
+	/// Profile the runtime API calls executed in wasm and the host functions they call.
+	///
+	/// The profile can be retrieved with the `dev_getHostFunctionProfile` RPC. Be aware that
+	/// profiling slows down the execution of the runtime.
+	#[arg(long)]
+	pub profile_host_functions: bool,
+
+	/// Write the host function profile to the given file when the node exits.
+	///
+	/// The file is in the folded stacks format, which can be rendered as a flamegraph with e.g.
+	/// `inferno-flamegraph`.
+	#[arg(long, value_name = "PATH", requires = "profile_host_functions")]
+	pub host_function_profile_file: Option<PathBuf>,
 }
 
 impl SharedParams {
@@ -138,4 +152,14 @@ impl SharedParams {
 	pub fn tracing_targets(&self) -> Option<String> {
 		self.tracing_targets.clone()
 	}
+
+	/// Is the host function profiler enabled?
+	pub fn profile_host_functions(&self) -> bool {
+		self.profile_host_functions
+	}
+
+	/// The file to write the host function profile to when the node exits.
+	pub fn host_function_profile_file(&self) -> Option<PathBuf> {
+		self.host_function_profile_file.clone()
+	}
 }
@@ -22,7 +22,7 @@ use futures::{future, future::FutureExt, pin_mut, select, Future};
 use log::info;
 use sc_service::{Configuration, Error as ServiceError, TaskManager};
 use sc_utils::metrics::{TOKIO_THREADS_ALIVE, TOKIO_THREADS_TOTAL};
-use std::marker::PhantomData;
+use std::{marker::PhantomData, path::PathBuf};
 
 #[cfg(target_family = "unix")]
 async fn main<F, E>(func: F) -> std::result::Result<(), E>
@@ -107,13 +107,21 @@ where
 pub struct Runner<C: SubstrateCli> {
 	config: Configuration,
 	tokio_runtime: tokio::runtime::Runtime,
+	host_function_profile_file: Option<PathBuf>,
 	phantom: PhantomData<C>,
 }
 
 impl<C: SubstrateCli> Runner<C> {
 	/// Create a new runtime with the command provided in argument
 	pub fn new(config: Configuration, tokio_runtime: tokio::runtime::Runtime) -> Result<Runner<C>> {
-		Ok(Runner { config, tokio_runtime, phantom: PhantomData })
+		Ok(Runner { config, tokio_runtime, host_function_profile_file: None, phantom: PhantomData })
+	}
+
+	/// Write the host function profile to `path` when the node started by
+	/// [`Self::run_node_until_exit`] exits.
+	pub fn with_host_function_profile_file(mut self, path: Option<PathBuf>) -> Self {
+		self.host_function_profile_file = path;
+		self
 	}
 
 	/// Log information about the node itself.
@@ -147,6 +155,13 @@ impl<C: SubstrateCli> Runner<C> {
 		self.print_node_infos();
 		let mut task_manager = self.tokio_runtime.block_on(initialize(self.config))?;
 		let res = self.tokio_runtime.block_on(main(task_manager.future().fuse()));
+
+		if let Some(path) = self.host_function_profile_file {
+			let profile = sc_executor_common::profiler::profile();
+			std::fs::write(&path, profile.to_folded_stacks()).map_err(ServiceError::Io)?;
+			info!("Host function profile written to {}", path.display());
+		}
+
 		Ok(res?)
 	}
 
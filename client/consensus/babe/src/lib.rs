@@ -97,14 +97,15 @@ use sc_consensus::{
 		BlockCheckParams, BlockImport, BlockImportParams, ForkChoiceStrategy, ImportResult,
 		StateAction,
 	},
+	equivocation::{EquivocationDetector, EquivocationStream},
 	import_queue::{BasicQueue, BoxJustificationImport, DefaultImportQueue, Verifier},
 };
 use sc_consensus_epochs::{
 	descendent_query, Epoch as EpochT, EpochChangesFor, SharedEpochChanges, ViableEpochDescriptor,
 };
 use sc_consensus_slots::{
-	check_equivocation, BackoffAuthoringBlocksStrategy, CheckedHeader, InherentDataProviderExt,
-	SlotInfo, StorageChanges,
+	equivocation_proof, slot_equivocation_detector, BackoffAuthoringBlocksStrategy, CheckedHeader,
+	InherentDataProviderExt, SlotInfo, StorageChanges,
 };
 use sc_telemetry::{telemetry, TelemetryHandle, CONSENSUS_DEBUG, CONSENSUS_TRACE};
 use sp_api::{ApiExt, ProvideRuntimeApi};
@@ -980,6 +981,7 @@ fn find_next_config_digest<B: BlockT>(
 pub struct BabeLink<Block: BlockT> {
 	epoch_changes: SharedEpochChanges<Block, Epoch>,
 	config: BabeConfiguration,
+	equivocation_detector: EquivocationDetector<AuthorityId, Block::Header>,
 }
 
 impl<Block: BlockT> BabeLink<Block> {
@@ -992,6 +994,11 @@ impl<Block: BlockT> BabeLink<Block> {
 	pub fn config(&self) -> &BabeConfiguration {
 		&self.config
 	}
+
+	/// Get a stream of the equivocations detected by the import queue.
+	pub fn equivocation_stream(&self) -> EquivocationStream<AuthorityId, Block::Header> {
+		self.equivocation_detector.equivocation_stream()
+	}
 }
 
 /// A verifier for Babe blocks.
@@ -1001,6 +1008,7 @@ pub struct BabeVerifier<Block: BlockT, Client, SelectChain, CIDP> {
 	create_inherent_data_providers: CIDP,
 	config: BabeConfiguration,
 	epoch_changes: SharedEpochChanges<Block, Epoch>,
+	equivocation_detector: EquivocationDetector<AuthorityId, Block::Header>,
 	telemetry: Option<TelemetryHandle>,
 }
 
@@ -1053,13 +1061,14 @@ where
 		}
 
 		// check if authorship of this header is an equivocation and return a proof if so.
-		let equivocation_proof =
-			match check_equivocation(&*self.client, slot_now, slot, header, author)
-				.map_err(Error::Client)?
-			{
-				Some(proof) => proof,
-				None => return Ok(()),
-			};
+		let equivocation_proof = match self
+			.equivocation_detector
+			.check(&*self.client, *slot_now, *slot, header, author)
+			.map_err(Error::Client)?
+		{
+			Some(equivocation) => equivocation_proof(equivocation),
+			None => return Ok(()),
+		};
 
 		info!(
 			"Slot author {:?} is equivocating at slot {} with headers {:?} and {:?}",
@@ -1717,7 +1726,11 @@ where
 		+ 'static,
 {
 	let epoch_changes = aux_schema::load_epoch_changes::<Block, _>(&*client, &config)?;
-	let link = BabeLink { epoch_changes: epoch_changes.clone(), config: config.clone() };
+	let link = BabeLink {
+		epoch_changes: epoch_changes.clone(),
+		config: config.clone(),
+		equivocation_detector: slot_equivocation_detector(),
+	};
 
 	// NOTE: this isn't entirely necessary, but since we didn't use to prune the
 	// epoch tree it is useful as a migration, so that nodes prune long trees on
@@ -1784,6 +1797,7 @@ where
 		create_inherent_data_providers,
 		config: babe_link.config,
 		epoch_changes: babe_link.epoch_changes,
+		equivocation_detector: babe_link.equivocation_detector,
 		telemetry,
 		client,
 	};
@@ -336,6 +336,7 @@ impl TestNetFactory for BabeTestNet {
 				}),
 				config: data.link.config.clone(),
 				epoch_changes: data.link.epoch_changes.clone(),
+				equivocation_detector: data.link.equivocation_detector.clone(),
 				telemetry: None,
 			},
 			mutator: MUTATOR.with(|m| m.borrow().clone()),
@@ -1051,3 +1052,50 @@ fn obsolete_blocks_aux_data_cleanup() {
 	// Present C4, C5
 	assert!(aux_data_check(&fork3_hashes, true));
 }
+
+#[test]
+fn verifier_notifies_equivocations() {
+	let mut net = BabeTestNet::new(1);
+
+	let peer = net.peer(0);
+	let client = peer.client().clone();
+	let link = peer.data.as_ref().expect("babe link set up during initialization").link.clone();
+	let genesis_hash = client.as_client().chain_info().genesis_hash;
+
+	let peer_data = Some(PeerData { link: link.clone(), block_import: Mutex::new(None) });
+	let verifier = net.make_verifier(client, &peer_data);
+	let mut equivocations = link.equivocation_stream().subscribe();
+
+	let author = AuthorityPair::from_seed(&[1; 32]).public();
+	let header = |number| {
+		TestHeader::new(
+			number,
+			Default::default(),
+			Default::default(),
+			genesis_hash,
+			Default::default(),
+		)
+	};
+	let check = |header: TestHeader| {
+		block_on(verifier.inner.check_and_report_equivocation(
+			1.into(),
+			1.into(),
+			&header,
+			&author,
+			&BlockOrigin::NetworkBroadcast,
+		))
+		.unwrap()
+	};
+
+	// importing the same header twice is not an equivocation.
+	check(header(1));
+	check(header(1));
+	// a second header by the same author in the same slot is.
+	check(header(2));
+
+	let equivocation = block_on(equivocations.next()).expect("equivocation was notified");
+	assert_eq!(equivocation.round, 1);
+	assert_eq!(equivocation.offender, author);
+	assert_eq!(equivocation.first, header(1));
+	assert_eq!(equivocation.second, header(2));
+}
@@ -0,0 +1,324 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Consensus-agnostic equivocation detection.
+//!
+//! Consensus engines record every item (e.g. a block header) authored in a round (e.g. a slot)
+//! together with its author. Two different items authored in the same round by the same author are
+//! an equivocation. Items are kept in the aux db for a bounded number of rounds.
+//!
+//! Only the slot based engines use this, through `sc-consensus-slots`: BABE checks imported headers
+//! with an [`EquivocationDetector`] and reports them on its equivocation stream, Aura only logs the
+//! equivocations found by [`check_equivocation`]. GRANDPA reports the vote equivocations found by
+//! its voter in its environment, BEEFY doesn't detect equivocations yet, and PoW blocks have no
+//! author that could equivocate.
+
+use codec::{Decode, Encode};
+use sc_client_api::backend::AuxStore;
+use sc_utils::notification::{NotificationSender, NotificationStream, TracingKeyStr};
+use sp_blockchain::{Error as ClientError, Result as ClientResult};
+
+/// Suffix of the aux db key under which the items of a round are stored.
+const ROUND_ITEMS_KEY: &[u8] = b"_header_map";
+/// Suffix of the aux db key under which the first stored round is kept.
+const FIRST_ROUND_KEY: &[u8] = b"_header_start";
+
+/// Two different items authored by the same author in the same round.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Equivocation<Id, T> {
+	/// The round in which the equivocation happened.
+	pub round: u64,
+	/// The author of the items.
+	pub offender: Id,
+	/// The first item seen.
+	pub first: T,
+	/// The second item seen.
+	pub second: T,
+}
+
+fn load_decode<C, T>(backend: &C, key: &[u8]) -> ClientResult<Option<T>>
+where
+	C: AuxStore,
+	T: Decode,
+{
+	match backend.get_aux(key)? {
+		None => Ok(None),
+		Some(t) => T::decode(&mut &t[..])
+			.map_err(|e| {
+				ClientError::Backend(format!("Equivocation DB is corrupted. Decode error: {}", e))
+			})
+			.map(Some),
+	}
+}
+
+fn key(prefix: &[u8], suffix: &[u8]) -> Vec<u8> {
+	let mut key = prefix.to_vec();
+	key.extend(suffix);
+	key
+}
+
+fn round_key(prefix: &[u8], round: u64) -> Vec<u8> {
+	let mut key = key(prefix, ROUND_ITEMS_KEY);
+	round.using_encoded(|r| key.extend(r));
+	key
+}
+
+/// Record `item` authored by `author` in `round` and check if it is an equivocation.
+///
+/// Items are stored in the aux db under keys starting with `prefix`, which must be unique per
+/// consensus engine. At least `capacity` rounds are kept, equivocations of older rounds are not
+/// detected.
+///
+/// Note: assumes that `round_now` is non-decreasing between calls.
+pub fn check_equivocation<C, Id, T>(
+	backend: &C,
+	prefix: &[u8],
+	capacity: u64,
+	round_now: u64,
+	round: u64,
+	item: &T,
+	author: &Id,
+) -> ClientResult<Option<Equivocation<Id, T>>>
+where
+	C: AuxStore,
+	Id: Clone + Encode + Decode + PartialEq,
+	T: Clone + Encode + Decode + PartialEq,
+{
+	// We don't check equivocations for old items out of our capacity.
+	if round_now.saturating_sub(round) > capacity {
+		return Ok(None)
+	}
+
+	let curr_round_key = round_key(prefix, round);
+	let mut items_with_author =
+		load_decode::<_, Vec<(T, Id)>>(backend, &curr_round_key)?.unwrap_or_default();
+
+	let first_round_key = key(prefix, FIRST_ROUND_KEY);
+	let first_saved_round = load_decode::<_, u64>(backend, &first_round_key)?.unwrap_or(round);
+
+	if round_now < first_saved_round {
+		// The code below assumes that rounds will be visited sequentially.
+		return Ok(None)
+	}
+
+	for (prev_item, prev_author) in items_with_author.iter() {
+		if prev_author == author {
+			return if prev_item != item {
+				Ok(Some(Equivocation {
+					round,
+					offender: author.clone(),
+					first: prev_item.clone(),
+					second: item.clone(),
+				}))
+			} else {
+				// We don't need to continue in case of duplicated item,
+				// since it's already saved and a possible equivocation
+				// would have been detected before.
+				Ok(None)
+			}
+		}
+	}
+
+	let mut keys_to_delete = vec![];
+	let mut new_first_saved_round = first_saved_round;
+
+	// We prune rounds once twice the capacity is stored.
+	if round_now - first_saved_round >= 2 * capacity {
+		new_first_saved_round = round_now.saturating_sub(capacity);
+		keys_to_delete
+			.extend((first_saved_round..new_first_saved_round).map(|r| round_key(prefix, r)));
+	}
+
+	items_with_author.push((item.clone(), author.clone()));
+
+	backend.insert_aux(
+		&[
+			(&curr_round_key[..], items_with_author.encode().as_slice()),
+			(&first_round_key[..], new_first_saved_round.encode().as_slice()),
+		],
+		&keys_to_delete.iter().map(|k| &k[..]).collect::<Vec<&[u8]>>()[..],
+	)?;
+
+	Ok(None)
+}
+
+/// Provides tracing key for the equivocation notification stream.
+#[derive(Clone)]
+pub struct EquivocationTracingKey;
+impl TracingKeyStr for EquivocationTracingKey {
+	const TRACING_KEY: &'static str = "mpsc_equivocation_notification_stream";
+}
+
+/// Stream of detected equivocations, e.g. to be reported as offences.
+pub type EquivocationStream<Id, T> =
+	NotificationStream<Equivocation<Id, T>, EquivocationTracingKey>;
+
+/// Equivocation detection service shared by a consensus engine.
+///
+/// Wraps [`check_equivocation`] and notifies subscribers of [`Self::equivocation_stream`] about
+/// any equivocation detected. Clones share the subscribers.
+pub struct EquivocationDetector<Id, T> {
+	prefix: &'static [u8],
+	capacity: u64,
+	sender: NotificationSender<Equivocation<Id, T>>,
+	stream: EquivocationStream<Id, T>,
+}
+
+impl<Id, T> Clone for EquivocationDetector<Id, T> {
+	fn clone(&self) -> Self {
+		Self {
+			prefix: self.prefix,
+			capacity: self.capacity,
+			sender: self.sender.clone(),
+			stream: self.stream.clone(),
+		}
+	}
+}
+
+impl<Id, T> EquivocationDetector<Id, T>
+where
+	Id: Clone + Encode + Decode + PartialEq,
+	T: Clone + Encode + Decode + PartialEq,
+{
+	/// Create a new detector storing items under aux db keys starting with `prefix`, which must be
+	/// unique per consensus engine, and keeping at least `capacity` rounds.
+	pub fn new(prefix: &'static [u8], capacity: u64) -> Self {
+		let (sender, stream) = EquivocationStream::channel();
+		Self { prefix, capacity, sender, stream }
+	}
+
+	/// Record `item` authored by `author` in `round` in the aux db of `backend` and check if it is
+	/// an equivocation.
+	///
+	/// See [`check_equivocation`].
+	pub fn check<C: AuxStore>(
+		&self,
+		backend: &C,
+		round_now: u64,
+		round: u64,
+		item: &T,
+		author: &Id,
+	) -> ClientResult<Option<Equivocation<Id, T>>> {
+		let equivocation = check_equivocation(
+			backend,
+			self.prefix,
+			self.capacity,
+			round_now,
+			round,
+			item,
+			author,
+		)?;
+
+		if let Some(equivocation) = &equivocation {
+			let _ = self.sender.notify(|| Ok::<_, ()>(equivocation.clone()));
+		}
+
+		Ok(equivocation)
+	}
+
+	/// Get a stream of the equivocations detected by this instance.
+	pub fn equivocation_stream(&self) -> EquivocationStream<Id, T> {
+		self.stream.clone()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::{executor::block_on, StreamExt};
+	use parking_lot::Mutex;
+	use std::collections::HashMap;
+
+	const PREFIX: &[u8] = b"test";
+	const CAPACITY: u64 = 10;
+
+	#[derive(Default)]
+	struct TestAuxStore(Mutex<HashMap<Vec<u8>, Vec<u8>>>);
+
+	impl AuxStore for TestAuxStore {
+		fn insert_aux<
+			'a,
+			'b: 'a,
+			'c: 'a,
+			I: IntoIterator<Item = &'a (&'c [u8], &'c [u8])>,
+			D: IntoIterator<Item = &'a &'b [u8]>,
+		>(
+			&self,
+			insert: I,
+			delete: D,
+		) -> ClientResult<()> {
+			let mut aux = self.0.lock();
+			for (k, v) in insert {
+				aux.insert(k.to_vec(), v.to_vec());
+			}
+			for k in delete {
+				aux.remove(*k);
+			}
+			Ok(())
+		}
+
+		fn get_aux(&self, key: &[u8]) -> ClientResult<Option<Vec<u8>>> {
+			Ok(self.0.lock().get(key).cloned())
+		}
+	}
+
+	#[test]
+	fn check_equivocation_works() {
+		let store = TestAuxStore::default();
+		let check = |round_now, round, item: u32, author: u8| {
+			check_equivocation(&store, PREFIX, CAPACITY, round_now, round, &item, &author).unwrap()
+		};
+
+		// It's ok to author the same item twice.
+		assert!(check(2, 2, 1, 0).is_none());
+		assert!(check(3, 2, 1, 0).is_none());
+
+		// Or for different authors to author different items in the same round.
+		assert!(check(3, 2, 2, 1).is_none());
+
+		// But not two different items by the same author in the same round.
+		assert_eq!(
+			check(4, 2, 2, 0),
+			Some(Equivocation { round: 2, offender: 0, first: 1, second: 2 })
+		);
+
+		// Different round is ok.
+		assert!(check(5, 4, 3, 0).is_none());
+
+		// Rounds out of capacity are ignored.
+		assert!(check(CAPACITY + 5, 4, 4, 0).is_none());
+
+		// Here we trigger pruning and save item 5.
+		assert!(check(2 * CAPACITY + 2, CAPACITY + 4, 5, 0).is_none());
+		assert!(check(2 * CAPACITY + 3, CAPACITY + 4, 6, 0).is_some());
+		assert!(store.0.lock().get(&round_key(PREFIX, 2)).is_none());
+		assert!(store.0.lock().get(&round_key(PREFIX, CAPACITY + 4)).is_some());
+	}
+
+	#[test]
+	fn detector_notifies_equivocations() {
+		let store = TestAuxStore::default();
+		let detector = EquivocationDetector::<u8, u32>::new(PREFIX, 10);
+		let mut equivocations = detector.clone().equivocation_stream().subscribe();
+
+		assert!(detector.check(&store, 1, 1, &1, &0).unwrap().is_none());
+		let equivocation = detector.check(&store, 1, 1, &2, &0).unwrap().unwrap();
+
+		assert_eq!(block_on(equivocations.next()), Some(equivocation));
+	}
+}
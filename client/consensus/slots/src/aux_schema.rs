@@ -20,33 +20,19 @@
 
 use codec::{Decode, Encode};
 use sc_client_api::backend::AuxStore;
-use sp_blockchain::{Error as ClientError, Result as ClientResult};
+use sc_consensus::equivocation::{Equivocation, EquivocationDetector};
+use sp_blockchain::Result as ClientResult;
 use sp_consensus_slots::{EquivocationProof, Slot};
 use sp_runtime::traits::Header;
 
-const SLOT_HEADER_MAP_KEY: &[u8] = b"slot_header_map";
-const SLOT_HEADER_START: &[u8] = b"slot_header_start";
+/// Prefix of the aux db keys under which slot headers are stored.
+const SLOT_HEADER_PREFIX: &[u8] = b"slot";
 
 /// We keep at least this number of slots in database.
 pub const MAX_SLOT_CAPACITY: u64 = 1000;
 /// We prune slots when they reach this number.
 pub const PRUNING_BOUND: u64 = 2 * MAX_SLOT_CAPACITY;
 
-fn load_decode<C, T>(backend: &C, key: &[u8]) -> ClientResult<Option<T>>
-where
-	C: AuxStore,
-	T: Decode,
-{
-	match backend.get_aux(key)? {
-		None => Ok(None),
-		Some(t) => T::decode(&mut &t[..])
-			.map_err(|e| {
-				ClientError::Backend(format!("Slots DB is corrupted. Decode error: {}", e))
-			})
-			.map(Some),
-	}
-}
-
 /// Checks if the header is an equivocation and returns the proof in that case.
 ///
 /// Note: it detects equivocations only when slot_now - slot <= MAX_SLOT_CAPACITY.
@@ -62,74 +48,38 @@ where
 	C: AuxStore,
 	P: Clone + Encode + Decode + PartialEq,
 {
-	// We don't check equivocations for old headers out of our capacity.
-	if slot_now.saturating_sub(*slot) > MAX_SLOT_CAPACITY {
-		return Ok(None)
-	}
-
-	// Key for this slot.
-	let mut curr_slot_key = SLOT_HEADER_MAP_KEY.to_vec();
-	slot.using_encoded(|s| curr_slot_key.extend(s));
-
-	// Get headers of this slot.
-	let mut headers_with_sig =
-		load_decode::<_, Vec<(H, P)>>(backend, &curr_slot_key[..])?.unwrap_or_else(Vec::new);
-
-	// Get first slot saved.
-	let slot_header_start = SLOT_HEADER_START.to_vec();
-	let first_saved_slot = load_decode::<_, Slot>(backend, &slot_header_start[..])?.unwrap_or(slot);
-
-	if slot_now < first_saved_slot {
-		// The code below assumes that slots will be visited sequentially.
-		return Ok(None)
-	}
-
-	for (prev_header, prev_signer) in headers_with_sig.iter() {
-		// A proof of equivocation consists of two headers:
-		// 1) signed by the same voter,
-		if prev_signer == signer {
-			// 2) with different hash
-			return if header.hash() != prev_header.hash() {
-				Ok(Some(EquivocationProof {
-					slot,
-					offender: signer.clone(),
-					first_header: prev_header.clone(),
-					second_header: header.clone(),
-				}))
-			} else {
-				// We don't need to continue in case of duplicated header,
-				// since it's already saved and a possible equivocation
-				// would have been detected before.
-				Ok(None)
-			}
-		}
-	}
+	let equivocation = sc_consensus::equivocation::check_equivocation(
+		backend,
+		SLOT_HEADER_PREFIX,
+		MAX_SLOT_CAPACITY,
+		*slot_now,
+		*slot,
+		header,
+		signer,
+	)?;
 
-	let mut keys_to_delete = vec![];
-	let mut new_first_saved_slot = first_saved_slot;
+	Ok(equivocation.map(equivocation_proof))
+}
 
-	if *slot_now - *first_saved_slot >= PRUNING_BOUND {
-		let prefix = SLOT_HEADER_MAP_KEY.to_vec();
-		new_first_saved_slot = slot_now.saturating_sub(MAX_SLOT_CAPACITY);
+/// Creates an [`EquivocationDetector`] of headers authored in slots.
+///
+/// The detector shares its aux db entries with [`check_equivocation`].
+pub fn slot_equivocation_detector<H, P>() -> EquivocationDetector<P, H>
+where
+	H: Header,
+	P: Clone + Encode + Decode + PartialEq,
+{
+	EquivocationDetector::new(SLOT_HEADER_PREFIX, MAX_SLOT_CAPACITY)
+}
 
-		for s in u64::from(first_saved_slot)..new_first_saved_slot.into() {
-			let mut p = prefix.clone();
-			s.using_encoded(|s| p.extend(s));
-			keys_to_delete.push(p);
-		}
+/// Converts an equivocation detected by a [`slot_equivocation_detector`] into its proof.
+pub fn equivocation_proof<H, P>(equivocation: Equivocation<P, H>) -> EquivocationProof<H, P> {
+	EquivocationProof {
+		slot: equivocation.round.into(),
+		offender: equivocation.offender,
+		first_header: equivocation.first,
+		second_header: equivocation.second,
 	}
-
-	headers_with_sig.push((header.clone(), signer.clone()));
-
-	backend.insert_aux(
-		&[
-			(&curr_slot_key[..], headers_with_sig.encode().as_slice()),
-			(&slot_header_start[..], new_first_saved_slot.encode().as_slice()),
-		],
-		&keys_to_delete.iter().map(|k| &k[..]).collect::<Vec<&[u8]>>()[..],
-	)?;
-
-	Ok(None)
 }
 
 #[cfg(test)]
@@ -28,7 +28,10 @@
 mod aux_schema;
 mod slots;
 
-pub use aux_schema::{check_equivocation, MAX_SLOT_CAPACITY, PRUNING_BOUND};
+pub use aux_schema::{
+	check_equivocation, equivocation_proof, slot_equivocation_detector, MAX_SLOT_CAPACITY,
+	PRUNING_BOUND,
+};
 pub use slots::SlotInfo;
 use slots::Slots;
 
@@ -0,0 +1,266 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! An opt-in profiler of the runtime API calls executed in wasm and the host functions they call.
+//!
+//! The profiler is global to the process and disabled by default. Once it is enabled with
+//! [`set_enabled`], the executor records the number of calls and the time spent in every runtime
+//! API call and in every host function per runtime API call. The collected [`Profile`] can be
+//! rendered as a flamegraph through [`Profile::to_folded_stacks`].
+
+use std::{
+	cell::RefCell,
+	collections::BTreeMap,
+	fmt::Write as _,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Mutex,
+	},
+	time::{Duration, Instant},
+};
+
+/// The name under which host functions are recorded that are not called by a profiled runtime
+/// API call.
+pub const UNKNOWN_RUNTIME_CALL: &str = "<unknown>";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static PROFILE: Mutex<Profile> = Mutex::new(Profile { runtime_calls: BTreeMap::new() });
+
+thread_local! {
+	/// The runtime API call that is currently executed by this thread.
+	static CURRENT_RUNTIME_CALL: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// The number of calls to a function and the time spent in them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CallStats {
+	/// Number of calls.
+	pub count: u64,
+	/// Total time spent in all calls.
+	pub total_time: Duration,
+}
+
+impl CallStats {
+	fn record(&mut self, time: Duration) {
+		self.count += 1;
+		self.total_time += time;
+	}
+}
+
+/// The profile of a runtime API call.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RuntimeCallProfile {
+	/// The calls of the runtime API function itself, including the host functions it calls.
+	pub stats: CallStats,
+	/// The host functions called by the runtime API function, by name.
+	pub host_functions: BTreeMap<&'static str, CallStats>,
+}
+
+/// The profile collected since the profiler was enabled or last reset.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Profile {
+	/// The runtime API calls, by name.
+	pub runtime_calls: BTreeMap<String, RuntimeCallProfile>,
+}
+
+impl Profile {
+	/// Renders the profile in the folded stacks format, as expected by flamegraph tools like
+	/// `inferno-flamegraph` or `flamegraph.pl`.
+	///
+	/// Every line is a runtime API call, optionally followed by a host function it calls, and the
+	/// time spent in it in microseconds. The time of a runtime API call excludes the time spent in
+	/// host functions.
+	pub fn to_folded_stacks(&self) -> String {
+		let mut folded = String::new();
+		for (name, runtime_call) in &self.runtime_calls {
+			let host_time =
+				runtime_call.host_functions.values().map(|stats| stats.total_time).sum();
+			let self_time = runtime_call.stats.total_time.saturating_sub(host_time);
+			if !self_time.is_zero() {
+				let _ = writeln!(folded, "{} {}", name, self_time.as_micros());
+			}
+			for (host_function, stats) in &runtime_call.host_functions {
+				let _ =
+					writeln!(folded, "{};{} {}", name, host_function, stats.total_time.as_micros());
+			}
+		}
+		folded
+	}
+
+	/// Returns the profile of the runtime API call `method`, without allocating its name if it
+	/// is already known.
+	fn runtime_call_mut(&mut self, method: &str) -> &mut RuntimeCallProfile {
+		if !self.runtime_calls.contains_key(method) {
+			self.runtime_calls.insert(method.to_owned(), Default::default());
+		}
+		self.runtime_calls.get_mut(method).expect("Inserted above if missing; qed")
+	}
+}
+
+/// Enables or disables the profiler.
+pub fn set_enabled(enabled: bool) {
+	ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether the profiler is enabled.
+pub fn is_enabled() -> bool {
+	ENABLED.load(Ordering::Relaxed)
+}
+
+/// Returns the profile collected so far.
+pub fn profile() -> Profile {
+	PROFILE.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Returns the profile collected so far and resets it.
+pub fn take_profile() -> Profile {
+	std::mem::take(&mut *PROFILE.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+/// Executes `call`, the runtime API function `method`, and records it if the profiler is enabled.
+pub fn profile_runtime_call<R>(method: &str, call: impl FnOnce() -> R) -> R {
+	if !is_enabled() {
+		return call()
+	}
+
+	/// Restores the previous runtime API call of this thread, e.g. after a nested call.
+	struct RestoreOnDrop(Option<String>);
+
+	impl Drop for RestoreOnDrop {
+		fn drop(&mut self) {
+			let previous = self.0.take();
+			CURRENT_RUNTIME_CALL.with(|current| *current.borrow_mut() = previous);
+		}
+	}
+
+	let _restore = RestoreOnDrop(
+		CURRENT_RUNTIME_CALL.with(|current| current.borrow_mut().replace(method.to_owned())),
+	);
+
+	let start = Instant::now();
+	let result = call();
+	let time = start.elapsed();
+
+	PROFILE
+		.lock()
+		.unwrap_or_else(|e| e.into_inner())
+		.runtime_call_mut(method)
+		.stats
+		.record(time);
+
+	result
+}
+
+/// Executes `call`, the host function `name`, and records it if the profiler is enabled.
+pub fn profile_host_function<R>(name: &'static str, call: impl FnOnce() -> R) -> R {
+	if !is_enabled() {
+		return call()
+	}
+
+	let start = Instant::now();
+	let result = call();
+	let time = start.elapsed();
+
+	CURRENT_RUNTIME_CALL.with(|current| {
+		let current = current.borrow();
+		let method = current.as_deref().unwrap_or(UNKNOWN_RUNTIME_CALL);
+
+		PROFILE
+			.lock()
+			.unwrap_or_else(|e| e.into_inner())
+			.runtime_call_mut(method)
+			.host_functions
+			.entry(name)
+			.or_default()
+			.record(time);
+	});
+
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn folded_stacks_exclude_host_function_time_from_runtime_calls() {
+		let stats = |count, micros| CallStats { count, total_time: Duration::from_micros(micros) };
+		let profile = Profile {
+			runtime_calls: [
+				(
+					"Core_execute_block".to_owned(),
+					RuntimeCallProfile {
+						stats: stats(1, 100),
+						host_functions: [("ext_storage_get_version_1", stats(5, 30))].into(),
+					},
+				),
+				(
+					UNKNOWN_RUNTIME_CALL.to_owned(),
+					RuntimeCallProfile {
+						stats: CallStats::default(),
+						host_functions: [("ext_hashing_twox_128_version_1", stats(1, 2))].into(),
+					},
+				),
+			]
+			.into(),
+		};
+
+		assert_eq!(
+			profile.to_folded_stacks(),
+			"<unknown>;ext_hashing_twox_128_version_1 2\n\
+			 Core_execute_block 70\n\
+			 Core_execute_block;ext_storage_get_version_1 30\n",
+		);
+	}
+
+	#[test]
+	fn records_runtime_calls_and_host_functions() {
+		take_profile();
+
+		// Nothing is recorded while the profiler is disabled.
+		profile_runtime_call("Core_version", || profile_host_function("ext_a", || ()));
+		assert_eq!(profile(), Profile::default());
+
+		set_enabled(true);
+		profile_runtime_call("Core_version", || {
+			profile_host_function("ext_a", || ());
+			profile_runtime_call("Nested_call", || profile_host_function("ext_b", || ()));
+			profile_host_function("ext_a", || ());
+		});
+		profile_host_function("ext_c", || ());
+		set_enabled(false);
+
+		let profile = take_profile();
+		let host_functions = |name: &str| {
+			profile.runtime_calls[name]
+				.host_functions
+				.iter()
+				.map(|(name, stats)| (*name, stats.count))
+				.collect::<Vec<_>>()
+		};
+		assert_eq!(profile.runtime_calls["Core_version"].stats.count, 1);
+		assert_eq!(host_functions("Core_version"), vec![("ext_a", 2)]);
+		assert_eq!(profile.runtime_calls["Nested_call"].stats.count, 1);
+		assert_eq!(host_functions("Nested_call"), vec![("ext_b", 1)]);
+		assert_eq!(profile.runtime_calls[UNKNOWN_RUNTIME_CALL].stats.count, 0);
+		assert_eq!(host_functions(UNKNOWN_RUNTIME_CALL), vec![("ext_c", 1)]);
+
+		assert_eq!(take_profile(), Profile::default());
+	}
+}
@@ -17,11 +17,12 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::error::WasmError;
+use sp_wasm_interface::{Signature, ValueType};
 use wasm_instrument::{
 	export_mutable_globals,
 	parity_wasm::elements::{
-		deserialize_buffer, serialize, DataSegment, ExportEntry, External, Internal, MemorySection,
-		MemoryType, Module, Section,
+		self, deserialize_buffer, serialize, DataSegment, ExportEntry, External, Internal,
+		MemorySection, MemoryType, Module, Section, Type,
 	},
 };
 
@@ -205,6 +206,39 @@ impl RuntimeBlob {
 			.map(|cs| cs.payload())
 	}
 
+	/// Returns the name and signature of every function imported from the `env` module, i.e. of
+	/// every host function this module requires.
+	///
+	/// Returns `Err` if a function import has a signature host functions can't have.
+	pub fn imported_host_functions(&self) -> Result<Vec<(String, Signature)>, WasmError> {
+		let types = self.raw_module.type_section().map(|ts| ts.types()).unwrap_or(&[]);
+		let imports = self.raw_module.import_section().map(|is| is.entries()).unwrap_or(&[]);
+
+		imports
+			.iter()
+			.filter(|entry| entry.module() == "env")
+			.filter_map(|entry| match entry.external() {
+				External::Function(type_idx) => Some((entry.field(), *type_idx)),
+				_ => None,
+			})
+			.map(|(name, type_idx)| {
+				let invalid = || {
+					WasmError::Other(format!("host function `{}` has an invalid signature", name))
+				};
+
+				let Type::Function(func_type) = types.get(type_idx as usize).ok_or_else(invalid)?;
+				let args = func_type.params().iter().map(|ty| into_value_type(*ty)).collect();
+				let return_value = match func_type.results() {
+					[] => None,
+					[ty] => Some(into_value_type(*ty)),
+					_ => return Err(invalid()),
+				};
+
+				Ok((name.to_owned(), Signature::new::<Vec<_>>(args, return_value)))
+			})
+			.collect()
+	}
+
 	/// Consumes this runtime blob and serializes it.
 	pub fn serialize(self) -> Vec<u8> {
 		serialize(self.raw_module).expect("serializing into a vec should succeed; qed")
@@ -215,3 +249,12 @@ impl RuntimeBlob {
 		self.raw_module
 	}
 }
+
+fn into_value_type(ty: elements::ValueType) -> ValueType {
+	match ty {
+		elements::ValueType::I32 => ValueType::I32,
+		elements::ValueType::I64 => ValueType::I64,
+		elements::ValueType::F32 => ValueType::F32,
+		elements::ValueType::F64 => ValueType::F64,
+	}
+}
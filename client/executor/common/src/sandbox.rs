@@ -18,11 +18,15 @@
 
 //! This module implements sandboxing support in the runtime.
 //!
-//! Sandboxing is backed by wasmi and wasmer, depending on the configuration.
+//! Sandboxing is backed by wasmi, wasmer and wasmtime, depending on the configuration.
 
+#[cfg(test)]
+mod tests;
 #[cfg(feature = "wasmer-sandbox")]
 mod wasmer_backend;
 mod wasmi_backend;
+#[cfg(feature = "wasmtime-sandbox")]
+mod wasmtime_backend;
 
 use std::{collections::HashMap, rc::Rc};
 
@@ -45,6 +49,12 @@ use self::wasmi_backend::{
 	get_global as wasmi_get_global, instantiate as wasmi_instantiate, invoke as wasmi_invoke,
 	new_memory as wasmi_new_memory, MemoryWrapper as WasmiMemoryWrapper,
 };
+#[cfg(feature = "wasmtime-sandbox")]
+use self::wasmtime_backend::{
+	get_global as wasmtime_get_global, instantiate as wasmtime_instantiate,
+	invoke as wasmtime_invoke, new_memory as wasmtime_new_memory, Backend as WasmtimeBackend,
+	InstanceWrapper as WasmtimeInstanceWrapper, MemoryWrapper as WasmtimeMemoryWrapper,
+};
 
 /// Index of a function inside the supervisor.
 ///
@@ -163,6 +173,10 @@ enum BackendInstance {
 	/// Wasmer module instance
 	#[cfg(feature = "wasmer-sandbox")]
 	Wasmer(wasmer::Instance),
+
+	/// Wasmtime module instance
+	#[cfg(feature = "wasmtime-sandbox")]
+	Wasmtime(WasmtimeInstanceWrapper),
 }
 
 /// Sandboxed instance of a wasm module.
@@ -206,6 +220,10 @@ impl SandboxInstance {
 			#[cfg(feature = "wasmer-sandbox")]
 			BackendInstance::Wasmer(wasmer_instance) =>
 				wasmer_invoke(wasmer_instance, export_name, args, state, sandbox_context),
+
+			#[cfg(feature = "wasmtime-sandbox")]
+			BackendInstance::Wasmtime(wasmtime_instance) =>
+				wasmtime_invoke(wasmtime_instance, export_name, args, state, sandbox_context),
 		}
 	}
 
@@ -218,6 +236,10 @@ impl SandboxInstance {
 
 			#[cfg(feature = "wasmer-sandbox")]
 			BackendInstance::Wasmer(wasmer_instance) => wasmer_get_global(wasmer_instance, name),
+
+			#[cfg(feature = "wasmtime-sandbox")]
+			BackendInstance::Wasmtime(wasmtime_instance) =>
+				wasmtime_get_global(wasmtime_instance, name),
 		}
 	}
 }
@@ -324,6 +346,13 @@ pub enum SandboxBackend {
 
 	/// Use wasmer backend if available. Fall back to wasmi otherwise.
 	TryWasmer,
+
+	/// Wasmtime environment
+	#[cfg(feature = "wasmtime-sandbox")]
+	Wasmtime,
+
+	/// Use wasmtime backend if available. Fall back to [`SandboxBackend::TryWasmer`] otherwise.
+	TryWasmtime,
 }
 
 /// Memory reference in terms of a selected backend
@@ -335,6 +364,10 @@ pub enum Memory {
 	/// Wasmer memory refernce
 	#[cfg(feature = "wasmer-sandbox")]
 	Wasmer(WasmerMemoryWrapper),
+
+	/// Wasmtime memory reference
+	#[cfg(feature = "wasmtime-sandbox")]
+	Wasmtime(WasmtimeMemoryWrapper),
 }
 
 impl Memory {
@@ -345,6 +378,9 @@ impl Memory {
 
 			#[cfg(feature = "wasmer-sandbox")]
 			Memory::Wasmer(_) => None,
+
+			#[cfg(feature = "wasmtime-sandbox")]
+			Memory::Wasmtime(_) => None,
 		}
 	}
 
@@ -354,6 +390,21 @@ impl Memory {
 		match self {
 			Memory::Wasmer(memory) => Some(memory.clone()),
 			Memory::Wasmi(_) => None,
+
+			#[cfg(feature = "wasmtime-sandbox")]
+			Memory::Wasmtime(_) => None,
+		}
+	}
+
+	/// View as wasmtime memory
+	#[cfg(feature = "wasmtime-sandbox")]
+	pub fn as_wasmtime(&self) -> Option<WasmtimeMemoryWrapper> {
+		match self {
+			Memory::Wasmtime(memory) => Some(memory.clone()),
+			Memory::Wasmi(_) => None,
+
+			#[cfg(feature = "wasmer-sandbox")]
+			Memory::Wasmer(_) => None,
 		}
 	}
 }
@@ -365,6 +416,9 @@ impl util::MemoryTransfer for Memory {
 
 			#[cfg(feature = "wasmer-sandbox")]
 			Memory::Wasmer(sandboxed_memory) => sandboxed_memory.read(source_addr, size),
+
+			#[cfg(feature = "wasmtime-sandbox")]
+			Memory::Wasmtime(sandboxed_memory) => sandboxed_memory.read(source_addr, size),
 		}
 	}
 
@@ -374,6 +428,10 @@ impl util::MemoryTransfer for Memory {
 
 			#[cfg(feature = "wasmer-sandbox")]
 			Memory::Wasmer(sandboxed_memory) => sandboxed_memory.read_into(source_addr, destination),
+
+			#[cfg(feature = "wasmtime-sandbox")]
+			Memory::Wasmtime(sandboxed_memory) =>
+				sandboxed_memory.read_into(source_addr, destination),
 		}
 	}
 
@@ -383,6 +441,9 @@ impl util::MemoryTransfer for Memory {
 
 			#[cfg(feature = "wasmer-sandbox")]
 			Memory::Wasmer(sandboxed_memory) => sandboxed_memory.write_from(dest_addr, source),
+
+			#[cfg(feature = "wasmtime-sandbox")]
+			Memory::Wasmtime(sandboxed_memory) => sandboxed_memory.write_from(dest_addr, source),
 		}
 	}
 }
@@ -395,6 +456,10 @@ enum BackendContext {
 	/// Wasmer specific context
 	#[cfg(feature = "wasmer-sandbox")]
 	Wasmer(WasmerBackend),
+
+	/// Wasmtime specific context
+	#[cfg(feature = "wasmtime-sandbox")]
+	Wasmtime(WasmtimeBackend),
 }
 
 impl BackendContext {
@@ -408,6 +473,13 @@ impl BackendContext {
 			#[cfg(feature = "wasmer-sandbox")]
 			SandboxBackend::Wasmer | SandboxBackend::TryWasmer =>
 				BackendContext::Wasmer(WasmerBackend::new()),
+
+			#[cfg(not(feature = "wasmtime-sandbox"))]
+			SandboxBackend::TryWasmtime => BackendContext::new(SandboxBackend::TryWasmer),
+
+			#[cfg(feature = "wasmtime-sandbox")]
+			SandboxBackend::Wasmtime | SandboxBackend::TryWasmtime =>
+				BackendContext::Wasmtime(WasmtimeBackend::new()),
 		}
 	}
 }
@@ -455,6 +527,9 @@ impl<DT: Clone> Store<DT> {
 
 			#[cfg(feature = "wasmer-sandbox")]
 			BackendContext::Wasmer(context) => wasmer_new_memory(context, initial, maximum)?,
+
+			#[cfg(feature = "wasmtime-sandbox")]
+			BackendContext::Wasmtime(context) => wasmtime_new_memory(context, initial, maximum)?,
 		};
 
 		let mem_idx = memories.len();
@@ -565,6 +640,10 @@ impl<DT: Clone> Store<DT> {
 			#[cfg(feature = "wasmer-sandbox")]
 			BackendContext::Wasmer(ref context) =>
 				wasmer_instantiate(context, wasm, guest_env, state, sandbox_context)?,
+
+			#[cfg(feature = "wasmtime-sandbox")]
+			BackendContext::Wasmtime(ref context) =>
+				wasmtime_instantiate(context, wasm, guest_env, state, sandbox_context)?,
 		};
 
 		Ok(UnregisteredInstance { sandbox_instance })
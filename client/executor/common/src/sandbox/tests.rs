@@ -0,0 +1,413 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Differential tests of the sandbox backends.
+//!
+//! Every contract fixture of `pallet-contracts` is instrumented the same way the pallet does it
+//! and then executed by the embedded executor of `sp-sandbox` and by every host backend. All of
+//! them need to agree on the outcome, on the host functions that were called and on the final
+//! contents of the sandboxed memory.
+
+use std::{
+	collections::hash_map::DefaultHasher,
+	hash::{Hash, Hasher},
+	path::PathBuf,
+};
+
+use codec::{Decode, Encode};
+use sp_sandbox::{
+	embedded_executor, env as sandbox_env, HostError, HostFuncType, ReturnValue,
+	SandboxEnvironmentBuilder, SandboxInstance as _, SandboxMemory, Value,
+};
+use sp_wasm_interface::{FunctionContext, Pointer, WordSize};
+use wasm_instrument::{
+	gas_metering::{self, ConstantCostRules},
+	parity_wasm::elements::{self, External, Type, ValueType},
+};
+
+use super::{GuestEnvironment, InstantiationError, SandboxBackend, SandboxContext, Store};
+use crate::{error::Result, util::MemoryTransfer};
+
+/// The amount of gas after which the `gas` host function fails.
+const GAS_LIMIT: u64 = 1_000_000;
+
+/// The stack height limit contracts are instrumented with.
+const STACK_LIMIT: u32 = 1024;
+
+/// Everything a contract did that is observable by the supervisor.
+#[derive(Default, Clone, Debug, PartialEq)]
+struct Trace {
+	/// The arguments of every host function call, in order.
+	calls: Vec<Vec<Value>>,
+	/// The amount of gas charged so far.
+	gas: u64,
+}
+
+fn gas(trace: &mut Trace, args: &[Value]) -> std::result::Result<ReturnValue, HostError> {
+	let amount = match args {
+		[Value::I32(amount)] => *amount as u32 as u64,
+		[Value::I64(amount)] => *amount as u64,
+		_ => return Err(HostError),
+	};
+	trace.gas = trace.gas.saturating_add(amount);
+	if trace.gas > GAS_LIMIT {
+		return Err(HostError)
+	}
+	Ok(ReturnValue::Unit)
+}
+
+fn stub_unit(trace: &mut Trace, args: &[Value]) -> std::result::Result<ReturnValue, HostError> {
+	trace.calls.push(args.to_vec());
+	Ok(ReturnValue::Unit)
+}
+
+fn stub_i32(trace: &mut Trace, args: &[Value]) -> std::result::Result<ReturnValue, HostError> {
+	trace.calls.push(args.to_vec());
+	Ok(ReturnValue::Value(Value::I32(0)))
+}
+
+fn stub_i64(trace: &mut Trace, args: &[Value]) -> std::result::Result<ReturnValue, HostError> {
+	trace.calls.push(args.to_vec());
+	Ok(ReturnValue::Value(Value::I64(0)))
+}
+
+/// Outcome of instantiating a module and calling its exports.
+#[derive(Clone, PartialEq)]
+enum Outcome {
+	/// The module could not be instantiated.
+	InvalidModule,
+	/// The start function trapped.
+	StartTrapped,
+	/// The module was instantiated and its exports were called.
+	Executed {
+		/// The results of calling `deploy` and `call`, in this order. Errors are not compared
+		/// in detail since every backend reports them differently.
+		results: Vec<std::result::Result<Option<Value>, ()>>,
+		/// The contents of the imported memory after both calls.
+		memory: Vec<u8>,
+	},
+}
+
+impl std::fmt::Debug for Outcome {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Outcome::InvalidModule => write!(f, "InvalidModule"),
+			Outcome::StartTrapped => write!(f, "StartTrapped"),
+			Outcome::Executed { results, memory } => f
+				.debug_struct("Executed")
+				.field("results", results)
+				.field("memory_len", &memory.len())
+				.field("memory_hash", &{
+					let mut hasher = DefaultHasher::new();
+					memory.hash(&mut hasher);
+					hasher.finish()
+				})
+				.finish(),
+		}
+	}
+}
+
+/// An instrumented contract fixture together with the host functions it imports.
+struct Fixture {
+	name: String,
+	code: Vec<u8>,
+	funcs: Vec<(String, String, HostFuncType<Trace>)>,
+	memory: Option<(String, String, u32, Option<u32>)>,
+}
+
+impl Fixture {
+	fn load(path: PathBuf) -> Self {
+		let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+		let code = wat::parse_file(&path).unwrap();
+
+		let module = elements::deserialize_buffer(&code).unwrap();
+		let module = gas_metering::inject(module, &ConstantCostRules::default(), "seal0").unwrap();
+		let module = wasm_instrument::inject_stack_limiter(module, STACK_LIMIT).unwrap();
+
+		let types = module.type_section().map(|s| s.types().to_vec()).unwrap_or_default();
+		let mut funcs = Vec::new();
+		let mut memory = None;
+		for entry in module.import_section().map(|s| s.entries()).unwrap_or_default() {
+			let module_name = entry.module().to_owned();
+			let field_name = entry.field().to_owned();
+			match entry.external() {
+				External::Function(type_idx) => {
+					let Type::Function(ty) = &types[*type_idx as usize];
+					let func: HostFuncType<Trace> = match (field_name.as_str(), ty.results()) {
+						("gas", _) => gas,
+						(_, []) => stub_unit,
+						(_, [ValueType::I64]) => stub_i64,
+						_ => stub_i32,
+					};
+					funcs.push((module_name, field_name, func));
+				},
+				External::Memory(ty) => {
+					let limits = ty.limits();
+					memory = Some((module_name, field_name, limits.initial(), limits.maximum()));
+				},
+				_ => (),
+			}
+		}
+
+		Fixture { name, code: module.into_bytes().unwrap(), funcs, memory }
+	}
+
+	/// Run the fixture in the embedded executor of `sp-sandbox`.
+	fn run_embedded(&self) -> (Outcome, Trace) {
+		let mut trace = Trace::default();
+		let mut env = embedded_executor::EnvironmentDefinitionBuilder::new();
+		for (module, field, func) in &self.funcs {
+			env.add_host_func(module.as_str(), field.as_str(), *func);
+		}
+		let memory = self.memory.as_ref().map(|(module, field, initial, maximum)| {
+			let memory = embedded_executor::Memory::new(*initial, *maximum).unwrap();
+			env.add_memory(module.as_str(), field.as_str(), memory.clone());
+			(memory, *initial)
+		});
+
+		let mut instance = match embedded_executor::Instance::new(&self.code, &env, &mut trace) {
+			Ok(instance) => instance,
+			Err(sp_sandbox::Error::Execution) => return (Outcome::StartTrapped, trace),
+			Err(_) => return (Outcome::InvalidModule, trace),
+		};
+
+		let results = ["deploy", "call"]
+			.iter()
+			.map(|export| match instance.invoke(export, &[], &mut trace) {
+				Ok(ReturnValue::Unit) => Ok(None),
+				Ok(ReturnValue::Value(value)) => Ok(Some(value)),
+				Err(_) => Err(()),
+			})
+			.collect();
+
+		let memory = memory
+			.map(|(memory, initial)| {
+				let mut contents = vec![0; initial as usize * 65536];
+				memory.get(0, &mut contents).unwrap();
+				contents
+			})
+			.unwrap_or_default();
+
+		(Outcome::Executed { results, memory }, trace)
+	}
+
+	/// Run the fixture in a host sandbox store using the given backend.
+	fn run_host(&self, backend: SandboxBackend) -> (Outcome, Trace) {
+		let mut store = Store::<()>::new(backend);
+		let mut context = HostContext {
+			supervisor: Supervisor::default(),
+			funcs: &self.funcs,
+			trace: Default::default(),
+		};
+
+		let mut entries = self
+			.funcs
+			.iter()
+			.enumerate()
+			.map(|(idx, (module, field, _))| sandbox_env::Entry {
+				module_name: module.as_bytes().to_vec(),
+				field_name: field.as_bytes().to_vec(),
+				entity: sandbox_env::ExternEntity::Function(idx as u32),
+			})
+			.collect::<Vec<_>>();
+		let memory = self.memory.as_ref().map(|(module, field, initial, maximum)| {
+			let memory_idx = store
+				.new_memory(*initial, maximum.unwrap_or(sandbox_env::MEM_UNLIMITED))
+				.unwrap();
+			entries.push(sandbox_env::Entry {
+				module_name: module.as_bytes().to_vec(),
+				field_name: field.as_bytes().to_vec(),
+				entity: sandbox_env::ExternEntity::Memory(memory_idx),
+			});
+			(memory_idx, *initial)
+		});
+
+		let raw_env_def = sandbox_env::EnvironmentDefinition { entries }.encode();
+		let guest_env = match GuestEnvironment::decode(&store, &raw_env_def) {
+			Ok(guest_env) => guest_env,
+			Err(_) => panic!("the environment definition is valid"),
+		};
+
+		let instance = match store.instantiate(&self.code, guest_env, 0, &mut context) {
+			Ok(instance) => instance.register(&mut store, ()),
+			Err(InstantiationError::StartTrapped) => return (Outcome::StartTrapped, context.trace),
+			Err(_) => return (Outcome::InvalidModule, context.trace),
+		};
+		let instance = store.instance(instance).unwrap();
+
+		let results = ["deploy", "call"]
+			.iter()
+			.map(|export| instance.invoke(export, &[], 0, &mut context).map_err(|_| ()))
+			.collect();
+
+		let memory = memory
+			.map(|(memory_idx, initial)| {
+				store
+					.memory(memory_idx)
+					.unwrap()
+					.read(Pointer::new(0), initial as usize * 65536)
+					.unwrap()
+			})
+			.unwrap_or_default();
+
+		(Outcome::Executed { results, memory }, context.trace)
+	}
+}
+
+/// A minimal supervisor which only provides the memory used to pass arguments and results
+/// between the sandbox and the host functions.
+struct Supervisor {
+	memory: Vec<u8>,
+	next_free: u32,
+	live_allocations: u32,
+}
+
+impl Default for Supervisor {
+	fn default() -> Self {
+		Supervisor { memory: vec![0; 65536], next_free: 8, live_allocations: 0 }
+	}
+}
+
+impl FunctionContext for Supervisor {
+	fn read_memory_into(
+		&self,
+		address: Pointer<u8>,
+		dest: &mut [u8],
+	) -> sp_wasm_interface::Result<()> {
+		let start = u32::from(address) as usize;
+		let source = self.memory.get(start..start + dest.len()).ok_or("read out of bounds")?;
+		dest.copy_from_slice(source);
+		Ok(())
+	}
+
+	fn write_memory(&mut self, address: Pointer<u8>, data: &[u8]) -> sp_wasm_interface::Result<()> {
+		let start = u32::from(address) as usize;
+		let dest = self.memory.get_mut(start..start + data.len()).ok_or("write out of bounds")?;
+		dest.copy_from_slice(data);
+		Ok(())
+	}
+
+	fn allocate_memory(&mut self, size: WordSize) -> sp_wasm_interface::Result<Pointer<u8>> {
+		let ptr = self.next_free;
+		if ptr as usize + size as usize > self.memory.len() {
+			return Err("out of supervisor memory".into())
+		}
+		self.next_free += size;
+		self.live_allocations += 1;
+		Ok(Pointer::new(ptr))
+	}
+
+	fn deallocate_memory(&mut self, _ptr: Pointer<u8>) -> sp_wasm_interface::Result<()> {
+		self.live_allocations -= 1;
+		if self.live_allocations == 0 {
+			self.next_free = 8;
+		}
+		Ok(())
+	}
+
+	fn sandbox(&mut self) -> &mut dyn sp_wasm_interface::Sandbox {
+		unreachable!("host functions of the fixtures never access the sandbox")
+	}
+
+	fn register_panic_error_message(&mut self, _message: &str) {}
+}
+
+/// Dispatches calls from the host backends to the host functions of a [`Fixture`].
+struct HostContext<'a> {
+	supervisor: Supervisor,
+	funcs: &'a [(String, String, HostFuncType<Trace>)],
+	trace: Trace,
+}
+
+impl SandboxContext for HostContext<'_> {
+	fn invoke(
+		&mut self,
+		invoke_args_ptr: Pointer<u8>,
+		invoke_args_len: WordSize,
+		_state: u32,
+		func_idx: super::SupervisorFuncIndex,
+	) -> Result<i64> {
+		let args = self.supervisor.read_memory(invoke_args_ptr, invoke_args_len)?;
+		let args = Vec::<Value>::decode(&mut &args[..]).map_err(|_| "invalid arguments")?;
+
+		let (_, _, func) = &self.funcs[func_idx.0];
+		let result = func(&mut self.trace, &args).encode();
+
+		let result_ptr = self.supervisor.allocate_memory(result.len() as WordSize)?;
+		self.supervisor.write_memory(result_ptr, &result)?;
+		Ok(((u32::from(result_ptr) as i64) << 32) | result.len() as i64)
+	}
+
+	fn supervisor_context(&mut self) -> &mut dyn FunctionContext {
+		&mut self.supervisor
+	}
+}
+
+fn fixtures() -> Vec<Fixture> {
+	let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../../frame/contracts/fixtures");
+	let mut paths = std::fs::read_dir(dir)
+		.unwrap()
+		.map(|entry| entry.unwrap().path())
+		.filter(|path| path.extension().map_or(false, |ext| ext == "wat"))
+		.collect::<Vec<_>>();
+	paths.sort();
+	assert!(!paths.is_empty(), "the contract fixtures are found");
+	paths.into_iter().map(Fixture::load).collect()
+}
+
+fn host_backends() -> Vec<(&'static str, SandboxBackend)> {
+	vec![
+		("wasmi", SandboxBackend::Wasmi),
+		#[cfg(feature = "wasmer-sandbox")]
+		("wasmer", SandboxBackend::Wasmer),
+		#[cfg(feature = "wasmtime-sandbox")]
+		("wasmtime", SandboxBackend::Wasmtime),
+	]
+}
+
+#[test]
+fn host_backends_agree_with_embedded_executor_on_contract_fixtures() {
+	for fixture in fixtures() {
+		let expected = fixture.run_embedded();
+		for (backend_name, backend) in host_backends() {
+			assert_eq!(
+				fixture.run_host(backend),
+				expected,
+				"`{}` differs between the embedded executor and the {} backend",
+				fixture.name,
+				backend_name,
+			);
+		}
+	}
+}
+
+#[test]
+fn running_out_of_gas_traps_in_every_backend() {
+	let fixture = fixtures().into_iter().find(|fixture| fixture.name == "run_out_of_gas").unwrap();
+
+	let expected = fixture.run_embedded();
+	assert_eq!(
+		expected.0,
+		Outcome::Executed { results: vec![Ok(None), Err(())], memory: Vec::new() }
+	);
+	assert!(expected.1.gas > GAS_LIMIT);
+
+	for (_, backend) in host_backends() {
+		assert_eq!(fixture.run_host(backend), expected);
+	}
+}
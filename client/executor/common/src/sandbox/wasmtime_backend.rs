@@ -0,0 +1,450 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Wasmtime specific impls for sandbox
+//!
+//! All modules and memories of a sandbox store live in one wasmtime store. Sandboxed modules call
+//! into the supervisor, which in turn may access sandboxed memories or call into other sandboxed
+//! instances while the wasmtime store is borrowed by the outer call. Every access to the store
+//! therefore goes through [`with_store`], which uses the store of the innermost call if it is the
+//! same store.
+
+use std::{
+	cell::RefCell,
+	rc::{Rc, Weak},
+};
+
+use wasmtime::{AsContextMut, Caller, Extern, StoreContextMut, Trap, Val};
+
+use codec::{Decode, Encode};
+use sp_sandbox::HostError;
+use sp_wasm_interface::{FunctionContext, Pointer, ReturnValue, Value, WordSize};
+
+use crate::{
+	error::{Error, Result},
+	sandbox::{
+		BackendInstance, GuestEnvironment, InstantiationError, Memory, SandboxContext,
+		SandboxInstance, SupervisorFuncIndex,
+	},
+	util::{checked_range, MemoryTransfer},
+};
+
+environmental::environmental!(SandboxContextStore: trait SandboxContext);
+
+/// Gives access to the wasmtime store through the caller of a host function.
+trait StoreAccess {
+	/// Whether this is the store shared through `store`.
+	fn is(&self, store: &SharedStore) -> bool;
+
+	fn store(&mut self) -> StoreContextMut<'_, ()>;
+}
+
+/// The caller of a host function together with the shared store it belongs to.
+struct CallerAccess<'a, 'b> {
+	caller: &'a mut Caller<'b, ()>,
+	owner: &'a Weak<RefCell<wasmtime::Store<()>>>,
+}
+
+impl StoreAccess for CallerAccess<'_, '_> {
+	fn is(&self, store: &SharedStore) -> bool {
+		std::ptr::eq(self.owner.as_ptr(), Rc::as_ptr(store))
+	}
+
+	fn store(&mut self) -> StoreContextMut<'_, ()> {
+		self.caller.as_context_mut()
+	}
+}
+
+/// The store of the innermost host function call.
+///
+/// Lives in its own module since every `environmental!` needs a module of its own.
+mod active_store {
+	use super::StoreAccess;
+
+	environmental::environmental!(ActiveStore: trait StoreAccess);
+
+	/// Make `store` the active store while running `f`.
+	pub(super) fn using<R>(store: &mut dyn StoreAccess, f: impl FnOnce() -> R) -> R {
+		ActiveStore::using(store, f)
+	}
+
+	/// Run `f` with the active store, if there is one.
+	pub(super) fn with<R>(f: impl FnOnce(&mut dyn StoreAccess) -> R) -> Option<R> {
+		ActiveStore::with(f)
+	}
+}
+
+type SharedStore = Rc<RefCell<wasmtime::Store<()>>>;
+
+/// Run `f` with the wasmtime store.
+///
+/// Uses the caller of the innermost host function call if we are inside of one that belongs to
+/// `store`, since the store itself is borrowed by the outer call then.
+fn with_store<R>(store: &SharedStore, f: impl FnOnce(StoreContextMut<()>) -> R) -> R {
+	let mut f = Some(f);
+	active_store::with(|active| {
+		active
+			.is(store)
+			.then(|| (f.take().expect("`f` is only taken once; qed"))(active.store()))
+	})
+	.flatten()
+	.unwrap_or_else(|| {
+		(f.take().expect("`f` is only taken once; qed"))(store.borrow_mut().as_context_mut())
+	})
+}
+
+/// Wasmtime specific context
+pub struct Backend {
+	store: SharedStore,
+}
+
+impl Backend {
+	pub fn new() -> Self {
+		let mut config = wasmtime::Config::new();
+		// Sandboxed code must behave the same on every node.
+		config.cranelift_nan_canonicalization(true);
+		config.wasm_simd(false);
+		config.wasm_reference_types(false);
+		config.wasm_bulk_memory(false);
+		config.wasm_multi_value(false);
+		config.wasm_multi_memory(false);
+
+		let engine =
+			wasmtime::Engine::new(&config).expect("the configuration is valid and static; qed");
+		Backend { store: Rc::new(RefCell::new(wasmtime::Store::new(&engine, ()))) }
+	}
+}
+
+/// A wasmtime module instance together with the store it lives in.
+pub struct InstanceWrapper {
+	instance: wasmtime::Instance,
+	store: SharedStore,
+}
+
+/// Invoke a function within a sandboxed module
+pub fn invoke(
+	instance: &InstanceWrapper,
+	export_name: &str,
+	args: &[Value],
+	_state: u32,
+	sandbox_context: &mut dyn SandboxContext,
+) -> std::result::Result<Option<Value>, Error> {
+	let args: Vec<Val> = args.iter().map(|v| into_wasmtime_val(*v)).collect();
+
+	let results = with_store(&instance.store, |mut store| {
+		let function = instance
+			.instance
+			.get_func(&mut store, export_name)
+			.ok_or_else(|| Error::Sandbox(format!("function `{}` is not exported", export_name)))?;
+
+		let mut results = vec![Val::I32(0); function.ty(&store).results().len()];
+		SandboxContextStore::using(sandbox_context, || {
+			function
+				.call(&mut store, &args, &mut results)
+				.map_err(|error| Error::Sandbox(error.to_string()))
+		})?;
+
+		Ok::<_, Error>(results)
+	})?;
+
+	match results.as_slice() {
+		[] => Ok(None),
+
+		[wasm_value] => from_wasmtime_val(wasm_value)
+			.map(Some)
+			.ok_or_else(|| Error::Sandbox(format!("Unsupported return value: {:?}", wasm_value))),
+
+		_ => Err(Error::Sandbox("multiple return types are not supported yet".into())),
+	}
+}
+
+/// Instantiate a module within a sandbox context
+pub fn instantiate(
+	context: &Backend,
+	wasm: &[u8],
+	guest_env: GuestEnvironment,
+	state: u32,
+	sandbox_context: &mut dyn SandboxContext,
+) -> std::result::Result<Rc<SandboxInstance>, InstantiationError> {
+	let instance = with_store(&context.store, |mut store| {
+		let module = wasmtime::Module::new(store.engine(), wasm)
+			.map_err(|_| InstantiationError::ModuleDecoding)?;
+
+		let mut imports = Vec::new();
+		for import in module.imports() {
+			let module_name = import.module();
+			let name = import.name();
+
+			let import = match import.ty() {
+				wasmtime::ExternType::Memory(_) => {
+					let memory = guest_env
+						.imports
+						.memory_by_name(module_name, name)
+						.ok_or(InstantiationError::ModuleDecoding)?;
+
+					let memory = memory.as_wasmtime().expect(
+						"memory is created by wasmtime; \
+						exported by the same module and backend; \
+						thus the operation can't fail; \
+						qed",
+					);
+
+					Extern::Memory(memory.memory)
+				},
+
+				wasmtime::ExternType::Func(func_ty) => {
+					let guest_func_index = guest_env
+						.imports
+						.func_by_name(module_name, name)
+						.ok_or(InstantiationError::Instantiation)?;
+
+					let supervisor_func_index = guest_env
+						.guest_to_supervisor_mapping
+						.func_by_guest_index(guest_func_index)
+						.ok_or(InstantiationError::ModuleDecoding)?;
+
+					Extern::Func(dispatch_function(
+						supervisor_func_index,
+						&mut store,
+						Rc::downgrade(&context.store),
+						func_ty,
+						state,
+					))
+				},
+
+				// Globals and tables can't be provided by the supervisor.
+				wasmtime::ExternType::Global(_) | wasmtime::ExternType::Table(_) =>
+					return Err(InstantiationError::Instantiation),
+			};
+
+			imports.push(import);
+		}
+
+		SandboxContextStore::using(sandbox_context, || {
+			wasmtime::Instance::new(&mut store, &module, &imports).map_err(|error| {
+				if error.downcast_ref::<Trap>().is_some() {
+					InstantiationError::StartTrapped
+				} else {
+					InstantiationError::Instantiation
+				}
+			})
+		})
+	})?;
+
+	Ok(Rc::new(SandboxInstance {
+		backend_instance: BackendInstance::Wasmtime(InstanceWrapper {
+			instance,
+			store: context.store.clone(),
+		}),
+		guest_to_supervisor_mapping: guest_env.guest_to_supervisor_mapping,
+	}))
+}
+
+fn dispatch_function(
+	supervisor_func_index: SupervisorFuncIndex,
+	store: impl AsContextMut<Data = ()>,
+	// Weak, since the function lives in the store itself.
+	owner: Weak<RefCell<wasmtime::Store<()>>>,
+	func_ty: wasmtime::FuncType,
+	state: u32,
+) -> wasmtime::Func {
+	wasmtime::Func::new(store, func_ty, move |mut caller, params, results| {
+		active_store::using(&mut CallerAccess { caller: &mut caller, owner: &owner }, || {
+			SandboxContextStore::with(|sandbox_context| {
+				dispatch(sandbox_context, supervisor_func_index, state, params, results)
+			})
+			.expect("SandboxContextStore is set when invoking sandboxed functions; qed")
+		})
+	})
+}
+
+fn dispatch(
+	sandbox_context: &mut dyn SandboxContext,
+	supervisor_func_index: SupervisorFuncIndex,
+	state: u32,
+	params: &[Val],
+	results: &mut [Val],
+) -> std::result::Result<(), Trap> {
+	// Serialize arguments into a byte vector.
+	let invoke_args_data = params
+		.iter()
+		.map(|val| {
+			from_wasmtime_val(val)
+				.ok_or_else(|| Trap::new(format!("Unsupported function argument: {:?}", val)))
+		})
+		.collect::<std::result::Result<Vec<_>, _>>()?
+		.encode();
+
+	// Move serialized arguments inside the memory, invoke dispatch thunk and
+	// then free allocated memory.
+	let invoke_args_len = invoke_args_data.len() as WordSize;
+	let invoke_args_ptr = sandbox_context
+		.supervisor_context()
+		.allocate_memory(invoke_args_len)
+		.map_err(|_| Trap::new("Can't allocate memory in supervisor for the arguments"))?;
+
+	let deallocate = |fe: &mut dyn FunctionContext, ptr, fail_msg| {
+		fe.deallocate_memory(ptr).map_err(|_| Trap::new(fail_msg))
+	};
+
+	if sandbox_context
+		.supervisor_context()
+		.write_memory(invoke_args_ptr, &invoke_args_data)
+		.is_err()
+	{
+		deallocate(
+			sandbox_context.supervisor_context(),
+			invoke_args_ptr,
+			"Failed dealloction after failed write of invoke arguments",
+		)?;
+
+		return Err(Trap::new("Can't write invoke args into memory"))
+	}
+
+	// Perform the actual call
+	let serialized_result = sandbox_context
+		.invoke(invoke_args_ptr, invoke_args_len, state, supervisor_func_index)
+		.map_err(|e| Trap::new(e.to_string()));
+
+	deallocate(
+		sandbox_context.supervisor_context(),
+		invoke_args_ptr,
+		"Failed dealloction after invoke",
+	)?;
+
+	let serialized_result = serialized_result?;
+
+	// dispatch_thunk returns pointer to serialized arguments.
+	// Unpack pointer and len of the serialized result data.
+	let (serialized_result_val_ptr, serialized_result_val_len) = {
+		// Cast to u64 to use zero-extension.
+		let v = serialized_result as u64;
+		let ptr = (v >> 32) as u32;
+		let len = (v & 0xFFFFFFFF) as u32;
+		(Pointer::new(ptr), len)
+	};
+
+	let serialized_result_val = sandbox_context
+		.supervisor_context()
+		.read_memory(serialized_result_val_ptr, serialized_result_val_len)
+		.map_err(|_| Trap::new("Can't read the serialized result from dispatch thunk"));
+
+	deallocate(
+		sandbox_context.supervisor_context(),
+		serialized_result_val_ptr,
+		"Can't deallocate memory for dispatch thunk's result",
+	)?;
+
+	let serialized_result_val = serialized_result_val?;
+
+	let deserialized_result = std::result::Result::<ReturnValue, HostError>::decode(
+		&mut serialized_result_val.as_slice(),
+	)
+	.map_err(|_| Trap::new("Decoding Result<ReturnValue, HostError> failed!"))?
+	.map_err(|_| Trap::new("Supervisor function returned sandbox::HostError"))?;
+
+	match (deserialized_result, results) {
+		(ReturnValue::Unit, []) => Ok(()),
+		(ReturnValue::Value(value), [result]) => {
+			*result = into_wasmtime_val(value);
+			Ok(())
+		},
+		_ => Err(Trap::new("Supervisor function returned unexpected result")),
+	}
+}
+
+fn into_wasmtime_val(value: Value) -> Val {
+	match value {
+		Value::I32(val) => Val::I32(val),
+		Value::I64(val) => Val::I64(val),
+		Value::F32(val) => Val::F32(val),
+		Value::F64(val) => Val::F64(val),
+	}
+}
+
+fn from_wasmtime_val(val: &Val) -> Option<Value> {
+	match *val {
+		Val::I32(val) => Some(Value::I32(val)),
+		Val::I64(val) => Some(Value::I64(val)),
+		Val::F32(val) => Some(Value::F32(val)),
+		Val::F64(val) => Some(Value::F64(val)),
+		_ => None,
+	}
+}
+
+/// Allocate new memory region
+pub fn new_memory(
+	context: &Backend,
+	initial: u32,
+	maximum: Option<u32>,
+) -> crate::error::Result<Memory> {
+	let ty = wasmtime::MemoryType::new(initial, maximum);
+	let memory = with_store(&context.store, |store| wasmtime::Memory::new(store, ty))
+		.map_err(|_| Error::InvalidMemoryReference)?;
+
+	Ok(Memory::Wasmtime(MemoryWrapper { memory, store: context.store.clone() }))
+}
+
+/// A wasmtime memory together with the store it lives in.
+///
+/// All memory operations go through the store, which enforces the memory access protocol.
+#[derive(Clone, Debug)]
+pub struct MemoryWrapper {
+	memory: wasmtime::Memory,
+	store: SharedStore,
+}
+
+impl MemoryTransfer for MemoryWrapper {
+	fn read(&self, source_addr: Pointer<u8>, size: usize) -> Result<Vec<u8>> {
+		let mut buffer = vec![0; size];
+		self.read_into(source_addr, &mut buffer)?;
+		Ok(buffer)
+	}
+
+	fn read_into(&self, source_addr: Pointer<u8>, destination: &mut [u8]) -> Result<()> {
+		with_store(&self.store, |store| {
+			let source = self.memory.data(&store);
+
+			let range = checked_range(source_addr.into(), destination.len(), source.len())
+				.ok_or_else(|| Error::Other("memory read is out of bounds".into()))?;
+
+			destination.copy_from_slice(&source[range]);
+			Ok(())
+		})
+	}
+
+	fn write_from(&self, dest_addr: Pointer<u8>, source: &[u8]) -> Result<()> {
+		with_store(&self.store, |store| {
+			let destination = self.memory.data_mut(store);
+
+			let range = checked_range(dest_addr.into(), source.len(), destination.len())
+				.ok_or_else(|| Error::Other("memory write is out of bounds".into()))?;
+
+			destination[range].copy_from_slice(source);
+			Ok(())
+		})
+	}
+}
+
+/// Get global value by name
+pub fn get_global(instance: &InstanceWrapper, name: &str) -> Option<Value> {
+	with_store(&instance.store, |mut store| {
+		let global = instance.instance.get_global(&mut store, name)?;
+		from_wasmtime_val(&global.get(&mut store))
+	})
+}
@@ -63,6 +63,15 @@ impl<'a> From<&'a str> for InvokeMethod<'a> {
 	}
 }
 
+/// The context in which a runtime call is made.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum CallContext {
+	/// The call is part of the on-chain execution, e.g. block import or block authorship.
+	Onchain,
+	/// The call is made outside of the on-chain execution, e.g. by an offchain worker or an RPC.
+	Offchain,
+}
+
 /// A trait that defines an abstract WASM runtime module.
 ///
 /// This can be implemented by an execution engine.
@@ -95,6 +104,24 @@ pub trait WasmInstance: Send {
 		data: &[u8],
 	) -> (Result<Vec<u8>, Error>, Option<AllocationStats>);
 
+	/// Call a method on this WASM instance in the given `context`.
+	///
+	/// Engines that meter the execution use the `context` to select the budget of the call, others
+	/// ignore it.
+	///
+	/// Before execution, instance is reset.
+	///
+	/// Returns the encoded result on success.
+	fn call_with_context(
+		&mut self,
+		method: InvokeMethod,
+		data: &[u8],
+		context: CallContext,
+	) -> Result<Vec<u8>, Error> {
+		let _ = context;
+		self.call(method, data)
+	}
+
 	/// Call an exported method on this WASM instance.
 	///
 	/// Before execution, instance is reset.
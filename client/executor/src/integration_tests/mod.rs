@@ -21,7 +21,11 @@ mod linux;
 mod sandbox;
 
 use codec::{Decode, Encode};
-use sc_executor_common::{error::Error, runtime_blob::RuntimeBlob, wasm_runtime::WasmModule};
+use sc_executor_common::{
+	error::Error,
+	runtime_blob::RuntimeBlob,
+	wasm_runtime::{InvokeMethod, WasmModule},
+};
 use sc_runtime_test::wasm_binary_unwrap;
 use sp_core::{
 	blake2_128, blake2_256, ed25519, map,
@@ -847,6 +851,58 @@ fn memory_is_cleared_between_invocations(wasm_method: WasmExecutionMethod) {
 	assert_eq!(1, u64::decode(&mut &res[..]).unwrap());
 }
 
+test_wasm_execution!(runtime_allocator_is_used_when_exported);
+fn runtime_allocator_is_used_when_exported(wasm_method: WasmExecutionMethod) {
+	// The runtime bumps every allocation by 1024 bytes starting at 65536 and `main` traps if the
+	// input data or the buffer returned by the host function were not allocated by it.
+	let binary = wat::parse_str(
+		r#"
+	(module
+	 (import "env" "ext_hashing_blake2_256_version_1" (func $blake2_256 (param i64) (result i32)))
+	 (global $bumper (mut i32) (i32.const 65536))
+	 (memory $0 2)
+	 (export "memory" (memory $0))
+	 (global (export "__heap_base") i32 (i32.const 65536))
+	 (func (export "__runtime_alloc") (param $size i32) (result i32)
+	  (global.get $bumper)
+	  (global.set $bumper (i32.add (global.get $bumper) (i32.const 1024)))
+	 )
+	 (func (export "__runtime_free") (param $ptr i32))
+	 (func (export "main") (param $data i32) (param $len i32) (result i64)
+	  (local $hash i32)
+	  (if (i32.ne (local.get $data) (i32.const 65536)) (then unreachable))
+	  (local.set $hash
+	   (call $blake2_256
+	    (i64.or
+	     (i64.extend_i32_u (local.get $data))
+	     (i64.shl (i64.extend_i32_u (local.get $len)) (i64.const 32))
+	    )
+	   )
+	  )
+	  (if (i32.ne (local.get $hash) (i32.const 66560)) (then unreachable))
+	  (i64.or (i64.extend_i32_u (local.get $hash)) (i64.const 137438953472))
+	 )
+	)"#,
+	)
+	.unwrap();
+
+	let runtime = crate::wasm_runtime::create_wasm_runtime_with_code::<HostFunctions>(
+		wasm_method,
+		1024,
+		RuntimeBlob::uncompress_if_needed(&binary[..]).unwrap(),
+		true,
+		None,
+	)
+	.unwrap();
+
+	let mut instance = runtime.new_instance().unwrap();
+	let (res, allocation_stats) =
+		instance.call_with_allocation_stats(InvokeMethod::Export("main"), b"Hello world!");
+	assert_eq!(res.unwrap(), blake2_256(b"Hello world!").to_vec());
+	// The host allocator isn't used.
+	assert!(allocation_stats.is_none());
+}
+
 test_wasm_execution!(return_i8);
 fn return_i8(wasm_method: WasmExecutionMethod) {
 	let mut ext = TestExternalities::default();
@@ -905,3 +961,40 @@ fn return_value(wasm_method: WasmExecutionMethod) {
 		(1234u64).encode()
 	);
 }
+
+#[test]
+#[cfg(feature = "wasmtime")]
+fn executor_meters_calls_by_call_context() {
+	use crate::{CallContext, CallContextExt};
+	use sp_core::traits::{CodeExecutor, RuntimeCode, WrappedRuntimeCode};
+
+	let mut executor = crate::WasmExecutor::<HostFunctions>::new(
+		WasmExecutionMethod::Compiled {
+			instantiation_strategy: sc_executor_wasmtime::InstantiationStrategy::PoolingCopyOnWrite,
+		},
+		Some(1024),
+		8,
+		None,
+		2,
+	);
+	executor.set_fuel_limits(Some(sc_executor_wasmtime::FuelLimits { offchain: 10 }));
+
+	let code = WrappedRuntimeCode(wasm_binary_unwrap().into());
+	let runtime_code =
+		RuntimeCode { code_fetcher: &code, hash: vec![0, 0, 0, 0], heap_pages: None };
+	let input = b"Hello world!".to_vec().encode();
+
+	// Calls are on-chain calls by default.
+	let mut ext = TestExternalities::default();
+	let (result, _) =
+		executor.call(&mut ext.ext(), &runtime_code, "test_blake2_256", &input, false);
+	assert_eq!(result.unwrap(), blake2_256(b"Hello world!").to_vec().encode());
+
+	ext.register_extension(CallContextExt(CallContext::Offchain));
+	let (result, _) =
+		executor.call(&mut ext.ext(), &runtime_code, "test_blake2_256", &input, false);
+	match result.unwrap_err() {
+		Error::OutOfFuel => {},
+		error => panic!("unexpected error: {:?}", error),
+	}
+}
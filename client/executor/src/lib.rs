@@ -39,7 +39,8 @@ mod wasm_runtime;
 
 pub use codec::Codec;
 pub use native_executor::{
-	with_externalities_safe, NativeElseWasmExecutor, NativeExecutionDispatch, WasmExecutor,
+	with_externalities_safe, CallContextExt, NativeElseWasmExecutor, NativeExecutionDispatch,
+	WasmExecutor,
 };
 #[doc(hidden)]
 pub use sp_core::traits::Externalities;
@@ -49,10 +50,14 @@ pub use sp_wasm_interface;
 pub use wasm_runtime::{read_embedded_version, WasmExecutionMethod};
 pub use wasmi;
 
-pub use sc_executor_common::{error, sandbox};
+pub use sc_executor_common::{error, profiler, sandbox, wasm_runtime::CallContext};
 
 #[cfg(feature = "wasmtime")]
-pub use sc_executor_wasmtime::InstantiationStrategy as WasmtimeInstantiationStrategy;
+pub use sc_executor_wasmtime::{
+	ArtifactCache as WasmtimeArtifactCache, FuelLimits as WasmtimeFuelLimits,
+	InstantiationStrategy as WasmtimeInstantiationStrategy,
+	DEFAULT_MAX_ARTIFACTS as WASMTIME_DEFAULT_MAX_ARTIFACTS,
+};
 
 /// Extracts the runtime version of a given runtime code.
 pub trait RuntimeVersionOf {
@@ -31,10 +31,12 @@ use std::{
 
 use codec::Encode;
 use sc_executor_common::{
+	profiler,
 	runtime_blob::RuntimeBlob,
-	wasm_runtime::{AllocationStats, WasmInstance, WasmModule},
+	wasm_runtime::{AllocationStats, CallContext, WasmInstance, WasmModule},
 };
 use sp_core::traits::{CodeExecutor, Externalities, RuntimeCode};
+use sp_externalities::ExternalitiesExt;
 use sp_version::{GetNativeVersion, NativeVersion, RuntimeVersion};
 use sp_wasm_interface::{ExtendedHostFunctions, HostFunctions};
 
@@ -64,6 +66,19 @@ where
 	})
 }
 
+sp_externalities::decl_extension! {
+	/// The context of the runtime calls made with the externalities this is registered in.
+	///
+	/// Calls made with externalities without this extension are [`CallContext::Onchain`] calls.
+	pub struct CallContextExt(CallContext);
+}
+
+/// Returns the context of the runtime calls made with `ext`, see [`CallContextExt`].
+fn call_context(mut ext: &mut dyn Externalities) -> CallContext {
+	ext.extension::<CallContextExt>()
+		.map_or(CallContext::Onchain, |context| context.0)
+}
+
 /// Delegate for dispatching a CodeExecutor call.
 ///
 /// By dispatching we mean that we execute a runtime function specified by it's name.
@@ -155,6 +170,26 @@ where
 		self.allow_missing_host_functions = allow_missing_host_functions
 	}
 
+	/// Persist the artifacts of the runtimes compiled with wasmtime in `artifact_cache`, so that
+	/// they are not recompiled e.g. after a restart of the node.
+	///
+	/// This clears the runtimes cached by this instance.
+	#[cfg(feature = "wasmtime")]
+	pub fn set_artifact_cache(&mut self, artifact_cache: sc_executor_wasmtime::ArtifactCache) {
+		self.cache = Arc::new(self.cache.with_artifact_cache(Arc::new(artifact_cache)));
+	}
+
+	/// Meter the calls into the runtimes compiled with wasmtime with the given `fuel_limits`.
+	///
+	/// The limit of a call is selected by its [`CallContext`], see [`CallContextExt`]. `None`
+	/// disables the metering, which is the default.
+	///
+	/// This clears the runtimes cached by this instance.
+	#[cfg(feature = "wasmtime")]
+	pub fn set_fuel_limits(&mut self, fuel_limits: Option<sc_executor_wasmtime::FuelLimits>) {
+		self.cache = Arc::new(self.cache.with_fuel_limits(fuel_limits));
+	}
+
 	/// Execute the given closure `f` with the latest runtime (based on `runtime_code`).
 	///
 	/// The closure `f` is expected to return `Err(_)` when there happened a `panic!` in native code
@@ -257,12 +292,14 @@ where
 		call_data: &[u8],
 		allocation_stats_out: &mut Option<AllocationStats>,
 	) -> std::result::Result<Vec<u8>, Error> {
-		let module = crate::wasm_runtime::create_wasm_runtime_with_code::<H>(
+		let module = crate::wasm_runtime::create_wasm_runtime_with_code_and_artifact_cache::<H>(
 			self.method,
 			self.default_heap_pages,
 			runtime_blob,
 			allow_missing_host_functions,
 			self.cache_path.as_deref(),
+			None,
+			self.cache.fuel_limits(),
 		)
 		.map_err(|e| format!("Failed to create module: {}", e))?;
 
@@ -274,8 +311,9 @@ where
 		let mut allocation_stats_out = AssertUnwindSafe(allocation_stats_out);
 
 		with_externalities_safe(&mut **ext, move || {
-			let (result, allocation_stats) =
-				instance.call_with_allocation_stats(export_name.into(), call_data);
+			let (result, allocation_stats) = profiler::profile_runtime_call(export_name, || {
+				instance.call_with_allocation_stats(export_name.into(), call_data)
+			});
 			**allocation_stats_out = allocation_stats;
 			result
 		})
@@ -341,9 +379,14 @@ where
 			"Executing function",
 		);
 
+		let context = call_context(ext);
 		let result =
 			self.with_instance(runtime_code, ext, |_, mut instance, _onchain_version, mut ext| {
-				with_externalities_safe(&mut **ext, move || instance.call_export(method, data))
+				with_externalities_safe(&mut **ext, move || {
+					profiler::profile_runtime_call(method, || {
+						instance.call_with_context(method.into(), data, context)
+					})
+				})
 			});
 		(result, false)
 	}
@@ -417,6 +460,22 @@ impl<D: NativeExecutionDispatch> NativeElseWasmExecutor<D> {
 	pub fn allow_missing_host_functions(&mut self, allow_missing_host_functions: bool) {
 		self.wasm.allow_missing_host_functions = allow_missing_host_functions
 	}
+
+	/// Persist the artifacts of the runtimes compiled with wasmtime in `artifact_cache`.
+	///
+	/// See [`WasmExecutor::set_artifact_cache`].
+	#[cfg(feature = "wasmtime")]
+	pub fn set_artifact_cache(&mut self, artifact_cache: sc_executor_wasmtime::ArtifactCache) {
+		self.wasm.set_artifact_cache(artifact_cache)
+	}
+
+	/// Meter the calls into the runtimes compiled with wasmtime with the given `fuel_limits`.
+	///
+	/// See [`WasmExecutor::set_fuel_limits`].
+	#[cfg(feature = "wasmtime")]
+	pub fn set_fuel_limits(&mut self, fuel_limits: Option<sc_executor_wasmtime::FuelLimits>) {
+		self.wasm.set_fuel_limits(fuel_limits)
+	}
 }
 
 impl<D: NativeExecutionDispatch> RuntimeVersionOf for NativeElseWasmExecutor<D> {
@@ -454,6 +513,7 @@ impl<D: NativeExecutionDispatch + 'static> CodeExecutor for NativeElseWasmExecut
 			"Executing function",
 		);
 
+		let context = call_context(ext);
 		let mut used_native = false;
 		let result = self.wasm.with_instance(
 			runtime_code,
@@ -486,7 +546,11 @@ impl<D: NativeExecutionDispatch + 'static> CodeExecutor for NativeElseWasmExecut
 						);
 					}
 
-					with_externalities_safe(&mut **ext, move || instance.call_export(method, data))
+					with_externalities_safe(&mut **ext, move || {
+						profiler::profile_runtime_call(method, || {
+							instance.call_with_context(method.into(), data, context)
+						})
+					})
 				}
 			},
 		);
@@ -32,6 +32,7 @@ use sc_executor_common::{
 use sp_core::traits::{Externalities, FetchRuntimeCode, RuntimeCode};
 use sp_version::RuntimeVersion;
 use std::{
+	collections::HashMap,
 	num::NonZeroUsize,
 	panic::AssertUnwindSafe,
 	path::{Path, PathBuf},
@@ -40,6 +41,18 @@ use std::{
 
 use sp_wasm_interface::HostFunctions;
 
+#[cfg(feature = "wasmtime")]
+use sc_executor_wasmtime::{ArtifactCache, FuelLimits};
+
+/// Persistent cache of compiled artifacts, only supported by wasmtime.
+#[cfg(not(feature = "wasmtime"))]
+pub(crate) enum ArtifactCache {}
+
+/// Fuel limits of the runtime calls, only supported by wasmtime.
+#[cfg(not(feature = "wasmtime"))]
+#[derive(Clone, Copy)]
+pub(crate) enum FuelLimits {}
+
 /// Specification of different methods of executing the runtime Wasm code.
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
 pub enum WasmExecutionMethod {
@@ -165,9 +178,15 @@ pub struct RuntimeCache {
 	///
 	/// Runtimes sorted by recent usage. The most recently used is at the front.
 	runtimes: Mutex<LruCache<VersionedRuntimeId, Arc<VersionedRuntime>>>,
+	/// The runtimes that are being created, so that each of them is only created once.
+	in_flight: Mutex<HashMap<VersionedRuntimeId, Arc<Mutex<()>>>>,
 	/// The size of the instances cache for each runtime.
 	max_runtime_instances: usize,
 	cache_path: Option<PathBuf>,
+	/// Persistent cache of the compiled runtime artifacts.
+	artifact_cache: Option<Arc<ArtifactCache>>,
+	/// The fuel available to the calls into the runtimes compiled with wasmtime.
+	fuel_limits: Option<FuelLimits>,
 }
 
 impl RuntimeCache {
@@ -188,7 +207,51 @@ impl RuntimeCache {
 	) -> RuntimeCache {
 		let cap =
 			NonZeroUsize::new(runtime_cache_size.max(1) as usize).expect("cache size is not zero");
-		RuntimeCache { runtimes: Mutex::new(LruCache::new(cap)), max_runtime_instances, cache_path }
+		RuntimeCache {
+			runtimes: Mutex::new(LruCache::new(cap)),
+			in_flight: Default::default(),
+			max_runtime_instances,
+			cache_path,
+			artifact_cache: None,
+			fuel_limits: None,
+		}
+	}
+
+	/// Creates a new empty runtimes cache with the same configuration, which persists the
+	/// artifacts of the runtimes compiled with wasmtime in `artifact_cache`.
+	///
+	/// This avoids recompiling the runtimes e.g. after a restart of the node.
+	#[cfg(feature = "wasmtime")]
+	pub fn with_artifact_cache(&self, artifact_cache: Arc<ArtifactCache>) -> RuntimeCache {
+		RuntimeCache {
+			runtimes: Mutex::new(LruCache::new(self.runtimes.lock().cap())),
+			in_flight: Default::default(),
+			max_runtime_instances: self.max_runtime_instances,
+			cache_path: self.cache_path.clone(),
+			artifact_cache: Some(artifact_cache),
+			fuel_limits: self.fuel_limits,
+		}
+	}
+
+	/// Creates a new empty runtimes cache with the same configuration, which meters the calls
+	/// into the runtimes compiled with wasmtime with the given `fuel_limits`.
+	///
+	/// See [`sc_executor_wasmtime::Semantics::fuel_limits`].
+	#[cfg(feature = "wasmtime")]
+	pub fn with_fuel_limits(&self, fuel_limits: Option<FuelLimits>) -> RuntimeCache {
+		RuntimeCache {
+			runtimes: Mutex::new(LruCache::new(self.runtimes.lock().cap())),
+			in_flight: Default::default(),
+			max_runtime_instances: self.max_runtime_instances,
+			cache_path: self.cache_path.clone(),
+			artifact_cache: self.artifact_cache.clone(),
+			fuel_limits,
+		}
+	}
+
+	/// Returns the fuel available to the calls into the runtimes compiled with wasmtime.
+	pub(crate) fn fuel_limits(&self) -> Option<FuelLimits> {
+		self.fuel_limits
 	}
 
 	/// Prepares a WASM module instance and executes given function for it.
@@ -239,51 +302,110 @@ impl RuntimeCache {
 		let versioned_runtime_id =
 			VersionedRuntimeId { code_hash: code_hash.clone(), heap_pages, wasm_method };
 
-		let mut runtimes = self.runtimes.lock(); // this must be released prior to calling f
-		let versioned_runtime = if let Some(versioned_runtime) = runtimes.get(&versioned_runtime_id)
-		{
-			versioned_runtime.clone()
-		} else {
-			let code = runtime_code.fetch_runtime_code().ok_or(WasmError::CodeNotFound)?;
+		let versioned_runtime = self.get_or_create_runtime::<H>(
+			versioned_runtime_id,
+			runtime_code,
+			ext,
+			allow_missing_func_imports,
+		)?;
+
+		Ok(versioned_runtime.with_instance(ext, f))
+	}
 
-			let time = std::time::Instant::now();
+	/// Returns the runtime with the given id from the cache, or creates it and adds it to the
+	/// cache.
+	///
+	/// The runtime is created without holding the lock of the cache, so that a runtime compiled in
+	/// the background doesn't block the calls into the cached runtimes. Concurrent calls for the
+	/// same runtime wait for the call creating it instead of creating it again.
+	fn get_or_create_runtime<H: HostFunctions>(
+		&self,
+		versioned_runtime_id: VersionedRuntimeId,
+		runtime_code: &RuntimeCode,
+		ext: &mut dyn Externalities,
+		allow_missing_func_imports: bool,
+	) -> Result<Arc<VersionedRuntime>, WasmError> {
+		if let Some(versioned_runtime) = self.runtimes.lock().get(&versioned_runtime_id) {
+			return Ok(versioned_runtime.clone())
+		}
 
-			let result = create_versioned_wasm_runtime::<H>(
-				&code,
-				ext,
-				wasm_method,
-				heap_pages,
-				allow_missing_func_imports,
-				self.max_runtime_instances,
-				self.cache_path.as_deref(),
-			);
-
-			match result {
-				Ok(ref result) => {
-					tracing::debug!(
-						target: "wasm-runtime",
-						"Prepared new runtime version {:?} in {} ms.",
-						result.version,
-						time.elapsed().as_millis(),
-					);
-				},
-				Err(ref err) => {
-					tracing::warn!(target: "wasm-runtime", error = ?err, "Cannot create a runtime");
-				},
+		let in_flight =
+			self.in_flight.lock().entry(versioned_runtime_id.clone()).or_default().clone();
+		let result = {
+			let _creating = in_flight.lock();
+
+			// The runtime may have been created while we were waiting.
+			let cached_runtime = self.runtimes.lock().get(&versioned_runtime_id).cloned();
+			match cached_runtime {
+				Some(versioned_runtime) => Ok(versioned_runtime),
+				None => self
+					.create_runtime::<H>(
+						&versioned_runtime_id,
+						runtime_code,
+						ext,
+						allow_missing_func_imports,
+					)
+					.map(|versioned_runtime| {
+						let versioned_runtime = Arc::new(versioned_runtime);
+						self.runtimes
+							.lock()
+							.put(versioned_runtime_id.clone(), versioned_runtime.clone());
+						versioned_runtime
+					}),
 			}
+		};
 
-			let versioned_runtime = Arc::new(result?);
-
-			// Save new versioned wasm runtime in cache
-			runtimes.put(versioned_runtime_id, versioned_runtime.clone());
+		let mut in_flight_runtimes = self.in_flight.lock();
+		if in_flight_runtimes
+			.get(&versioned_runtime_id)
+			.map_or(false, |creating| Arc::ptr_eq(creating, &in_flight))
+		{
+			in_flight_runtimes.remove(&versioned_runtime_id);
+		}
 
-			versioned_runtime
-		};
+		result
+	}
 
-		// Lock must be released prior to calling f
-		drop(runtimes);
+	fn create_runtime<H: HostFunctions>(
+		&self,
+		versioned_runtime_id: &VersionedRuntimeId,
+		runtime_code: &RuntimeCode,
+		ext: &mut dyn Externalities,
+		allow_missing_func_imports: bool,
+	) -> Result<VersionedRuntime, WasmError> {
+		let code = runtime_code.fetch_runtime_code().ok_or(WasmError::CodeNotFound)?;
+
+		let time = std::time::Instant::now();
+
+		let result = create_versioned_wasm_runtime::<H>(
+			&code,
+			ext,
+			versioned_runtime_id.wasm_method,
+			versioned_runtime_id.heap_pages,
+			allow_missing_func_imports,
+			self.max_runtime_instances,
+			self.cache_path.as_deref(),
+			self.artifact_cache
+				.as_deref()
+				.map(|cache| (&versioned_runtime_id.code_hash[..], cache)),
+			self.fuel_limits,
+		);
+
+		match result {
+			Ok(ref result) => {
+				tracing::debug!(
+					target: "wasm-runtime",
+					"Prepared new runtime version {:?} in {} ms.",
+					result.version,
+					time.elapsed().as_millis(),
+				);
+			},
+			Err(ref err) => {
+				tracing::warn!(target: "wasm-runtime", error = ?err, "Cannot create a runtime");
+			},
+		}
 
-		Ok(versioned_runtime.with_instance(ext, f))
+		result
 	}
 }
 
@@ -295,6 +417,31 @@ pub fn create_wasm_runtime_with_code<H>(
 	allow_missing_func_imports: bool,
 	cache_path: Option<&Path>,
 ) -> Result<Arc<dyn WasmModule>, WasmError>
+where
+	H: HostFunctions,
+{
+	create_wasm_runtime_with_code_and_artifact_cache::<H>(
+		wasm_method,
+		heap_pages,
+		blob,
+		allow_missing_func_imports,
+		cache_path,
+		None,
+		None,
+	)
+}
+
+/// Same as [`create_wasm_runtime_with_code`], but reuses the artifact compiled for the code with
+/// the given hash from the artifact cache if available, and meters the calls with `fuel_limits`.
+pub(crate) fn create_wasm_runtime_with_code_and_artifact_cache<H>(
+	wasm_method: WasmExecutionMethod,
+	heap_pages: u64,
+	blob: RuntimeBlob,
+	allow_missing_func_imports: bool,
+	cache_path: Option<&Path>,
+	artifact_cache: Option<(&[u8], &ArtifactCache)>,
+	fuel_limits: Option<FuelLimits>,
+) -> Result<Arc<dyn WasmModule>, WasmError>
 where
 	H: HostFunctions,
 {
@@ -305,6 +452,8 @@ where
 			// We drop the cache_path here to silence warnings that cache_path is not used if
 			// compiling without the `wasmtime` flag.
 			let _ = cache_path;
+			let _ = artifact_cache;
+			let _ = fuel_limits;
 
 			sc_executor_wasmi::create_runtime(
 				blob,
@@ -315,23 +464,33 @@ where
 			.map(|runtime| -> Arc<dyn WasmModule> { Arc::new(runtime) })
 		},
 		#[cfg(feature = "wasmtime")]
-		WasmExecutionMethod::Compiled { instantiation_strategy } =>
-			sc_executor_wasmtime::create_runtime::<H>(
-				blob,
-				sc_executor_wasmtime::Config {
-					allow_missing_func_imports,
-					cache_path: cache_path.map(ToOwned::to_owned),
-					semantics: sc_executor_wasmtime::Semantics {
-						extra_heap_pages: heap_pages,
-						instantiation_strategy,
-						deterministic_stack_limit: None,
-						canonicalize_nans: false,
-						parallel_compilation: true,
-						max_memory_size: None,
-					},
+		WasmExecutionMethod::Compiled { instantiation_strategy } => {
+			let config = sc_executor_wasmtime::Config {
+				allow_missing_func_imports,
+				cache_path: cache_path.map(ToOwned::to_owned),
+				semantics: sc_executor_wasmtime::Semantics {
+					extra_heap_pages: heap_pages,
+					instantiation_strategy,
+					deterministic_stack_limit: None,
+					canonicalize_nans: false,
+					parallel_compilation: true,
+					max_memory_size: None,
+					fuel_limits,
 				},
-			)
-			.map(|runtime| -> Arc<dyn WasmModule> { Arc::new(runtime) }),
+			};
+
+			match artifact_cache {
+				Some((code_hash, artifact_cache)) =>
+					sc_executor_wasmtime::create_runtime_with_artifact_cache::<H>(
+						blob,
+						code_hash,
+						config,
+						artifact_cache,
+					),
+				None => sc_executor_wasmtime::create_runtime::<H>(blob, config),
+			}
+			.map(|runtime| -> Arc<dyn WasmModule> { Arc::new(runtime) })
+		},
 	}
 }
 
@@ -399,6 +558,8 @@ fn create_versioned_wasm_runtime<H>(
 	allow_missing_func_imports: bool,
 	max_instances: usize,
 	cache_path: Option<&Path>,
+	artifact_cache: Option<(&[u8], &ArtifactCache)>,
+	fuel_limits: Option<FuelLimits>,
 ) -> Result<VersionedRuntime, WasmError>
 where
 	H: HostFunctions,
@@ -412,12 +573,14 @@ where
 	// runtime.
 	let mut version: Option<_> = read_embedded_version(&blob)?;
 
-	let runtime = create_wasm_runtime_with_code::<H>(
+	let runtime = create_wasm_runtime_with_code_and_artifact_cache::<H>(
 		wasm_method,
 		heap_pages,
 		blob,
 		allow_missing_func_imports,
 		cache_path,
+		artifact_cache,
+		fuel_limits,
 	)?;
 
 	// If the runtime blob doesn't embed the runtime version then use the legacy version query
@@ -468,6 +631,41 @@ mod tests {
 		pub apis: sp_version::ApisVec,
 	}
 
+	#[test]
+	fn concurrent_calls_share_the_created_runtime() {
+		let cache = Arc::new(RuntimeCache::new(1, None, 1));
+
+		let modules = (0..4)
+			.map(|_| {
+				let cache = cache.clone();
+				std::thread::spawn(move || {
+					let code = sp_core::traits::WrappedRuntimeCode(
+						substrate_test_runtime::wasm_binary_unwrap().into(),
+					);
+					let runtime_code =
+						RuntimeCode { code_fetcher: &code, heap_pages: None, hash: vec![1] };
+					cache
+						.with_instance::<sp_io::SubstrateHostFunctions, _, _>(
+							&runtime_code,
+							&mut sp_state_machine::BasicExternalities::default(),
+							WasmExecutionMethod::Interpreted,
+							1024,
+							false,
+							|module, _, _, _| Ok(module.clone()),
+						)
+						.unwrap()
+						.unwrap()
+				})
+			})
+			.collect::<Vec<_>>()
+			.into_iter()
+			.map(|thread| thread.join().unwrap())
+			.collect::<Vec<_>>();
+
+		assert!(modules.iter().all(|module| Arc::ptr_eq(module, &modules[0])));
+		assert!(cache.in_flight.lock().is_empty());
+	}
+
 	#[test]
 	fn host_functions_are_equal() {
 		let host_functions = sp_io::SubstrateHostFunctions::host_functions();
@@ -23,15 +23,17 @@ use std::{cell::RefCell, rc::Rc, str, sync::Arc};
 use log::{debug, error, trace};
 use wasmi::{
 	memory_units::Pages,
-	FuncInstance, ImportsBuilder, MemoryInstance, MemoryRef, Module, ModuleInstance, ModuleRef,
+	FuncInstance, FuncRef, ImportsBuilder, MemoryInstance, MemoryRef, Module, ModuleInstance,
+	ModuleRef,
 	RuntimeValue::{self, I32, I64},
-	TableRef,
+	Signature, TableRef, ValueType,
 };
 
 use codec::{Decode, Encode};
 use sc_allocator::AllocationStats;
 use sc_executor_common::{
 	error::{Error, MessageWithBacktrace, WasmError},
+	profiler,
 	runtime_blob::{DataSegmentsSnapshot, RuntimeBlob},
 	sandbox,
 	util::MemoryTransfer,
@@ -41,11 +43,20 @@ use sp_runtime_interface::unpack_ptr_and_len;
 use sp_sandbox::env as sandbox_env;
 use sp_wasm_interface::{
 	Function, FunctionContext, MemoryId, Pointer, Result as WResult, Sandbox, WordSize,
+	RUNTIME_ALLOC_EXPORT, RUNTIME_FREE_EXPORT,
 };
 
+/// The functions through which the runtime allocates and frees memory on behalf of the host.
+#[derive(Clone)]
+struct RuntimeAllocator {
+	alloc: FuncRef,
+	free: FuncRef,
+}
+
 struct FunctionExecutor {
 	sandbox_store: Rc<RefCell<sandbox::Store<wasmi::FuncRef>>>,
 	heap: RefCell<sc_allocator::FreeingBumpHeapAllocator>,
+	runtime_allocator: Option<RuntimeAllocator>,
 	memory: MemoryRef,
 	table: Option<TableRef>,
 	host_functions: Arc<Vec<&'static dyn Function>>,
@@ -58,6 +69,7 @@ impl FunctionExecutor {
 	fn new(
 		m: MemoryRef,
 		heap_base: u32,
+		runtime_allocator: Option<RuntimeAllocator>,
 		t: Option<TableRef>,
 		host_functions: Arc<Vec<&'static dyn Function>>,
 		allow_missing_func_imports: bool,
@@ -68,6 +80,7 @@ impl FunctionExecutor {
 				sandbox::SandboxBackend::Wasmi,
 			))),
 			heap: RefCell::new(sc_allocator::FreeingBumpHeapAllocator::new(heap_base)),
+			runtime_allocator,
 			memory: m,
 			table: t,
 			host_functions,
@@ -124,12 +137,27 @@ impl FunctionContext for FunctionExecutor {
 	}
 
 	fn allocate_memory(&mut self, size: WordSize) -> WResult<Pointer<u8>> {
+		if let Some(allocator) = self.runtime_allocator.clone() {
+			return match FuncInstance::invoke(&allocator.alloc, &[I32(size as i32)], self) {
+				Ok(Some(I32(0))) => Err(sc_allocator::Error::AllocatorOutOfSpace.to_string()),
+				Ok(Some(I32(ptr))) => Ok(Pointer::new(ptr as u32)),
+				Ok(_) => Err("runtime allocator returned unexpected result".into()),
+				Err(trap) => Err(format!("runtime allocator trapped: {}", trap)),
+			}
+		}
+
 		let heap = &mut self.heap.borrow_mut();
 		self.memory
 			.with_direct_access_mut(|mem| heap.allocate(mem, size).map_err(|e| e.to_string()))
 	}
 
 	fn deallocate_memory(&mut self, ptr: Pointer<u8>) -> WResult<()> {
+		if let Some(allocator) = self.runtime_allocator.clone() {
+			return FuncInstance::invoke(&allocator.free, &[I32(u32::from(ptr) as i32)], self)
+				.map(drop)
+				.map_err(|trap| format!("runtime allocator trapped: {}", trap))
+		}
+
 		let heap = &mut self.heap.borrow_mut();
 		self.memory
 			.with_direct_access_mut(|mem| heap.deallocate(mem, ptr).map_err(|e| e.to_string()))
@@ -436,9 +464,8 @@ impl wasmi::Externals for FunctionExecutor {
 	) -> Result<Option<wasmi::RuntimeValue>, wasmi::Trap> {
 		let mut args = args.as_ref().iter().copied().map(Into::into);
 
-		if let Some(function) = self.host_functions.clone().get(index) {
-			function
-				.execute(self, &mut args)
+		if let Some(&function) = self.host_functions.clone().get(index) {
+			profiler::profile_host_function(function.name(), || function.execute(self, &mut args))
 				.map_err(|msg| Error::FunctionExecution(function.name().to_string(), msg))
 				.map_err(wasmi::Trap::from)
 				.map(|v| v.map(Into::into))
@@ -482,6 +509,43 @@ fn get_heap_base(module: &ModuleRef) -> Result<u32, Error> {
 	}
 }
 
+/// Find the functions through which the runtime allocates and frees memory in the given wasm
+/// module instance.
+///
+/// Returns `None` if the runtime doesn't export them, in which case its heap is managed by the
+/// host.
+fn get_runtime_allocator(module: &ModuleRef) -> Result<Option<RuntimeAllocator>, Error> {
+	let get_func = |name: &str, signature: Signature| -> Result<Option<FuncRef>, Error> {
+		let func = match module.export_by_name(name) {
+			Some(export) => export
+				.as_func()
+				.cloned()
+				.ok_or_else(|| format!("`{}` is not a function", name))?,
+			None => return Ok(None),
+		};
+
+		if *func.signature() != signature {
+			return Err(format!("`{}` has an invalid signature", name).into())
+		}
+
+		Ok(Some(func))
+	};
+
+	let alloc = match get_func(
+		RUNTIME_ALLOC_EXPORT,
+		Signature::new(&[ValueType::I32][..], Some(ValueType::I32)),
+	)? {
+		Some(alloc) => alloc,
+		None => return Ok(None),
+	};
+	let free = get_func(RUNTIME_FREE_EXPORT, Signature::new(&[ValueType::I32][..], None))?
+		.ok_or_else(|| {
+			format!("`{}` is exported without `{}`", RUNTIME_ALLOC_EXPORT, RUNTIME_FREE_EXPORT)
+		})?;
+
+	Ok(Some(RuntimeAllocator { alloc, free }))
+}
+
 /// Call a given method in the given wasm-module runtime.
 fn call_in_wasm_module(
 	module_instance: &ModuleRef,
@@ -498,10 +562,12 @@ fn call_in_wasm_module(
 		.export_by_name("__indirect_function_table")
 		.and_then(|e| e.as_table().cloned());
 	let heap_base = get_heap_base(module_instance)?;
+	let runtime_allocator = get_runtime_allocator(module_instance)?;
 
 	let mut function_executor = FunctionExecutor::new(
 		memory.clone(),
 		heap_base,
+		runtime_allocator,
 		table.clone(),
 		host_functions,
 		allow_missing_func_imports,
@@ -564,7 +630,9 @@ fn call_in_wasm_module(
 		},
 	};
 
-	*allocation_stats = Some(function_executor.heap.borrow().stats());
+	if function_executor.runtime_allocator.is_none() {
+		*allocation_stats = Some(function_executor.heap.borrow().stats());
+	}
 
 	match result {
 		Ok(Some(I64(r))) => {
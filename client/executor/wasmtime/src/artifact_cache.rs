@@ -0,0 +1,423 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Persistent on-disk cache of compiled runtime artifacts.
+//!
+//! Artifacts are keyed by the hash of the runtime code, a fingerprint of the wasmtime engine
+//! (which covers the wasmtime version and the compilation settings) and the [`Semantics`]
+//! affecting the compiled code. Each artifact is stored next to a checksum which is verified
+//! before the artifact is loaded. Once more than the configured number of artifacts are stored,
+//! the least recently used ones are evicted.
+
+use std::{
+	collections::HashMap,
+	fs,
+	path::{Path, PathBuf},
+};
+
+use codec::Encode;
+use parking_lot::Mutex;
+use sc_executor_common::{error::WasmError, runtime_blob::RuntimeBlob};
+use sp_core_hashing::blake2_256;
+use sp_wasm_interface::HostFunctions;
+use wasmtime::Engine;
+
+use crate::{
+	runtime::{
+		common_config, create_runtime, create_runtime_from_artifact, prepare_runtime_artifact,
+		Config, DeterministicStackLimit, InstantiationStrategy, Semantics, WasmtimeRuntime,
+	},
+	util::replace_strategy_if_broken,
+};
+
+const ARTIFACT_EXTENSION: &str = "artifact";
+const CHECKSUM_EXTENSION: &str = "checksum";
+
+/// Default number of artifacts kept by an [`ArtifactCache`].
+pub const DEFAULT_MAX_ARTIFACTS: usize = 16;
+
+/// Persistent on-disk cache of compiled runtime artifacts.
+pub struct ArtifactCache {
+	dir: PathBuf,
+	max_artifacts: usize,
+	/// Serializes the accesses to the cache directory of this instance.
+	lock: Mutex<()>,
+	/// The engine fingerprints computed so far, by the encoded semantics they were computed for.
+	fingerprints: Mutex<HashMap<Vec<u8>, [u8; 32]>>,
+}
+
+impl ArtifactCache {
+	/// Create a new cache storing at most `max_artifacts` artifacts in `dir`.
+	///
+	/// The directory is created if it doesn't exist.
+	pub fn new(dir: impl Into<PathBuf>, max_artifacts: usize) -> std::io::Result<Self> {
+		let dir = dir.into();
+		fs::create_dir_all(&dir)?;
+		Ok(Self {
+			dir,
+			max_artifacts: max_artifacts.max(1),
+			lock: Mutex::new(()),
+			fingerprints: Mutex::new(HashMap::new()),
+		})
+	}
+
+	/// The directory in which the artifacts are stored.
+	pub fn dir(&self) -> &Path {
+		&self.dir
+	}
+
+	/// The key under which the artifact of the code with `code_hash` is stored.
+	fn artifact_key(&self, code_hash: &[u8], semantics: &Semantics) -> Result<String, WasmError> {
+		let encoded_semantics = encode_semantics(semantics);
+		let fingerprint = self.engine_fingerprint(semantics, &encoded_semantics)?;
+		let key = (code_hash, fingerprint, encoded_semantics).using_encoded(blake2_256);
+		Ok(key.iter().map(|byte| format!("{:02x}", byte)).collect())
+	}
+
+	/// The fingerprint of the engine configured for `semantics`, which is only computed once.
+	fn engine_fingerprint(
+		&self,
+		semantics: &Semantics,
+		encoded_semantics: &[u8],
+	) -> Result<[u8; 32], WasmError> {
+		if let Some(fingerprint) = self.fingerprints.lock().get(encoded_semantics) {
+			return Ok(*fingerprint)
+		}
+		let fingerprint = engine_fingerprint(semantics)?;
+		self.fingerprints.lock().insert(encoded_semantics.to_vec(), fingerprint);
+		Ok(fingerprint)
+	}
+
+	fn path(&self, key: &str, extension: &str) -> PathBuf {
+		self.dir.join(key).with_extension(extension)
+	}
+
+	/// Return the path of the stored artifact if its checksum is valid.
+	fn load(&self, key: &str) -> Option<PathBuf> {
+		let artifact_path = self.path(key, ARTIFACT_EXTENSION);
+		let checksum_path = self.path(key, CHECKSUM_EXTENSION);
+
+		let artifact = fs::read(&artifact_path).ok()?;
+		let checksum = fs::read(&checksum_path).ok()?;
+		if checksum[..] != blake2_256(&artifact)[..] {
+			log::warn!(
+				target: "wasmtime-runtime",
+				"Corrupted runtime artifact found at {}, removing it.",
+				artifact_path.display(),
+			);
+			self.remove(key);
+			return None
+		}
+
+		// Refresh the modification time of the checksum, which is used for eviction.
+		let _ = fs::write(&checksum_path, checksum);
+		Some(artifact_path)
+	}
+
+	/// Store the artifact, returning its path.
+	fn store(&self, key: &str, artifact: &[u8]) -> std::io::Result<PathBuf> {
+		let artifact_path = self.path(key, ARTIFACT_EXTENSION);
+
+		// Write to a temporary file first, so that a crash can never leave a truncated artifact
+		// with a valid name behind.
+		let tmp_path = self.path(key, "tmp");
+		fs::write(&tmp_path, artifact)?;
+		fs::rename(&tmp_path, &artifact_path)?;
+		fs::write(self.path(key, CHECKSUM_EXTENSION), blake2_256(artifact))?;
+
+		self.evict();
+		Ok(artifact_path)
+	}
+
+	fn remove(&self, key: &str) {
+		let _ = fs::remove_file(self.path(key, CHECKSUM_EXTENSION));
+		let _ = fs::remove_file(self.path(key, ARTIFACT_EXTENSION));
+	}
+
+	/// Remove the least recently used artifacts until at most `max_artifacts` remain.
+	fn evict(&self) {
+		let entries = match fs::read_dir(&self.dir) {
+			Ok(entries) => entries,
+			Err(_) => return,
+		};
+		let mut artifacts = entries
+			.filter_map(|entry| {
+				let path = entry.ok()?.path();
+				if path.extension()? != CHECKSUM_EXTENSION {
+					return None
+				}
+				let last_used = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+				Some((last_used, path.file_stem()?.to_str()?.to_owned()))
+			})
+			.collect::<Vec<_>>();
+
+		if artifacts.len() <= self.max_artifacts {
+			return
+		}
+
+		artifacts.sort();
+		for (_, key) in &artifacts[..artifacts.len() - self.max_artifacts] {
+			self.remove(key);
+		}
+	}
+}
+
+/// The parts of `semantics` which affect the compiled code, encoded.
+fn encode_semantics(semantics: &Semantics) -> Vec<u8> {
+	let DeterministicStackLimit { logical_max, native_stack_max } = semantics
+		.deterministic_stack_limit
+		.clone()
+		.unwrap_or(DeterministicStackLimit { logical_max: 0, native_stack_max: 0 });
+	let instantiation_strategy = match semantics.instantiation_strategy {
+		InstantiationStrategy::PoolingCopyOnWrite => 0u8,
+		InstantiationStrategy::RecreateInstanceCopyOnWrite => 1,
+		InstantiationStrategy::Pooling => 2,
+		InstantiationStrategy::RecreateInstance => 3,
+		InstantiationStrategy::LegacyInstanceReuse => 4,
+	};
+	(
+		semantics.extra_heap_pages,
+		semantics.deterministic_stack_limit.is_some(),
+		logical_max,
+		native_stack_max,
+		semantics.canonicalize_nans,
+		semantics.parallel_compilation,
+		semantics.fuel_limits.is_some(),
+		instantiation_strategy,
+		semantics.max_memory_size.map(|size| size as u64),
+	)
+		.encode()
+}
+
+/// Fingerprint of the wasmtime engine configured for `semantics`.
+fn engine_fingerprint(semantics: &Semantics) -> Result<[u8; 32], WasmError> {
+	let engine = Engine::new(&common_config(semantics)?)
+		.map_err(|e| WasmError::Other(format!("cannot create the wasmtime engine: {:#}", e)))?;
+	// The precompiled module embeds the wasmtime version and the engine settings, which must
+	// match for an artifact to be loaded.
+	let empty_module = engine
+		.precompile_module(b"\0asm\x01\0\0\0")
+		.map_err(|e| WasmError::Other(format!("cannot precompile module: {:#}", e)))?;
+	Ok(blake2_256(&empty_module))
+}
+
+/// Same as [`create_runtime`], but reuses the artifact compiled for the code with the same
+/// `code_hash` from `cache` if available, and stores the compiled artifact into it otherwise.
+///
+/// Falls back to [`create_runtime`] for the
+/// [`InstantiationStrategy::LegacyInstanceReuse`] strategy, which doesn't support precompiled
+/// artifacts, and when the cache can't be used.
+pub fn create_runtime_with_artifact_cache<H>(
+	blob: RuntimeBlob,
+	code_hash: &[u8],
+	mut config: Config,
+	cache: &ArtifactCache,
+) -> Result<WasmtimeRuntime, WasmError>
+where
+	H: HostFunctions,
+{
+	replace_strategy_if_broken(&mut config.semantics.instantiation_strategy);
+	if let InstantiationStrategy::LegacyInstanceReuse = config.semantics.instantiation_strategy {
+		return create_runtime::<H>(blob, config)
+	}
+
+	let key = cache.artifact_key(code_hash, &config.semantics)?;
+	let _lock = cache.lock.lock();
+
+	if let Some(artifact_path) = cache.load(&key) {
+		// SAFETY: The artifact was produced by `prepare_runtime_artifact`, and its checksum was
+		//         just verified. The cache directory is owned by the node.
+		match unsafe { create_runtime_from_artifact::<H>(&artifact_path, config.clone()) } {
+			Ok(runtime) => return Ok(runtime),
+			Err(e) => {
+				log::debug!(
+					target: "wasmtime-runtime",
+					"Cannot use cached runtime artifact, recompiling: {}",
+					e,
+				);
+				cache.remove(&key);
+			},
+		}
+	}
+
+	let artifact = prepare_runtime_artifact(blob.clone(), &config.semantics)?;
+	match cache.store(&key, &artifact) {
+		// SAFETY: The artifact was just produced by `prepare_runtime_artifact`.
+		Ok(artifact_path) => unsafe { create_runtime_from_artifact::<H>(&artifact_path, config) },
+		Err(e) => {
+			log::warn!(
+				target: "wasmtime-runtime",
+				"Cannot store runtime artifact in {}: {}",
+				cache.dir.display(),
+				e,
+			);
+			create_runtime::<H>(blob, config)
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::FuelLimits;
+
+	type HostFunctions = sp_io::SubstrateHostFunctions;
+
+	fn config() -> Config {
+		Config {
+			allow_missing_func_imports: true,
+			cache_path: None,
+			semantics: Semantics {
+				instantiation_strategy: InstantiationStrategy::RecreateInstance,
+				deterministic_stack_limit: None,
+				canonicalize_nans: false,
+				parallel_compilation: true,
+				extra_heap_pages: 1024,
+				max_memory_size: None,
+				fuel_limits: None,
+			},
+		}
+	}
+
+	fn blob() -> RuntimeBlob {
+		let wat = r#"
+			(module
+				(memory $0 1)
+				(export "memory" (memory $0))
+				(global (export "__heap_base") i32 (i32.const 0))
+				(func (export "main") (param i32 i32) (result i64)
+					(i64.const 0)
+				)
+			)
+		"#;
+		RuntimeBlob::new(&wat::parse_str(wat).unwrap()).unwrap()
+	}
+
+	fn stored_artifacts(cache: &ArtifactCache) -> usize {
+		fs::read_dir(cache.dir())
+			.unwrap()
+			.filter(|e| e.as_ref().unwrap().path().extension().unwrap() == ARTIFACT_EXTENSION)
+			.count()
+	}
+
+	#[test]
+	fn artifact_key_depends_on_code_and_semantics() {
+		let dir = tempfile::tempdir().unwrap();
+		let cache = ArtifactCache::new(dir.path(), DEFAULT_MAX_ARTIFACTS).unwrap();
+		let semantics = config().semantics;
+
+		let key = cache.artifact_key(b"code", &semantics).unwrap();
+		assert_eq!(key, cache.artifact_key(b"code", &semantics).unwrap());
+		assert_ne!(key, cache.artifact_key(b"other code", &semantics).unwrap());
+
+		let mut other_semantics = semantics.clone();
+		other_semantics.extra_heap_pages += 1;
+		assert_ne!(key, cache.artifact_key(b"code", &other_semantics).unwrap());
+
+		let mut other_semantics = semantics.clone();
+		other_semantics.canonicalize_nans = true;
+		assert_ne!(key, cache.artifact_key(b"code", &other_semantics).unwrap());
+
+		let mut other_semantics = semantics.clone();
+		other_semantics.fuel_limits = Some(FuelLimits { offchain: 1 });
+		assert_ne!(key, cache.artifact_key(b"code", &other_semantics).unwrap());
+	}
+
+	#[test]
+	fn engine_fingerprint_is_computed_once_per_semantics() {
+		let dir = tempfile::tempdir().unwrap();
+		let cache = ArtifactCache::new(dir.path(), DEFAULT_MAX_ARTIFACTS).unwrap();
+		let semantics = config().semantics;
+
+		cache.artifact_key(b"code", &semantics).unwrap();
+		cache.artifact_key(b"other code", &semantics).unwrap();
+		assert_eq!(cache.fingerprints.lock().len(), 1);
+
+		let mut other_semantics = semantics.clone();
+		other_semantics.canonicalize_nans = true;
+		cache.artifact_key(b"code", &other_semantics).unwrap();
+		assert_eq!(cache.fingerprints.lock().len(), 2);
+		assert_eq!(
+			cache.fingerprints.lock().get(&encode_semantics(&semantics)),
+			Some(&engine_fingerprint(&semantics).unwrap())
+		);
+	}
+
+	#[test]
+	fn artifacts_are_cached() {
+		let dir = tempfile::tempdir().unwrap();
+		let cache = ArtifactCache::new(dir.path(), DEFAULT_MAX_ARTIFACTS).unwrap();
+
+		create_runtime_with_artifact_cache::<HostFunctions>(blob(), b"code", config(), &cache)
+			.unwrap();
+		assert_eq!(stored_artifacts(&cache), 1);
+
+		let key = cache.artifact_key(b"code", &config().semantics).unwrap();
+		let artifact = fs::read(cache.path(&key, ARTIFACT_EXTENSION)).unwrap();
+
+		// The cached artifact is reused.
+		create_runtime_with_artifact_cache::<HostFunctions>(blob(), b"code", config(), &cache)
+			.unwrap();
+		assert_eq!(stored_artifacts(&cache), 1);
+		assert_eq!(cache.load(&key), Some(cache.path(&key, ARTIFACT_EXTENSION)));
+		assert_eq!(fs::read(cache.path(&key, ARTIFACT_EXTENSION)).unwrap(), artifact);
+	}
+
+	#[test]
+	fn corrupted_artifacts_are_not_loaded() {
+		let dir = tempfile::tempdir().unwrap();
+		let cache = ArtifactCache::new(dir.path(), DEFAULT_MAX_ARTIFACTS).unwrap();
+
+		create_runtime_with_artifact_cache::<HostFunctions>(blob(), b"code", config(), &cache)
+			.unwrap();
+		let key = cache.artifact_key(b"code", &config().semantics).unwrap();
+		let artifact_path = cache.path(&key, ARTIFACT_EXTENSION);
+		let mut artifact = fs::read(&artifact_path).unwrap();
+		*artifact.last_mut().unwrap() ^= 1;
+		fs::write(&artifact_path, artifact).unwrap();
+
+		assert_eq!(cache.load(&key), None);
+		assert_eq!(stored_artifacts(&cache), 0);
+
+		// The artifact is compiled and stored again.
+		create_runtime_with_artifact_cache::<HostFunctions>(blob(), b"code", config(), &cache)
+			.unwrap();
+		assert!(cache.load(&key).is_some());
+	}
+
+	#[test]
+	fn least_recently_used_artifacts_are_evicted() {
+		let dir = tempfile::tempdir().unwrap();
+		let cache = ArtifactCache::new(dir.path(), 2).unwrap();
+		let semantics = config().semantics;
+		let key = |code: &[u8]| cache.artifact_key(code, &semantics).unwrap();
+
+		cache.store(&key(b"a"), b"artifact a").unwrap();
+		std::thread::sleep(std::time::Duration::from_millis(10));
+		cache.store(&key(b"b"), b"artifact b").unwrap();
+		std::thread::sleep(std::time::Duration::from_millis(10));
+		assert!(cache.load(&key(b"a")).is_some());
+		std::thread::sleep(std::time::Duration::from_millis(10));
+		cache.store(&key(b"c"), b"artifact c").unwrap();
+
+		assert_eq!(stored_artifacts(&cache), 2);
+		assert!(cache.load(&key(b"a")).is_some());
+		assert!(cache.load(&key(b"b")).is_none());
+		assert!(cache.load(&key(b"c")).is_some());
+	}
+}
@@ -20,7 +20,7 @@
 //! required for execution of host.
 
 use log::trace;
-use wasmtime::{Caller, Func, Val};
+use wasmtime::{AsContextMut, Caller, Func, TypedFunc, Val};
 
 use codec::{Decode, Encode};
 use sc_allocator::{AllocationStats, FreeingBumpHeapAllocator};
@@ -41,21 +41,78 @@ struct SandboxStore(Option<Box<sandbox::Store<Func>>>);
 // those within one thread so this should be safe.
 unsafe impl Send for SandboxStore {}
 
+/// The functions through which the runtime allocates and frees memory on behalf of the host.
+///
+/// See [`RUNTIME_ALLOC_EXPORT`](sp_wasm_interface::RUNTIME_ALLOC_EXPORT) and
+/// [`RUNTIME_FREE_EXPORT`](sp_wasm_interface::RUNTIME_FREE_EXPORT).
+#[derive(Clone, Copy)]
+pub(crate) struct RuntimeAllocator {
+	alloc: TypedFunc<u32, u32>,
+	free: TypedFunc<u32, ()>,
+}
+
+impl RuntimeAllocator {
+	/// Constructs a new `RuntimeAllocator` out of the exported functions.
+	pub(crate) fn new(alloc: TypedFunc<u32, u32>, free: TypedFunc<u32, ()>) -> Self {
+		Self { alloc, free }
+	}
+
+	/// Allocates `size` bytes in the runtime's heap.
+	pub(crate) fn allocate(
+		&self,
+		store: impl AsContextMut<Data = StoreData>,
+		size: WordSize,
+	) -> Result<Pointer<u8>> {
+		let ptr = self
+			.alloc
+			.call(store, size)
+			.map_err(|trap| format!("runtime allocator trapped: {}", trap))?;
+
+		if ptr == 0 {
+			return Err(sc_allocator::Error::AllocatorOutOfSpace.into())
+		}
+
+		Ok(Pointer::new(ptr))
+	}
+
+	/// Frees the allocation at `ptr` in the runtime's heap.
+	pub(crate) fn deallocate(
+		&self,
+		store: impl AsContextMut<Data = StoreData>,
+		ptr: Pointer<u8>,
+	) -> Result<()> {
+		self.free
+			.call(store, u32::from(ptr))
+			.map_err(|trap| format!("runtime allocator trapped: {}", trap).into())
+	}
+}
+
+/// The allocator managing the heap of the runtime.
+pub(crate) enum Allocator {
+	/// The heap is managed by the host.
+	Host(FreeingBumpHeapAllocator),
+	/// The heap is managed by the runtime itself.
+	Runtime(RuntimeAllocator),
+}
+
 /// The state required to construct a HostContext context. The context only lasts for one host
 /// call, whereas the state is maintained for the duration of a Wasm runtime call, which may make
 /// many different host calls that must share state.
 pub struct HostState {
 	sandbox_store: SandboxStore,
-	allocator: FreeingBumpHeapAllocator,
+	allocator: Allocator,
 	panic_message: Option<String>,
 }
 
 impl HostState {
 	/// Constructs a new `HostState`.
-	pub fn new(allocator: FreeingBumpHeapAllocator) -> Self {
+	pub(crate) fn new(allocator: Allocator) -> Self {
 		HostState {
 			sandbox_store: SandboxStore(Some(Box::new(sandbox::Store::new(
+				#[cfg(not(feature = "wasmtime-sandbox"))]
 				sandbox::SandboxBackend::TryWasmer,
+				#[cfg(feature = "wasmtime-sandbox")]
+				sandbox::SandboxBackend::TryWasmtime,
 			)))),
 			allocator,
 			panic_message: None,
@@ -67,8 +124,28 @@ impl HostState {
 		self.panic_message.take()
 	}
 
-	pub(crate) fn allocation_stats(&self) -> AllocationStats {
-		self.allocator.stats()
+	/// Returns the statistics of the host allocator, `None` if the heap is managed by the runtime.
+	pub(crate) fn allocation_stats(&self) -> Option<AllocationStats> {
+		match &self.allocator {
+			Allocator::Host(allocator) => Some(allocator.stats()),
+			Allocator::Runtime(_) => None,
+		}
+	}
+
+	fn runtime_allocator(&self) -> Option<RuntimeAllocator> {
+		match self.allocator {
+			Allocator::Host(_) => None,
+			Allocator::Runtime(allocator) => Some(allocator),
+		}
+	}
+
+	fn host_allocator(&mut self) -> &mut FreeingBumpHeapAllocator {
+		match &mut self.allocator {
+			Allocator::Host(allocator) => allocator,
+			Allocator::Runtime(_) => unreachable!(
+				"the host allocator is only used if the runtime doesn't export one; qed"
+			),
+		}
 	}
 }
 
@@ -125,21 +202,29 @@ impl<'a> sp_wasm_interface::FunctionContext for HostContext<'a> {
 	}
 
 	fn allocate_memory(&mut self, size: WordSize) -> sp_wasm_interface::Result<Pointer<u8>> {
+		if let Some(allocator) = self.host_state().runtime_allocator() {
+			return allocator.allocate(&mut self.caller, size).map_err(|e| e.to_string())
+		}
+
 		let memory = self.caller.data().memory();
 		let (memory, data) = memory.data_and_store_mut(&mut self.caller);
 		data.host_state_mut()
 			.expect("host state is not empty when calling a function in wasm; qed")
-			.allocator
+			.host_allocator()
 			.allocate(memory, size)
 			.map_err(|e| e.to_string())
 	}
 
 	fn deallocate_memory(&mut self, ptr: Pointer<u8>) -> sp_wasm_interface::Result<()> {
+		if let Some(allocator) = self.host_state().runtime_allocator() {
+			return allocator.deallocate(&mut self.caller, ptr).map_err(|e| e.to_string())
+		}
+
 		let memory = self.caller.data().memory();
 		let (memory, data) = memory.data_and_store_mut(&mut self.caller);
 		data.host_state_mut()
 			.expect("host state is not empty when calling a function in wasm; qed")
-			.allocator
+			.host_allocator()
 			.deallocate(memory, ptr)
 			.map_err(|e| e.to_string())
 	}
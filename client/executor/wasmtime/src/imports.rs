@@ -17,7 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{host::HostContext, runtime::StoreData};
-use sc_executor_common::error::WasmError;
+use sc_executor_common::{error::WasmError, profiler};
 use sp_wasm_interface::{FunctionContext, HostFunctions};
 use std::collections::HashMap;
 use wasmtime::{ExternType, FuncType, ImportType, Linker, Module, Trap};
@@ -104,6 +104,10 @@ impl<'a, 'b> sp_wasm_interface::HostFunctionRegistry for Registry<'a, 'b> {
 		callback(&mut HostContext { caller })
 	}
 
+	fn call_host_function<R>(fn_name: &'static str, call: impl FnOnce() -> R) -> R {
+		profiler::profile_host_function(fn_name, call)
+	}
+
 	fn register_static<Params, Results>(
 		&mut self,
 		fn_name: &str,
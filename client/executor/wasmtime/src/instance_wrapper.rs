@@ -19,12 +19,15 @@
 //! Defines data and logic needed for interaction with an WebAssembly instance of a substrate
 //! runtime module.
 
-use crate::runtime::{Store, StoreData};
+use crate::{
+	host::RuntimeAllocator,
+	runtime::{Store, StoreData},
+};
 use sc_executor_common::{
 	error::{Backtrace, Error, MessageWithBacktrace, Result, WasmError},
 	wasm_runtime::InvokeMethod,
 };
-use sp_wasm_interface::{Pointer, Value, WordSize};
+use sp_wasm_interface::{Pointer, Value, WordSize, RUNTIME_ALLOC_EXPORT, RUNTIME_FREE_EXPORT};
 use wasmtime::{
 	AsContext, AsContextMut, Engine, Extern, Func, Global, Instance, InstancePre, Memory, Table,
 	Val,
@@ -70,6 +73,12 @@ impl EntryPoint {
 				dispatcher.call(&mut *store, (func, data_ptr, data_len)),
 		}
 		.map_err(|trap| {
+			// Running out of fuel traps with a host error rather than a trap code, so we look at
+			// the fuel left in the store instead.
+			let out_of_fuel = store
+				.fuel_consumed()
+				.map_or(false, |consumed| consumed >= store.data().fuel_added);
+
 			let host_state = store
 				.data_mut()
 				.host_state
@@ -92,6 +101,8 @@ impl EntryPoint {
 					message: error,
 					backtrace: Some(backtrace),
 				})
+			} else if out_of_fuel {
+				Error::OutOfFuel
 			} else {
 				Error::AbortedDueToTrap(MessageWithBacktrace {
 					message: trap.display_reason().to_string(),
@@ -168,8 +179,10 @@ pub(crate) fn create_store(engine: &wasmtime::Engine, max_memory_size: Option<us
 		Default::default()
 	};
 
-	let mut store =
-		Store::new(engine, StoreData { limits, host_state: None, memory: None, table: None });
+	let mut store = Store::new(
+		engine,
+		StoreData { limits, host_state: None, memory: None, table: None, fuel_added: 0 },
+	);
 	if max_memory_size.is_some() {
 		store.limiter(|s| &mut s.limits);
 	}
@@ -280,6 +293,32 @@ impl InstanceWrapper {
 		Ok(heap_base as u32)
 	}
 
+	/// Extract the functions through which the runtime allocates and frees memory.
+	///
+	/// Returns `None` if the runtime doesn't export them, in which case its heap is managed by
+	/// the host.
+	pub(crate) fn extract_runtime_allocator(&mut self) -> Result<Option<RuntimeAllocator>> {
+		let alloc = match self.instance.get_export(&mut self.store, RUNTIME_ALLOC_EXPORT) {
+			Some(alloc) => alloc,
+			None => return Ok(None),
+		};
+		let free =
+			self.instance.get_export(&mut self.store, RUNTIME_FREE_EXPORT).ok_or_else(|| {
+				format!("`{}` is exported without `{}`", RUNTIME_ALLOC_EXPORT, RUNTIME_FREE_EXPORT)
+			})?;
+
+		let alloc = extern_func(&alloc)
+			.ok_or_else(|| format!("`{}` is not a function", RUNTIME_ALLOC_EXPORT))?
+			.typed::<u32, u32, _>(&self.store)
+			.map_err(|e| format!("`{}` has an invalid signature: {}", RUNTIME_ALLOC_EXPORT, e))?;
+		let free = extern_func(&free)
+			.ok_or_else(|| format!("`{}` is not a function", RUNTIME_FREE_EXPORT))?
+			.typed::<u32, (), _>(&self.store)
+			.map_err(|e| format!("`{}` has an invalid signature: {}", RUNTIME_FREE_EXPORT, e))?;
+
+		Ok(Some(RuntimeAllocator::new(alloc, free)))
+	}
+
 	/// Get the value from a global with the given `name`.
 	pub fn get_global_val(&mut self, name: &str) -> Result<Option<Value>> {
 		let global = match self.instance.get_export(&mut self.store, name) {
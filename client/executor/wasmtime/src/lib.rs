@@ -27,6 +27,7 @@
 //! | `"jitdump"` | jitdump profiling       |
 //! | other value | No profiling (warning)  |
 
+mod artifact_cache;
 mod host;
 mod imports;
 mod instance_wrapper;
@@ -36,7 +37,10 @@ mod util;
 #[cfg(test)]
 mod tests;
 
+pub use artifact_cache::{
+	create_runtime_with_artifact_cache, ArtifactCache, DEFAULT_MAX_ARTIFACTS,
+};
 pub use runtime::{
 	create_runtime, create_runtime_from_artifact, prepare_runtime_artifact, Config,
-	DeterministicStackLimit, InstantiationStrategy, Semantics,
+	DeterministicStackLimit, FuelLimits, InstantiationStrategy, Semantics,
 };
@@ -19,7 +19,7 @@
 //! Defines the compiled Wasm runtime that uses Wasmtime internally.
 
 use crate::{
-	host::HostState,
+	host::{Allocator, HostState},
 	instance_wrapper::{EntryPoint, InstanceWrapper},
 	util::{self, replace_strategy_if_broken},
 };
@@ -30,7 +30,7 @@ use sc_executor_common::{
 	runtime_blob::{
 		self, DataSegmentsSnapshot, ExposedMutableGlobalsSet, GlobalsSnapshot, RuntimeBlob,
 	},
-	wasm_runtime::{InvokeMethod, WasmInstance, WasmModule},
+	wasm_runtime::{CallContext, InvokeMethod, WasmInstance, WasmModule},
 };
 use sp_runtime_interface::unpack_ptr_and_len;
 use sp_wasm_interface::{HostFunctions, Pointer, Value, WordSize};
@@ -53,6 +53,8 @@ pub(crate) struct StoreData {
 	pub(crate) memory: Option<Memory>,
 	/// This will be set only if the runtime actually contains a table.
 	pub(crate) table: Option<Table>,
+	/// The total amount of fuel ever added to the store.
+	pub(crate) fuel_added: u64,
 }
 
 impl StoreData {
@@ -174,7 +176,7 @@ impl WasmModule for WasmtimeRuntime {
 			}),
 		};
 
-		Ok(Box::new(WasmtimeInstance { strategy }))
+		Ok(Box::new(WasmtimeInstance { strategy, fuel_limits: self.config.semantics.fuel_limits }))
 	}
 }
 
@@ -182,6 +184,7 @@ impl WasmModule for WasmtimeRuntime {
 /// to execute the compiled code.
 pub struct WasmtimeInstance {
 	strategy: Strategy,
+	fuel_limits: Option<FuelLimits>,
 }
 
 impl WasmtimeInstance {
@@ -189,8 +192,11 @@ impl WasmtimeInstance {
 		&mut self,
 		method: InvokeMethod,
 		data: &[u8],
+		context: CallContext,
 		allocation_stats: &mut Option<AllocationStats>,
 	) -> Result<Vec<u8>> {
+		let fuel = self.fuel_limits.map(|limits| limits.for_context(context));
+
 		match &mut self.strategy {
 			Strategy::LegacyInstanceReuse {
 				ref mut instance_wrapper,
@@ -208,10 +214,16 @@ impl WasmtimeInstance {
 					)
 				})?;
 				globals_snapshot.apply(&mut InstanceGlobals { instance: instance_wrapper });
-				let allocator = FreeingBumpHeapAllocator::new(*heap_base);
+				let allocator = create_allocator(instance_wrapper, *heap_base)?;
 
-				let result =
-					perform_call(data, instance_wrapper, entrypoint, allocator, allocation_stats);
+				let result = perform_call(
+					data,
+					instance_wrapper,
+					entrypoint,
+					allocator,
+					fuel,
+					allocation_stats,
+				);
 
 				// Signal to the OS that we are done with the linear memory and that it can be
 				// reclaimed.
@@ -224,8 +236,15 @@ impl WasmtimeInstance {
 				let heap_base = instance_wrapper.extract_heap_base()?;
 				let entrypoint = instance_wrapper.resolve_entrypoint(method)?;
 
-				let allocator = FreeingBumpHeapAllocator::new(heap_base);
-				perform_call(data, &mut instance_wrapper, entrypoint, allocator, allocation_stats)
+				let allocator = create_allocator(&mut instance_wrapper, heap_base)?;
+				perform_call(
+					data,
+					&mut instance_wrapper,
+					entrypoint,
+					allocator,
+					fuel,
+					allocation_stats,
+				)
 			},
 		}
 	}
@@ -238,10 +257,19 @@ impl WasmInstance for WasmtimeInstance {
 		data: &[u8],
 	) -> (Result<Vec<u8>>, Option<AllocationStats>) {
 		let mut allocation_stats = None;
-		let result = self.call_impl(method, data, &mut allocation_stats);
+		let result = self.call_impl(method, data, CallContext::Onchain, &mut allocation_stats);
 		(result, allocation_stats)
 	}
 
+	fn call_with_context(
+		&mut self,
+		method: InvokeMethod,
+		data: &[u8],
+		context: CallContext,
+	) -> Result<Vec<u8>> {
+		self.call_impl(method, data, context, &mut None)
+	}
+
 	fn get_global_const(&mut self, name: &str) -> Result<Option<Value>> {
 		match &mut self.strategy {
 			Strategy::LegacyInstanceReuse { instance_wrapper, .. } =>
@@ -302,7 +330,9 @@ directory = \"{cache_dir}\"
 	Ok(())
 }
 
-fn common_config(semantics: &Semantics) -> std::result::Result<wasmtime::Config, WasmError> {
+pub(crate) fn common_config(
+	semantics: &Semantics,
+) -> std::result::Result<wasmtime::Config, WasmError> {
 	let mut config = wasmtime::Config::new();
 	config.cranelift_opt_level(wasmtime::OptLevel::SpeedAndSize);
 	config.cranelift_nan_canonicalization(semantics.canonicalize_nans);
@@ -336,6 +366,8 @@ fn common_config(semantics: &Semantics) -> std::result::Result<wasmtime::Config,
 
 	config.parallel_compilation(semantics.parallel_compilation);
 
+	config.consume_fuel(semantics.fuel_limits.is_some());
+
 	// Be clear and specific about the extensions we support. If an update brings new features
 	// they should be introduced here as well.
 	config.wasm_reference_types(false);
@@ -539,6 +571,43 @@ pub struct Semantics {
 	///
 	/// The default is `None`.
 	pub max_memory_size: Option<usize>,
+
+	/// The amount of fuel a single off-chain call can consume.
+	///
+	/// If specified, the compiled code is instrumented to consume fuel as it executes and a call
+	/// which runs out of fuel is aborted with [`Error::OutOfFuel`]. Most wasm instructions
+	/// consume one unit of fuel, host functions consume none.
+	///
+	/// On-chain calls are not limited. Their outcome must not depend on the configuration of the
+	/// node, otherwise nodes with different limits could disagree on the validity of a block.
+	///
+	/// The default is `None`, i.e. the execution is not metered.
+	///
+	/// [`Error::OutOfFuel`]: sc_executor_common::error::Error::OutOfFuel
+	pub fuel_limits: Option<FuelLimits>,
+}
+
+/// The amount of fuel a single call can consume, see [`Semantics::fuel_limits`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FuelLimits {
+	/// The fuel available to calls made in [`CallContext::Offchain`], e.g. by offchain workers or
+	/// RPCs.
+	pub offchain: u64,
+}
+
+impl FuelLimits {
+	/// The fuel available to calls made in [`CallContext::Onchain`].
+	///
+	/// This is the most fuel a store can hold, which is practically unlimited.
+	pub const ONCHAIN: u64 = i64::MAX as u64;
+
+	/// Returns the fuel available to a call made in the given `context`.
+	pub fn for_context(&self, context: CallContext) -> u64 {
+		match context {
+			CallContext::Onchain => Self::ONCHAIN,
+			CallContext::Offchain => self.offchain,
+		}
+	}
 }
 
 #[derive(Clone)]
@@ -758,9 +827,15 @@ fn perform_call(
 	data: &[u8],
 	instance_wrapper: &mut InstanceWrapper,
 	entrypoint: EntryPoint,
-	mut allocator: FreeingBumpHeapAllocator,
+	mut allocator: Allocator,
+	fuel: Option<u64>,
 	allocation_stats: &mut Option<AllocationStats>,
 ) -> Result<Vec<u8>> {
+	// Refuel first, the runtime allocator consumes fuel to inject the input data.
+	if let Some(fuel) = fuel {
+		refuel(instance_wrapper.store_mut(), fuel)?;
+	}
+
 	let (data_ptr, data_len) = inject_input_data(instance_wrapper, &mut allocator, data)?;
 
 	let host_state = HostState::new(allocator);
@@ -776,7 +851,7 @@ fn perform_call(
 	let host_state = instance_wrapper.store_mut().data_mut().host_state.take().expect(
 		"the host state is always set before calling into WASM so it can't be None here; qed",
 	);
-	*allocation_stats = Some(host_state.allocation_stats());
+	*allocation_stats = host_state.allocation_stats();
 
 	let (output_ptr, output_len) = ret?;
 	let output = extract_output_data(instance_wrapper, output_ptr, output_len)?;
@@ -784,16 +859,55 @@ fn perform_call(
 	Ok(output)
 }
 
+/// Set the fuel of the `store` to exactly `fuel`.
+///
+/// The fuel left over by a previous call into a reused instance is discarded.
+fn refuel(store: &mut Store, fuel: u64) -> Result<()> {
+	// The store doesn't expose the remaining fuel, so we derive it from the fuel we added. It is
+	// negative if the previous call ran out of fuel in the middle of an instruction sequence.
+	let consumed = store.fuel_consumed().ok_or("fuel consumption is not enabled")?;
+	let remaining = i128::from(store.data().fuel_added) - i128::from(consumed);
+	let fuel_i128 = i128::from(fuel);
+
+	let result = if remaining > fuel_i128 {
+		store.consume_fuel((remaining - fuel_i128) as u64).map(drop)
+	} else {
+		// The store caps the fuel it holds, so we read back how much it actually got.
+		store.add_fuel((fuel_i128 - remaining) as u64).and_then(|()| {
+			let remaining = store.consume_fuel(0)?;
+			store.data_mut().fuel_added = consumed.saturating_add(remaining);
+			Ok(())
+		})
+	};
+
+	result.map_err(|error| format!("failed to refuel the store: {:#}", error).into())
+}
+
+/// Create the allocator managing the heap of the instance during a call.
+///
+/// The heap is managed by the runtime if it exports an allocator, otherwise by the host.
+fn create_allocator(instance_wrapper: &mut InstanceWrapper, heap_base: u32) -> Result<Allocator> {
+	Ok(match instance_wrapper.extract_runtime_allocator()? {
+		Some(allocator) => Allocator::Runtime(allocator),
+		None => Allocator::Host(FreeingBumpHeapAllocator::new(heap_base)),
+	})
+}
+
 fn inject_input_data(
 	instance: &mut InstanceWrapper,
-	allocator: &mut FreeingBumpHeapAllocator,
+	allocator: &mut Allocator,
 	data: &[u8],
 ) -> Result<(Pointer<u8>, WordSize)> {
-	let mut ctx = instance.store_mut();
-	let memory = ctx.data().memory();
-	let memory = memory.data_mut(&mut ctx);
 	let data_len = data.len() as WordSize;
-	let data_ptr = allocator.allocate(memory, data_len)?;
+	let data_ptr = match allocator {
+		Allocator::Host(allocator) => {
+			let mut ctx = instance.store_mut();
+			let memory = ctx.data().memory();
+			let memory = memory.data_mut(&mut ctx);
+			allocator.allocate(memory, data_len)?
+		},
+		Allocator::Runtime(allocator) => allocator.allocate(instance.store_mut(), data_len)?,
+	};
 	util::write_memory_from(instance.store_mut(), data_ptr, data)?;
 	Ok((data_ptr, data_len))
 }
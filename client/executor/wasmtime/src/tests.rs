@@ -17,10 +17,14 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use codec::{Decode as _, Encode as _};
-use sc_executor_common::{error::Error, runtime_blob::RuntimeBlob, wasm_runtime::WasmModule};
+use sc_executor_common::{
+	error::Error,
+	runtime_blob::RuntimeBlob,
+	wasm_runtime::{CallContext, WasmModule},
+};
 use sc_runtime_test::wasm_binary_unwrap;
 
-use crate::InstantiationStrategy;
+use crate::{FuelLimits, InstantiationStrategy};
 
 type HostFunctions = sp_io::SubstrateHostFunctions;
 
@@ -79,6 +83,7 @@ struct RuntimeBuilder {
 	deterministic_stack: bool,
 	extra_heap_pages: u64,
 	max_memory_size: Option<usize>,
+	fuel_limits: Option<FuelLimits>,
 	precompile_runtime: bool,
 	tmpdir: Option<tempfile::TempDir>,
 }
@@ -92,6 +97,7 @@ impl RuntimeBuilder {
 			deterministic_stack: false,
 			extra_heap_pages: 1024,
 			max_memory_size: None,
+			fuel_limits: None,
 			precompile_runtime: false,
 			tmpdir: None,
 		}
@@ -122,6 +128,11 @@ impl RuntimeBuilder {
 		self
 	}
 
+	fn fuel_limits(mut self, fuel_limits: Option<FuelLimits>) -> Self {
+		self.fuel_limits = fuel_limits;
+		self
+	}
+
 	fn build(&mut self) -> impl WasmModule + '_ {
 		let blob = {
 			let wasm: Vec<u8>;
@@ -154,6 +165,7 @@ impl RuntimeBuilder {
 				parallel_compilation: true,
 				extra_heap_pages: self.extra_heap_pages,
 				max_memory_size: self.max_memory_size,
+				fuel_limits: self.fuel_limits,
 			},
 		};
 
@@ -311,6 +323,65 @@ fn test_stack_depth_reaching(instantiation_strategy: InstantiationStrategy) {
 	}
 }
 
+fn busy_loop_wat(iterations: u32) -> String {
+	format!(
+		r#"
+			(module
+			  (memory $0 32)
+			  (export "memory" (memory $0))
+			  (global (export "__heap_base") i32 (i32.const 0))
+
+			  (func (export "main")
+			    (param i32 i32) (result i64)
+			    (local $i i32)
+			    (local.set $i (i32.const {iterations}))
+			    (loop $loop
+			      (local.set $i (i32.sub (local.get $i) (i32.const 1)))
+			      (br_if $loop (local.get $i))
+			    )
+			    (i64.const 0)
+			  )
+			)
+		"#
+	)
+}
+
+test_wasm_execution!(test_fuel_limits_depend_on_call_context);
+fn test_fuel_limits_depend_on_call_context(instantiation_strategy: InstantiationStrategy) {
+	let mut builder = RuntimeBuilder::new(instantiation_strategy)
+		.use_wat(busy_loop_wat(10_000))
+		.fuel_limits(Some(FuelLimits { offchain: 1_000 }));
+	let runtime = builder.build();
+	let mut instance = runtime.new_instance().expect("failed to instantiate a runtime");
+
+	// The budget is reset before every call, so the instance can be reused.
+	for _ in 0..2 {
+		instance.call_with_context("main".into(), &[], CallContext::Onchain).unwrap();
+
+		match instance
+			.call_with_context("main".into(), &[], CallContext::Offchain)
+			.unwrap_err()
+		{
+			Error::OutOfFuel => {},
+			error => panic!("unexpected error: {:?}", error),
+		}
+	}
+
+	// Calls without a context are on-chain calls.
+	instance.call_export("main", &[]).unwrap();
+}
+
+test_wasm_execution!(test_unmetered_execution_does_not_run_out_of_fuel);
+fn test_unmetered_execution_does_not_run_out_of_fuel(
+	instantiation_strategy: InstantiationStrategy,
+) {
+	let mut builder = RuntimeBuilder::new(instantiation_strategy).use_wat(busy_loop_wat(10_000));
+	let runtime = builder.build();
+	let mut instance = runtime.new_instance().expect("failed to instantiate a runtime");
+
+	instance.call_with_context("main".into(), &[], CallContext::Offchain).unwrap();
+}
+
 test_wasm_execution!(test_max_memory_pages_imported_memory_without_precompilation);
 fn test_max_memory_pages_imported_memory_without_precompilation(
 	instantiation_strategy: InstantiationStrategy,
@@ -540,6 +611,7 @@ fn test_instances_without_reuse_are_not_leaked() {
 				parallel_compilation: true,
 				extra_heap_pages: 2048,
 				max_memory_size: None,
+				fuel_limits: None,
 			},
 		},
 	)
@@ -0,0 +1,57 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+
+use parity_scale_codec::{Codec, Encode};
+use sc_client_api::ProofProvider;
+use sc_finality_grandpa::AncestryProofProvider;
+use sp_api::ProvideRuntimeApi;
+use sp_mmr_primitives::MmrApi;
+use sp_runtime::traits::{Block as BlockT, NumberFor};
+
+#[derive(Serialize, Deserialize)]
+pub struct EncodedAncestryProof(pub sp_core::Bytes);
+
+/// Local trait mainly to allow mocking in tests.
+pub trait RpcAncestryProofProvider<Block: BlockT> {
+	/// Prove that the given block is an ancestor of the latest block finalized with a
+	/// justification.
+	fn rpc_prove_ancestry(
+		&self,
+		block: NumberFor<Block>,
+	) -> Result<Option<EncodedAncestryProof>, sc_finality_grandpa::AncestryProofError>;
+}
+
+impl<B, Client, Block, MmrHash> RpcAncestryProofProvider<Block>
+	for AncestryProofProvider<B, Client, Block, MmrHash>
+where
+	Block: BlockT,
+	B: sc_client_api::backend::Backend<Block> + Send + Sync + 'static,
+	Client: ProvideRuntimeApi<Block> + ProofProvider<Block>,
+	Client::Api: MmrApi<Block, MmrHash, NumberFor<Block>>,
+	MmrHash: Codec,
+{
+	fn rpc_prove_ancestry(
+		&self,
+		block: NumberFor<Block>,
+	) -> Result<Option<EncodedAncestryProof>, sc_finality_grandpa::AncestryProofError> {
+		self.prove_ancestry(block)
+			.map(|x| x.map(|y| EncodedAncestryProof(y.encode().into())))
+	}
+}
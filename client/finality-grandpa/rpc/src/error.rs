@@ -36,6 +36,9 @@ pub enum Error {
 	/// GRANDPA prove finality failed.
 	#[error("GRANDPA prove finality rpc failed: {0}")]
 	ProveFinalityFailed(#[from] sc_finality_grandpa::FinalityProofError),
+	/// GRANDPA prove ancestry failed.
+	#[error("GRANDPA prove ancestry rpc failed: {0}")]
+	ProveAncestryFailed(#[from] sc_finality_grandpa::AncestryProofError),
 }
 
 /// The error codes returned by jsonrpc.
@@ -48,6 +51,8 @@ pub enum ErrorCode {
 	VoterStateTooLarge,
 	/// Failed to prove finality.
 	ProveFinality,
+	/// Failed to prove ancestry.
+	ProveAncestry,
 }
 
 impl From<Error> for ErrorCode {
@@ -57,6 +62,7 @@ impl From<Error> for ErrorCode {
 			Error::AuthoritySetIdReportedAsUnreasonablyLarge => ErrorCode::AuthoritySetTooLarge,
 			Error::VoterStateReportsUnreasonablyLargeNumbers => ErrorCode::VoterStateTooLarge,
 			Error::ProveFinalityFailed(_) => ErrorCode::ProveFinality,
+			Error::ProveAncestryFailed(_) => ErrorCode::ProveAncestry,
 		}
 	}
 }
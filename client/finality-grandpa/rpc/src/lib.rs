@@ -21,7 +21,7 @@
 
 use futures::{FutureExt, StreamExt};
 use log::warn;
-use std::sync::Arc;
+use std::{marker::PhantomData, sync::Arc};
 
 use jsonrpsee::{
 	core::{async_trait, RpcResult},
@@ -30,6 +30,7 @@ use jsonrpsee::{
 	SubscriptionSink,
 };
 
+mod ancestry;
 mod error;
 mod finality;
 mod notification;
@@ -39,6 +40,7 @@ use sc_finality_grandpa::GrandpaJustificationStream;
 use sc_rpc::SubscriptionTaskExecutor;
 use sp_runtime::traits::{Block as BlockT, NumberFor};
 
+use ancestry::{EncodedAncestryProof, RpcAncestryProofProvider};
 use finality::{EncodedFinalityProof, RpcFinalityProofProvider};
 use notification::JustificationNotification;
 use report::{ReportAuthoritySet, ReportVoterState, ReportedRoundStates};
@@ -66,6 +68,15 @@ pub trait GrandpaApi<Notification, Hash, Number> {
 	async fn prove_finality(&self, block: Number) -> RpcResult<Option<EncodedFinalityProof>>;
 }
 
+/// Provides RPC methods for proving ancestry of GRANDPA-finalized blocks.
+#[rpc(client, server)]
+pub trait GrandpaAncestryApi<Number> {
+	/// Prove that the given block is an ancestor of the latest block finalized with a
+	/// justification, by returning a compact MMR-based ancestry proof.
+	#[method(name = "grandpa_proveAncestry")]
+	async fn prove_ancestry(&self, block: Number) -> RpcResult<Option<EncodedAncestryProof>>;
+}
+
 /// Provides RPC methods for interacting with GRANDPA.
 pub struct Grandpa<AuthoritySet, VoterState, Block: BlockT, ProofProvider> {
 	executor: SubscriptionTaskExecutor,
@@ -132,6 +143,40 @@ where
 	}
 }
 
+/// Provides RPC methods for proving ancestry of GRANDPA-finalized blocks.
+pub struct GrandpaAncestry<Block, ProofProvider> {
+	ancestry_proof_provider: Arc<ProofProvider>,
+	_phantom: PhantomData<Block>,
+}
+
+impl<Block, ProofProvider> GrandpaAncestry<Block, ProofProvider> {
+	/// Prepare a new [`GrandpaAncestry`] Rpc handler.
+	pub fn new(ancestry_proof_provider: Arc<ProofProvider>) -> Self {
+		Self { ancestry_proof_provider, _phantom: PhantomData }
+	}
+}
+
+#[async_trait]
+impl<Block, ProofProvider> GrandpaAncestryApiServer<NumberFor<Block>>
+	for GrandpaAncestry<Block, ProofProvider>
+where
+	Block: BlockT,
+	ProofProvider: RpcAncestryProofProvider<Block> + Send + Sync + 'static,
+{
+	async fn prove_ancestry(
+		&self,
+		block: NumberFor<Block>,
+	) -> RpcResult<Option<EncodedAncestryProof>> {
+		self.ancestry_proof_provider
+			.rpc_prove_ancestry(block)
+			.map_err(|e| {
+				warn!("Error proving ancestry: {}", e);
+				error::Error::ProveAncestryFailed(e)
+			})
+			.map_err(Into::into)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -148,6 +193,7 @@ mod tests {
 	};
 	use sp_blockchain::HeaderBackend;
 	use sp_core::{crypto::ByteArray, testing::TaskExecutor};
+	use sp_finality_grandpa_ancestry::AncestryProof;
 	use sp_keyring::Ed25519Keyring;
 	use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
 	use substrate_test_runtime_client::{
@@ -422,4 +468,43 @@ mod tests {
 		let finality_proof_rpc: FinalityProof<Header> = Decode::decode(&mut &bytes[..]).unwrap();
 		assert_eq!(finality_proof_rpc, finality_proof);
 	}
+
+	struct TestAncestryProofProvider {
+		ancestry_proof: AncestryProof<Header, H256>,
+	}
+
+	impl RpcAncestryProofProvider<Block> for TestAncestryProofProvider {
+		fn rpc_prove_ancestry(
+			&self,
+			block: NumberFor<Block>,
+		) -> Result<Option<EncodedAncestryProof>, sc_finality_grandpa::AncestryProofError> {
+			if block > *self.ancestry_proof.finalized_header.number() {
+				return Err(sc_finality_grandpa::AncestryProofError::BlockNotYetFinalized)
+			}
+			Ok(Some(EncodedAncestryProof(self.ancestry_proof.encode().into())))
+		}
+	}
+
+	#[tokio::test]
+	async fn prove_ancestry_with_test_ancestry_proof_provider() {
+		let ancestry_proof = AncestryProof {
+			finalized_header: header(42),
+			justification: create_justification().encode(),
+			mmr_proof: None,
+		};
+		let rpc = GrandpaAncestry::<Block, _>::new(Arc::new(TestAncestryProofProvider {
+			ancestry_proof: ancestry_proof.clone(),
+		}))
+		.into_rpc();
+
+		let bytes: sp_core::Bytes = rpc.call("grandpa_proveAncestry", [42]).await.unwrap();
+		let ancestry_proof_rpc: AncestryProof<Header, H256> =
+			Decode::decode(&mut &bytes[..]).unwrap();
+		assert_eq!(ancestry_proof_rpc, ancestry_proof);
+
+		let request = r#"{"jsonrpc":"2.0","method":"grandpa_proveAncestry","params":[43],"id":0}"#;
+		let (response, _) = rpc.raw_json_request(&request).await.unwrap();
+		let expected_response = r#"{"jsonrpc":"2.0","error":{"code":5,"message":"GRANDPA prove ancestry rpc failed: Block not yet finalized"},"id":0}"#;
+		assert_eq!(response.result, expected_response);
+	}
 }
@@ -0,0 +1,159 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! GRANDPA compact ancestry proof generation.
+//!
+//! Ancestry of block B is proved against the latest block F for which we hold a GRANDPA
+//! justification, by providing:
+//! 1) the header and justification of block F;
+//! 2) a storage proof of the MMR root at block F;
+//! 3) the MMR leaf added by block B + 1 (which commits to B) and its inclusion proof.
+//!
+//! Unlike [`FinalityProof`](crate::FinalityProof)s, the size of the proof does not depend on the
+//! distance between B and F, nor on the number of authority set changes in between. See
+//! [`sp_finality_grandpa_ancestry`] for verification.
+
+use log::trace;
+use std::{marker::PhantomData, sync::Arc};
+
+use parity_scale_codec::{Codec, Encode};
+use sc_client_api::{backend::Backend, ProofProvider};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_finality_grandpa_ancestry::{AncestryProof, MmrAncestryProof};
+use sp_mmr_primitives::MmrApi;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, NumberFor, One},
+};
+
+use crate::{best_justification, justification::GrandpaJustification};
+
+/// Errors occurring when trying to prove ancestry.
+#[derive(Debug, thiserror::Error)]
+pub enum AncestryProofError {
+	/// The requested block has not yet been finalized.
+	#[error("Block not yet finalized")]
+	BlockNotYetFinalized,
+	/// The runtime failed to generate the MMR proof.
+	#[error("Failed to generate MMR proof: {0}")]
+	Mmr(#[from] sp_mmr_primitives::Error),
+	/// Errors originating from the client.
+	#[error(transparent)]
+	Client(#[from] sp_blockchain::Error),
+}
+
+/// Ancestry proof provider for serving RPC requests.
+pub struct AncestryProofProvider<BE, Client, Block, MmrHash> {
+	backend: Arc<BE>,
+	client: Arc<Client>,
+	mmr_root_key: Vec<u8>,
+	_phantom: PhantomData<(Block, MmrHash)>,
+}
+
+impl<BE, Client, Block, MmrHash> AncestryProofProvider<BE, Client, Block, MmrHash> {
+	/// Create new ancestry proof provider using:
+	///
+	/// - backend for accessing justifications and headers;
+	/// - client for calling the MMR runtime API and proving storage reads;
+	/// - mmr_root_key, the storage key of the MMR root (see
+	///   [`sp_finality_grandpa_ancestry::mmr_root_storage_key`]).
+	pub fn new(backend: Arc<BE>, client: Arc<Client>, mmr_root_key: Vec<u8>) -> Self {
+		AncestryProofProvider { backend, client, mmr_root_key, _phantom: PhantomData }
+	}
+
+	/// Create new ancestry proof provider for the service, see [`Self::new`].
+	pub fn new_for_service(
+		backend: Arc<BE>,
+		client: Arc<Client>,
+		mmr_root_key: Vec<u8>,
+	) -> Arc<Self> {
+		Arc::new(Self::new(backend, client, mmr_root_key))
+	}
+}
+
+impl<BE, Client, Block, MmrHash> AncestryProofProvider<BE, Client, Block, MmrHash>
+where
+	Block: BlockT,
+	BE: Backend<Block>,
+	Client: ProvideRuntimeApi<Block> + ProofProvider<Block>,
+	Client::Api: MmrApi<Block, MmrHash, NumberFor<Block>>,
+	MmrHash: Codec,
+{
+	/// Prove that the given block is an ancestor of the latest block finalized with a
+	/// justification.
+	///
+	/// Returns `None` if no justification is known yet.
+	pub fn prove_ancestry(
+		&self,
+		block: NumberFor<Block>,
+	) -> Result<Option<AncestryProof<Block::Header, MmrHash>>, AncestryProofError> {
+		let justification: GrandpaJustification<Block> =
+			if let Some(justification) = best_justification(&*self.backend)? {
+				justification
+			} else {
+				trace!(
+					target: "afg",
+					"No justification found for the latest finalized block. \
+					Returning empty ancestry proof.",
+				);
+				return Ok(None)
+			};
+
+		let (finalized_number, finalized_hash) = justification.target();
+		if finalized_number < block {
+			trace!(
+				target: "afg",
+				"Requested ancestry proof for #{} while we only have justified #{}.",
+				block,
+				finalized_number,
+			);
+			return Err(AncestryProofError::BlockNotYetFinalized)
+		}
+
+		let finalized_header =
+			self.backend.blockchain().expect_header(BlockId::Hash(finalized_hash))?;
+
+		let mmr_proof = if block == finalized_number {
+			None
+		} else {
+			// The MMR leaf added by block B + 1 commits to its parent B.
+			let (mut leaves, leaf_proof) = self
+				.client
+				.runtime_api()
+				.generate_proof(&BlockId::Hash(finalized_hash), vec![block + One::one()], None)
+				.map_err(sp_blockchain::Error::RuntimeApiError)??;
+			let leaf = match (leaves.pop(), leaves.is_empty()) {
+				(Some(leaf), true) => leaf,
+				_ => return Err(AncestryProofError::Mmr(sp_mmr_primitives::Error::GenerateProof)),
+			};
+
+			let mmr_root_proof = self
+				.client
+				.read_proof(finalized_hash, &mut std::iter::once(&self.mmr_root_key[..]))?;
+
+			Some(MmrAncestryProof { mmr_root_proof, leaf: leaf.0, leaf_proof })
+		};
+
+		Ok(Some(AncestryProof {
+			finalized_header,
+			justification: justification.encode(),
+			mmr_proof,
+		}))
+	}
+}
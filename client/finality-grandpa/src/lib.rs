@@ -110,6 +110,7 @@ macro_rules! afg_log {
 	};
 }
 
+mod ancestry_proof;
 mod authorities;
 mod aux_schema;
 mod communication;
@@ -123,6 +124,7 @@ mod until_imported;
 mod voting_rule;
 pub mod warp_proof;
 
+pub use ancestry_proof::{AncestryProofError, AncestryProofProvider};
 pub use authorities::{AuthoritySet, AuthoritySetChanges, SharedAuthoritySet};
 pub use aux_schema::best_justification;
 pub use communication::grandpa_protocol_name::standard_name as protocol_standard_name;
@@ -19,11 +19,16 @@
 
 use async_trait::async_trait;
 use parking_lot::RwLock;
+#[cfg(feature = "bandersnatch-experimental")]
+use sp_application_crypto::bandersnatch;
+#[cfg(feature = "bls-experimental")]
+use sp_application_crypto::{bls377, bls381};
 use sp_application_crypto::{ecdsa, ed25519, sr25519, AppKey, AppPair, IsWrappedBy};
 use sp_core::{
 	crypto::{
 		ByteArray, CryptoTypePublicPair, ExposeSecret, KeyTypeId, Pair as PairT, SecretString,
 	},
+	hashing::blake2_256,
 	sr25519::{Pair as Sr25519Pair, Public as Sr25519Public},
 	Encode,
 };
@@ -35,12 +40,18 @@ use std::{
 	collections::{HashMap, HashSet},
 	fs::{self, File},
 	io::Write,
-	path::PathBuf,
+	path::{Path, PathBuf},
 	sync::Arc,
 };
 
 use crate::{Error, Result};
 
+/// The maximum length of a key file name, see [`KeystoreInner::key_file_path`].
+const MAX_KEY_FILE_NAME_LEN: usize = 255;
+
+/// The extension of key files that are named after the hash of the public key.
+const HASHED_KEY_FILE_EXTENSION: &str = "hashed";
+
 /// A local based keystore that is either memory-based or filesystem-based.
 pub struct LocalKeystore(RwLock<KeystoreInner>);
 
@@ -67,6 +78,52 @@ impl LocalKeystore {
 	) -> Result<Option<Pair>> {
 		self.0.read().key_pair::<Pair>(public)
 	}
+
+	#[cfg(any(feature = "bls-experimental", feature = "bandersnatch-experimental"))]
+	fn public_keys<T: PairT>(&self, key_type: KeyTypeId) -> Vec<T::Public> {
+		self.0
+			.read()
+			.raw_public_keys(key_type)
+			.map(|v| {
+				v.into_iter().filter_map(|k| T::Public::from_slice(k.as_slice()).ok()).collect()
+			})
+			.unwrap_or_default()
+	}
+
+	#[cfg(any(feature = "bls-experimental", feature = "bandersnatch-experimental"))]
+	fn generate_new<T: PairT>(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<T::Public, TraitError> {
+		let pair = match seed {
+			Some(seed) => self.0.write().insert_ephemeral_from_seed_by_type::<T>(seed, key_type),
+			None => self.0.write().generate_by_type::<T>(key_type),
+		}
+		.map_err(|e| -> TraitError { e.into() })?;
+
+		Ok(pair.public())
+	}
+
+	#[cfg(any(feature = "bls-experimental", feature = "bandersnatch-experimental"))]
+	fn sign<T: PairT>(
+		&self,
+		key_type: KeyTypeId,
+		public: &[u8],
+		msg: &[u8],
+	) -> std::result::Result<Option<Vec<u8>>, TraitError>
+	where
+		T::Signature: Encode,
+	{
+		let pub_key = T::Public::from_slice(public)
+			.map_err(|()| TraitError::Other("Corrupted public key - Invalid size".into()))?;
+		let key_pair = self
+			.0
+			.read()
+			.key_pair_by_type::<T>(&pub_key, key_type)
+			.map_err(TraitError::from)?;
+		Ok(key_pair.map(|k| k.sign(msg).encode()))
+	}
 }
 
 #[async_trait]
@@ -161,6 +218,58 @@ impl CryptoStore for LocalKeystore {
 	) -> std::result::Result<Option<ecdsa::Signature>, TraitError> {
 		SyncCryptoStore::ecdsa_sign_prehashed(self, id, public, msg)
 	}
+
+	#[cfg(feature = "bls-experimental")]
+	async fn bls377_public_keys(&self, id: KeyTypeId) -> Vec<bls377::Public> {
+		SyncCryptoStore::bls377_public_keys(self, id)
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	async fn bls377_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<bls377::Public, TraitError> {
+		SyncCryptoStore::bls377_generate_new(self, id, seed)
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	async fn bls381_public_keys(&self, id: KeyTypeId) -> Vec<bls381::Public> {
+		SyncCryptoStore::bls381_public_keys(self, id)
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	async fn bls381_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<bls381::Public, TraitError> {
+		SyncCryptoStore::bls381_generate_new(self, id, seed)
+	}
+
+	#[cfg(feature = "bandersnatch-experimental")]
+	async fn bandersnatch_public_keys(&self, id: KeyTypeId) -> Vec<bandersnatch::Public> {
+		SyncCryptoStore::bandersnatch_public_keys(self, id)
+	}
+
+	#[cfg(feature = "bandersnatch-experimental")]
+	async fn bandersnatch_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<bandersnatch::Public, TraitError> {
+		SyncCryptoStore::bandersnatch_generate_new(self, id, seed)
+	}
+
+	#[cfg(feature = "bandersnatch-experimental")]
+	async fn bandersnatch_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &bandersnatch::Public,
+		data: &bandersnatch::vrf::VrfSignData,
+	) -> std::result::Result<Option<bandersnatch::vrf::VrfSignature>, TraitError> {
+		SyncCryptoStore::bandersnatch_vrf_sign(self, key_type, public, data)
+	}
 }
 
 impl SyncCryptoStore for LocalKeystore {
@@ -169,7 +278,14 @@ impl SyncCryptoStore for LocalKeystore {
 		Ok(raw_keys.into_iter().fold(Vec::new(), |mut v, k| {
 			v.push(CryptoTypePublicPair(sr25519::CRYPTO_ID, k.clone()));
 			v.push(CryptoTypePublicPair(ed25519::CRYPTO_ID, k.clone()));
-			v.push(CryptoTypePublicPair(ecdsa::CRYPTO_ID, k));
+			v.push(CryptoTypePublicPair(ecdsa::CRYPTO_ID, k.clone()));
+			#[cfg(feature = "bls-experimental")]
+			{
+				v.push(CryptoTypePublicPair(bls377::CRYPTO_ID, k.clone()));
+				v.push(CryptoTypePublicPair(bls381::CRYPTO_ID, k.clone()));
+			}
+			#[cfg(feature = "bandersnatch-experimental")]
+			v.push(CryptoTypePublicPair(bandersnatch::CRYPTO_ID, k.clone()));
 			v
 		}))
 	}
@@ -223,6 +339,12 @@ impl SyncCryptoStore for LocalKeystore {
 					.map_err(TraitError::from)?;
 				key_pair.map(|k| k.sign(msg).encode()).map(Ok).transpose()
 			},
+			#[cfg(feature = "bls-experimental")]
+			bls377::CRYPTO_ID => self.sign::<bls377::Pair>(id, key.1.as_slice(), msg),
+			#[cfg(feature = "bls-experimental")]
+			bls381::CRYPTO_ID => self.sign::<bls381::Pair>(id, key.1.as_slice(), msg),
+			#[cfg(feature = "bandersnatch-experimental")]
+			bandersnatch::CRYPTO_ID => self.sign::<bandersnatch::Pair>(id, key.1.as_slice(), msg),
 			_ => Err(TraitError::KeyNotSupported(id)),
 		}
 	}
@@ -350,6 +472,60 @@ impl SyncCryptoStore for LocalKeystore {
 
 		pair.map(|k| k.sign_prehashed(msg)).map(Ok).transpose()
 	}
+
+	#[cfg(feature = "bls-experimental")]
+	fn bls377_public_keys(&self, key_type: KeyTypeId) -> Vec<bls377::Public> {
+		self.public_keys::<bls377::Pair>(key_type)
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	fn bls377_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<bls377::Public, TraitError> {
+		self.generate_new::<bls377::Pair>(id, seed)
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_public_keys(&self, key_type: KeyTypeId) -> Vec<bls381::Public> {
+		self.public_keys::<bls381::Pair>(key_type)
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<bls381::Public, TraitError> {
+		self.generate_new::<bls381::Pair>(id, seed)
+	}
+
+	#[cfg(feature = "bandersnatch-experimental")]
+	fn bandersnatch_public_keys(&self, key_type: KeyTypeId) -> Vec<bandersnatch::Public> {
+		self.public_keys::<bandersnatch::Pair>(key_type)
+	}
+
+	#[cfg(feature = "bandersnatch-experimental")]
+	fn bandersnatch_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<bandersnatch::Public, TraitError> {
+		self.generate_new::<bandersnatch::Pair>(id, seed)
+	}
+
+	#[cfg(feature = "bandersnatch-experimental")]
+	fn bandersnatch_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &bandersnatch::Public,
+		data: &bandersnatch::vrf::VrfSignData,
+	) -> std::result::Result<Option<bandersnatch::vrf::VrfSignature>, TraitError> {
+		let pair = self.0.read().key_pair_by_type::<bandersnatch::Pair>(public, key_type)?;
+
+		Ok(pair.map(|k| k.vrf_sign(data)))
+	}
 }
 
 impl Into<SyncCryptoStorePtr> for LocalKeystore {
@@ -416,7 +592,7 @@ impl KeystoreInner {
 	/// Places it into the file system store, if a path is configured.
 	fn insert_unknown(&self, key_type: KeyTypeId, suri: &str, public: &[u8]) -> Result<()> {
 		if let Some(path) = self.key_file_path(public, key_type) {
-			Self::write_to_file(path, suri)?;
+			Self::write_key_file(path, public, suri)?;
 		}
 
 		Ok(())
@@ -429,7 +605,7 @@ impl KeystoreInner {
 	fn generate_by_type<Pair: PairT>(&mut self, key_type: KeyTypeId) -> Result<Pair> {
 		let (pair, phrase, _) = Pair::generate_with_phrase(self.password());
 		if let Some(path) = self.key_file_path(pair.public().as_slice(), key_type) {
-			Self::write_to_file(path, &phrase)?;
+			Self::write_key_file(path, pair.public().as_slice(), &phrase)?;
 		} else {
 			self.insert_ephemeral_pair(&pair, &phrase, key_type);
 		}
@@ -437,8 +613,23 @@ impl KeystoreInner {
 		Ok(pair)
 	}
 
+	/// Write the key `phrase` of `public` to the key file at `path`.
+	///
+	/// Key files with hashed names store the public key next to the phrase, see
+	/// [`Self::key_file_path`].
+	fn write_key_file(path: PathBuf, public: &[u8], phrase: &str) -> Result<()> {
+		if is_hashed_key_file(&path) {
+			Self::write_to_file(
+				path,
+				serde_json::json!([array_bytes::bytes2hex("", public), phrase]),
+			)
+		} else {
+			Self::write_to_file(path, phrase.into())
+		}
+	}
+
 	/// Write the given `data` to `file`.
-	fn write_to_file(file: PathBuf, data: &str) -> Result<()> {
+	fn write_to_file(file: PathBuf, data: serde_json::Value) -> Result<()> {
 		let mut file = File::create(file)?;
 
 		#[cfg(target_family = "unix")]
@@ -478,9 +669,14 @@ impl KeystoreInner {
 		};
 
 		if path.exists() {
-			let file = File::open(path)?;
+			let file = File::open(&path)?;
 
-			serde_json::from_reader(&file).map_err(Into::into).map(Some)
+			if is_hashed_key_file(&path) {
+				let (_, phrase): (String, String) = serde_json::from_reader(&file)?;
+				Ok(Some(phrase))
+			} else {
+				serde_json::from_reader(&file).map_err(Into::into).map(Some)
+			}
 		} else {
 			Ok(None)
 		}
@@ -510,11 +706,21 @@ impl KeystoreInner {
 	/// Get the file path for the given public key and key type.
 	///
 	/// Returns `None` if the keystore only exists in-memory and there isn't any path to provide.
+	///
+	/// The file name is the hex encoded key type followed by the hex encoded public key. If this
+	/// is longer than [`MAX_KEY_FILE_NAME_LEN`], e.g. for BLS keys, the hex encoded BLAKE2 hash
+	/// of the public key is used instead, with the [`HASHED_KEY_FILE_EXTENSION`]. These files
+	/// store the public key next to the phrase, so that the keys can still be listed.
 	fn key_file_path(&self, public: &[u8], key_type: KeyTypeId) -> Option<PathBuf> {
 		let mut buf = self.path.as_ref()?.clone();
 		let key_type = array_bytes::bytes2hex("", &key_type.0);
 		let key = array_bytes::bytes2hex("", public);
-		buf.push(key_type + key.as_str());
+		if key_type.len() + key.len() > MAX_KEY_FILE_NAME_LEN {
+			let hash = array_bytes::bytes2hex("", &blake2_256(public));
+			buf.push(format!("{}{}.{}", key_type, hash, HASHED_KEY_FILE_EXTENSION));
+		} else {
+			buf.push(key_type + key.as_str());
+		}
 		Some(buf)
 	}
 
@@ -531,19 +737,31 @@ impl KeystoreInner {
 			for entry in fs::read_dir(&path)? {
 				let entry = entry?;
 				let path = entry.path();
-
-				// skip directories and non-unicode file names (hex is unicode)
-				if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-					match array_bytes::hex2bytes(name) {
-						Ok(ref hex) if hex.len() > 4 => {
-							if hex[0..4] != id.0 {
-								continue
+				let hashed = is_hashed_key_file(&path);
+
+				// skip invalid file names
+				let name = if hashed { path.file_stem() } else { path.file_name() };
+				let name = match name.and_then(|n| n.to_str()) {
+					Some(name) => name,
+					None => continue,
+				};
+
+				match array_bytes::hex2bytes(name) {
+					Ok(ref hex) if hex.len() > 4 => {
+						if hex[0..4] != id.0 {
+							continue
+						}
+						if hashed {
+							let (public, _): (String, String) =
+								serde_json::from_reader(File::open(&path)?)?;
+							if let Ok(public) = array_bytes::hex2bytes(&public) {
+								public_keys.push(public);
 							}
-							let public = hex[4..].to_vec();
-							public_keys.push(public);
-						},
-						_ => continue,
-					}
+						} else {
+							public_keys.push(hex[4..].to_vec());
+						}
+					},
+					_ => continue,
 				}
 			}
 		}
@@ -564,6 +782,11 @@ impl KeystoreInner {
 	}
 }
 
+/// Returns whether the key file at `path` is named after the hash of the public key.
+fn is_hashed_key_file(path: &Path) -> bool {
+	path.extension().map_or(false, |ext| ext == HASHED_KEY_FILE_EXTENSION)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -769,6 +992,64 @@ mod tests {
 		assert_eq!(SyncCryptoStore::sr25519_public_keys(&store, TEST_KEY_TYPE).len(), 2);
 	}
 
+	#[test]
+	#[cfg(feature = "bls-experimental")]
+	fn bls_keys_are_stored_and_sign() {
+		use sp_core::{testing::BLS377, Decode};
+
+		let temp_dir = TempDir::new().unwrap();
+		let store = LocalKeystore::open(temp_dir.path(), None).unwrap();
+
+		let public = SyncCryptoStore::bls377_generate_new(&store, BLS377, None).unwrap();
+		assert!(SyncCryptoStore::ed25519_public_keys(&store, BLS377).is_empty());
+
+		// The public key is too long for a file name, so the file is named after its hash.
+		let files = fs::read_dir(temp_dir.path())
+			.unwrap()
+			.map(|entry| entry.unwrap().path())
+			.collect::<Vec<_>>();
+		assert_eq!(files.len(), 1);
+		assert!(files[0].is_file() && is_hashed_key_file(&files[0]));
+
+		drop(store);
+		let store = LocalKeystore::open(temp_dir.path(), None).unwrap();
+		assert_eq!(SyncCryptoStore::bls377_public_keys(&store, BLS377), vec![public]);
+
+		let msg = b"message";
+		let signature = SyncCryptoStore::sign_with(&store, BLS377, &public.into(), msg)
+			.unwrap()
+			.expect("Key is in the keystore");
+		let signature = bls377::Signature::decode(&mut &signature[..]).unwrap();
+		assert!(bls377::Pair::verify(&signature, msg, &public));
+
+		let app_pair = store.key_pair::<bls377::AppPair>(&public.into()).unwrap().unwrap();
+		assert_eq!(app_pair.public(), public.into());
+	}
+
+	#[test]
+	#[cfg(feature = "bandersnatch-experimental")]
+	fn bandersnatch_vrf_sign_works() {
+		use bandersnatch::vrf::{VrfInput, VrfSignData};
+		use sp_core::testing::BANDERSNATCH;
+
+		let store = LocalKeystore::in_memory();
+		let public =
+			SyncCryptoStore::bandersnatch_generate_new(&store, BANDERSNATCH, Some("//Alice"))
+				.unwrap();
+		let data = VrfSignData::new(VrfInput::new(b"domain", b"input"), b"extra".to_vec());
+
+		let other = bandersnatch::Pair::from_string("//Bob", None).unwrap().public();
+		assert!(SyncCryptoStore::bandersnatch_vrf_sign(&store, BANDERSNATCH, &other, &data)
+			.unwrap()
+			.is_none());
+
+		let signature =
+			SyncCryptoStore::bandersnatch_vrf_sign(&store, BANDERSNATCH, &public, &data)
+				.unwrap()
+				.expect("Key is in the keystore");
+		assert!(public.vrf_verify(&data, &signature));
+	}
+
 	#[test]
 	#[cfg(target_family = "unix")]
 	fn uses_correct_file_permissions_on_unix() {
@@ -49,6 +49,7 @@ pub enum EndProposingReason {
 	HitDeadline,
 	HitBlockSizeLimit,
 	HitBlockWeightLimit,
+	HitProofSizeLimit,
 }
 
 /// Authorship metrics.
@@ -112,6 +113,7 @@ impl Metrics {
 			EndProposingReason::NoMoreTransactions => "no_more_transactions",
 			EndProposingReason::HitBlockSizeLimit => "hit_block_size_limit",
 			EndProposingReason::HitBlockWeightLimit => "hit_block_weight_limit",
+			EndProposingReason::HitProofSizeLimit => "hit_proof_size_limit",
 		};
 
 		self.end_proposing_reason.with_label_values(&[reason]).inc();
@@ -47,6 +47,44 @@ pub struct BlockStats {
 	pub num_extrinsics: u64,
 }
 
+/// The number of calls to a host function by a runtime API call and the time spent in them.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostFunctionProfile {
+	/// The name of the host function.
+	pub name: String,
+	/// The number of calls.
+	pub count: u64,
+	/// The total time spent in all calls, in nanoseconds.
+	pub total_time_ns: u64,
+}
+
+/// The number of calls to a runtime API function and the time spent in them.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeCallProfile {
+	/// The name of the runtime API function.
+	pub name: String,
+	/// The number of calls.
+	pub count: u64,
+	/// The total time spent in all calls, including the host functions, in nanoseconds.
+	pub total_time_ns: u64,
+	/// The host functions called by the runtime API function.
+	pub host_functions: Vec<HostFunctionProfile>,
+}
+
+/// The profile of runtime API calls and host functions returned by the
+/// `dev_getHostFunctionProfile` RPC.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionProfile {
+	/// The runtime API calls executed in wasm.
+	pub runtime_calls: Vec<RuntimeCallProfile>,
+	/// The same profile in the folded stacks format with times in microseconds, which can be
+	/// rendered as a flamegraph with e.g. `inferno-flamegraph`.
+	pub folded_stacks: String,
+}
+
 /// Substrate dev API.
 ///
 /// This API contains unstable and unsafe methods only meant for development nodes. They
@@ -60,4 +98,12 @@ pub trait DevApi<Hash> {
 	/// this function will return `None`.
 	#[method(name = "dev_getBlockStats")]
 	fn block_stats(&self, block_hash: Hash) -> RpcResult<Option<BlockStats>>;
+
+	/// Get the runtime API calls executed in wasm and the host functions they called, as
+	/// recorded by the host function profiler since it was last reset.
+	///
+	/// If `reset` is `true`, the profile is reset after returning it. Returns `None` if the
+	/// profiler isn't enabled, see `--profile-host-functions`.
+	#[method(name = "dev_getHostFunctionProfile")]
+	fn host_function_profile(&self, reset: Option<bool>) -> RpcResult<Option<ExecutionProfile>>;
 }
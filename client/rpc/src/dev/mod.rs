@@ -24,6 +24,7 @@ mod tests;
 
 use jsonrpsee::core::RpcResult;
 use sc_client_api::{BlockBackend, HeaderBackend};
+use sc_executor_common::profiler;
 use sc_rpc_api::{dev::error::Error, DenyUnsafe};
 use sp_api::{ApiExt, Core, ProvideRuntimeApi};
 use sp_core::Encode;
@@ -36,7 +37,9 @@ use std::{
 	sync::Arc,
 };
 
-pub use sc_rpc_api::dev::{BlockStats, DevApiServer};
+pub use sc_rpc_api::dev::{
+	BlockStats, DevApiServer, ExecutionProfile, HostFunctionProfile, RuntimeCallProfile,
+};
 
 type HasherOf<Block> = <<Block as BlockT>::Header as Header>::Hashing;
 
@@ -113,4 +116,38 @@ where
 			.encoded_size() as u64;
 		Ok(Some(BlockStats { witness_len, witness_compact_len, block_len, num_extrinsics }))
 	}
+
+	fn host_function_profile(&self, reset: Option<bool>) -> RpcResult<Option<ExecutionProfile>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		if !profiler::is_enabled() {
+			return Ok(None)
+		}
+
+		let profile =
+			if reset.unwrap_or(false) { profiler::take_profile() } else { profiler::profile() };
+		let nanos = |stats: &profiler::CallStats| {
+			stats.total_time.as_nanos().try_into().unwrap_or(u64::MAX)
+		};
+		let runtime_calls = profile
+			.runtime_calls
+			.iter()
+			.map(|(name, runtime_call)| RuntimeCallProfile {
+				name: name.clone(),
+				count: runtime_call.stats.count,
+				total_time_ns: nanos(&runtime_call.stats),
+				host_functions: runtime_call
+					.host_functions
+					.iter()
+					.map(|(name, stats)| HostFunctionProfile {
+						name: name.to_string(),
+						count: stats.count,
+						total_time_ns: nanos(stats),
+					})
+					.collect(),
+			})
+			.collect();
+
+		Ok(Some(ExecutionProfile { runtime_calls, folded_stacks: profile.to_folded_stacks() }))
+	}
 }
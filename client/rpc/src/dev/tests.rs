@@ -20,7 +20,7 @@ use super::*;
 use sc_block_builder::BlockBuilderProvider;
 use sp_blockchain::HeaderBackend;
 use sp_consensus::BlockOrigin;
-use substrate_test_runtime_client::{prelude::*, runtime::Block};
+use substrate_test_runtime_client::{prelude::*, runtime::Block, ExecutionStrategy};
 
 #[tokio::test]
 async fn block_stats_work() {
@@ -74,3 +74,38 @@ async fn deny_unsafe_works() {
 		r#"{"jsonrpc":"2.0","error":{"code":-32601,"message":"RPC call is unsafe to be called externally"},"id":1}"#
 	);
 }
+
+#[tokio::test]
+async fn host_function_profile_works() {
+	let mut client = Arc::new(
+		TestClientBuilder::new()
+			.set_execution_strategy(ExecutionStrategy::AlwaysWasm)
+			.build(),
+	);
+	let api = <Dev<Block, _>>::new(client.clone(), DenyUnsafe::No).into_rpc();
+
+	profiler::set_enabled(true);
+	let block = client.new_block(Default::default()).unwrap().build().unwrap().block;
+	client.import(BlockOrigin::Own, block).await.unwrap();
+
+	let profile = api
+		.call::<_, Option<ExecutionProfile>>("dev_getHostFunctionProfile", [true])
+		.await
+		.unwrap()
+		.expect("The profiler is enabled");
+
+	let execute_block = profile
+		.runtime_calls
+		.iter()
+		.find(|runtime_call| runtime_call.name == "Core_execute_block")
+		.expect("Importing the block executes it in wasm");
+	assert!(execute_block.count >= 1);
+	assert!(!execute_block.host_functions.is_empty());
+	for runtime_call in &profile.runtime_calls {
+		for host_function in &runtime_call.host_functions {
+			assert!(profile
+				.folded_stacks
+				.contains(&format!("{};{} ", runtime_call.name, host_function.name)));
+		}
+	}
+}
@@ -33,6 +33,7 @@ use sc_client_api::{
 	Backend, BlockBackend, BlockchainEvents, CallExecutor, ExecutorProvider, ProofProvider,
 	StorageProvider,
 };
+use sc_executor::{CallContext, CallContextExt};
 use sc_rpc_api::state::ReadProof;
 use sp_api::{CallApiAt, Metadata, ProvideRuntimeApi};
 use sp_blockchain::{
@@ -45,6 +46,7 @@ use sp_core::{
 	},
 	Bytes,
 };
+use sp_externalities::Extensions;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use sp_version::RuntimeVersion;
 
@@ -193,6 +195,10 @@ where
 	) -> std::result::Result<Bytes, Error> {
 		self.block_or_best(block)
 			.and_then(|block| {
+				// The call is made outside of a block, so it gets the off-chain fuel limit.
+				let mut extensions = Extensions::new();
+				extensions.register(CallContextExt(CallContext::Offchain));
+
 				self.client
 					.executor()
 					.call(
@@ -200,7 +206,7 @@ where
 						&method,
 						&call_data,
 						self.client.execution_extensions().strategies().other,
-						None,
+						Some(extensions),
 					)
 					.map(Into::into)
 			})
@@ -26,7 +26,7 @@ use crate::{
 };
 use futures::{channel::oneshot, future::ready, FutureExt, StreamExt};
 use jsonrpsee::RpcModule;
-use log::info;
+use log::{debug, info, warn};
 use prometheus_endpoint::Registry;
 use sc_chain_spec::get_extension;
 use sc_client_api::{
@@ -35,7 +35,12 @@ use sc_client_api::{
 };
 use sc_client_db::{Backend, DatabaseSettings};
 use sc_consensus::import_queue::ImportQueue;
-use sc_executor::RuntimeVersionOf;
+use sc_executor::{
+	sp_wasm_interface::HostFunctions, NativeElseWasmExecutor, NativeExecutionDispatch,
+	RuntimeVersionOf, WasmExecutor,
+};
+#[cfg(feature = "wasmtime")]
+use sc_executor::{WasmtimeArtifactCache, WASMTIME_DEFAULT_MAX_ARTIFACTS};
 use sc_keystore::LocalKeystore;
 use sc_network::{config::SyncMode, NetworkService};
 use sc_network_bitswap::BitswapRequestHandler;
@@ -67,14 +72,22 @@ use sp_blockchain::{HeaderBackend, HeaderMetadata};
 use sp_consensus::block_validation::{
 	BlockAnnounceValidator, Chain, DefaultBlockAnnounceValidator,
 };
-use sp_core::traits::{CodeExecutor, SpawnNamed};
+use sp_core::{
+	storage::{well_known_keys, StorageKey},
+	traits::{CodeExecutor, SpawnNamed},
+};
 use sp_keystore::{CryptoStore, SyncCryptoStore, SyncCryptoStorePtr};
 use sp_runtime::{
 	generic::BlockId,
 	traits::{Block as BlockT, BlockIdTo, NumberFor, Zero},
 	BuildStorage,
 };
-use std::{str::FromStr, sync::Arc, time::SystemTime};
+use sp_version::RuntimeVersion;
+use std::{
+	str::FromStr,
+	sync::Arc,
+	time::{Instant, SystemTime},
+};
 
 /// Full client type.
 pub type TFullClient<TBl, TRtApi, TExec> =
@@ -169,6 +182,60 @@ impl KeystoreContainer {
 	}
 }
 
+/// Creates a wasm-only executor for the given config.
+///
+/// Unlike `NativeElseWasmExecutor` it never executes a native runtime, so the runtime doesn't need
+/// to be compiled into the node. To debug the execution of blocks, replace the on-chain runtime
+/// with a debug build for these blocks through
+/// [`Configuration::wasm_runtime_debug_substitutes`].
+pub fn new_wasm_executor<H: HostFunctions>(
+	config: &Configuration,
+) -> Result<WasmExecutor<H>, Error> {
+	// Compiled runtimes are only persisted in the opt-in artifact cache below, like for
+	// `NativeElseWasmExecutor`, so the cache of wasmtime itself stays disabled.
+	#[allow(unused_mut)]
+	let mut executor = WasmExecutor::new(
+		config.wasm_method,
+		config.default_heap_pages,
+		config.max_runtime_instances,
+		None,
+		config.runtime_cache_size,
+	);
+	#[cfg(feature = "wasmtime")]
+	if let Some(artifact_cache) = new_wasmtime_artifact_cache(config)? {
+		executor.set_artifact_cache(artifact_cache);
+	}
+	Ok(executor)
+}
+
+/// Persist the artifacts of the runtimes compiled with wasmtime by `executor` in
+/// [`Configuration::wasmtime_artifact_cache`], if set.
+///
+/// Without the `wasmtime` feature no runtimes are compiled with wasmtime, so this does nothing.
+pub fn set_wasmtime_artifact_cache<D: NativeExecutionDispatch>(
+	config: &Configuration,
+	executor: &mut NativeElseWasmExecutor<D>,
+) -> Result<(), Error> {
+	#[cfg(feature = "wasmtime")]
+	if let Some(artifact_cache) = new_wasmtime_artifact_cache(config)? {
+		executor.set_artifact_cache(artifact_cache);
+	}
+	#[cfg(not(feature = "wasmtime"))]
+	let _ = (config, executor);
+	Ok(())
+}
+
+#[cfg(feature = "wasmtime")]
+fn new_wasmtime_artifact_cache(
+	config: &Configuration,
+) -> Result<Option<WasmtimeArtifactCache>, Error> {
+	let dir = match &config.wasmtime_artifact_cache {
+		Some(dir) => dir,
+		None => return Ok(None),
+	};
+	Ok(Some(WasmtimeArtifactCache::new(dir, WASMTIME_DEFAULT_MAX_ARTIFACTS)?))
+}
+
 /// Creates a new full client for the given config.
 pub fn new_full_client<TBl, TRtApi, TExec>(
 	config: &Configuration,
@@ -241,6 +308,23 @@ where
 			})
 			.collect::<Result<std::collections::HashMap<_, _>, Error>>()?;
 
+		let wasm_runtime_debug_substitutes = config
+			.wasm_runtime_debug_substitutes
+			.iter()
+			.map(|substitute| {
+				let code = std::fs::read(&substitute.path).map_err(|e| {
+					Error::Application(Box::from(format!(
+						"Failed to read the debug substitute `{}`: {}",
+						substitute.path.display(),
+						e
+					)))
+				})?;
+				let blocks = NumberFor::<TBl>::from(*substitute.blocks.start())..=
+					NumberFor::<TBl>::from(*substitute.blocks.end());
+				Ok((blocks, code))
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+
 		let client = new_client(
 			backend.clone(),
 			executor,
@@ -260,6 +344,7 @@ where
 					SyncMode::Fast { .. } | SyncMode::Warp { .. }
 				),
 				wasm_runtime_substitutes,
+				wasm_runtime_debug_substitutes,
 			},
 		)?;
 
@@ -493,6 +578,15 @@ where
 		),
 	);
 
+	// Compile upgraded runtimes before the first block using them gets executed.
+	if config.precompile_runtime_upgrades {
+		spawn_handle.spawn_blocking(
+			"runtime-precompilation",
+			None,
+			precompile_runtime_upgrades(client.clone()),
+		);
+	}
+
 	// Prometheus metrics.
 	let metrics_service =
 		if let Some(PrometheusConfig { port, registry }) = config.prometheus_config.clone() {
@@ -581,6 +675,82 @@ async fn transaction_notifications<Block, ExPool>(
 		.await;
 }
 
+/// Compile the runtimes of the imported blocks which changed `:code`.
+///
+/// The runtimes are compiled by querying their version, which puts them into the runtime cache of
+/// the executor. This way the first block executed with a new runtime doesn't have to wait for it
+/// to be compiled.
+async fn precompile_runtime_upgrades<Block, Backend, Client>(client: Arc<Client>)
+where
+	Block: BlockT,
+	Backend: sc_client_api::backend::Backend<Block>,
+	Client: BlockchainEvents<Block> + StorageProvider<Block, Backend> + CallApiAt<Block>,
+{
+	let mut precompiler = RuntimePrecompiler::<Block>::default();
+	client
+		.import_notification_stream()
+		.for_each(|notification| {
+			precompiler.on_block_imported(&*client, notification.hash);
+			ready(())
+		})
+		.await;
+}
+
+/// Compiles the runtimes of imported blocks whose code differs from the last imported block.
+struct RuntimePrecompiler<Block: BlockT> {
+	code_hash: Option<Block::Hash>,
+}
+
+impl<Block: BlockT> Default for RuntimePrecompiler<Block> {
+	fn default() -> Self {
+		Self { code_hash: None }
+	}
+}
+
+impl<Block: BlockT> RuntimePrecompiler<Block> {
+	/// Compile `:code` of the block `hash`, if it changed.
+	///
+	/// Returns the version of the compiled runtime.
+	fn on_block_imported<Backend, Client>(
+		&mut self,
+		client: &Client,
+		hash: Block::Hash,
+	) -> Option<RuntimeVersion>
+	where
+		Backend: sc_client_api::backend::Backend<Block>,
+		Client: StorageProvider<Block, Backend> + CallApiAt<Block>,
+	{
+		let code_hash = StorageProvider::storage_hash(
+			client,
+			hash,
+			&StorageKey(well_known_keys::CODE.to_vec()),
+		)
+		.ok()
+		.flatten();
+		if code_hash.is_none() || code_hash == self.code_hash {
+			return None
+		}
+		self.code_hash = code_hash;
+
+		debug!("Runtime upgraded at block {:?}, compiling the new runtime.", hash);
+		let start = Instant::now();
+		match CallApiAt::runtime_version_at(client, &BlockId::Hash(hash)) {
+			Ok(version) => {
+				info!(
+					"⚙️  Compiled upgraded runtime {} in {} ms",
+					version,
+					start.elapsed().as_millis(),
+				);
+				Some(version)
+			},
+			Err(e) => {
+				warn!("Failed to compile upgraded runtime: {}", e);
+				None
+			},
+		}
+	}
+}
+
 fn init_telemetry<Block, Client, Network>(
 	config: &mut Configuration,
 	network: Network,
@@ -1005,3 +1175,37 @@ impl NetworkStarter {
 		let _ = self.0.send(());
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::executor::block_on;
+	use sc_block_builder::BlockBuilderProvider;
+	use sp_consensus::BlockOrigin;
+	use substrate_test_runtime_client::{prelude::*, runtime};
+
+	#[test]
+	fn runtime_precompiler_compiles_changed_code() {
+		let mut client = TestClientBuilder::new().build();
+		let mut precompiler = RuntimePrecompiler::default();
+		let mut import_block = |changes: Vec<(&[u8], Option<Vec<u8>>)>| {
+			let mut builder = client.new_block(Default::default()).unwrap();
+			for (key, value) in changes {
+				builder.push_storage_change(key.to_vec(), value).unwrap();
+			}
+			let block = builder.build().unwrap().block;
+			let hash = block.hash();
+			block_on(client.import(BlockOrigin::Own, block)).unwrap();
+			precompiler.on_block_imported(&client, hash)
+		};
+
+		// The runtime of the first imported block is compiled.
+		let version = import_block(vec![]).unwrap();
+		assert_eq!(version.spec_version, runtime::VERSION.spec_version);
+		assert!(import_block(vec![]).is_none());
+
+		// Writing the same code doesn't compile anything.
+		let code = substrate_test_runtime::wasm_binary_unwrap().to_vec();
+		assert!(import_block(vec![(well_known_keys::CODE, Some(code))]).is_none());
+	}
+}
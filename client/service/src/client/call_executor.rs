@@ -16,7 +16,10 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use super::{client::ClientConfig, wasm_override::WasmOverride, wasm_substitutes::WasmSubstitutes};
+use super::{
+	client::ClientConfig, wasm_debug_substitutes::WasmDebugSubstitutes,
+	wasm_override::WasmOverride, wasm_substitutes::WasmSubstitutes,
+};
 use sc_client_api::{backend, call_executor::CallExecutor, HeaderBackend};
 use sc_executor::{RuntimeVersion, RuntimeVersionOf};
 use sp_api::{ProofRecorder, StorageTransactionCache};
@@ -36,6 +39,7 @@ pub struct LocalCallExecutor<Block: BlockT, B, E> {
 	executor: E,
 	wasm_override: Arc<Option<WasmOverride>>,
 	wasm_substitutes: WasmSubstitutes<Block, E, B>,
+	wasm_debug_substitutes: WasmDebugSubstitutes<Block>,
 	spawn_handle: Box<dyn SpawnNamed>,
 	client_config: ClientConfig<Block>,
 }
@@ -64,6 +68,11 @@ where
 			backend.clone(),
 		)?;
 
+		let wasm_debug_substitutes = WasmDebugSubstitutes::new(
+			client_config.wasm_runtime_debug_substitutes.clone(),
+			&executor,
+		)?;
+
 		Ok(LocalCallExecutor {
 			backend,
 			executor,
@@ -71,6 +80,7 @@ where
 			spawn_handle,
 			client_config,
 			wasm_substitutes,
+			wasm_debug_substitutes,
 		})
 	}
 
@@ -86,9 +96,29 @@ where
 		Block: BlockT,
 		B: backend::Backend<Block>,
 	{
+		// Don't get the runtime version if there is no code that could replace the on-chain one.
+		if self.wasm_debug_substitutes.is_empty() &&
+			self.wasm_override.is_none() &&
+			self.wasm_substitutes.is_empty()
+		{
+			return Ok(onchain_code)
+		}
+
 		let spec = CallExecutor::runtime_version(self, id)?;
+		let debug_substitute =
+			self.backend.blockchain().block_number_from_id(id)?.and_then(|number| {
+				self.wasm_debug_substitutes
+					.get(spec.spec_version, onchain_code.heap_pages, number)
+			});
 		let code =
-			if let Some(d) =
+			if let Some(d) = debug_substitute {
+				log::debug!(
+					target: "wasm_debug_substitutes",
+					"Using WASM debug substitute on top of block {}",
+					id,
+				);
+				d
+			} else if let Some(d) =
 				self.wasm_override.as_ref().as_ref().and_then(|o| {
 					o.get(&spec.spec_version, onchain_code.heap_pages, &spec.spec_name)
 				}) {
@@ -124,6 +154,7 @@ where
 			spawn_handle: self.spawn_handle.clone(),
 			client_config: self.client_config.clone(),
 			wasm_substitutes: self.wasm_substitutes.clone(),
+			wasm_debug_substitutes: self.wasm_debug_substitutes.clone(),
 		}
 	}
 }
@@ -392,6 +423,8 @@ mod tests {
 				backend.clone(),
 			)
 			.unwrap(),
+			wasm_debug_substitutes: WasmDebugSubstitutes::new(Default::default(), &executor)
+				.unwrap(),
 		};
 
 		let check = call_executor
@@ -400,4 +433,74 @@ mod tests {
 
 		assert_eq!(Some(vec![2, 2, 2, 2, 2, 2, 2, 2]), check.fetch_runtime_code().map(Into::into));
 	}
+
+	#[test]
+	fn should_get_debug_substitute_for_blocks_in_range() {
+		let executor = NativeElseWasmExecutor::<LocalExecutorDispatch>::new(
+			WasmExecutionMethod::Interpreted,
+			Some(128),
+			1,
+			2,
+		);
+
+		let onchain_code = WrappedRuntimeCode(substrate_test_runtime::wasm_binary_unwrap().into());
+		let onchain_code = || RuntimeCode {
+			code_fetcher: &onchain_code,
+			heap_pages: Some(128),
+			hash: vec![0, 0, 0, 0],
+		};
+
+		let backend = Arc::new(in_mem::Backend::<runtime::Block>::new());
+
+		// client is used for the convenience of creating and inserting the genesis block.
+		let _client = substrate_test_runtime_client::client::new_with_backend::<
+			_,
+			_,
+			runtime::Block,
+			_,
+			runtime::RuntimeApi,
+		>(
+			backend.clone(),
+			executor.clone(),
+			&substrate_test_runtime_client::GenesisParameters::default().genesis_storage(),
+			None,
+			Box::new(TaskExecutor::new()),
+			None,
+			None,
+			Default::default(),
+		)
+		.expect("Creates a client");
+
+		let call_executor = |blocks| LocalCallExecutor {
+			backend: backend.clone(),
+			executor: executor.clone(),
+			wasm_override: Arc::new(None),
+			spawn_handle: Box::new(TaskExecutor::new()),
+			client_config: ClientConfig::default(),
+			wasm_substitutes: WasmSubstitutes::new(
+				Default::default(),
+				executor.clone(),
+				backend.clone(),
+			)
+			.unwrap(),
+			wasm_debug_substitutes: WasmDebugSubstitutes::new(
+				vec![(blocks, substrate_test_runtime::wasm_binary_unwrap().to_vec())],
+				&executor,
+			)
+			.unwrap(),
+		};
+
+		// The debug substitute is used for calls at the state of the parent of block 1.
+		let call_executor_in_range = call_executor(1..=3);
+		let check = call_executor_in_range
+			.check_override(onchain_code(), &BlockId::Number(Default::default()))
+			.expect("RuntimeCode override");
+		assert_ne!(check.hash, vec![0, 0, 0, 0]);
+
+		let call_executor_out_of_range = call_executor(2..=3);
+		let check = call_executor_out_of_range
+			.check_override(onchain_code(), &BlockId::Number(Default::default()))
+			.expect("RuntimeCode override");
+		assert_eq!(check.hash, vec![0, 0, 0, 0]);
+	}
 }
@@ -81,6 +81,7 @@ use sp_trie::{CompactProof, StorageProof};
 use std::{
 	collections::{hash_map::DefaultHasher, HashMap, HashSet},
 	marker::PhantomData,
+	ops::RangeInclusive,
 	path::PathBuf,
 	sync::Arc,
 };
@@ -197,6 +198,9 @@ pub struct ClientConfig<Block: BlockT> {
 	/// Map of WASM runtime substitute starting at the child of the given block until the runtime
 	/// version doesn't match anymore.
 	pub wasm_runtime_substitutes: HashMap<NumberFor<Block>, Vec<u8>>,
+	/// WASM runtimes used instead of the on-chain runtime to execute the given ranges of blocks,
+	/// as long as their spec version matches the on-chain one.
+	pub wasm_runtime_debug_substitutes: Vec<(RangeInclusive<NumberFor<Block>>, Vec<u8>)>,
 }
 
 impl<Block: BlockT> Default for ClientConfig<Block> {
@@ -207,6 +211,7 @@ impl<Block: BlockT> Default for ClientConfig<Block> {
 			wasm_runtime_overrides: None,
 			no_genesis: false,
 			wasm_runtime_substitutes: HashMap::new(),
+			wasm_runtime_debug_substitutes: Vec::new(),
 		}
 	}
 }
@@ -48,6 +48,7 @@ mod block_rules;
 mod call_executor;
 mod client;
 pub mod genesis;
+mod wasm_debug_substitutes;
 mod wasm_override;
 mod wasm_substitutes;
 
@@ -0,0 +1,160 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # WASM debug substitutes
+//!
+//! Debug substitutes replace the on-chain wasm with a locally stored wasm blob for a range of
+//! blocks. The local blob is usually a build of the very same runtime with logging enabled, which
+//! makes it possible to debug the execution of some blocks without a native runtime.
+//!
+//! A debug substitute is used to execute the blocks in its range, i.e. for all runtime calls at
+//! the state of the parents of these blocks. It is only used if its spec version matches the
+//! on-chain one, since executing blocks with a different runtime could change their outcome.
+
+use sc_executor::RuntimeVersionOf;
+use sp_blockchain::Result;
+use sp_core::traits::{FetchRuntimeCode, RuntimeCode, WrappedRuntimeCode};
+use sp_runtime::traits::{Block as BlockT, NumberFor, One};
+use sp_state_machine::BasicExternalities;
+use std::{
+	collections::hash_map::DefaultHasher, hash::Hasher as _, ops::RangeInclusive, sync::Arc,
+};
+
+/// A wasm blob that is used instead of the on-chain wasm for a range of blocks.
+#[derive(Debug)]
+struct WasmDebugSubstitute<Block: BlockT> {
+	code: Vec<u8>,
+	hash: Vec<u8>,
+	/// The blocks that are executed with this substitute.
+	blocks: RangeInclusive<NumberFor<Block>>,
+	/// The spec version of the substitute.
+	spec_version: u32,
+}
+
+impl<Block: BlockT> WasmDebugSubstitute<Block> {
+	fn runtime_code(&self, heap_pages: Option<u64>) -> RuntimeCode {
+		RuntimeCode { code_fetcher: self, hash: self.hash.clone(), heap_pages }
+	}
+}
+
+/// Make a hash out of a byte string using the default rust hasher
+fn make_hash<K: std::hash::Hash + ?Sized>(val: &K) -> Vec<u8> {
+	let mut state = DefaultHasher::new();
+	val.hash(&mut state);
+	state.finish().to_le_bytes().to_vec()
+}
+
+impl<Block: BlockT> FetchRuntimeCode for WasmDebugSubstitute<Block> {
+	fn fetch_runtime_code(&self) -> Option<std::borrow::Cow<[u8]>> {
+		Some(self.code.as_slice().into())
+	}
+}
+
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum WasmDebugSubstituteError {
+	#[error("Failed to get runtime version of the debug substitute for blocks {0}: {1}")]
+	VersionInvalid(String, String),
+}
+
+impl From<WasmDebugSubstituteError> for sp_blockchain::Error {
+	fn from(err: WasmDebugSubstituteError) -> Self {
+		Self::Application(Box::new(err))
+	}
+}
+
+/// Substitutes the on-chain wasm with local debug builds for ranges of blocks.
+#[derive(Debug)]
+pub struct WasmDebugSubstitutes<Block: BlockT> {
+	substitutes: Arc<Vec<WasmDebugSubstitute<Block>>>,
+}
+
+impl<Block: BlockT> Clone for WasmDebugSubstitutes<Block> {
+	fn clone(&self) -> Self {
+		Self { substitutes: self.substitutes.clone() }
+	}
+}
+
+impl<Block: BlockT> WasmDebugSubstitutes<Block> {
+	/// Create a new instance.
+	pub fn new<Executor: RuntimeVersionOf>(
+		substitutes: Vec<(RangeInclusive<NumberFor<Block>>, Vec<u8>)>,
+		executor: &Executor,
+	) -> Result<Self> {
+		let substitutes = substitutes
+			.into_iter()
+			.map(|(blocks, code)| {
+				let hash = make_hash(&code);
+
+				let wrapped_code = WrappedRuntimeCode(code.as_slice().into());
+				let runtime_code = RuntimeCode {
+					code_fetcher: &wrapped_code,
+					hash: hash.clone(),
+					heap_pages: None,
+				};
+				let mut ext = BasicExternalities::default();
+				let spec_version = executor
+					.runtime_version(&mut ext, &runtime_code)
+					.map_err(|e| {
+						WasmDebugSubstituteError::VersionInvalid(
+							format!("{:?}", blocks),
+							e.to_string(),
+						)
+					})?
+					.spec_version;
+
+				Ok(WasmDebugSubstitute { code, hash, blocks, spec_version })
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		Ok(Self { substitutes: Arc::new(substitutes) })
+	}
+
+	/// Returns `true` if there are no debug substitutes.
+	pub fn is_empty(&self) -> bool {
+		self.substitutes.is_empty()
+	}
+
+	/// Get the debug substitute for executing a call at the state of block `parent_number`.
+	///
+	/// Returns `None` if there isn't any substitute for the child of `parent_number` with the
+	/// given `spec_version`.
+	pub fn get(
+		&self,
+		spec_version: u32,
+		heap_pages: Option<u64>,
+		parent_number: NumberFor<Block>,
+	) -> Option<RuntimeCode<'_>> {
+		let number = parent_number + One::one();
+		let substitute = self.substitutes.iter().find(|s| s.blocks.contains(&number))?;
+
+		if substitute.spec_version != spec_version {
+			log::warn!(
+				target: "wasm_debug_substitutes",
+				"Not using the debug substitute for block {}: its spec version {} doesn't match \
+				 the on-chain spec version {}",
+				number,
+				substitute.spec_version,
+				spec_version,
+			);
+			return None
+		}
+
+		Some(substitute.runtime_code(heap_pages))
+	}
+}
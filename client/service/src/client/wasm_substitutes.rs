@@ -131,6 +131,11 @@ where
 		Ok(Self { executor, substitutes: Arc::new(substitutes), backend })
 	}
 
+	/// Returns `true` if there are no substitutes.
+	pub fn is_empty(&self) -> bool {
+		self.substitutes.is_empty()
+	}
+
 	/// Get a substitute.
 	///
 	/// Returns `None` if there isn't any substitute required.
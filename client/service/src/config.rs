@@ -42,6 +42,7 @@ use sp_core::crypto::SecretString;
 use std::{
 	io, iter,
 	net::SocketAddr,
+	ops::RangeInclusive,
 	path::{Path, PathBuf},
 };
 use tempfile::TempDir;
@@ -85,6 +86,10 @@ pub struct Configuration {
 	/// over on-chain runtimes when the spec version matches. Set to `None` to
 	/// disable overrides (default).
 	pub wasm_runtime_overrides: Option<PathBuf>,
+	/// Local WASM runtimes that replace the on-chain runtime for ranges of blocks, e.g. to debug
+	/// their execution with a build of the runtime that has logging enabled. They take precedence
+	/// over `wasm_runtime_overrides` and are only used when the spec version matches.
+	pub wasm_runtime_debug_substitutes: Vec<WasmRuntimeDebugSubstitute>,
 	/// Execution strategies.
 	pub execution_strategies: ExecutionStrategies,
 	/// RPC over HTTP binding address. `None` if disabled.
@@ -125,6 +130,10 @@ pub struct Configuration {
 	pub offchain_worker: OffchainWorkerConfig,
 	/// Enable authoring even when offline.
 	pub force_authoring: bool,
+	/// Storage proof size limit in bytes for authored blocks.
+	///
+	/// The storage proof size is not limited if `None`.
+	pub proof_size_limit: Option<usize>,
 	/// Disable GRANDPA when running in validator mode
 	pub disable_grandpa: bool,
 	/// Development key seed.
@@ -150,6 +159,21 @@ pub struct Configuration {
 	pub informant_output_format: sc_informant::OutputFormat,
 	/// Maximum number of different runtime versions that can be cached.
 	pub runtime_cache_size: u8,
+	/// Compile upgraded and pending runtimes in the background after block import.
+	pub precompile_runtime_upgrades: bool,
+	/// Directory in which the artifacts of the runtimes compiled with wasmtime are persisted.
+	///
+	/// The artifacts are not persisted if `None`.
+	pub wasmtime_artifact_cache: Option<PathBuf>,
+}
+
+/// A local WASM runtime that is used to execute a range of blocks.
+#[derive(Debug, Clone)]
+pub struct WasmRuntimeDebugSubstitute {
+	/// The blocks that are executed with this runtime.
+	pub blocks: RangeInclusive<u32>,
+	/// Path to the WASM runtime.
+	pub path: PathBuf,
 }
 
 /// Type for tasks spawned by the executor.
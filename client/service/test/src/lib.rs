@@ -238,6 +238,7 @@ fn node_config<
 		chain_spec: Box::new((*spec).clone()),
 		wasm_method: sc_service::config::WasmExecutionMethod::Interpreted,
 		wasm_runtime_overrides: Default::default(),
+		wasm_runtime_debug_substitutes: Default::default(),
 		execution_strategies: Default::default(),
 		rpc_http: None,
 		rpc_ipc: None,
@@ -256,6 +257,7 @@ fn node_config<
 		default_heap_pages: None,
 		offchain_worker: Default::default(),
 		force_authoring: false,
+		proof_size_limit: None,
 		disable_grandpa: false,
 		dev_key_seed: key_seed,
 		tracing_targets: None,
@@ -265,6 +267,8 @@ fn node_config<
 		base_path: Some(BasePath::new(root)),
 		informant_output_format: Default::default(),
 		runtime_cache_size: 2,
+		precompile_runtime_upgrades: true,
+		wasmtime_artifact_cache: None,
 	}
 }
 
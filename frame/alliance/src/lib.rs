@@ -237,6 +237,15 @@ pub mod pallet {
 	#[pallet::storage_version(migration::STORAGE_VERSION)]
 	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
+	#[pallet::extra_constants]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// The current storage version of the pallet.
+		#[pallet::constant_name(StorageVersion)]
+		fn storage_version() -> StorageVersion {
+			migration::STORAGE_VERSION
+		}
+	}
+
 	#[pallet::config]
 	pub trait Config<I: 'static = ()>: frame_system::Config {
 		/// The overarching event type.
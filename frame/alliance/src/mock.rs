@@ -49,6 +49,7 @@ impl frame_system::Config for Test {
 	type BlockLength = ();
 	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeCall = RuntimeCall;
+	type RuntimeTask = RuntimeTask;
 	type Index = u64;
 	type BlockNumber = BlockNumber;
 	type Hash = H256;
@@ -680,6 +680,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				ensure!(details.owner == check_owner, Error::<T, I>::NoPermission);
 			}
 			details.status = AssetStatus::Destroying;
+			DestroyQueue::<T, I>::insert(id, ());
 
 			Self::deposit_event(Event::DestructionStarted { asset_id: id });
 			Ok(())
@@ -693,6 +694,18 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	pub(super) fn do_destroy_accounts(
 		id: T::AssetId,
 		max_items: u32,
+	) -> Result<u32, DispatchError> {
+		Self::destroy_accounts_with(id, |id| {
+			Account::<T, I>::drain_prefix(id).take(max_items as usize).collect()
+		})
+	}
+
+	/// Destroy the accounts of the asset `id` removed from storage by `drain`.
+	///
+	/// Emits the `Event::DestroyedAccounts` event and returns the number of destroyed accounts.
+	fn destroy_accounts_with(
+		id: T::AssetId,
+		drain: impl FnOnce(T::AssetId) -> Vec<(T::AccountId, AssetAccountOf<T, I>)>,
 	) -> Result<u32, DispatchError> {
 		let mut dead_accounts: Vec<T::AccountId> = vec![];
 		let mut remaining_accounts = 0;
@@ -702,12 +715,9 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				// Should only destroy accounts while the asset is in a destroying state
 				ensure!(details.status == AssetStatus::Destroying, Error::<T, I>::IncorrectStatus);
 
-				for (who, v) in Account::<T, I>::drain_prefix(id) {
+				for (who, v) in drain(id) {
 					let _ = Self::dead_account(&who, &mut details, &v.reason, true);
 					dead_accounts.push(who);
-					if dead_accounts.len() >= (max_items as usize) {
-						break
-					}
 				}
 				remaining_accounts = details.accounts;
 				Ok(())
@@ -732,6 +742,18 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	pub(super) fn do_destroy_approvals(
 		id: T::AssetId,
 		max_items: u32,
+	) -> Result<u32, DispatchError> {
+		Self::destroy_approvals_with(id, |id| {
+			Approvals::<T, I>::drain_prefix((id,)).take(max_items as usize).collect()
+		})
+	}
+
+	/// Destroy the approvals of the asset `id` removed from storage by `drain`.
+	///
+	/// Emits the `Event::DestroyedApprovals` event and returns the number of destroyed approvals.
+	fn destroy_approvals_with(
+		id: T::AssetId,
+		drain: impl FnOnce(T::AssetId) -> Vec<((T::AccountId, T::AccountId), ApprovalOf<T, I>)>,
 	) -> Result<u32, DispatchError> {
 		let mut removed_approvals = 0;
 		let _ =
@@ -741,13 +763,10 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				// Should only destroy accounts while the asset is in a destroying state.
 				ensure!(details.status == AssetStatus::Destroying, Error::<T, I>::IncorrectStatus);
 
-				for ((owner, _), approval) in Approvals::<T, I>::drain_prefix((id,)) {
+				for ((owner, _), approval) in drain(id) {
 					T::Currency::unreserve(&owner, approval.deposit);
 					removed_approvals = removed_approvals.saturating_add(1);
 					details.approvals = details.approvals.saturating_sub(1);
-					if removed_approvals >= max_items {
-						break
-					}
 				}
 				Self::deposit_event(Event::ApprovalsDestroyed {
 					asset_id: id,
@@ -759,6 +778,47 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		Ok(removed_approvals)
 	}
 
+	/// Destroy the accounts and approvals of the assets in [`DestroyQueue`] as long as `meter`
+	/// allows.
+	///
+	/// An asset leaves the queue once all its accounts and approvals are destroyed, it is then
+	/// ready for `finish_destroy`.
+	pub(super) fn destroy_on_idle(meter: &mut WeightMeter) {
+		let db_weight = T::DbWeight::get();
+		while meter.check_accrue(db_weight.reads(1)) {
+			let id = match DestroyQueue::<T, I>::iter_keys().next() {
+				Some(id) => id,
+				None => return,
+			};
+
+			let base = T::WeightInfo::destroy_accounts(0);
+			if !meter.check_accrue(base) {
+				return
+			}
+			let per_account = T::WeightInfo::destroy_accounts(1).saturating_sub(base);
+			let _ = Self::destroy_accounts_with(id, |id| {
+				Account::<T, I>::drain_prefix_bounded(id, meter, per_account)
+			});
+
+			let base = T::WeightInfo::destroy_approvals(0);
+			if !meter.check_accrue(base) {
+				return
+			}
+			let per_approval = T::WeightInfo::destroy_approvals(1).saturating_sub(base);
+			let _ = Self::destroy_approvals_with(id, |id| {
+				Approvals::<T, I>::drain_prefix_bounded((id,), meter, per_approval)
+			});
+
+			if !meter.check_accrue(db_weight.reads_writes(1, 1)) {
+				return
+			}
+			match Asset::<T, I>::get(id) {
+				Some(details) if details.accounts > 0 || details.approvals > 0 => return,
+				_ => DestroyQueue::<T, I>::remove(id),
+			}
+		}
+	}
+
 	/// Complete destroying an asset and unreserve the deposit.
 	///
 	/// On success, the `Event::Destroyed` event is emitted.
@@ -769,6 +829,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			ensure!(details.accounts == 0, Error::<T, I>::InUse);
 			ensure!(details.approvals == 0, Error::<T, I>::InUse);
 
+			DestroyQueue::<T, I>::remove(id);
 			let metadata = Metadata::<T, I>::take(&id);
 			T::Currency::unreserve(
 				&details.owner,
@@ -926,3 +987,50 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		})
 	}
 }
+
+#[cfg(any(test, feature = "try-runtime"))]
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Ensure the correctness of the state of this pallet.
+	///
+	/// * The accounts, approvals and metadata of an asset only exist while the asset exists.
+	/// * The account, sufficient and approval counters of an asset match its storage items.
+	/// * The supply of a live or frozen asset is the sum of the balances of its accounts.
+	pub(crate) fn do_try_state(_: T::BlockNumber) -> Result<(), &'static str> {
+		ensure!(
+			Account::<T, I>::iter_keys().all(|(id, _)| Asset::<T, I>::contains_key(&id)),
+			"account of a non-existent asset"
+		);
+		ensure!(
+			Approvals::<T, I>::iter_keys().all(|(id, _, _)| Asset::<T, I>::contains_key(&id)),
+			"approval of a non-existent asset"
+		);
+		ensure!(
+			Metadata::<T, I>::iter_keys().all(|id| Asset::<T, I>::contains_key(&id)),
+			"metadata of a non-existent asset"
+		);
+
+		Asset::<T, I>::iter()
+			.map(|(id, details)| {
+				let (mut accounts, mut sufficients, mut supply) = (0u32, 0u32, T::Balance::zero());
+				for account in Account::<T, I>::iter_prefix_values(id) {
+					accounts.saturating_inc();
+					if matches!(account.reason, ExistenceReason::Sufficient) {
+						sufficients.saturating_inc();
+					}
+					supply = supply.saturating_add(account.balance);
+				}
+				let approvals = Approvals::<T, I>::iter_prefix_values((id,)).count() as u32;
+
+				ensure!(details.accounts == accounts, "wrong account count");
+				ensure!(details.sufficients == sufficients, "wrong sufficient count");
+				ensure!(details.approvals == approvals, "wrong approval count");
+				// the accounts of an asset being destroyed are removed without burning the supply.
+				ensure!(
+					details.status == AssetStatus::Destroying || details.supply == supply,
+					"supply is not the sum of balances"
+				);
+				Ok(())
+			})
+			.collect::<Result<_, _>>()
+	}
+}
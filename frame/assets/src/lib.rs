@@ -164,6 +164,7 @@ use frame_support::{
 		BalanceStatus::Reserved,
 		Currency, EnsureOriginWithArg, ReservableCurrency, StoredMap,
 	},
+	weights::WeightMeter,
 };
 use frame_system::Config as SystemConfig;
 
@@ -186,6 +187,15 @@ pub mod pallet {
 	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T, I = ()>(_);
 
+	#[pallet::extra_constants]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// The current storage version of the pallet.
+		#[pallet::constant_name(StorageVersion)]
+		fn storage_version() -> StorageVersion {
+			STORAGE_VERSION
+		}
+	}
+
 	#[pallet::config]
 	/// The module configuration trait.
 	pub trait Config<I: 'static = ()>: frame_system::Config {
@@ -315,6 +325,11 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	#[pallet::storage]
+	/// The assets being destroyed, whose accounts and approvals are destroyed in `on_idle`.
+	pub(super) type DestroyQueue<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, (), OptionQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
 		/// Genesis assets: id, owner, is_sufficient, min_balance
@@ -522,6 +537,20 @@ pub mod pallet {
 		NotFrozen,
 	}
 
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let mut meter = WeightMeter::from_limit(remaining_weight);
+			Self::destroy_on_idle(&mut meter);
+			meter.consumed
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(n: BlockNumberFor<T>) -> Result<(), &'static str> {
+			Self::do_try_state(n)
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		/// Issue a new class of fungible assets from a public origin.
@@ -623,6 +652,9 @@ pub mod pallet {
 		///   asset.
 		///
 		/// Assets must be freezed before calling start_destroy.
+		///
+		/// The accounts and approvals of the asset are also destroyed in `on_idle`, as long as
+		/// there is weight left in the block.
 		#[pallet::weight(T::WeightInfo::start_destroy())]
 		pub fn start_destroy(
 			origin: OriginFor<T>,
@@ -21,7 +21,8 @@ use super::*;
 use crate::{mock::*, Error};
 use frame_support::{
 	assert_noop, assert_ok,
-	traits::{fungibles::InspectEnumerable, Currency},
+	traits::{fungibles::InspectEnumerable, Currency, Hooks},
+	weights::Weight,
 };
 use pallet_balances::Error as BalancesError;
 use sp_runtime::{traits::ConvertInto, TokenError};
@@ -393,6 +394,41 @@ fn destroy_should_refund_approvals() {
 	});
 }
 
+#[test]
+fn on_idle_destroys_accounts_and_approvals() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 2, 100));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 3, 100));
+		assert_ok!(Assets::approve_transfer(RuntimeOrigin::signed(1), 0, 2, 50));
+		assert_eq!(Balances::reserved_balance(&1), 1);
+		assert_ok!(Assets::freeze_asset(RuntimeOrigin::signed(1), 0));
+
+		// nothing to destroy yet.
+		assert_eq!(Assets::on_idle(1, Weight::MAX), Weight::zero());
+
+		assert_ok!(Assets::start_destroy(RuntimeOrigin::signed(1), 0));
+
+		// only enough weight to destroy two accounts.
+		let weight = <() as WeightInfo>::destroy_accounts(2);
+		assert_eq!(Assets::on_idle(1, weight), weight);
+		assert_eq!(Account::<Test>::iter_prefix(0).count(), 1);
+		assert_eq!(Approvals::<Test>::iter_prefix((0,)).count(), 1);
+		assert!(DestroyQueue::<Test>::contains_key(0));
+
+		Assets::on_idle(2, Weight::MAX);
+		assert_eq!(Account::<Test>::iter_prefix(0).count(), 0);
+		assert_eq!(Approvals::<Test>::iter_prefix((0,)).count(), 0);
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert!(!DestroyQueue::<Test>::contains_key(0));
+
+		assert_ok!(Assets::finish_destroy(RuntimeOrigin::signed(1), 0));
+		assert_eq!(asset_ids(), vec![999]);
+	});
+}
+
 #[test]
 fn partial_destroy_should_work() {
 	new_test_ext().execute_with(|| {
@@ -1194,3 +1230,30 @@ fn querying_roles_should_work() {
 		assert_eq!(Assets::freezer(0), Some(4));
 	});
 }
+
+#[test]
+fn try_state_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, false, 1));
+		Balances::make_free_balance_be(&1, 100);
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), 999, 2, 50));
+		assert_ok!(Assets::approve_transfer(RuntimeOrigin::signed(1), 0, 2, 50));
+		assert_ok!(Assets::do_try_state(System::block_number()));
+
+		Asset::<Test>::mutate(0, |d| d.as_mut().unwrap().supply += 1);
+		assert_eq!(
+			Assets::do_try_state(System::block_number()),
+			Err("supply is not the sum of balances")
+		);
+
+		// the supply is not burned while the accounts of an asset are destroyed.
+		assert_ok!(Assets::freeze_asset(RuntimeOrigin::signed(1), 0));
+		assert_ok!(Assets::start_destroy(RuntimeOrigin::signed(1), 0));
+		assert_ok!(Assets::destroy_accounts(RuntimeOrigin::signed(1), 0));
+		assert_ok!(Assets::do_try_state(System::block_number()));
+
+		Asset::<Test>::mutate(999, |d| d.as_mut().unwrap().sufficients -= 1);
+		assert_eq!(Assets::do_try_state(System::block_number()), Err("wrong sufficient count"));
+	});
+}
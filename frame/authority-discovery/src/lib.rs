@@ -239,6 +239,7 @@ mod tests {
 		type Index = u64;
 		type BlockNumber = BlockNumber;
 		type RuntimeCall = RuntimeCall;
+		type RuntimeTask = RuntimeTask;
 		type Hash = H256;
 		type Hashing = ::sp_runtime::traits::BlakeTwo256;
 		type AccountId = AuthorityId;
@@ -200,7 +200,7 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
-	#[pallet::config]
+	#[pallet::config(with_default)]
 	pub trait Config<I: 'static = ()>: frame_system::Config {
 		/// The balance of an account.
 		type Balance: Parameter
@@ -216,6 +216,7 @@ pub mod pallet {
 			+ FixedPointOperand;
 
 		/// Handler for the unbalanced reduction when removing a dust account.
+		#[pallet::no_default_bounds]
 		type DustRemoval: OnUnbalanced<NegativeImbalance<Self, I>>;
 
 		/// The overarching event type.
@@ -227,6 +228,7 @@ pub mod pallet {
 		type ExistentialDeposit: Get<Self::Balance>;
 
 		/// The means of storing the balances of an account.
+		#[pallet::no_default]
 		type AccountStore: StoredMap<Self::AccountId, AccountData<Self::Balance>>;
 
 		/// Weight information for extrinsics in this pallet.
@@ -245,10 +247,51 @@ pub mod pallet {
 		type ReserveIdentifier: Parameter + Member + MaxEncodedLen + Ord + Copy;
 	}
 
+	/// Default configs of this pallet, to be used with
+	/// [`derive_impl`](`frame_support::derive_impl`).
+	pub mod config_preludes {
+		use super::DefaultConfig;
+		use frame_support::{
+			derive_impl, inject_runtime_type, register_default_impl,
+			traits::{ConstU32, ConstU64},
+		};
+
+		/// A default config for mock runtimes, with `u64` balances and an existential deposit
+		/// of 1. `AccountStore` must still be provided by the runtime, usually `System`.
+		pub struct TestDefaultConfig;
+
+		#[derive_impl(
+			frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig,
+			no_aggregated_types
+		)]
+		impl frame_system::DefaultConfig for TestDefaultConfig {}
+
+		#[register_default_impl(TestDefaultConfig)]
+		impl DefaultConfig for TestDefaultConfig {
+			type Balance = u64;
+			type DustRemoval = ();
+			#[inject_runtime_type]
+			type RuntimeEvent = ();
+			type ExistentialDeposit = ConstU64<1>;
+			type WeightInfo = ();
+			type MaxLocks = ConstU32<50>;
+			type MaxReserves = ConstU32<50>;
+			type ReserveIdentifier = [u8; 8];
+		}
+	}
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
 	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		#[cfg(feature = "try-runtime")]
+		fn try_state(n: BlockNumberFor<T>) -> Result<(), &'static str> {
+			Self::do_try_state(n)
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		/// Transfer some liquid free balance to another account.
@@ -747,6 +790,66 @@ impl<T: Config<I>, I: 'static> Drop for DustCleaner<T, I> {
 	}
 }
 
+#[cfg(any(test, feature = "try-runtime"))]
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Ensure the correctness of the state of this pallet.
+	///
+	/// * The total issuance is the sum of the free and reserved balances of all accounts.
+	/// * The frozen balances of an account are the maximum of its locks.
+	/// * The named reserves of an account don't exceed its reserved balance.
+	pub(crate) fn do_try_state(_: BlockNumberFor<T>) -> Result<(), &'static str> {
+		Self::check_total_issuance()?;
+		Self::check_locks()?;
+		Self::check_reserves()
+	}
+
+	fn check_total_issuance() -> Result<(), &'static str> {
+		// every account holding a balance has a provider reference in `frame_system`, whatever the
+		// `AccountStore` is.
+		let total = system::Account::<T>::iter_keys()
+			.map(|who| Self::account(&who).total())
+			.fold(Zero::zero(), |acc: T::Balance, x| acc.saturating_add(x));
+		ensure!(total == TotalIssuance::<T, I>::get(), "total issuance is not the sum of balances");
+		Ok(())
+	}
+
+	fn check_locks() -> Result<(), &'static str> {
+		Locks::<T, I>::iter()
+			.map(|(who, locks)| {
+				let (misc_frozen, fee_frozen) = locks.iter().fold(
+					(Zero::zero(), Zero::zero()),
+					|(misc, fee): (T::Balance, T::Balance), l| match l.reasons {
+						Reasons::All => (misc.max(l.amount), fee.max(l.amount)),
+						Reasons::Misc => (misc.max(l.amount), fee),
+						Reasons::Fee => (misc, fee.max(l.amount)),
+					},
+				);
+				let account = Self::account(&who);
+				ensure!(
+					account.misc_frozen == misc_frozen && account.fee_frozen == fee_frozen,
+					"frozen balances don't match the locks"
+				);
+				Ok(())
+			})
+			.collect::<Result<_, _>>()
+	}
+
+	fn check_reserves() -> Result<(), &'static str> {
+		Reserves::<T, I>::iter()
+			.map(|(who, reserves)| {
+				let named = reserves
+					.iter()
+					.fold(Zero::zero(), |acc: T::Balance, r| acc.saturating_add(r.amount));
+				ensure!(
+					named <= Self::account(&who).reserved,
+					"named reserves exceed the reserved balance"
+				);
+				Ok(())
+			})
+			.collect::<Result<_, _>>()
+	}
+}
+
 impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// Get the free balance of an account.
 	pub fn free_balance(who: impl sp_std::borrow::Borrow<T::AccountId>) -> T::Balance {
@@ -1456,5 +1456,37 @@ macro_rules! decl_tests {
 				);
 			});
 		}
+
+		#[test]
+		fn try_state_works() {
+			use frame_support::traits::NamedReservableCurrency;
+
+			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+				Balances::set_lock(ID_1, &1, 5, WithdrawReasons::all());
+				Balances::set_lock(ID_2, &1, 7, WithdrawReasons::TRANSACTION_PAYMENT);
+				assert_ok!(<Balances as NamedReservableCurrency<_>>::reserve_named(&[1u8; 8], &2, 5));
+				assert_ok!(Balances::do_try_state(System::block_number()));
+
+				<TotalIssuance<$test>>::mutate(|t| *t += 1);
+				assert_eq!(
+					Balances::do_try_state(System::block_number()),
+					Err("total issuance is not the sum of balances"),
+				);
+				<TotalIssuance<$test>>::mutate(|t| *t -= 1);
+
+				<Locks<$test>>::mutate(&1, |locks| locks[0].amount = 6);
+				assert_eq!(
+					Balances::do_try_state(System::block_number()),
+					Err("frozen balances don't match the locks"),
+				);
+				<Locks<$test>>::mutate(&1, |locks| locks[0].amount = 5);
+
+				<Reserves<$test>>::mutate(&2, |reserves| reserves[0].amount = 6);
+				assert_eq!(
+					Balances::do_try_state(System::block_number()),
+					Err("named reserves exceed the reserved balance"),
+				);
+			});
+		}
 	}
 }
@@ -0,0 +1,210 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verification of BEEFY commitments signed with an aggregated signature.
+//!
+//! Signature schemes supporting aggregation (e.g. BLS) allow the signatures of all the validators
+//! to be combined into a single one, which is verified against the public keys of the signers.
+//! The signature scheme itself is abstracted by [`AggregateSignatureVerifier`].
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+use beefy_primitives::{AuthorityIndex, Commitment};
+
+use crate::{
+	threshold, verify_authority_proof, AuthorityMerkleProof, AuthoritySetCommitment, Error,
+};
+
+/// Means of verifying an aggregated signature.
+pub trait AggregateSignatureVerifier {
+	/// Public key of a validator.
+	type Public;
+	/// Aggregated signature.
+	type Signature;
+
+	/// Return the merkle leaf of the authority with the given public key.
+	fn authority_leaf(public: &Self::Public) -> Vec<u8>;
+
+	/// Check that `signature` is an aggregate of signatures over `msg` by all `publics`.
+	fn verify_aggregate(signature: &Self::Signature, msg: &[u8], publics: &[Self::Public]) -> bool;
+}
+
+/// A validator which contributed to an aggregated signature.
+#[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq, TypeInfo)]
+pub struct AggregatedSigner<Public> {
+	/// Index of the signer in the validator set.
+	pub index: AuthorityIndex,
+	/// Public key of the signer.
+	pub public: Public,
+	/// Merkle proof of the signer's leaf.
+	pub proof: AuthorityMerkleProof,
+}
+
+/// A BEEFY finality proof with an aggregated signature.
+#[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq, TypeInfo)]
+pub struct AggregatedFinalityProof<N, Public, Signature> {
+	/// The signed commitment.
+	pub commitment: Commitment<N>,
+	/// Validators whose signatures are aggregated, ordered by validator index.
+	pub signers: Vec<AggregatedSigner<Public>>,
+	/// Aggregated signature over the commitment.
+	pub signature: Signature,
+}
+
+/// Verify an [`AggregatedFinalityProof`].
+///
+/// Returns the verified commitment.
+pub fn verify_aggregated_finality_proof<N, V>(
+	authority_set: &AuthoritySetCommitment,
+	finality_proof: AggregatedFinalityProof<N, V::Public, V::Signature>,
+) -> Result<Commitment<N>, Error>
+where
+	N: Encode,
+	V: AggregateSignatureVerifier,
+{
+	let AggregatedFinalityProof { commitment, signers, signature } = finality_proof;
+
+	if commitment.validator_set_id != authority_set.id {
+		return Err(Error::InvalidValidatorSetId)
+	}
+	// signers must be unique, which is enforced by ordering them.
+	if !signers.windows(2).all(|w| w[0].index < w[1].index) {
+		return Err(Error::UnorderedSigners)
+	}
+	if signers.len() < threshold(authority_set.len as usize) {
+		return Err(Error::NotEnoughSignatures)
+	}
+
+	for AggregatedSigner { index, public, proof } in &signers {
+		verify_authority_proof(authority_set, *index, &V::authority_leaf(public), proof)?;
+	}
+
+	let publics = signers.into_iter().map(|s| s.public).collect::<Vec<_>>();
+	if !V::verify_aggregate(&signature, &commitment.encode(), &publics) {
+		return Err(Error::InvalidAggregatedSignature)
+	}
+
+	Ok(commitment)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::commitment;
+	use sp_core::{keccak_256, H256};
+	use sp_runtime::traits::Keccak256;
+
+	/// Insecure aggregation scheme where the signature is the hash of the message and the public
+	/// keys of the signers.
+	struct TestAggregate;
+
+	impl TestAggregate {
+		fn sign(msg: &[u8], publics: &[u32]) -> H256 {
+			keccak_256(&(msg, publics).encode()).into()
+		}
+	}
+
+	impl AggregateSignatureVerifier for TestAggregate {
+		type Public = u32;
+		type Signature = H256;
+
+		fn authority_leaf(public: &u32) -> Vec<u8> {
+			public.encode()
+		}
+
+		fn verify_aggregate(signature: &H256, msg: &[u8], publics: &[u32]) -> bool {
+			*signature == Self::sign(msg, publics)
+		}
+	}
+
+	fn leaves() -> Vec<Vec<u8>> {
+		(100u32..107).map(|p| p.encode()).collect()
+	}
+
+	fn authority_set() -> AuthoritySetCommitment {
+		beefy_primitives::mmr::BeefyAuthoritySet {
+			id: 5,
+			len: 7,
+			root: beefy_merkle_tree::merkle_root::<Keccak256, _>(leaves()),
+		}
+	}
+
+	fn aggregated_finality_proof(signers: &[u32]) -> AggregatedFinalityProof<u64, u32, H256> {
+		let commitment = commitment(5);
+		let publics = signers.iter().map(|i| 100 + i).collect::<Vec<_>>();
+		let signature = TestAggregate::sign(&commitment.encode(), &publics);
+		let signers = signers
+			.iter()
+			.map(|index| AggregatedSigner {
+				index: *index,
+				public: 100 + index,
+				proof: beefy_merkle_tree::merkle_proof::<Keccak256, _, _>(
+					leaves(),
+					*index as usize,
+				)
+				.proof,
+			})
+			.collect();
+		AggregatedFinalityProof { commitment, signers, signature }
+	}
+
+	#[test]
+	fn verifies_aggregated_finality_proof() {
+		let proof = aggregated_finality_proof(&[0, 1, 3, 4, 6]);
+		assert_eq!(
+			verify_aggregated_finality_proof::<_, TestAggregate>(&authority_set(), proof),
+			Ok(commitment(5))
+		);
+	}
+
+	#[test]
+	fn rejects_invalid_aggregated_finality_proofs() {
+		// not enough signers.
+		let proof = aggregated_finality_proof(&[0, 1, 3, 4]);
+		assert_eq!(
+			verify_aggregated_finality_proof::<_, TestAggregate>(&authority_set(), proof),
+			Err(Error::NotEnoughSignatures)
+		);
+
+		// duplicated signer.
+		let proof = aggregated_finality_proof(&[0, 1, 3, 3, 4]);
+		assert_eq!(
+			verify_aggregated_finality_proof::<_, TestAggregate>(&authority_set(), proof),
+			Err(Error::UnorderedSigners)
+		);
+
+		// signer not in the validator set.
+		let mut proof = aggregated_finality_proof(&[0, 1, 3, 4, 6]);
+		proof.signers[4].public = 200;
+		assert_eq!(
+			verify_aggregated_finality_proof::<_, TestAggregate>(&authority_set(), proof),
+			Err(Error::InvalidAuthorityProof(6))
+		);
+
+		// signature not matching the signers.
+		let mut proof = aggregated_finality_proof(&[0, 1, 3, 4, 6]);
+		proof.signers.push(aggregated_finality_proof(&[5]).signers.remove(0));
+		proof.signers.swap(4, 5);
+		assert_eq!(
+			verify_aggregated_finality_proof::<_, TestAggregate>(&authority_set(), proof),
+			Err(Error::InvalidAggregatedSignature)
+		);
+	}
+}
@@ -0,0 +1,348 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(missing_docs)]
+
+//! A BEEFY light client verifier.
+//!
+//! Light clients (e.g. on-chain bridge pallets) do not know the BEEFY validator set itself, only
+//! the commitment to it stored in the MMR leaves by `pallet_beefy_mmr`: a
+//! [`BeefyAuthoritySet`], i.e. the set id, its length and a binary merkle root (see
+//! `beefy_merkle_tree`) of the validators' merkle leaves (e.g. Ethereum addresses of their ECDSA
+//! keys).
+//!
+//! This crate verifies BEEFY commitments against such an authority set in three flavours:
+//!
+//! - [`verify_finality_proof`] checks every signature of a [`VersionedFinalityProof`];
+//! - [`sampling::verify_sampled_finality_proof`] checks only a pseudo-random sample of the
+//!   signatures, in the spirit of the interactive protocol described in
+//!   [`beefy_primitives::witness`];
+//! - [`aggregate::verify_aggregated_finality_proof`] checks a single aggregated signature (e.g.
+//!   BLS) over the commitment.
+//!
+//! In all cases the signer of each signature is linked to the authority set through a merkle proof
+//! of its leaf.
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_core::H256;
+use sp_runtime::{traits::Keccak256, RuntimeDebug};
+use sp_std::prelude::*;
+
+use beefy_primitives::{
+	mmr::BeefyAuthoritySet, AuthorityIndex, Commitment, SignedCommitment, VersionedFinalityProof,
+};
+
+pub mod aggregate;
+pub mod sampling;
+
+/// Commitment to a BEEFY validator set, as stored by `pallet_beefy_mmr`.
+pub type AuthoritySetCommitment = BeefyAuthoritySet<H256>;
+
+/// Merkle proof of an authority leaf, i.e. the inner nodes required to compute the root of the
+/// authority set merkle tree.
+pub type AuthorityMerkleProof = Vec<H256>;
+
+/// Errors that can occur while verifying a BEEFY finality proof.
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+#[derive(RuntimeDebug, PartialEq, Eq)]
+pub enum Error {
+	/// The commitment is signed by a different validator set.
+	#[cfg_attr(feature = "std", error("Commitment signed by unexpected validator set"))]
+	InvalidValidatorSetId,
+	/// The number of signatures does not match the size of the validator set.
+	#[cfg_attr(feature = "std", error("Signatures count does not match the validator set"))]
+	InvalidSignaturesLen,
+	/// Not enough validators signed the commitment.
+	#[cfg_attr(feature = "std", error("Not enough signatures"))]
+	NotEnoughSignatures,
+	/// The number of merkle proofs does not match the number of checked signatures.
+	#[cfg_attr(feature = "std", error("Merkle proofs count does not match the signatures"))]
+	InvalidProofsLen,
+	/// A signature is invalid.
+	#[cfg_attr(feature = "std", error("Invalid signature of authority {0}"))]
+	InvalidSignature(AuthorityIndex),
+	/// The signer is not part of the validator set.
+	#[cfg_attr(feature = "std", error("Invalid merkle proof of authority {0}"))]
+	InvalidAuthorityProof(AuthorityIndex),
+	/// The sampled signatures do not match the ones requested.
+	#[cfg_attr(feature = "std", error("Sampled signatures do not match the requested ones"))]
+	InvalidSample,
+	/// The signers of an aggregated signature are not ordered by unique validator index.
+	#[cfg_attr(feature = "std", error("Signers are not ordered by unique validator index"))]
+	UnorderedSigners,
+	/// The aggregated signature is invalid.
+	#[cfg_attr(feature = "std", error("Invalid aggregated signature"))]
+	InvalidAggregatedSignature,
+}
+
+/// Means of linking a BEEFY signature to the merkle leaf of its signer.
+pub trait AuthorityLeafVerifier {
+	/// Signature type found in BEEFY finality proofs.
+	type Signature;
+
+	/// Check `signature` over the SCALE-encoded commitment `msg`.
+	///
+	/// Returns the merkle leaf of the signer if the signature is valid.
+	fn recover_leaf(signature: &Self::Signature, msg: &[u8]) -> Option<Vec<u8>>;
+}
+
+/// Verifier for ECDSA BEEFY signatures, where authority leaves are Ethereum addresses.
+///
+/// This matches `pallet_beefy_mmr::BeefyEcdsaToEthereum`.
+pub struct EcdsaToEthereum;
+
+impl AuthorityLeafVerifier for EcdsaToEthereum {
+	type Signature = beefy_primitives::crypto::Signature;
+
+	fn recover_leaf(signature: &Self::Signature, msg: &[u8]) -> Option<Vec<u8>> {
+		let signature: &sp_core::ecdsa::Signature = signature.as_ref();
+		let msg_hash = sp_io::hashing::keccak_256(msg);
+		let public = sp_io::crypto::secp256k1_ecdsa_recover(signature.as_ref(), &msg_hash).ok()?;
+		Some(sp_io::hashing::keccak_256(&public)[12..].to_vec())
+	}
+}
+
+/// Return the minimum number of signatures required to consider a commitment final.
+pub fn threshold(authorities: usize) -> usize {
+	let faulty = authorities.saturating_sub(1) / 3;
+	authorities - faulty
+}
+
+/// A signature together with the merkle proof of its signer's leaf.
+#[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq, TypeInfo)]
+pub struct SignatureWithProof<Signature> {
+	/// Index of the signer in the validator set.
+	pub index: AuthorityIndex,
+	/// The signature.
+	pub signature: Signature,
+	/// Merkle proof of the signer's leaf.
+	pub proof: AuthorityMerkleProof,
+}
+
+/// Verify all the signatures of a [`VersionedFinalityProof`].
+///
+/// `authority_proofs` must contain the merkle proof of the leaf of each signer, in the order of
+/// the signatures present in the finality proof.
+///
+/// Returns the verified commitment.
+pub fn verify_finality_proof<N, V>(
+	authority_set: &AuthoritySetCommitment,
+	finality_proof: VersionedFinalityProof<N, V::Signature>,
+	authority_proofs: Vec<AuthorityMerkleProof>,
+) -> Result<Commitment<N>, Error>
+where
+	N: Encode,
+	V: AuthorityLeafVerifier,
+{
+	let VersionedFinalityProof::V1(SignedCommitment { commitment, signatures }) = finality_proof;
+
+	if signatures.len() != authority_set.len as usize {
+		return Err(Error::InvalidSignaturesLen)
+	}
+	let signatures = signatures
+		.into_iter()
+		.enumerate()
+		.filter_map(|(index, signature)| signature.map(|s| (index as AuthorityIndex, s)))
+		.collect::<Vec<_>>();
+	if signatures.len() != authority_proofs.len() {
+		return Err(Error::InvalidProofsLen)
+	}
+
+	let signatures = signatures
+		.into_iter()
+		.zip(authority_proofs)
+		.map(|((index, signature), proof)| SignatureWithProof { index, signature, proof })
+		.collect::<Vec<_>>();
+
+	verify_signatures::<N, V>(authority_set, &commitment, signatures.len(), &signatures)?;
+	Ok(commitment)
+}
+
+/// Check that `commitment` is signed by `signers` validators of `authority_set`, verifying the
+/// given signatures.
+fn verify_signatures<N, V>(
+	authority_set: &AuthoritySetCommitment,
+	commitment: &Commitment<N>,
+	signers: usize,
+	signatures: &[SignatureWithProof<V::Signature>],
+) -> Result<(), Error>
+where
+	N: Encode,
+	V: AuthorityLeafVerifier,
+{
+	if commitment.validator_set_id != authority_set.id {
+		return Err(Error::InvalidValidatorSetId)
+	}
+	if signers < threshold(authority_set.len as usize) {
+		return Err(Error::NotEnoughSignatures)
+	}
+
+	let msg = commitment.encode();
+	for SignatureWithProof { index, signature, proof } in signatures {
+		let leaf = V::recover_leaf(signature, &msg).ok_or(Error::InvalidSignature(*index))?;
+		verify_authority_proof(authority_set, *index, &leaf, proof)?;
+	}
+
+	Ok(())
+}
+
+/// Check that `leaf` is the leaf at position `index` of the authority set merkle tree.
+fn verify_authority_proof(
+	authority_set: &AuthoritySetCommitment,
+	index: AuthorityIndex,
+	leaf: &[u8],
+	proof: &AuthorityMerkleProof,
+) -> Result<(), Error> {
+	if beefy_merkle_tree::verify_proof::<Keccak256, _, _>(
+		&authority_set.root,
+		proof.iter().cloned(),
+		authority_set.len as usize,
+		index as usize,
+		&leaf,
+	) {
+		Ok(())
+	} else {
+		Err(Error::InvalidAuthorityProof(index))
+	}
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+	use super::*;
+	use beefy_primitives::{crypto, known_payloads, Payload};
+	use sp_core::{keccak_256, Pair};
+
+	pub(crate) struct TestValidators {
+		pub pairs: Vec<sp_core::ecdsa::Pair>,
+		pub leaves: Vec<Vec<u8>>,
+		pub set: AuthoritySetCommitment,
+	}
+
+	impl TestValidators {
+		pub fn new(n: usize) -> Self {
+			let pairs = (0..n)
+				.map(|i| {
+					sp_core::ecdsa::Pair::from_string(&format!("//Validator{}", i), None).unwrap()
+				})
+				.collect::<Vec<_>>();
+			let leaves = pairs
+				.iter()
+				.map(|p| {
+					let signature = p.sign_prehashed(&keccak_256(b"")).into();
+					EcdsaToEthereum::recover_leaf(&signature, b"").unwrap()
+				})
+				.collect::<Vec<_>>();
+			let root = beefy_merkle_tree::merkle_root::<Keccak256, _>(&leaves);
+			Self { pairs, leaves, set: BeefyAuthoritySet { id: 5, len: n as u32, root } }
+		}
+
+		pub fn proof(&self, index: usize) -> AuthorityMerkleProof {
+			beefy_merkle_tree::merkle_proof::<Keccak256, _, _>(&self.leaves, index).proof
+		}
+
+		pub fn sign(&self, index: usize, commitment: &Commitment<u64>) -> crypto::Signature {
+			self.pairs[index].sign_prehashed(&keccak_256(&commitment.encode())).into()
+		}
+	}
+
+	pub(crate) fn commitment(validator_set_id: u64) -> Commitment<u64> {
+		Commitment {
+			payload: Payload::from_single_entry(known_payloads::MMR_ROOT_ID, vec![42; 32]),
+			block_number: 7,
+			validator_set_id,
+		}
+	}
+
+	fn finality_proof(
+		validators: &TestValidators,
+		commitment: &Commitment<u64>,
+		signers: &[usize],
+	) -> (VersionedFinalityProof<u64, crypto::Signature>, Vec<AuthorityMerkleProof>) {
+		let signatures = (0..validators.pairs.len())
+			.map(|i| signers.contains(&i).then(|| validators.sign(i, commitment)))
+			.collect();
+		let proofs = signers.iter().map(|i| validators.proof(*i)).collect();
+		(SignedCommitment { commitment: commitment.clone(), signatures }.into(), proofs)
+	}
+
+	#[test]
+	fn threshold_works() {
+		assert_eq!(threshold(1), 1);
+		assert_eq!(threshold(3), 3);
+		assert_eq!(threshold(4), 3);
+		assert_eq!(threshold(10), 7);
+	}
+
+	#[test]
+	fn verifies_finality_proof() {
+		let validators = TestValidators::new(5);
+		let commitment = commitment(5);
+		let (proof, proofs) = finality_proof(&validators, &commitment, &[0, 2, 3, 4]);
+
+		assert_eq!(
+			verify_finality_proof::<_, EcdsaToEthereum>(&validators.set, proof, proofs),
+			Ok(commitment)
+		);
+	}
+
+	#[test]
+	fn rejects_invalid_finality_proofs() {
+		let validators = TestValidators::new(5);
+
+		// wrong validator set.
+		let (proof, proofs) = finality_proof(&validators, &commitment(4), &[0, 1, 2, 3]);
+		assert_eq!(
+			verify_finality_proof::<_, EcdsaToEthereum>(&validators.set, proof, proofs),
+			Err(Error::InvalidValidatorSetId)
+		);
+
+		// not enough signatures.
+		let (proof, proofs) = finality_proof(&validators, &commitment(5), &[0, 1, 2]);
+		assert_eq!(
+			verify_finality_proof::<_, EcdsaToEthereum>(&validators.set, proof, proofs),
+			Err(Error::NotEnoughSignatures)
+		);
+
+		// missing merkle proof.
+		let (proof, mut proofs) = finality_proof(&validators, &commitment(5), &[0, 1, 2, 3]);
+		proofs.pop();
+		assert_eq!(
+			verify_finality_proof::<_, EcdsaToEthereum>(&validators.set, proof, proofs),
+			Err(Error::InvalidProofsLen)
+		);
+
+		// signature placed at the index of another validator.
+		let (proof, proofs) = finality_proof(&validators, &commitment(5), &[0, 1, 2, 3]);
+		let VersionedFinalityProof::V1(mut signed) = proof;
+		signed.signatures.swap(3, 4);
+		assert_eq!(
+			verify_finality_proof::<_, EcdsaToEthereum>(&validators.set, signed.into(), proofs),
+			Err(Error::InvalidAuthorityProof(4))
+		);
+
+		// signature over a different commitment.
+		let (proof, proofs) = finality_proof(&validators, &commitment(5), &[0, 1, 2, 3]);
+		let VersionedFinalityProof::V1(mut signed) = proof;
+		signed.commitment.block_number += 1;
+		assert!(matches!(
+			verify_finality_proof::<_, EcdsaToEthereum>(&validators.set, signed.into(), proofs),
+			Err(Error::InvalidAuthorityProof(_))
+		));
+	}
+}
@@ -0,0 +1,208 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verification of a random sample of BEEFY signatures.
+//!
+//! Instead of checking all the signatures of a commitment, the light client first receives the
+//! list of validators claiming to have signed it (see
+//! [`SignedCommitmentWitness`](beefy_primitives::witness::SignedCommitmentWitness)), and then
+//! picks a random subset of them using a seed the relayer could not predict when submitting the
+//! witness (e.g. on-chain randomness from a later block). Only the signatures of the sampled
+//! validators have to be provided and verified.
+//!
+//! The security of the scheme relies on the seed being unknown to the relayer at the time it
+//! commits to the set of signers.
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+use beefy_primitives::{AuthorityIndex, Commitment};
+
+use crate::{
+	verify_signatures, AuthorityLeafVerifier, AuthoritySetCommitment, Error, SignatureWithProof,
+};
+
+/// A BEEFY finality proof carrying only the signatures of the sampled validators.
+#[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq, TypeInfo)]
+pub struct SampledFinalityProof<N, Signature> {
+	/// The signed commitment.
+	pub commitment: Commitment<N>,
+	/// The bit vector of validators who signed the commitment.
+	pub signed_by: Vec<bool>,
+	/// Signatures of the sampled validators, ordered by validator index.
+	pub signatures: Vec<SignatureWithProof<Signature>>,
+}
+
+/// Deterministically select `sample_size` validators out of those who signed the commitment.
+///
+/// Returns the indices of the selected validators in ascending order. All signers are returned if
+/// there are no more than `sample_size` of them.
+pub fn sample_signers(
+	seed: &[u8; 32],
+	signed_by: &[bool],
+	sample_size: usize,
+) -> Vec<AuthorityIndex> {
+	let signers = signed_by
+		.iter()
+		.enumerate()
+		.filter_map(|(index, signed)| signed.then(|| index as AuthorityIndex))
+		.collect::<Vec<_>>();
+	if signers.len() <= sample_size {
+		return signers
+	}
+
+	let mut sample = Vec::with_capacity(sample_size);
+	let mut nonce = 0u64;
+	while sample.len() < sample_size {
+		let hash = sp_io::hashing::keccak_256(&(seed, nonce).encode());
+		nonce += 1;
+
+		let mut random = [0u8; 8];
+		random.copy_from_slice(&hash[..8]);
+		let signer = signers[(u64::from_le_bytes(random) % signers.len() as u64) as usize];
+		if let Err(position) = sample.binary_search(&signer) {
+			sample.insert(position, signer);
+		}
+	}
+	sample
+}
+
+/// Verify a [`SampledFinalityProof`].
+///
+/// Checks that enough validators claim to have signed the commitment, and that the proof contains
+/// valid signatures for exactly the validators selected by [`sample_signers`].
+///
+/// Returns the verified commitment.
+pub fn verify_sampled_finality_proof<N, V>(
+	authority_set: &AuthoritySetCommitment,
+	finality_proof: SampledFinalityProof<N, V::Signature>,
+	seed: &[u8; 32],
+	sample_size: usize,
+) -> Result<Commitment<N>, Error>
+where
+	N: Encode,
+	V: AuthorityLeafVerifier,
+{
+	let SampledFinalityProof { commitment, signed_by, signatures } = finality_proof;
+
+	if signed_by.len() != authority_set.len as usize {
+		return Err(Error::InvalidSignaturesLen)
+	}
+
+	let sample = sample_signers(seed, &signed_by, sample_size);
+	if !signatures.iter().map(|s| s.index).eq(sample.into_iter()) {
+		return Err(Error::InvalidSample)
+	}
+
+	let signers = signed_by.iter().filter(|signed| **signed).count();
+	verify_signatures::<N, V>(authority_set, &commitment, signers, &signatures)?;
+	Ok(commitment)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		tests::{commitment, TestValidators},
+		EcdsaToEthereum,
+	};
+
+	fn sampled_finality_proof(
+		validators: &TestValidators,
+		commitment: &Commitment<u64>,
+		signed_by: Vec<bool>,
+		sample: &[AuthorityIndex],
+	) -> SampledFinalityProof<u64, beefy_primitives::crypto::Signature> {
+		let signatures = sample
+			.iter()
+			.map(|index| SignatureWithProof {
+				index: *index,
+				signature: validators.sign(*index as usize, commitment),
+				proof: validators.proof(*index as usize),
+			})
+			.collect();
+		SampledFinalityProof { commitment: commitment.clone(), signed_by, signatures }
+	}
+
+	#[test]
+	fn sample_signers_works() {
+		let signed_by = vec![true, false, true, true, false, true, true, true];
+
+		let sample = sample_signers(&[1; 32], &signed_by, 3);
+		assert_eq!(sample.len(), 3);
+		assert!(sample.windows(2).all(|w| w[0] < w[1]));
+		assert!(sample.iter().all(|i| signed_by[*i as usize]));
+
+		// deterministic for a given seed.
+		assert_eq!(sample, sample_signers(&[1; 32], &signed_by, 3));
+
+		// all signers when sample is larger than signers.
+		assert_eq!(sample_signers(&[1; 32], &signed_by, 10), vec![0, 2, 3, 5, 6, 7]);
+	}
+
+	#[test]
+	fn verifies_sampled_finality_proof() {
+		let validators = TestValidators::new(10);
+		let commitment = commitment(5);
+		let signed_by = vec![true, true, true, false, true, true, true, false, true, false];
+		let seed = [7; 32];
+		let sample = sample_signers(&seed, &signed_by, 3);
+
+		let proof = sampled_finality_proof(&validators, &commitment, signed_by, &sample);
+		assert_eq!(
+			verify_sampled_finality_proof::<_, EcdsaToEthereum>(&validators.set, proof, &seed, 3),
+			Ok(commitment)
+		);
+	}
+
+	#[test]
+	fn rejects_invalid_sampled_finality_proofs() {
+		let validators = TestValidators::new(10);
+		let commitment = commitment(5);
+		let seed = [7; 32];
+
+		// not enough validators claim to have signed.
+		let signed_by = vec![true, true, true, false, true, true, false, false, true, false];
+		let sample = sample_signers(&seed, &signed_by, 3);
+		let proof = sampled_finality_proof(&validators, &commitment, signed_by, &sample);
+		assert_eq!(
+			verify_sampled_finality_proof::<_, EcdsaToEthereum>(&validators.set, proof, &seed, 3),
+			Err(Error::NotEnoughSignatures)
+		);
+
+		// signatures for validators that were not sampled.
+		let signed_by = vec![true, true, true, false, true, true, true, false, true, false];
+		let proof = sampled_finality_proof(&validators, &commitment, signed_by.clone(), &[0, 1, 2]);
+		let sample = sample_signers(&seed, &signed_by, 3);
+		assert_ne!(sample, vec![0, 1, 2]);
+		assert_eq!(
+			verify_sampled_finality_proof::<_, EcdsaToEthereum>(&validators.set, proof, &seed, 3),
+			Err(Error::InvalidSample)
+		);
+
+		// invalid sampled signature.
+		let mut proof = sampled_finality_proof(&validators, &commitment, signed_by, &sample);
+		let other = (0..10).find(|i| !sample.contains(i)).unwrap();
+		proof.signatures[0].signature = validators.sign(other as usize, &commitment);
+		assert_eq!(
+			verify_sampled_finality_proof::<_, EcdsaToEthereum>(&validators.set, proof, &seed, 3),
+			Err(Error::InvalidAuthorityProof(sample[0]))
+		);
+	}
+}
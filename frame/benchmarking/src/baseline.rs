@@ -180,6 +180,7 @@ pub mod mock {
 		type Index = AccountIndex;
 		type BlockNumber = BlockNumber;
 		type RuntimeCall = RuntimeCall;
+		type RuntimeTask = RuntimeTask;
 		type Hash = H256;
 		type Hashing = ::sp_runtime::traits::BlakeTwo256;
 		type AccountId = AccountId;
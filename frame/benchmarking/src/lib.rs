@@ -185,6 +185,22 @@ macro_rules! whitelist {
 ///
 /// These `verify` blocks will not affect your benchmark results!
 ///
+/// The proof size of a benchmark is by default estimated from the `MaxEncodedLen` of the storage
+/// items it accesses. A benchmark can change this with a `#[pov_mode]` attribute, either for all
+/// storage items or per storage item. It has to be the first attribute of the benchmark:
+///
+/// ```ignore
+/// #[pov_mode = MaxEncodedLen {
+/// 	// The storage item is unbounded, use the measured proof size instead.
+/// 	Pallet::Unbounded: Measured,
+/// 	// The storage item is always part of the proof anyway.
+/// 	System::Account: Ignored,
+/// }]
+/// transfer {
+/// 	// ...
+/// }: _(RawOrigin::Signed(caller), dest, amount)
+/// ```
+///
 /// You can construct benchmark by using the `impl_benchmark_test_suite` macro or
 /// by manually implementing them like so:
 ///
@@ -211,6 +227,7 @@ macro_rules! benchmarks {
 			( )
 			( )
 			( )
+			( )
 			$( $rest )*
 		);
 	}
@@ -231,6 +248,7 @@ macro_rules! benchmarks_instance {
 			( )
 			( )
 			( )
+			( )
 			$( $rest )*
 		);
 	}
@@ -251,6 +269,7 @@ macro_rules! benchmarks_instance_pallet {
 			( )
 			( )
 			( )
+			( )
 			$( $rest )*
 		);
 	}
@@ -268,6 +287,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $pov_modes:tt )* )
 		impl_benchmark_test_suite!(
 			$bench_module:ident,
 			$new_test_ext:expr,
@@ -282,6 +302,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* )
 			( $( $names_extra )* )
 			( $( $names_skip_meta )* )
+			( $( $pov_modes )* )
 			$( $rest )*
 		}
 	};
@@ -293,6 +314,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $pov_modes:tt )* )
 		impl_benchmark_test_suite!(
 			$bench_module:ident,
 			$new_test_ext:expr,
@@ -307,6 +329,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* )
 			( $( $names_extra )* )
 			( $( $names_skip_meta )* )
+			( $( $pov_modes )* )
 			$( $rest )*
 		}
 	};
@@ -318,6 +341,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $pov_modes:tt )* )
 		where_clause { where $( $where_bound:tt )* }
 		$( $rest:tt )*
 	) => {
@@ -328,6 +352,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* )
 			( $( $names_extra )* )
 			( $( $names_skip_meta )* )
+			( $( $pov_modes )* )
 			$( $rest )*
 		}
 	};
@@ -339,6 +364,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $pov_modes:tt )* )
 		#[skip_meta]
 		$name:ident
 		$( $rest:tt )*
@@ -350,6 +376,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* )
 			( $( $names_extra )* )
 			( $( $names_skip_meta )* $name )
+			( $( $pov_modes )* )
 			$name
 			$( $rest )*
 		}
@@ -362,6 +389,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $pov_modes:tt )* )
 		#[extra]
 		$name:ident
 		$( $rest:tt )*
@@ -373,6 +401,34 @@ macro_rules! benchmarks_iter {
 			( $( $names )* )
 			( $( $names_extra )* $name )
 			( $( $names_skip_meta )* )
+			( $( $pov_modes )* )
+			$name
+			$( $rest )*
+		}
+	};
+	// detect and extract `#[pov_mode = Mode { Pallet::Storage: Mode, ... }]` tag:
+	(
+		{ $($bench_module:ident, $new_test_ext:expr, $test:path $(, $( $args:tt )* )?)? }
+		{ $( $instance:ident: $instance_bound:tt )? }
+		{ $( $where_clause:tt )* }
+		( $( $names:tt )* )
+		( $( $names_extra:tt )* )
+		( $( $names_skip_meta:tt )* )
+		( $( $pov_modes:tt )* )
+		#[pov_mode = $mode:ident $( { $( $storage:path: $storage_mode:ident ),* $(,)? } )?]
+		$( #[$attr:ident] )*
+		$name:ident
+		$( $rest:tt )*
+	) => {
+		$crate::benchmarks_iter! {
+			{ $($bench_module, $new_test_ext, $test $(, $( $args )* )?)? }
+			{ $( $instance: $instance_bound )? }
+			{ $( $where_clause )* }
+			( $( $names )* )
+			( $( $names_extra )* )
+			( $( $names_skip_meta )* )
+			( $( $pov_modes )* { $name $mode $( $( $storage: $storage_mode, )* )? } )
+			$( #[$attr] )*
 			$name
 			$( $rest )*
 		}
@@ -385,6 +441,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* ) // This contains $( $( { $instance } )? $name:ident )*
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $pov_modes:tt )* )
 		$name:ident { $( $code:tt )* }: _ $(< $origin_type:ty>)? ( $origin:expr $( , $arg:expr )* )
 		verify $postcode:block
 		$( $rest:tt )*
@@ -396,6 +453,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* )
 			( $( $names_extra )* )
 			( $( $names_skip_meta )* )
+			( $( $pov_modes )* )
 			$name { $( $code )* }: $name $(< $origin_type >)? ( $origin $( , $arg )* )
 			verify $postcode
 			$( $rest )*
@@ -409,6 +467,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $pov_modes:tt )* )
 		$name:ident { $( $code:tt )* }: $dispatch:ident $(<$origin_type:ty>)? ( $origin:expr $( , $arg:expr )* )
 		verify $postcode:block
 		$( $rest:tt )*
@@ -421,6 +480,7 @@ macro_rules! benchmarks_iter {
 				( $( $names )* )
 				( $( $names_extra )* )
 				( $( $names_skip_meta )* )
+				( $( $pov_modes )* )
 				$name {
 					$( $code )*
 					let __call = Call::<
@@ -455,6 +515,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $pov_modes:tt )* )
 		$name:ident { $( $code:tt )* }: $eval:block
 		verify $postcode:block
 		$( $rest:tt )*
@@ -483,6 +544,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* { $( $instance )? } $name )
 			( $( $names_extra )* )
 			( $( $names_skip_meta )* )
+			( $( $pov_modes )* )
 			$( $rest )*
 		);
 	};
@@ -494,6 +556,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $pov_modes:tt )* )
 	) => {
 		$crate::selected_benchmark!(
 			{ $( $where_clause)* }
@@ -506,6 +569,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* )
 			( $( $names_extra ),* )
 			( $( $names_skip_meta ),* )
+			( $( $pov_modes )* )
 		);
 		$crate::impl_test_function!(
 			( $( $names )* )
@@ -525,6 +589,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $pov_modes:tt )* )
 	) => {
 		$crate::selected_benchmark!(
 			{ $( $where_clause)* }
@@ -537,6 +602,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* )
 			( $( $names_extra ),* )
 			( $( $names_skip_meta ),* )
+			( $( $pov_modes )* )
 		);
 	};
 	// add verify block to _() format
@@ -547,6 +613,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $pov_modes:tt )* )
 		$name:ident { $( $code:tt )* }: _ $(<$origin_type:ty>)? ( $origin:expr $( , $arg:expr )* )
 		$( $rest:tt )*
 	) => {
@@ -557,6 +624,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* )
 			( $( $names_extra )* )
 			( $( $names_skip_meta )* )
+			( $( $pov_modes )* )
 			$name { $( $code )* }: _ $(<$origin_type>)? ( $origin $( , $arg )* )
 			verify { }
 			$( $rest )*
@@ -570,6 +638,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $pov_modes:tt )* )
 		$name:ident { $( $code:tt )* }: $dispatch:ident $(<$origin_type:ty>)? ( $origin:expr $( , $arg:expr )* )
 		$( $rest:tt )*
 	) => {
@@ -580,6 +649,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* )
 			( $( $names_extra )* )
 			( $( $names_skip_meta )* )
+			( $( $pov_modes )* )
 			$name { $( $code )* }: $dispatch $(<$origin_type>)? ( $origin $( , $arg )* )
 			verify { }
 			$( $rest )*
@@ -593,6 +663,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $pov_modes:tt )* )
 		$name:ident { $( $code:tt )* }: $(<$origin_type:ty>)? $eval:block
 		$( $rest:tt )*
 	) => {
@@ -603,6 +674,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* )
 			( $( $names_extra )* )
 			( $( $names_skip_meta )* )
+			( $( $pov_modes )* )
 			$name { $( $code )* }: $(<$origin_type>)? $eval
 			verify { }
 			$( $rest )*
@@ -974,6 +1046,7 @@ macro_rules! impl_benchmark {
 		( $( { $( $name_inst:ident )? } $name:ident )* )
 		( $( $name_extra:ident ),* )
 		( $( $name_skip_meta:ident ),* )
+		( $( { $pov_name:ident $pov_mode:ident $( $storage:path: $storage_mode:ident, )* } )* )
 	) => {
 		// We only need to implement benchmarks for the runtime-benchmarks feature or testing.
 		#[cfg(any(feature = "runtime-benchmarks", test))]
@@ -995,10 +1068,29 @@ macro_rules! impl_benchmark {
 					let components = <
 						SelectedBenchmark as $crate::BenchmarkingSetup<T $(, $instance)?>
 					>::components(&selected_benchmark);
+					let pov_modes: $crate::Vec<(&str, $crate::Vec<(&str, $crate::PovEstimationMode)>)> = $crate::vec![
+						$( (stringify!($pov_name), $crate::vec![
+							("ALL", $crate::PovEstimationMode::$pov_mode),
+							$( (stringify!($storage), $crate::PovEstimationMode::$storage_mode), )*
+						]), )*
+					];
+					let pov_modes = pov_modes
+						.into_iter()
+						.find(|(name, _)| *name == benchmark)
+						.map(|(_, modes)| modes)
+						.unwrap_or_default()
+						.into_iter()
+						.map(|(storage, mode)| {
+							// `stringify!` may add whitespace to the storage path.
+							let storage = storage.bytes().filter(|b| !b.is_ascii_whitespace());
+							(storage.collect(), mode)
+						})
+						.collect();
 
 					$crate::BenchmarkMetadata {
 						name: benchmark.as_bytes().to_vec(),
 						components,
+						pov_modes,
 					}
 				}).collect::<$crate::Vec<_>>()
 			}
@@ -95,6 +95,7 @@ impl frame_system::Config for Test {
 	type BlockNumber = u64;
 	type Hash = H256;
 	type RuntimeCall = RuntimeCall;
+	type RuntimeTask = RuntimeTask;
 	type Hashing = BlakeTwo256;
 	type AccountId = u64;
 	type Lookup = IdentityLookup<Self::AccountId>;
@@ -136,7 +137,10 @@ thread_local! {
 #[allow(unreachable_code)]
 mod benchmarks {
 	use super::{new_test_ext, pallet_test::Value, Test, VALUES_PER_COMPONENT};
-	use crate::{account, BenchmarkError, BenchmarkParameter, BenchmarkResult, BenchmarkingSetup};
+	use crate::{
+		account, BenchmarkError, BenchmarkParameter, BenchmarkResult, Benchmarking,
+		BenchmarkingSetup, PovEstimationMode,
+	};
 	use frame_support::{assert_err, assert_ok, ensure, traits::Get};
 	use frame_system::RawOrigin;
 	use rusty_fork::rusty_fork_test;
@@ -217,6 +221,12 @@ mod benchmarks {
 			assert_eq!(Value::<T>::get(), Some(b));
 		}
 
+		#[pov_mode = Measured { TestPallet::Value: MaxEncodedLen, System::Account: Ignored }]
+		#[extra]
+		pov_mode_benchmark {
+			let caller = account::<T::AccountId>("caller", 0, 0);
+		}: set_value(RawOrigin::Signed(caller), 1)
+
 		override_benchmark {
 			let b in 1 .. 1000;
 			let caller = account::<T::AccountId>("caller", 0, 0);
@@ -368,6 +378,32 @@ mod benchmarks {
 		});
 	}
 
+	#[test]
+	fn benchmarks_metadata_contains_pov_modes() {
+		let metadata = <Pallet<Test> as Benchmarking>::benchmarks(true);
+		let pov_modes = |name: &[u8]| {
+			metadata
+				.iter()
+				.find(|m| m.name == name)
+				.expect("benchmark exists")
+				.pov_modes
+				.clone()
+		};
+
+		assert_eq!(
+			pov_modes(b"pov_mode_benchmark"),
+			vec![
+				(b"ALL".to_vec(), PovEstimationMode::Measured),
+				(b"TestPallet::Value".to_vec(), PovEstimationMode::MaxEncodedLen),
+				(b"System::Account".to_vec(), PovEstimationMode::Ignored),
+			],
+		);
+		assert!(pov_modes(b"set_value").is_empty());
+		// The `#[extra]` attribute after `#[pov_mode]` is still respected.
+		let metadata = <Pallet<Test> as Benchmarking>::benchmarks(false);
+		assert!(!metadata.iter().any(|m| m.name == b"pov_mode_benchmark"));
+	}
+
 	#[test]
 	fn benchmarks_generate_unit_tests() {
 		new_test_ext().execute_with(|| {
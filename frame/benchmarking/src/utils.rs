@@ -221,10 +221,30 @@ pub struct BenchmarkList {
 pub struct BenchmarkMetadata {
 	pub name: Vec<u8>,
 	pub components: Vec<(BenchmarkParameter, u32, u32)>,
+	/// The PoV estimation modes set with `#[pov_mode]`.
+	///
+	/// Maps either `ALL` or a `Pallet::Storage` name to its mode.
+	pub pov_modes: Vec<(Vec<u8>, PovEstimationMode)>,
+}
+
+/// How the proof size of the storage accesses of a benchmark is estimated.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PovEstimationMode {
+	/// Use the `MaxEncodedLen` of the storage item and the depth of the trie to calculate the
+	/// worst case proof size.
+	MaxEncodedLen,
+	/// Use the proof size that was measured while running the benchmark.
+	Measured,
+	/// Do not account the storage accesses in the proof size.
+	Ignored,
 }
 
 sp_api::decl_runtime_apis! {
 	/// Runtime api for benchmarking a FRAME runtime.
+	///
+	/// Version 2 added the PoV estimation modes to [`BenchmarkMetadata`].
+	#[api_version(2)]
 	pub trait Benchmark {
 		/// Get the benchmark metadata available for this runtime.
 		///
@@ -178,6 +178,15 @@ pub mod pallet {
 	#[pallet::without_storage_info]
 	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
+	#[pallet::extra_constants]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// The current storage version of the pallet.
+		#[pallet::constant_name(StorageVersion)]
+		fn storage_version() -> StorageVersion {
+			STORAGE_VERSION
+		}
+	}
+
 	#[pallet::config]
 	pub trait Config<I: 'static = ()>: frame_system::Config {
 		/// The runtime origin type.
@@ -213,6 +213,15 @@ pub mod pallet {
 	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
 
+	#[pallet::extra_constants]
+	impl<T: Config> Pallet<T> {
+		/// The current storage version of the pallet.
+		#[pallet::constant_name(StorageVersion)]
+		fn storage_version() -> StorageVersion {
+			STORAGE_VERSION
+		}
+	}
+
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// The time implementation used to supply timestamps to contracts through `seal_now`.
@@ -221,6 +221,15 @@ pub mod pallet {
 	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	#[pallet::extra_constants]
+	impl<T: Config> Pallet<T> {
+		/// The current storage version of the pallet.
+		#[pallet::constant_name(StorageVersion)]
+		fn storage_version() -> StorageVersion {
+			STORAGE_VERSION
+		}
+	}
+
 	#[pallet::config]
 	pub trait Config: frame_system::Config + Sized {
 		type WeightInfo: WeightInfo;
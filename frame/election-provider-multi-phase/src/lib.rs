@@ -732,6 +732,12 @@ pub mod pallet {
 		fn max_votes_per_voter() -> u32 {
 			<T::MinerConfig as MinerConfig>::MaxVotesPerVoter::get()
 		}
+
+		/// The current storage version of the pallet.
+		#[pallet::constant_name(StorageVersion)]
+		fn storage_version() -> StorageVersion {
+			STORAGE_VERSION
+		}
 	}
 
 	#[pallet::hooks]
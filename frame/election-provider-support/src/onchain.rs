@@ -213,6 +213,7 @@ mod tests {
 		type Index = AccountId;
 		type BlockNumber = BlockNumber;
 		type RuntimeCall = RuntimeCall;
+		type RuntimeTask = RuntimeTask;
 		type Hash = sp_core::H256;
 		type Hashing = sp_runtime::traits::BlakeTwo256;
 		type AccountId = AccountId;
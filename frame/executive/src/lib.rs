@@ -408,12 +408,16 @@ where
 			// any initial checks
 			Self::initial_checks(&block);
 
+			// Verify the signatures of all signed extrinsics in parallel, after executing them.
+			// An invalid signature makes the whole block invalid either way.
+			#[cfg(feature = "batch-signature-verification")]
 			let signature_batching = sp_runtime::SignatureBatching::start();
 
 			// execute extrinsics
 			let (header, extrinsics) = block.deconstruct();
 			Self::execute_extrinsics_with_book_keeping(extrinsics, *header.number());
 
+			#[cfg(feature = "batch-signature-verification")]
 			if !signature_batching.verify() {
 				panic!("Signature verification failed.");
 			}
@@ -594,7 +598,7 @@ where
 mod tests {
 	use super::*;
 
-	use sp_core::H256;
+	use sp_core::{testing::TaskExecutor, traits::TaskExecutorExt, H256};
 	use sp_runtime::{
 		generic::{DigestItem, Era},
 		testing::{Block, Digest, Header},
@@ -704,6 +708,20 @@ mod tests {
 				sp_io::storage::set("storage_root".as_bytes(), &root);
 				Ok(())
 			}
+
+			#[pallet::weight(0)]
+			pub fn batch_verify_signature(
+				origin: OriginFor<T>,
+				signature: sp_core::sr25519::Signature,
+				signer: sp_core::sr25519::Public,
+				msg: Vec<u8>,
+			) -> DispatchResult {
+				use sp_runtime::traits::Verify;
+
+				frame_system::ensure_signed(origin)?;
+				ensure!(signature.batch_verify(&msg[..], &signer), "Invalid signature");
+				Ok(())
+			}
 		}
 
 		#[pallet::inherent]
@@ -782,6 +800,7 @@ mod tests {
 		type RuntimeOrigin = RuntimeOrigin;
 		type Index = u64;
 		type RuntimeCall = RuntimeCall;
+		type RuntimeTask = RuntimeTask;
 		type BlockNumber = u64;
 		type Hash = sp_core::H256;
 		type Hashing = BlakeTwo256;
@@ -922,7 +941,10 @@ mod tests {
 		pallet_balances::GenesisConfig::<Runtime> { balances: vec![(1, 111 * balance_factor)] }
 			.assimilate_storage(&mut t)
 			.unwrap();
-		t.into()
+		let mut ext: sp_io::TestExternalities = t.into();
+		// Needed to verify signatures in `execute_block` with `batch-signature-verification`.
+		ext.register_extension(TaskExecutorExt::new(TaskExecutor::new()));
+		ext
 	}
 
 	fn new_test_ext_v0(balance_factor: Balance) -> sp_io::TestExternalities {
@@ -930,7 +952,10 @@ mod tests {
 		pallet_balances::GenesisConfig::<Runtime> { balances: vec![(1, 111 * balance_factor)] }
 			.assimilate_storage(&mut t)
 			.unwrap();
-		(t, sp_runtime::StateVersion::V0).into()
+		let mut ext: sp_io::TestExternalities = (t, sp_runtime::StateVersion::V0).into();
+		// Needed to verify signatures in `execute_block` with `batch-signature-verification`.
+		ext.register_extension(TaskExecutorExt::new(TaskExecutor::new()));
+		ext
 	}
 
 	#[test]
@@ -1478,6 +1503,60 @@ mod tests {
 		});
 	}
 
+	#[cfg(feature = "batch-signature-verification")]
+	fn signature_block(valid: bool) -> (Header, TestXt) {
+		use sp_core::Pair;
+
+		let pair = sp_core::sr25519::Pair::from_seed(&[1u8; 32]);
+		let msg = b"message".to_vec();
+		let signature = if valid { pair.sign(&msg) } else { pair.sign(b"other message") };
+		let call = RuntimeCall::Custom(custom::Call::batch_verify_signature {
+			signature,
+			signer: pair.public(),
+			msg,
+		});
+		let xt = TestXt::new(call, sign_extra(1, 0, 0));
+
+		let header = new_test_ext(1).execute_with(|| {
+			Executive::initialize_block(&Header::new(
+				1,
+				H256::default(),
+				H256::default(),
+				[69u8; 32].into(),
+				Digest::default(),
+			));
+
+			// Outside of `execute_block` the signature is verified immediately.
+			assert_eq!(Executive::apply_extrinsic(xt.clone()).unwrap().is_ok(), valid);
+
+			Executive::finalize_block()
+		});
+
+		(header, xt)
+	}
+
+	#[test]
+	#[cfg(feature = "batch-signature-verification")]
+	fn execute_block_batch_verifies_signatures() {
+		let (header, xt) = signature_block(true);
+
+		new_test_ext(1).execute_with(|| {
+			Executive::execute_block(Block::new(header, vec![xt]));
+		});
+	}
+
+	#[test]
+	#[cfg(feature = "batch-signature-verification")]
+	#[should_panic(expected = "Signature verification failed.")]
+	fn execute_block_with_invalid_batched_signature_fails() {
+		let (header, xt) = signature_block(false);
+
+		new_test_ext(1).execute_with(|| {
+			// The dispatch succeeds since the signature is only queued, but the block is invalid.
+			Executive::execute_block(Block::new(header, vec![xt]));
+		});
+	}
+
 	#[test]
 	#[should_panic(expected = "Invalid inherent position for extrinsic at index 1")]
 	fn invalid_inherent_position_fail() {
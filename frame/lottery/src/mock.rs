@@ -60,6 +60,7 @@ impl frame_system::Config for Test {
 	type RuntimeOrigin = RuntimeOrigin;
 	type Index = u64;
 	type RuntimeCall = RuntimeCall;
+	type RuntimeTask = RuntimeTask;
 	type BlockNumber = u64;
 	type Hash = H256;
 	type Hashing = BlakeTwo256;
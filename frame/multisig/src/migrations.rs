@@ -19,7 +19,7 @@
 
 use super::*;
 use frame_support::{
-	dispatch::GetStorageVersion,
+	migrations::VersionedMigration,
 	traits::{OnRuntimeUpgrade, WrapperKeepOpaque},
 	Identity,
 };
@@ -40,42 +40,28 @@ pub mod v1 {
 		(OpaqueCall<T>, <T as frame_system::Config>::AccountId, BalanceOf<T>),
 	>;
 
-	pub struct MigrateToV1<T>(sp_std::marker::PhantomData<T>);
-	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+	/// Refunds and deletes all stored calls, without checking the storage version.
+	///
+	/// Use [`MigrateToV1`] instead, which also checks and sets the storage version.
+	pub struct UncheckedMigrateToV1<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for UncheckedMigrateToV1<T> {
 		#[cfg(feature = "try-runtime")]
 		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
-			let onchain = Pallet::<T>::on_chain_storage_version();
-
-			ensure!(onchain < 1, "this migration can be deleted");
-
 			log!(info, "Number of calls to refund and delete: {}", Calls::<T>::iter().count());
 
 			Ok(Vec::new())
 		}
 
 		fn on_runtime_upgrade() -> Weight {
-			let current = Pallet::<T>::current_storage_version();
-			let onchain = Pallet::<T>::on_chain_storage_version();
-
-			if onchain > 0 {
-				log!(info, "MigrateToV1 should be removed");
-				return T::DbWeight::get().reads(1)
-			}
-
 			Calls::<T>::drain().for_each(|(_call_hash, (_data, caller, deposit))| {
 				T::Currency::unreserve(&caller, deposit);
 			});
 
-			current.put::<Pallet<T>>();
-
 			<T as frame_system::Config>::BlockWeights::get().max_block
 		}
 
 		#[cfg(feature = "try-runtime")]
 		fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
-			let onchain = Pallet::<T>::on_chain_storage_version();
-			ensure!(onchain < 2, "this migration needs to be removed");
-			ensure!(onchain == 1, "this migration needs to be run");
 			ensure!(
 				Calls::<T>::iter().count() == 0,
 				"there are some dangling calls that need to be destroyed and refunded"
@@ -83,4 +69,14 @@ pub mod v1 {
 			Ok(())
 		}
 	}
+
+	/// Refunds and deletes all stored calls if the on-chain storage version is 0, and sets it to
+	/// 1 afterwards.
+	pub type MigrateToV1<T> = VersionedMigration<
+		0,
+		1,
+		UncheckedMigrateToV1<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
 }
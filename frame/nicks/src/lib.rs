@@ -285,6 +285,7 @@ mod tests {
 		type BlockNumber = u64;
 		type Hash = H256;
 		type RuntimeCall = RuntimeCall;
+		type RuntimeTask = RuntimeTask;
 		type Hashing = BlakeTwo256;
 		type AccountId = u64;
 		type Lookup = IdentityLookup<Self::AccountId>;
@@ -1180,6 +1180,15 @@ pub mod pallet {
 	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	#[pallet::extra_constants]
+	impl<T: Config> Pallet<T> {
+		/// The current storage version of the pallet.
+		#[pallet::constant_name(StorageVersion)]
+		fn storage_version() -> StorageVersion {
+			STORAGE_VERSION
+		}
+	}
+
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// The overarching event type.
@@ -2120,27 +2129,30 @@ pub mod pallet {
 			);
 		}
 	}
-}
 
-impl<T: Config> Pallet<T> {
-	/// Returns the pending rewards for the specified `member_account`.
-	///
-	/// In the case of error, `None` is returned.
-	pub fn pending_rewards(member_account: T::AccountId) -> Option<BalanceOf<T>> {
-		if let Some(pool_member) = PoolMembers::<T>::get(member_account) {
-			if let Some((reward_pool, bonded_pool)) = RewardPools::<T>::get(pool_member.pool_id)
-				.zip(BondedPools::<T>::get(pool_member.pool_id))
-			{
-				let current_reward_counter = reward_pool
-					.current_reward_counter(pool_member.pool_id, bonded_pool.points)
-					.ok()?;
-				return pool_member.pending_rewards(current_reward_counter).ok()
+	#[pallet::view_functions]
+	impl<T: Config> Pallet<T> {
+		/// Returns the pending rewards for the specified `member_account`.
+		///
+		/// In the case of error, `None` is returned.
+		pub fn pending_rewards(member_account: T::AccountId) -> Option<BalanceOf<T>> {
+			if let Some(pool_member) = PoolMembers::<T>::get(member_account) {
+				if let Some((reward_pool, bonded_pool)) = RewardPools::<T>::get(pool_member.pool_id)
+					.zip(BondedPools::<T>::get(pool_member.pool_id))
+				{
+					let current_reward_counter = reward_pool
+						.current_reward_counter(pool_member.pool_id, bonded_pool.points)
+						.ok()?;
+					return pool_member.pending_rewards(current_reward_counter).ok()
+				}
 			}
-		}
 
-		None
+			None
+		}
 	}
+}
 
+impl<T: Config> Pallet<T> {
 	/// The amount of bond that MUST REMAIN IN BONDED in ALL POOLS.
 	///
 	/// It is the responsibility of the depositor to put these funds into the pool initially. Upon
@@ -2418,6 +2430,9 @@ impl<T: Config> Pallet<T> {
 	/// * each `BondedPool.member_counter` must be:
 	///   - correct (compared to actual count of member who have `.pool_id` this pool)
 	///   - less than `MaxPoolMembersPerPool`.
+	/// * the sum of the active points of the members of each `BondedPool` must not exceed its
+	///   `points`.
+	/// * the depositor of each `BondedPool` must be a member of that pool.
 	/// * each `member.pool_id` must correspond to an existing `BondedPool.id` (which implies the
 	///   existence of the reward pool as well).
 	/// * count of all members must be less than `MaxPoolMembers`.
@@ -2444,42 +2459,60 @@ impl<T: Config> Pallet<T> {
 		// result in the same set of keys, in the same order.
 		let bonded_pools = BondedPools::<T>::iter_keys().collect::<Vec<_>>();
 		let reward_pools = RewardPools::<T>::iter_keys().collect::<Vec<_>>();
-		assert_eq!(bonded_pools, reward_pools);
+		ensure!(bonded_pools == reward_pools, "bonded and reward pools don't match");
 
-		assert!(Metadata::<T>::iter_keys().all(|k| bonded_pools.contains(&k)));
-		assert!(SubPoolsStorage::<T>::iter_keys().all(|k| bonded_pools.contains(&k)));
+		ensure!(
+			Metadata::<T>::iter_keys().all(|k| bonded_pools.contains(&k)),
+			"metadata of a non-existent pool"
+		);
+		ensure!(
+			SubPoolsStorage::<T>::iter_keys().all(|k| bonded_pools.contains(&k)),
+			"sub pools of a non-existent pool"
+		);
 
-		assert!(MaxPools::<T>::get().map_or(true, |max| bonded_pools.len() <= (max as usize)));
+		ensure!(
+			MaxPools::<T>::get().map_or(true, |max| bonded_pools.len() <= (max as usize)),
+			"too many pools"
+		);
 
 		for id in reward_pools {
 			let account = Self::create_reward_account(id);
-			assert!(
-				T::Currency::free_balance(&account) >= T::Currency::minimum_balance(),
-				"reward pool of {id}: {:?} (ed = {:?})",
-				T::Currency::free_balance(&account),
-				T::Currency::minimum_balance()
-			);
+			if T::Currency::free_balance(&account) < T::Currency::minimum_balance() {
+				log!(
+					error,
+					"reward pool of {:?}: {:?} (ed = {:?})",
+					id,
+					T::Currency::free_balance(&account),
+					T::Currency::minimum_balance()
+				);
+				return Err("reward pool account below the existential deposit")
+			}
 		}
 
 		let mut pools_members = BTreeMap::<PoolId, u32>::new();
+		let mut pools_members_points = BTreeMap::<PoolId, BalanceOf<T>>::new();
 		let mut pools_members_pending_rewards = BTreeMap::<PoolId, BalanceOf<T>>::new();
 		let mut all_members = 0u32;
-		PoolMembers::<T>::iter().for_each(|(_, d)| {
-			let bonded_pool = BondedPools::<T>::get(d.pool_id).unwrap();
-			assert!(!d.total_points().is_zero(), "no member should have zero points: {:?}", d);
+		PoolMembers::<T>::iter().try_for_each(|(_, d)| -> Result<(), &'static str> {
+			let bonded_pool =
+				BondedPools::<T>::get(d.pool_id).ok_or("member of a non-existent pool")?;
+			ensure!(!d.total_points().is_zero(), "no member should have zero points");
 			*pools_members.entry(d.pool_id).or_default() += 1;
+			*pools_members_points.entry(d.pool_id).or_default() += d.active_points();
 			all_members += 1;
 
-			let reward_pool = RewardPools::<T>::get(d.pool_id).unwrap();
+			let reward_pool = RewardPools::<T>::get(d.pool_id).ok_or("reward pool not found")?;
 			if !bonded_pool.points.is_zero() {
-				let current_rc =
-					reward_pool.current_reward_counter(d.pool_id, bonded_pool.points).unwrap();
+				let current_rc = reward_pool
+					.current_reward_counter(d.pool_id, bonded_pool.points)
+					.map_err(|_| "reward counter overflow")?;
 				*pools_members_pending_rewards.entry(d.pool_id).or_default() +=
-					d.pending_rewards(current_rc).unwrap();
+					d.pending_rewards(current_rc).map_err(|_| "pending rewards overflow")?;
 			} // else this pool has been heavily slashed and cannot have any rewards anymore.
-		});
+			Ok(())
+		})?;
 
-		RewardPools::<T>::iter_keys().for_each(|id| {
+		RewardPools::<T>::iter_keys().try_for_each(|id| -> Result<(), &'static str> {
 			// the sum of the pending rewards must be less than the leftover balance. Since the
 			// reward math rounds down, we might accumulate some dust here.
 			log!(
@@ -2489,30 +2522,46 @@ impl<T: Config> Pallet<T> {
 				pools_members_pending_rewards.get(&id),
 				RewardPool::<T>::current_balance(id)
 			);
-			assert!(
+			ensure!(
 				RewardPool::<T>::current_balance(id) >=
-					pools_members_pending_rewards.get(&id).map(|x| *x).unwrap_or_default()
-			)
-		});
+					pools_members_pending_rewards.get(&id).map(|x| *x).unwrap_or_default(),
+				"pending rewards exceed the reward pool balance"
+			);
+			Ok(())
+		})?;
 
-		BondedPools::<T>::iter().for_each(|(id, inner)| {
+		BondedPools::<T>::iter().try_for_each(|(id, inner)| -> Result<(), &'static str> {
 			let bonded_pool = BondedPool { id, inner };
-			assert_eq!(
-				pools_members.get(&id).map(|x| *x).unwrap_or_default(),
-				bonded_pool.member_counter
+			ensure!(
+				pools_members.get(&id).map(|x| *x).unwrap_or_default() ==
+					bonded_pool.member_counter,
+				"wrong member counter"
+			);
+			ensure!(
+				MaxPoolMembersPerPool::<T>::get()
+					.map_or(true, |max| bonded_pool.member_counter <= max),
+				"too many members in a pool"
+			);
+			ensure!(
+				pools_members_points.get(&id).copied().unwrap_or_default() <= bonded_pool.points,
+				"members have more points than their pool"
 			);
-			assert!(MaxPoolMembersPerPool::<T>::get()
-				.map_or(true, |max| bonded_pool.member_counter <= max));
 
-			let depositor = PoolMembers::<T>::get(&bonded_pool.roles.depositor).unwrap();
-			assert!(
+			let depositor = PoolMembers::<T>::get(&bonded_pool.roles.depositor)
+				.ok_or("depositor is not a member")?;
+			ensure!(depositor.pool_id == id, "depositor is a member of another pool");
+			ensure!(
 				bonded_pool.is_destroying_and_only_depositor(depositor.active_points()) ||
 					depositor.active_points() >= MinCreateBond::<T>::get(),
 				"depositor must always have MinCreateBond stake in the pool, except for when the \
 				pool is being destroyed and the depositor is the last member",
 			);
-		});
-		assert!(MaxPoolMembers::<T>::get().map_or(true, |max| all_members <= max));
+			Ok(())
+		})?;
+		ensure!(
+			MaxPoolMembers::<T>::get().map_or(true, |max| all_members <= max),
+			"too many members"
+		);
 
 		if level <= 1 {
 			return Ok(())
@@ -2526,15 +2575,18 @@ impl<T: Config> Pallet<T> {
 			let bonded_balance = T::Staking::active_stake(&pool_account).unwrap_or_default();
 			let total_balance = T::Currency::total_balance(&pool_account);
 
-			assert!(
-				total_balance >= bonded_balance + sum_unbonding_balance,
-				"faulty pool: {:?} / {:?}, total_balance {:?} >= bonded_balance {:?} + sum_unbonding_balance {:?}",
-				pool_id,
-				_pool,
-				total_balance,
-				bonded_balance,
-				sum_unbonding_balance
-			);
+			if total_balance < bonded_balance + sum_unbonding_balance {
+				log!(
+					error,
+					"faulty pool: {:?} / {:?}, total_balance {:?} >= bonded_balance {:?} + sum_unbonding_balance {:?}",
+					pool_id,
+					_pool,
+					total_balance,
+					bonded_balance,
+					sum_unbonding_balance
+				);
+				return Err("pool balance below its bonded and unbonding balance")
+			}
 		}
 
 		Ok(())
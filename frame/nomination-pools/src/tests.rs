@@ -1300,6 +1300,20 @@ mod claim_payout {
 		});
 	}
 
+	#[test]
+	fn pending_rewards_view_function_works() {
+		use frame_support::view_functions::ViewFunction;
+
+		ExtBuilder::default().build_and_execute(|| {
+			Balances::mutate_account(&default_reward_account(), |f| f.free += 30).unwrap();
+
+			let id = <PendingRewardsViewFunction<Runtime> as ViewFunction>::id();
+			let input = PendingRewardsViewFunction::<Runtime>::new(10).encode();
+			let output = Runtime::execute_view_function(id, input).unwrap();
+			assert_eq!(Option::<Balance>::decode(&mut &output[..]).unwrap(), Some(30));
+		});
+	}
+
 	#[test]
 	fn pending_rewards_per_member_works() {
 		ExtBuilder::default().build_and_execute(|| {
@@ -5100,3 +5114,40 @@ mod reward_counter_precision {
 			});
 	}
 }
+
+mod try_state {
+	use super::*;
+
+	#[test]
+	fn try_state_detects_corrupted_state() {
+		ExtBuilder::default().add_members(vec![(20, 20)]).build().execute_with(|| {
+			assert_ok!(Pools::do_try_state(u8::MAX));
+
+			// a member of a pool which doesn't exist.
+			let member = PoolMembers::<Runtime>::get(20).unwrap();
+			PoolMembers::<Runtime>::insert(20, PoolMember { pool_id: 2, ..member.clone() });
+			assert_eq!(Pools::do_try_state(u8::MAX), Err("member of a non-existent pool"));
+			PoolMembers::<Runtime>::insert(20, member);
+
+			// the member counter of the pool doesn't count all of its members.
+			BondedPools::<Runtime>::mutate(1, |pool| pool.as_mut().unwrap().member_counter -= 1);
+			assert_eq!(Pools::do_try_state(u8::MAX), Err("wrong member counter"));
+			BondedPools::<Runtime>::mutate(1, |pool| pool.as_mut().unwrap().member_counter += 1);
+
+			// the members have more points than the pool.
+			BondedPools::<Runtime>::mutate(1, |pool| pool.as_mut().unwrap().points -= 1);
+			assert_eq!(
+				Pools::do_try_state(u8::MAX),
+				Err("members have more points than their pool")
+			);
+			BondedPools::<Runtime>::mutate(1, |pool| pool.as_mut().unwrap().points += 1);
+
+			// the reward pool is missing.
+			let reward_pool = RewardPools::<Runtime>::take(1).unwrap();
+			assert_eq!(Pools::do_try_state(u8::MAX), Err("bonded and reward pools don't match"));
+			RewardPools::<Runtime>::insert(1, reward_pool);
+
+			assert_ok!(Pools::do_try_state(u8::MAX));
+		});
+	}
+}
@@ -178,6 +178,14 @@ pub mod pallet {
 		type AnnouncementDepositFactor: Get<BalanceOf<Self>>;
 	}
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		#[cfg(feature = "try-runtime")]
+		fn try_state(n: BlockNumberFor<T>) -> Result<(), &'static str> {
+			Self::do_try_state(n)
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Dispatch the given `call` from an account that the sender is authorised for through
@@ -793,3 +801,40 @@ impl<T: Config> Pallet<T> {
 		Self::deposit_event(Event::ProxyExecuted { result: e.map(|_| ()).map_err(|e| e.error) });
 	}
 }
+
+#[cfg(any(test, feature = "try-runtime"))]
+impl<T: Config> Pallet<T> {
+	/// Ensure the correctness of the state of this pallet.
+	///
+	/// * The proxies of an account are not empty, sorted, without duplicates and don't include the
+	///   account itself. Their deposit is reserved.
+	/// * The announcements of an account are not empty and their deposit is reserved.
+	///
+	/// The deposits are only checked to be reserved, since the deposit configuration may have
+	/// changed after they were taken. The proxy deposit of a pure account is reserved by its
+	/// spawner, which is one of its delegates, so the reserved balances of the delegates count
+	/// towards it.
+	pub(crate) fn do_try_state(_: T::BlockNumber) -> Result<(), &'static str> {
+		Proxies::<T>::iter().try_for_each(
+			|(who, (proxies, deposit))| -> Result<(), &'static str> {
+				ensure!(!proxies.is_empty(), "empty proxies");
+				ensure!(proxies.windows(2).all(|w| w[0] < w[1]), "proxies are not sorted");
+				ensure!(proxies.iter().all(|p| p.delegate != who), "account is its own proxy");
+				let reserved = proxies.iter().fold(T::Currency::reserved_balance(&who), |r, p| {
+					r.saturating_add(T::Currency::reserved_balance(&p.delegate))
+				});
+				ensure!(reserved >= deposit, "proxy deposit is not reserved");
+				Ok(())
+			},
+		)?;
+
+		Announcements::<T>::iter().try_for_each(|(who, (pending, deposit))| {
+			ensure!(!pending.is_empty(), "empty announcements");
+			ensure!(
+				T::Currency::reserved_balance(&who) >= deposit,
+				"announcement deposit is not reserved"
+			);
+			Ok(())
+		})
+	}
+}
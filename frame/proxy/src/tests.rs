@@ -66,6 +66,7 @@ impl frame_system::Config for Test {
 	type BlockNumber = u64;
 	type Hash = H256;
 	type RuntimeCall = RuntimeCall;
+	type RuntimeTask = RuntimeTask;
 	type Hashing = BlakeTwo256;
 	type AccountId = u64;
 	type Lookup = IdentityLookup<Self::AccountId>;
@@ -98,6 +99,8 @@ impl pallet_utility::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
 	type PalletsOrigin = OriginCaller;
+	type MaxBatchAllChanges = ();
+	type MaxBatchAllLayers = ConstU32<10>;
 	type WeightInfo = ();
 }
 
@@ -608,3 +611,35 @@ fn pure_works() {
 		);
 	});
 }
+
+#[test]
+fn try_state_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Proxy::add_proxy(RuntimeOrigin::signed(1), 3, ProxyType::Any, 1));
+		assert_ok!(Proxy::add_proxy(RuntimeOrigin::signed(1), 2, ProxyType::JustTransfer, 0));
+		assert_ok!(Proxy::create_pure(RuntimeOrigin::signed(2), ProxyType::Any, 0, 0));
+		assert_ok!(Proxy::announce(RuntimeOrigin::signed(3), 1, [1; 32].into()));
+		assert_ok!(Proxy::do_try_state(System::block_number()));
+
+		Proxies::<Test>::mutate(1, |(proxies, _)| proxies.sort_by(|x, y| y.cmp(x)));
+		assert_eq!(Proxy::do_try_state(System::block_number()), Err("proxies are not sorted"));
+		Proxies::<Test>::mutate(1, |(proxies, _)| proxies.sort());
+
+		// A deposit lower than the current configuration is fine, it may have been taken before.
+		Proxies::<Test>::mutate(1, |(_, deposit)| *deposit -= 1);
+		assert_ok!(Proxy::do_try_state(System::block_number()));
+		Proxies::<Test>::mutate(1, |(_, deposit)| *deposit += 1);
+
+		assert_eq!(Balances::unreserve(&3, 2), 0);
+		assert_eq!(
+			Proxy::do_try_state(System::block_number()),
+			Err("announcement deposit is not reserved")
+		);
+
+		Proxies::<Test>::mutate(1, |(_, deposit)| *deposit += 100);
+		assert_eq!(
+			Proxy::do_try_state(System::block_number()),
+			Err("proxy deposit is not reserved")
+		);
+	});
+}
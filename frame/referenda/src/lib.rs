@@ -370,6 +370,14 @@ pub mod pallet {
 		NoDeposit,
 	}
 
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		#[cfg(feature = "try-runtime")]
+		fn try_state(n: BlockNumberFor<T>) -> Result<(), &'static str> {
+			Self::do_try_state(n)
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		/// Propose a referendum on a privileged action.
@@ -1163,3 +1171,44 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			approval_needed.passing(x, tally.approval(id))
 	}
 }
+
+#[cfg(any(test, feature = "try-runtime"))]
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Ensure the correctness of the state of this pallet.
+	///
+	/// * All referenda have an index below `ReferendumCount`.
+	/// * Ongoing referenda and track queues belong to known tracks.
+	/// * A referendum being decided is not queued.
+	/// * `DecidingCount` is at most `max_deciding` and at least the number of referenda being
+	///   decided. It may be higher while a `one_fewer_deciding` call is pending.
+	/// * Track queues are sorted by ascending approval.
+	pub(crate) fn do_try_state(_: T::BlockNumber) -> Result<(), &'static str> {
+		let count = ReferendumCount::<T, I>::get();
+		let mut deciding = sp_std::collections::btree_map::BTreeMap::<TrackIdOf<T, I>, u32>::new();
+		for (index, info) in ReferendumInfoFor::<T, I>::iter() {
+			ensure!(index < count, "referendum index above the referendum count");
+			if let ReferendumInfo::Ongoing(status) = info {
+				ensure!(T::Tracks::info(status.track).is_some(), "referendum of an unknown track");
+				if status.deciding.is_some() {
+					ensure!(!status.in_queue, "referendum both deciding and queued");
+					*deciding.entry(status.track).or_default() += 1;
+				}
+			}
+		}
+
+		for (track, info) in T::Tracks::tracks() {
+			let deciding_count = DecidingCount::<T, I>::get(track);
+			ensure!(deciding_count <= info.max_deciding, "too many referenda being decided");
+			ensure!(
+				deciding_count >= deciding.get(track).copied().unwrap_or_default(),
+				"deciding count below the number of referenda being decided"
+			);
+		}
+
+		TrackQueue::<T, I>::iter().try_for_each(|(track, queue)| {
+			ensure!(T::Tracks::info(track).is_some(), "queue of an unknown track");
+			ensure!(queue.windows(2).all(|w| w[0].1 <= w[1].1), "track queue is not sorted");
+			Ok(())
+		})
+	}
+}
@@ -519,3 +519,45 @@ fn curve_handles_all_inputs() {
 	let threshold = test_curve.threshold(Perbill::one());
 	assert_eq!(threshold, Perbill::zero());
 }
+
+#[test]
+fn try_state_works() {
+	new_test_ext().execute_with(|| {
+		for i in 0..=2 {
+			assert_ok!(Referenda::submit(
+				RuntimeOrigin::signed(i + 1),
+				Box::new(RawOrigin::Root.into()),
+				set_balance_proposal_bounded(i),
+				DispatchTime::After(0),
+			));
+			assert_ok!(Referenda::place_decision_deposit(RuntimeOrigin::signed(i + 1), i as u32));
+		}
+		set_tally(1, 10, 0);
+		set_tally(2, 20, 0);
+		run_to(5);
+		assert_eq!(DecidingCount::<Test>::get(0), 1);
+		assert_eq!(TrackQueue::<Test>::get(0).len(), 2);
+		assert_ok!(Referenda::do_try_state(System::block_number()));
+
+		TrackQueue::<Test>::mutate(0, |q| q.sort_by(|x, y| y.cmp(x)));
+		assert_eq!(
+			Referenda::do_try_state(System::block_number()),
+			Err("track queue is not sorted")
+		);
+		TrackQueue::<Test>::mutate(0, |q| q.sort());
+
+		DecidingCount::<Test>::insert(0, 0);
+		assert_eq!(
+			Referenda::do_try_state(System::block_number()),
+			Err("deciding count below the number of referenda being decided")
+		);
+		DecidingCount::<Test>::insert(0, 1);
+
+		// cancelling the referendum being decided leaves the deciding count as is until the
+		// `one_fewer_deciding` call is serviced.
+		assert_ok!(Referenda::cancel(RuntimeOrigin::signed(4), 0));
+		assert_ok!(Referenda::do_try_state(System::block_number()));
+		run_to(6);
+		assert_ok!(Referenda::do_try_state(System::block_number()));
+	});
+}
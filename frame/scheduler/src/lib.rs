@@ -175,6 +175,15 @@ pub mod pallet {
 	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	#[pallet::extra_constants]
+	impl<T: Config> Pallet<T> {
+		/// The current storage version of the pallet.
+		#[pallet::constant_name(StorageVersion)]
+		fn storage_version() -> StorageVersion {
+			STORAGE_VERSION
+		}
+	}
+
 	/// `system::Config` should always be included in our implied traits.
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -291,6 +300,11 @@ pub mod pallet {
 			Self::service_agendas(&mut weight_counter, now, u32::max_value());
 			weight_counter.consumed
 		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(n: BlockNumberFor<T>) -> Result<(), &'static str> {
+			Self::do_try_state(n)
+		}
 	}
 
 	#[pallet::call]
@@ -1119,6 +1133,49 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+#[cfg(any(test, feature = "try-runtime"))]
+impl<T: Config> Pallet<T> {
+	/// Ensure the correctness of the state of this pallet.
+	///
+	/// * The lookup of a name points to a task with this name.
+	/// * Every named task which is not due yet can be found through the lookup.
+	/// * The named tasks up to `now` have been serviced, unless they are after `IncompleteSince`.
+	///
+	/// Tasks whose call was unavailable or permanently overweight stay in their agenda without a
+	/// lookup, and so do postponed overweight tasks. Due tasks without a lookup are not checked.
+	pub(crate) fn do_try_state(now: T::BlockNumber) -> Result<(), &'static str> {
+		let incomplete_since = IncompleteSince::<T>::get();
+		Lookup::<T>::iter().try_for_each(|(name, (when, index))| -> Result<(), &'static str> {
+			let agenda = Agenda::<T>::get(when);
+			let task = agenda
+				.get(index as usize)
+				.and_then(Option::as_ref)
+				.ok_or("lookup of a non-existent task")?;
+			ensure!(task.maybe_id == Some(name), "lookup of a task with another name");
+			ensure!(
+				when > now || incomplete_since.map_or(false, |since| since <= when),
+				"task not serviced in time"
+			);
+			Ok(())
+		})?;
+
+		Agenda::<T>::iter().try_for_each(|(when, agenda)| -> Result<(), &'static str> {
+			if when <= now {
+				return Ok(())
+			}
+			for (index, task) in agenda.iter().enumerate() {
+				if let Some(name) = task.as_ref().and_then(|task| task.maybe_id) {
+					ensure!(
+						Lookup::<T>::get(name) == Some((when, index as u32)),
+						"named task missing from the lookup"
+					);
+				}
+			}
+			Ok(())
+		})
+	}
+}
+
 impl<T: Config<Hash = PreimageHash>>
 	schedule::v2::Anon<T::BlockNumber, <T as Config>::RuntimeCall, T::PalletsOrigin> for Pallet<T>
 {
@@ -18,9 +18,10 @@
 //! Migrations for the scheduler pallet.
 
 use super::*;
-use frame_support::traits::OnRuntimeUpgrade;
+use frame_support::{migrations::VersionedMigration, traits::OnRuntimeUpgrade};
 
 /// The log target.
+#[cfg(feature = "try-runtime")]
 const TARGET: &'static str = "runtime::scheduler::migration";
 
 pub mod v1 {
@@ -92,14 +93,14 @@ pub mod v3 {
 		TaskAddress<<T as frame_system::Config>::BlockNumber>,
 	>;
 
-	/// Migrate the scheduler pallet from V3 to V4.
-	pub struct MigrateToV4<T>(sp_std::marker::PhantomData<T>);
+	/// Migrate the scheduler pallet from V3 to V4, without checking the storage version.
+	///
+	/// Use [`MigrateToV4`] instead, which also checks and sets the storage version.
+	pub struct UncheckedMigrateToV4<T>(sp_std::marker::PhantomData<T>);
 
-	impl<T: Config<Hash = PreimageHash>> OnRuntimeUpgrade for MigrateToV4<T> {
+	impl<T: Config<Hash = PreimageHash>> OnRuntimeUpgrade for UncheckedMigrateToV4<T> {
 		#[cfg(feature = "try-runtime")]
 		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
-			assert_eq!(StorageVersion::get::<Pallet<T>>(), 3, "Can only upgrade from version 3");
-
 			let agendas = Agenda::<T>::iter_keys().count() as u32;
 			let decodable_agendas = Agenda::<T>::iter_values().count() as u32;
 			if agendas != decodable_agendas {
@@ -154,24 +155,11 @@ pub mod v3 {
 		}
 
 		fn on_runtime_upgrade() -> Weight {
-			let version = StorageVersion::get::<Pallet<T>>();
-			if version != 3 {
-				log::warn!(
-					target: TARGET,
-					"skipping v3 to v4 migration: executed on wrong storage version.\
-				Expected version 3, found {:?}",
-					version,
-				);
-				return T::DbWeight::get().reads(1)
-			}
-
 			crate::Pallet::<T>::migrate_v3_to_v4()
 		}
 
 		#[cfg(feature = "try-runtime")]
 		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
-			assert_eq!(StorageVersion::get::<Pallet<T>>(), 4, "Must upgrade");
-
 			// Check that everything decoded fine.
 			for k in crate::Agenda::<T>::iter_keys() {
 				assert!(crate::Agenda::<T>::try_get(k).is_ok(), "Cannot decode V4 Agenda");
@@ -196,6 +184,15 @@ pub mod v3 {
 			Ok(())
 		}
 	}
+
+	/// Migrate the scheduler pallet from V3 to V4 if the on-chain storage version is 3.
+	pub type MigrateToV4<T> = VersionedMigration<
+		3,
+		4,
+		UncheckedMigrateToV4<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
 }
 
 #[cfg(test)]
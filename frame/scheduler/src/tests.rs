@@ -1767,3 +1767,107 @@ fn scheduler_v3_named_next_schedule_time_works() {
 		);
 	});
 }
+
+#[test]
+fn try_state_works() {
+	new_test_ext().execute_with(|| {
+		let call =
+			RuntimeCall::Logger(LoggerCall::log { i: 42, weight: Weight::from_ref_time(10) });
+		assert_ok!(Scheduler::do_schedule_named(
+			[1u8; 32],
+			DispatchTime::At(4),
+			None,
+			127,
+			root(),
+			Preimage::bound(call.clone()).unwrap(),
+		));
+		assert_ok!(Scheduler::do_schedule(
+			DispatchTime::At(5),
+			None,
+			127,
+			root(),
+			Preimage::bound(call).unwrap()
+		));
+		run_to_block(3);
+		assert_ok!(Scheduler::do_try_state(3));
+
+		Lookup::<Test>::insert([2u8; 32], (4, 0));
+		assert_eq!(Scheduler::do_try_state(3), Err("lookup of a task with another name"));
+		Lookup::<Test>::remove([2u8; 32]);
+
+		Lookup::<Test>::remove([1u8; 32]);
+		assert_eq!(Scheduler::do_try_state(3), Err("named task missing from the lookup"));
+		Lookup::<Test>::insert([1u8; 32], (4, 0));
+
+		// the agenda of block 4 is only serviced in `on_initialize`.
+		System::set_block_number(4);
+		assert_eq!(Scheduler::do_try_state(4), Err("task not serviced in time"));
+		Scheduler::on_initialize(4);
+		assert_ok!(Scheduler::do_try_state(4));
+
+		run_to_block(5);
+		assert_eq!(logger::log(), vec![(root(), 42u32), (root(), 42u32)]);
+		assert_ok!(Scheduler::do_try_state(5));
+	});
+}
+
+#[test]
+fn try_state_works_with_dropped_and_postponed_tasks() {
+	let max_weight: Weight = <Test as Config>::MaximumWeight::get();
+	new_test_ext().execute_with(|| {
+		// The preimage of this call is never noted.
+		let call = RuntimeCall::Logger(LoggerCall::log { i: 1, weight: Weight::from_ref_time(10) });
+		let hash = <Test as frame_system::Config>::Hashing::hash_of(&call);
+		let len = call.using_encoded(|x| x.len()) as u32;
+		assert_ok!(Scheduler::do_schedule_named(
+			[1u8; 32],
+			DispatchTime::At(4),
+			None,
+			127,
+			root(),
+			Preimage::pick(hash, len),
+		));
+		let call = RuntimeCall::Logger(LoggerCall::log { i: 2, weight: max_weight });
+		assert_ok!(Scheduler::do_schedule_named(
+			[2u8; 32],
+			DispatchTime::At(4),
+			None,
+			127,
+			root(),
+			Preimage::bound(call).unwrap(),
+		));
+		// Only one of these fits into a block.
+		let call = RuntimeCall::Logger(LoggerCall::log { i: 3, weight: (max_weight / 3) * 2 });
+		for name in [[3u8; 32], [4u8; 32]] {
+			assert_ok!(Scheduler::do_schedule_named(
+				name,
+				DispatchTime::At(6),
+				None,
+				127,
+				root(),
+				Preimage::bound(call.clone()).unwrap(),
+			));
+		}
+
+		// The unavailable and the permanently overweight tasks stay in the agenda, but lose
+		// their lookup.
+		run_to_block(4);
+		assert!(Agenda::<Test>::get(4).iter().all(Option::is_some));
+		assert_eq!(Lookup::<Test>::get([1u8; 32]), None);
+		assert_eq!(Lookup::<Test>::get([2u8; 32]), None);
+		assert_eq!(IncompleteSince::<Test>::get(), None);
+		assert_ok!(Scheduler::do_try_state(4));
+
+		// The postponed task is due, so it isn't checked although it lost its lookup.
+		run_to_block(6);
+		assert_eq!(logger::log(), vec![(root(), 3u32)]);
+		assert_eq!(IncompleteSince::<Test>::get(), Some(6));
+		assert_eq!(Lookup::<Test>::get([4u8; 32]), None);
+		assert_ok!(Scheduler::do_try_state(6));
+
+		run_to_block(7);
+		assert_eq!(logger::log(), vec![(root(), 3u32), (root(), 3u32)]);
+		assert_eq!(Lookup::<Test>::get([4u8; 32]), None);
+		assert_ok!(Scheduler::do_try_state(7));
+	});
+}
@@ -1086,6 +1086,7 @@ mod mock {
 		type BlockLength = ();
 		type RuntimeOrigin = RuntimeOrigin;
 		type RuntimeCall = RuntimeCall;
+		type RuntimeTask = RuntimeTask;
 		type Index = u64;
 		type BlockNumber = u32;
 		type Hash = H256;
@@ -37,6 +37,14 @@ pub fn expand_outer_event(
 			let index = pallet_decl.index;
 			let instance = pallet_decl.instance.as_ref();
 			let generics = &pallet_entry.generics;
+			let attr = pallet_decl.cfg_pattern.iter().fold(TokenStream::new(), |acc, pattern| {
+				let attr = TokenStream::from_str(&format!("#[cfg({})]", pattern.original()))
+					.expect("was successfully parsed before; qed");
+				quote! {
+					#acc
+					#attr
+				}
+			});
 
 			if instance.is_some() && generics.params.is_empty() {
 				let msg = format!(
@@ -64,6 +72,7 @@ pub fn expand_outer_event(
 			));
 			event_conversions.extend(expand_event_conversion(scrate, pallet_decl, &pallet_event));
 			query_event_part_macros.push(quote! {
+				#attr
 				#path::__substrate_event_check::is_event_part_defined!(#pallet_name);
 			});
 		}
@@ -46,10 +46,11 @@ pub fn expand_outer_inherent(
 			});
 
 			pallet_names.push(name);
-			pallet_attrs.push(attr);
 			query_inherent_part_macros.push(quote! {
+				#attr
 				#path::__substrate_inherent_check::is_inherent_part_defined!(#name);
 			});
+			pallet_attrs.push(attr);
 		}
 	}
 
@@ -21,7 +21,9 @@ mod event;
 mod inherent;
 mod metadata;
 mod origin;
+mod task;
 mod unsigned;
+mod view_function;
 
 pub use call::expand_outer_dispatch;
 pub use config::expand_outer_config;
@@ -29,4 +31,6 @@ pub use event::expand_outer_event;
 pub use inherent::expand_outer_inherent;
 pub use metadata::expand_runtime_metadata;
 pub use origin::expand_outer_origin;
+pub use task::expand_outer_task;
 pub use unsigned::expand_outer_validate_unsigned;
+pub use view_function::expand_outer_view_function;
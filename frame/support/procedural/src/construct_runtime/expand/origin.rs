@@ -38,6 +38,14 @@ pub fn expand_outer_origin(
 			let generics = &pallet_entry.generics;
 			let name = &pallet_decl.name;
 			let path = &pallet_decl.path;
+			let attr = pallet_decl.cfg_pattern.iter().fold(TokenStream::new(), |acc, pattern| {
+				let attr = TokenStream::from_str(&format!("#[cfg({})]", pattern.original()))
+					.expect("was successfully parsed before; qed");
+				quote! {
+					#acc
+					#attr
+				}
+			});
 
 			if instance.is_some() && generics.params.is_empty() {
 				let msg = format!(
@@ -63,6 +71,7 @@ pub fn expand_outer_origin(
 				generics,
 			));
 			query_origin_part_macros.push(quote! {
+				#attr
 				#path::__substrate_origin_check::is_origin_part_defined!(#name);
 			});
 		}
@@ -0,0 +1,125 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021-2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License
+
+use crate::construct_runtime::Pallet;
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::str::FromStr;
+
+/// Generate the aggregated `RuntimeTask` enum of all the pallets with a `Task` part.
+pub fn expand_outer_task(pallet_decls: &[Pallet], scrate: &TokenStream) -> TokenStream {
+	let mut variant_defs = TokenStream::new();
+	let mut from_impls = Vec::new();
+	let mut pallet_names = Vec::new();
+	let mut pallet_attrs = Vec::new();
+	let mut query_task_part_macros = Vec::new();
+
+	for pallet_decl in pallet_decls.iter().filter(|decl| decl.exists_part("Task")) {
+		let name = &pallet_decl.name;
+		let path = &pallet_decl.path;
+		let index = pallet_decl.index;
+		let attr = pallet_decl.cfg_pattern.iter().fold(TokenStream::new(), |acc, pattern| {
+			let attr = TokenStream::from_str(&format!("#[cfg({})]", pattern.original()))
+				.expect("was successfully parsed before; qed");
+			quote! {
+				#acc
+				#attr
+			}
+		});
+
+		variant_defs.extend(quote! {
+			#attr
+			#[codec(index = #index)]
+			#name( #scrate::traits::TaskFor<#name> ),
+		});
+		from_impls.push(quote! {
+			#attr
+			impl From<#scrate::traits::TaskFor<#name>> for RuntimeTask {
+				fn from(task: #scrate::traits::TaskFor<#name>) -> Self {
+					RuntimeTask::#name(task)
+				}
+			}
+		});
+		pallet_names.push(name);
+		query_task_part_macros.push(quote! {
+			#attr
+			#path::__substrate_task_check::is_task_part_defined!(#name);
+		});
+		pallet_attrs.push(attr);
+	}
+
+	quote! {
+		#( #query_task_part_macros )*
+
+		/// The aggregated task type of the runtime.
+		#[derive(
+			Clone, PartialEq, Eq,
+			#scrate::codec::Encode,
+			#scrate::codec::Decode,
+			#scrate::scale_info::TypeInfo,
+			#scrate::RuntimeDebug,
+		)]
+		pub enum RuntimeTask {
+			#variant_defs
+		}
+
+		#( #from_impls )*
+
+		impl #scrate::traits::Task for RuntimeTask {
+			type Enumeration = #scrate::sp_std::vec::IntoIter<RuntimeTask>;
+
+			fn iter() -> Self::Enumeration {
+				let mut tasks = #scrate::sp_std::vec::Vec::new();
+				#(
+					#pallet_attrs
+					tasks.extend(
+						<#scrate::traits::TaskFor<#pallet_names> as #scrate::traits::Task>::iter()
+							.map(RuntimeTask::#pallet_names),
+					);
+				)*
+				tasks.into_iter()
+			}
+
+			fn is_valid(&self) -> bool {
+				match *self {
+					#(
+						#pallet_attrs
+						RuntimeTask::#pallet_names(ref task) => #scrate::traits::Task::is_valid(task),
+					)*
+				}
+			}
+
+			fn run(&self) -> #scrate::sp_runtime::DispatchResult {
+				match *self {
+					#(
+						#pallet_attrs
+						RuntimeTask::#pallet_names(ref task) => #scrate::traits::Task::run(task),
+					)*
+				}
+			}
+
+			fn weight(&self) -> #scrate::weights::Weight {
+				match *self {
+					#(
+						#pallet_attrs
+						RuntimeTask::#pallet_names(ref task) => #scrate::traits::Task::weight(task),
+					)*
+				}
+			}
+		}
+	}
+}
@@ -44,10 +44,11 @@ pub fn expand_outer_validate_unsigned(
 			});
 
 			pallet_names.push(name);
-			pallet_attrs.push(attr);
 			query_validate_unsigned_part_macros.push(quote! {
+				#attr
 				#path::__substrate_validate_unsigned_check::is_validate_unsigned_part_defined!(#name);
 			});
+			pallet_attrs.push(attr);
 		}
 	}
 
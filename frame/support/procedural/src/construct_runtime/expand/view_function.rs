@@ -0,0 +1,97 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License
+
+use crate::construct_runtime::Pallet;
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::str::FromStr;
+use syn::Ident;
+
+/// Generate the dispatch and the metadata of the view functions of all the pallets with a
+/// `ViewFunctions` part.
+pub fn expand_outer_view_function(
+	runtime: &Ident,
+	pallet_decls: &[Pallet],
+	scrate: &TokenStream,
+) -> TokenStream {
+	let mut pallet_names = Vec::new();
+	let mut pallet_attrs = Vec::new();
+	let mut query_view_functions_part_macros = Vec::new();
+
+	for pallet_decl in pallet_decls.iter().filter(|decl| decl.exists_part("ViewFunctions")) {
+		let name = &pallet_decl.name;
+		let path = &pallet_decl.path;
+		let attr = pallet_decl.cfg_pattern.iter().fold(TokenStream::new(), |acc, pattern| {
+			let attr = TokenStream::from_str(&format!("#[cfg({})]", pattern.original()))
+				.expect("was successfully parsed before; qed");
+			quote! {
+				#acc
+				#attr
+			}
+		});
+
+		pallet_names.push(name);
+		query_view_functions_part_macros.push(quote! {
+			#attr
+			#path::__substrate_view_functions_check::is_view_functions_part_defined!(#name);
+		});
+		pallet_attrs.push(attr);
+	}
+
+	quote! {
+		#( #query_view_functions_part_macros )*
+
+		impl #runtime {
+			/// Execute the view function `id` of one of the pallets with the encoded `input`.
+			#[allow(unused_variables, unused_mut)]
+			pub fn execute_view_function(
+				id: #scrate::view_functions::ViewFunctionId,
+				input: #scrate::sp_std::vec::Vec<u8>,
+			) -> Result<
+				#scrate::sp_std::vec::Vec<u8>,
+				#scrate::view_functions::ViewFunctionDispatchError,
+			> {
+				let mut output = #scrate::sp_std::vec::Vec::new();
+				#(
+					#pallet_attrs
+					if id.prefix == <
+						#pallet_names as #scrate::view_functions::ViewFunctionIdPrefix
+					>::prefix() {
+						<#pallet_names as #scrate::view_functions::DispatchViewFunction>
+							::dispatch_view_function(&id, &mut &input[..], &mut output)?;
+						return Ok(output)
+					}
+				)*
+				Err(#scrate::view_functions::ViewFunctionDispatchError::NotFound(id))
+			}
+
+			/// The metadata of the view functions of all the pallets.
+			pub fn view_functions_metadata() -> #scrate::view_functions::RuntimeViewFunctionsMetadata {
+				#[allow(unused_mut)]
+				let mut pallets = #scrate::sp_std::vec::Vec::new();
+				#(
+					#pallet_attrs
+					pallets.push(#scrate::view_functions::PalletViewFunctionsMetadata {
+						name: stringify!(#pallet_names),
+						view_functions: #pallet_names::pallet_view_functions_metadata(),
+					});
+				)*
+				#scrate::view_functions::RuntimeViewFunctionsMetadata::new(pallets)
+			}
+		}
+	}
+}
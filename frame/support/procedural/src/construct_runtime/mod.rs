@@ -265,6 +265,8 @@ fn construct_runtime_final_expansion(
 	let inherent =
 		expand::expand_outer_inherent(&name, &block, &unchecked_extrinsic, &pallets, &scrate);
 	let validate_unsigned = expand::expand_outer_validate_unsigned(&name, &pallets, &scrate);
+	let task = expand::expand_outer_task(&pallets, &scrate);
+	let view_function = expand::expand_outer_view_function(&name, &pallets, &scrate);
 	let integrity_test = decl_integrity_test(&scrate);
 	let static_assertions = decl_static_assertions(&name, &pallets, &scrate);
 
@@ -307,6 +309,10 @@ fn construct_runtime_final_expansion(
 
 		#validate_unsigned
 
+		#task
+
+		#view_function
+
 		#integrity_test
 
 		#static_assertions
@@ -592,6 +598,14 @@ fn decl_static_assertions(
 ) -> TokenStream2 {
 	let error_encoded_size_check = pallet_decls.iter().map(|decl| {
 		let path = &decl.path;
+		let attr = decl.cfg_pattern.iter().fold(TokenStream2::new(), |acc, pattern| {
+			let attr = TokenStream2::from_str(&format!("#[cfg({})]", pattern.original()))
+				.expect("was successfully parsed before; qed");
+			quote! {
+				#acc
+				#attr
+			}
+		});
 		let assert_message = format!(
 			"The maximum encoded size of the error type in the `{}` pallet exceeds \
 			`MAX_MODULE_ERROR_ENCODED_SIZE`",
@@ -599,6 +613,7 @@ fn decl_static_assertions(
 		);
 
 		quote! {
+			#attr
 			#scrate::tt_call! {
 				macro = [{ #path::tt_error_token }]
 				frame_support = [{ #scrate }]
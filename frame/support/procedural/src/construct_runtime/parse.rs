@@ -38,6 +38,8 @@ mod keyword {
 	syn::custom_keyword!(Origin);
 	syn::custom_keyword!(Inherent);
 	syn::custom_keyword!(ValidateUnsigned);
+	syn::custom_keyword!(Task);
+	syn::custom_keyword!(ViewFunctions);
 	syn::custom_keyword!(exclude_parts);
 	syn::custom_keyword!(use_parts);
 }
@@ -370,6 +372,8 @@ pub enum PalletPartKeyword {
 	Origin(keyword::Origin),
 	Inherent(keyword::Inherent),
 	ValidateUnsigned(keyword::ValidateUnsigned),
+	Task(keyword::Task),
+	ViewFunctions(keyword::ViewFunctions),
 }
 
 impl Parse for PalletPartKeyword {
@@ -392,6 +396,10 @@ impl Parse for PalletPartKeyword {
 			Ok(Self::Inherent(input.parse()?))
 		} else if lookahead.peek(keyword::ValidateUnsigned) {
 			Ok(Self::ValidateUnsigned(input.parse()?))
+		} else if lookahead.peek(keyword::Task) {
+			Ok(Self::Task(input.parse()?))
+		} else if lookahead.peek(keyword::ViewFunctions) {
+			Ok(Self::ViewFunctions(input.parse()?))
 		} else {
 			Err(lookahead.error())
 		}
@@ -410,6 +418,8 @@ impl PalletPartKeyword {
 			Self::Origin(_) => "Origin",
 			Self::Inherent(_) => "Inherent",
 			Self::ValidateUnsigned(_) => "ValidateUnsigned",
+			Self::Task(_) => "Task",
+			Self::ViewFunctions(_) => "ViewFunctions",
 		}
 	}
 
@@ -435,6 +445,8 @@ impl Spanned for PalletPartKeyword {
 			Self::Origin(inner) => inner.span(),
 			Self::Inherent(inner) => inner.span(),
 			Self::ValidateUnsigned(inner) => inner.span(),
+			Self::Task(inner) => inner.span(),
+			Self::ViewFunctions(inner) => inner.span(),
 		}
 	}
 }
@@ -0,0 +1,261 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of the `derive_impl` and `register_default_impl` macros.
+//!
+//! `register_default_impl` declares a tt-call macro named after the default impl which returns
+//! the associated types of the impl. `derive_impl` calls this macro with `tt_call!` and forwards
+//! the local impl to `__derive_impl_expand`, which adds the associated types missing from the
+//! local impl.
+
+use frame_support_procedural_tools::generate_crate_access_2018;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use std::collections::HashSet;
+use syn::{spanned::Spanned, Ident, Result};
+
+/// The name of the attribute marking the types aggregated by `construct_runtime!`.
+const INJECT_RUNTIME_TYPE: &str = "inject_runtime_type";
+
+mod keyword {
+	syn::custom_keyword!(no_aggregated_types);
+	syn::custom_keyword!(default_impl);
+	syn::custom_keyword!(default_trait);
+	syn::custom_keyword!(local_impl);
+	syn::custom_keyword!(default_types);
+	syn::custom_keyword!(runtime_types);
+}
+
+/// The arguments of `#[derive_impl(default_impl as DefaultTrait, no_aggregated_types)]`.
+struct DeriveImplAttrArgs {
+	default_impl: syn::Path,
+	default_trait: Option<syn::Path>,
+	no_aggregated_types: bool,
+}
+
+impl syn::parse::Parse for DeriveImplAttrArgs {
+	fn parse(input: syn::parse::ParseStream) -> Result<Self> {
+		let default_impl = input.parse()?;
+		let default_trait = if input.peek(syn::Token![as]) {
+			input.parse::<syn::Token![as]>()?;
+			Some(input.parse()?)
+		} else {
+			None
+		};
+		let no_aggregated_types = if input.peek(syn::Token![,]) {
+			input.parse::<syn::Token![,]>()?;
+			input.parse::<keyword::no_aggregated_types>()?;
+			true
+		} else {
+			false
+		};
+
+		Ok(Self { default_impl, default_trait, no_aggregated_types })
+	}
+}
+
+/// Parse `$key = [{ $value }]` and return `$value`.
+fn parse_arg<K: syn::parse::Parse>(input: syn::parse::ParseStream) -> Result<TokenStream> {
+	let mut value;
+	input.parse::<K>()?;
+	input.parse::<syn::Token![=]>()?;
+	let _: syn::token::Bracket = syn::bracketed!(value in input);
+	let _: syn::token::Brace = syn::braced!(value in value);
+	value.parse()
+}
+
+/// Parse a list of identifiers separated by whitespace.
+fn parse_idents(input: TokenStream) -> Result<Vec<Ident>> {
+	syn::parse::Parser::parse2(
+		|input: syn::parse::ParseStream| {
+			let mut idents = Vec::new();
+			while !input.is_empty() {
+				idents.push(input.parse()?);
+			}
+			Ok(idents)
+		},
+		input,
+	)
+}
+
+/// The input of `__derive_impl_expand`, i.e. the arguments given by `derive_impl` followed by the
+/// ones returned by the macro declared by `register_default_impl`.
+struct DeriveImplDef {
+	default_impl: syn::Path,
+	default_trait: syn::Path,
+	no_aggregated_types: bool,
+	local_impl: syn::ItemImpl,
+	default_types: Vec<Ident>,
+	runtime_types: Vec<Ident>,
+}
+
+impl syn::parse::Parse for DeriveImplDef {
+	fn parse(input: syn::parse::ParseStream) -> Result<Self> {
+		let default_impl = syn::parse2(parse_arg::<keyword::default_impl>(input)?)?;
+		let default_trait = syn::parse2(parse_arg::<keyword::default_trait>(input)?)?;
+		let no_aggregated_types =
+			syn::parse2::<syn::LitBool>(parse_arg::<keyword::no_aggregated_types>(input)?)?.value;
+		let local_impl = syn::parse2(parse_arg::<keyword::local_impl>(input)?)?;
+		let default_types = parse_idents(parse_arg::<keyword::default_types>(input)?)?;
+		let runtime_types = parse_idents(parse_arg::<keyword::runtime_types>(input)?)?;
+
+		Ok(Self {
+			default_impl,
+			default_trait,
+			no_aggregated_types,
+			local_impl,
+			default_types,
+			runtime_types,
+		})
+	}
+}
+
+/// The default trait of the trait implemented by `local_impl`, i.e. `path::DefaultConfig` for
+/// `path::Config<I>`.
+fn default_trait_of(local_impl: &syn::ItemImpl) -> Result<syn::Path> {
+	let (_, trait_path, _) = local_impl.trait_.as_ref().ok_or_else(|| {
+		let msg = "Invalid derive_impl, expected a trait impl";
+		syn::Error::new(local_impl.impl_token.span, msg)
+	})?;
+
+	let mut default_trait = trait_path.clone();
+	let last = default_trait.segments.last_mut().expect("A path has at least one segment; qed");
+	last.ident = Ident::new(&format!("Default{}", last.ident), last.ident.span());
+	last.arguments = syn::PathArguments::None;
+
+	Ok(default_trait)
+}
+
+pub fn derive_impl(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
+	let DeriveImplAttrArgs { default_impl, default_trait, no_aggregated_types } =
+		syn::parse2(attr)?;
+	let local_impl = syn::parse2::<syn::ItemImpl>(item)?;
+	let default_trait = match default_trait {
+		Some(default_trait) => default_trait,
+		None => default_trait_of(&local_impl)?,
+	};
+	let frame_support = generate_crate_access_2018("frame-support")?;
+
+	Ok(quote! {
+		#frame_support::tt_call! {
+			macro = [{ #default_impl }]
+			frame_support = [{ #frame_support }]
+			~~> #frame_support::__derive_impl_expand! {
+				default_impl = [{ #default_impl }]
+				default_trait = [{ #default_trait }]
+				no_aggregated_types = [{ #no_aggregated_types }]
+				local_impl = [{ #local_impl }]
+			}
+		}
+	})
+}
+
+pub fn derive_impl_expand(input: TokenStream) -> Result<TokenStream> {
+	let DeriveImplDef {
+		default_impl,
+		default_trait,
+		no_aggregated_types,
+		mut local_impl,
+		default_types,
+		runtime_types,
+	} = syn::parse2(input)?;
+
+	let local_items = local_impl
+		.items
+		.iter()
+		.filter_map(|item| match item {
+			syn::ImplItem::Type(type_) => Some(type_.ident.clone()),
+			syn::ImplItem::Const(const_) => Some(const_.ident.clone()),
+			syn::ImplItem::Method(method) => Some(method.sig.ident.clone()),
+			_ => None,
+		})
+		.collect::<HashSet<_>>();
+
+	let span = local_impl.impl_token.span;
+	for ident in default_types.iter().filter(|ident| !local_items.contains(ident)) {
+		local_impl.items.push(syn::parse_quote_spanned!(span =>
+			type #ident = <#default_impl as #default_trait>::#ident;
+		));
+	}
+	for ident in runtime_types.iter().filter(|ident| !local_items.contains(ident)) {
+		if no_aggregated_types {
+			local_impl.items.push(syn::parse_quote_spanned!(span =>
+				type #ident = <#default_impl as #default_trait>::#ident;
+			));
+		} else {
+			// The type aggregated by `construct_runtime!` is expected in the scope of the impl.
+			let runtime_type = Ident::new(&ident.to_string(), span);
+			local_impl.items.push(syn::parse_quote_spanned!(span =>
+				type #ident = #runtime_type;
+			));
+		}
+	}
+
+	Ok(quote!(#local_impl))
+}
+
+pub fn register_default_impl(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
+	let name = syn::parse2::<Ident>(attr)?;
+	let mut item_impl = syn::parse2::<syn::ItemImpl>(item)?;
+
+	if item_impl.trait_.is_none() {
+		let msg = "Invalid register_default_impl, expected a trait impl";
+		return Err(syn::Error::new(item_impl.impl_token.span, msg))
+	}
+
+	let mut default_types = Vec::new();
+	let mut runtime_types = Vec::new();
+	for item in &mut item_impl.items {
+		let type_ = if let syn::ImplItem::Type(type_) = item {
+			type_
+		} else {
+			let msg = "Invalid register_default_impl, only associated types are supported";
+			return Err(syn::Error::new(item.span(), msg))
+		};
+
+		let len = type_.attrs.len();
+		type_.attrs.retain(|attr| !attr.path.is_ident(INJECT_RUNTIME_TYPE));
+		if type_.attrs.len() < len {
+			runtime_types.push(type_.ident.clone());
+		} else {
+			default_types.push(type_.ident.clone());
+		}
+	}
+
+	let frame_support = generate_crate_access_2018("frame-support")?;
+
+	Ok(quote! {
+		#item_impl
+
+		#frame_support::__create_tt_macro! {
+			#name,
+			default_types = [{ #( #default_types )* }]
+			runtime_types = [{ #( #runtime_types )* }]
+		}
+	})
+}
+
+pub fn inject_runtime_type(item: TokenStream) -> TokenStream {
+	let span = syn::parse2::<syn::ImplItemType>(item.clone())
+		.map_or_else(|_| Span::call_site(), |type_| type_.ident.span());
+	quote::quote_spanned!(span =>
+		compile_error!(
+			"`#[inject_runtime_type]` can only be used on the associated types of an impl \
+			annotated with `#[register_default_impl]`"
+		);
+	)
+}
@@ -24,6 +24,7 @@ mod construct_runtime;
 mod crate_version;
 mod debug_no_bound;
 mod default_no_bound;
+mod derive_impl;
 mod dummy_part_checker;
 mod key_prefix;
 mod match_and_insert;
@@ -388,6 +389,24 @@ pub fn decl_storage(input: TokenStream) -> TokenStream {
 ///   pallet4 .., // Here pallet4 is given index 1
 ///   ```
 ///
+/// * `#[cfg(..)]` optional: the pallet is only included in the runtime if the predicate holds, only
+///   `feature = ".."` and `test` are supported in `AllPalletsWithSystem` and
+///   `AllPalletsWithoutSystem`. The attribute is propagated to the type alias of the pallet, to the
+///   variants of `RuntimeCall`, `RuntimeEvent`, `OriginCaller` and `RuntimeTask`, to the fields of
+///   `GenesisConfig`, to `PalletInfo` and to the metadata. The `System` pallet can't be feature
+///   gated.
+///
+///   For example, to only include a pallet in the runtime when benchmarking:
+///   ```nocompile
+///   #[cfg(feature = "runtime-benchmarks")]
+///   Benchmarks: pallet_benchmarks,
+///   ```
+///
+///   The implementation of the `Config` of the pallet for the runtime must be gated as well. If
+///   the pallet module itself is gated, its parts must be declared explicitly, e.g.
+///   `Benchmarks: pallet_benchmarks::{Pallet, Call}`, as the default parts can't be queried from
+///   a module which doesn't exist.
+///
 /// # Note
 ///
 /// The population of the genesis storage depends on the order of pallets. So, if one of your
@@ -655,6 +674,39 @@ pub fn storage_alias(_: TokenStream, input: TokenStream) -> TokenStream {
 		.into()
 }
 
+/// Implement a trait by deriving the associated types missing from the impl from a default impl
+/// registered with `#[register_default_impl]`, see `frame_support::derive_impl`.
+#[proc_macro_attribute]
+pub fn derive_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+	derive_impl::derive_impl(attr.into(), item.into())
+		.unwrap_or_else(|r| r.into_compile_error())
+		.into()
+}
+
+/// Internal macro used by `derive_impl` to expand the impl once the default impl is known.
+#[proc_macro]
+pub fn __derive_impl_expand(input: TokenStream) -> TokenStream {
+	derive_impl::derive_impl_expand(input.into())
+		.unwrap_or_else(|r| r.into_compile_error())
+		.into()
+}
+
+/// Register a default impl usable with `#[derive_impl]`, see
+/// `frame_support::register_default_impl`.
+#[proc_macro_attribute]
+pub fn register_default_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+	derive_impl::register_default_impl(attr.into(), item.into())
+		.unwrap_or_else(|r| r.into_compile_error())
+		.into()
+}
+
+/// Mark an associated type of a default impl as aggregated by `construct_runtime!`, see
+/// `frame_support::register_default_impl`.
+#[proc_macro_attribute]
+pub fn inject_runtime_type(_: TokenStream, item: TokenStream) -> TokenStream {
+	derive_impl::inject_runtime_type(item.into()).into()
+}
+
 /// Used internally to decorate pallet attribute macro stubs when they are erroneously used
 /// outside of a pallet module
 fn pallet_macro_stub() -> TokenStream {
@@ -687,6 +739,14 @@ fn pallet_macro_stub() -> TokenStream {
 ///
 /// [`pallet::event`](`macro@event`) must be present if `RuntimeEvent` exists as a config item
 /// in your `#[pallet::config]`.
+///
+/// ## Optional: `with_default`
+///
+/// `#[pallet::config(with_default)]` also generates a `DefaultConfig` trait with the associated
+/// types of `Config`, except the ones annotated with [`pallet::no_default`](`macro@no_default`).
+/// It has `frame_system::DefaultConfig` as supertrait if `Config` has `frame_system::Config` as
+/// supertrait. The pallet can then provide default configs, e.g. for tests, which runtimes use with
+/// `#[derive_impl]` to only specify the associated types which differ from the defaults.
 #[proc_macro_attribute]
 pub fn config(_: TokenStream, _: TokenStream) -> TokenStream {
 	pallet_macro_stub()
@@ -707,6 +767,40 @@ pub fn constant(_: TokenStream, _: TokenStream) -> TokenStream {
 	pallet_macro_stub()
 }
 
+/// The `#[pallet::no_default]` attribute excludes an associated type of a
+/// [`pallet::config(with_default)`](`macro@config`) from the generated `DefaultConfig` trait, e.g.
+/// because it can only be provided by the runtime:
+///
+/// ```ignore
+/// #[pallet::config(with_default)]
+/// pub trait Config: frame_system::Config {
+/// 	#[pallet::no_default]
+/// 	type AccountStore: StoredMap<Self::AccountId, AccountData<Self::Balance>>;
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn no_default(_: TokenStream, _: TokenStream) -> TokenStream {
+	pallet_macro_stub()
+}
+
+/// The `#[pallet::no_default_bounds]` attribute keeps an associated type of a
+/// [`pallet::config(with_default)`](`macro@config`) in the generated `DefaultConfig` trait, but
+/// without its bounds, e.g. because they require `Config`:
+///
+/// ```ignore
+/// #[pallet::config(with_default)]
+/// pub trait Config: frame_system::Config {
+/// 	#[pallet::no_default_bounds]
+/// 	type OnSetCode: SetCode<Self>;
+/// }
+/// ```
+///
+/// The bounds of `RuntimeEvent` are always removed.
+#[proc_macro_attribute]
+pub fn no_default_bounds(_: TokenStream, _: TokenStream) -> TokenStream {
+	pallet_macro_stub()
+}
+
 /// To bypass the `frame_system::Config` supertrait check, use the attribute
 /// `pallet::disable_frame_system_supertrait_check`, e.g.:
 ///
@@ -775,6 +869,20 @@ pub fn generate_storage_info(_: TokenStream, _: TokenStream) -> TokenStream {
 /// ```
 ///
 /// If not present, the current storage version is set to the default value.
+///
+/// The storage version is not part of the metadata. Tools like `frame-storage-layout-diff`
+/// read it from a `StorageVersion` constant, which the pallet can expose with
+/// `#[pallet::extra_constants]`:
+///
+/// ```ignore
+/// #[pallet::extra_constants]
+/// impl<T: Config> Pallet<T> {
+/// 	#[pallet::constant_name(StorageVersion)]
+/// 	fn storage_version() -> StorageVersion {
+/// 		STORAGE_VERSION
+/// 	}
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn storage_version(_: TokenStream, _: TokenStream) -> TokenStream {
 	pallet_macro_stub()
@@ -1289,3 +1397,62 @@ pub fn validate_unsigned(_: TokenStream, _: TokenStream) -> TokenStream {
 pub fn origin(_: TokenStream, _: TokenStream) -> TokenStream {
 	pallet_macro_stub()
 }
+
+/// The `#[pallet::tasks]` attribute allows you to declare background tasks of the pallet, which
+/// anyone can execute through `frame_system::Pallet::do_task` while they are valid.
+///
+/// Item must be defined as:
+///
+/// ```ignore
+/// #[pallet::tasks]
+/// pub enum Task<T: Config> {
+/// 	DestroyAccounts { id: T::AssetId },
+/// }
+///
+/// impl<T: Config> frame_support::traits::Task for Task<T> {
+/// 	// ... regular trait implementation
+/// }
+/// ```
+///
+/// I.e. a public enum named `Task`, generic over `T` (and `I` for instantiable pallets), with at
+/// least one variant. The enum must implement `frame_support::traits::Task`.
+///
+/// ## Macro expansion
+///
+/// The macro adds a hidden variant `__Ignore(PhantomData<T>, Never)` to the enum, which the
+/// trait implementation must match (e.g. `Task::__Ignore(_, never) => match *never {}`), and
+/// derives `Clone`, `PartialEq`, `Eq`, `Debug`, `Encode`, `Decode` and `TypeInfo` for the enum.
+/// It also implements `PalletTasks` for `Pallet` and adds the `Task` part to the pallet parts
+/// used by `construct_runtime`, which aggregates the tasks of all pallets into `RuntimeTask`.
+#[proc_macro_attribute]
+pub fn tasks(_: TokenStream, _: TokenStream) -> TokenStream {
+	pallet_macro_stub()
+}
+
+/// The `#[pallet::view_functions]` attribute allows you to declare read-only queries of the
+/// pallet, callable through a runtime API aggregated by `construct_runtime`.
+///
+/// Item must be defined as:
+///
+/// ```ignore
+/// #[pallet::view_functions]
+/// impl<T: Config> Pallet<T> {
+/// 	pub fn balance_of(who: T::AccountId) -> BalanceOf<T> {
+/// 		// ...
+/// 	}
+/// }
+/// ```
+///
+/// I.e. an inherent implementation of `Pallet` with public functions without `self`, generics or
+/// where clause.
+///
+/// ## Macro expansion
+///
+/// For each function, the macro generates a struct named after the function in PascalCase and
+/// suffixed with `ViewFunction` with its arguments as fields, which implements `ViewFunction`. It
+/// implements `ViewFunctionIdPrefix` and `DispatchViewFunction` for `Pallet` and adds the
+/// `ViewFunctions` part to the pallet parts used by `construct_runtime`.
+#[proc_macro_attribute]
+pub fn view_functions(_: TokenStream, _: TokenStream) -> TokenStream {
+	pallet_macro_stub()
+}
@@ -17,9 +17,11 @@
 
 use crate::pallet::Def;
 use frame_support_procedural_tools::get_doc_literals;
+use quote::quote;
 
 ///
 /// * Generate default rust doc
+/// * Generate the `DefaultConfig` trait if `#[pallet::config(with_default)]` is used
 pub fn expand_config(def: &mut Def) -> proc_macro2::TokenStream {
 	let config = &def.config;
 	let config_item = {
@@ -41,5 +43,31 @@ pub fn expand_config(def: &mut Def) -> proc_macro2::TokenStream {
 		));
 	}
 
-	Default::default()
+	if let Some(default_sub_trait) = &def.config.default_sub_trait {
+		let frame_system = &def.frame_system;
+		let supertrait =
+			default_sub_trait.has_system.then(|| quote!(: #frame_system::DefaultConfig));
+		let items = default_sub_trait.items.iter().map(|(item, no_default_bounds)| {
+			let mut item = item.clone();
+			if let (syn::TraitItem::Type(type_), true) = (&mut item, no_default_bounds) {
+				type_.colon_token = None;
+				type_.bounds.clear();
+			}
+			item
+		});
+
+		quote!(
+			/// Based on [`Config`]. Auto-generated by
+			/// [`#[pallet::config(with_default)]`](`frame_support::pallet_macros::config`).
+			///
+			/// Implemented by the default configs of the pallet, used with
+			/// [`#[derive_impl]`](`frame_support::derive_impl`) to implement [`Config`] for a
+			/// runtime by only specifying the associated types which differ from the defaults.
+			pub trait DefaultConfig #supertrait {
+				#( #items )*
+			}
+		)
+	} else {
+		Default::default()
+	}
 }
@@ -0,0 +1,129 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{pallet::Def, COUNTER};
+use frame_support_procedural_tools::get_doc_literals;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{spanned::Spanned, Ident};
+
+/// * add `__Ignore` variant and derives on the `Task` enum,
+/// * impl `PalletTasks` on the pallet,
+/// * generate the `is_task_part_defined` macro used by `construct_runtime`.
+pub fn expand_tasks(def: &mut Def) -> TokenStream {
+	let count = COUNTER.with(|counter| counter.borrow_mut().inc());
+	let macro_ident = Ident::new(&format!("__is_task_part_defined_{}", count), def.item.span());
+
+	let maybe_compile_error = if def.tasks.is_none() {
+		quote! {
+			compile_error!(concat!(
+				"`",
+				stringify!($pallet_name),
+				"` does not have #[pallet::tasks] defined, perhaps you should \
+				remove `Task` from construct_runtime?",
+			));
+		}
+	} else {
+		TokenStream::new()
+	};
+
+	let pallet_tasks_impl = def.tasks.as_ref().map(|tasks| {
+		let frame_support = &def.frame_support;
+		let type_impl_gen = &def.type_impl_generics(tasks.attr_span);
+		let type_use_gen = &def.type_use_generics(tasks.attr_span);
+		let config_where_clause = &def.config.where_clause;
+
+		quote::quote_spanned!(tasks.attr_span =>
+			impl<#type_impl_gen> #frame_support::traits::PalletTasks for Pallet<#type_use_gen>
+				#config_where_clause
+			{
+				type Task = Task<#type_use_gen>;
+			}
+		)
+	});
+
+	if let Some(tasks) = &def.tasks {
+		let frame_support = &def.frame_support;
+		let type_use_gen = &def.type_use_generics(tasks.attr_span);
+		let task_item = {
+			let item =
+				&mut def.item.content.as_mut().expect("Checked by def parser").1[tasks.index];
+			if let syn::Item::Enum(item) = item {
+				item
+			} else {
+				unreachable!("Checked by tasks parser")
+			}
+		};
+
+		// Phantom data is added as the task enum is always generic.
+		task_item.variants.push(syn::parse_quote!(
+			#[doc(hidden)]
+			#[codec(skip)]
+			__Ignore(
+				#frame_support::sp_std::marker::PhantomData<(#type_use_gen)>,
+				#frame_support::Never,
+			)
+		));
+
+		if get_doc_literals(&task_item.attrs).is_empty() {
+			task_item.attrs.push(syn::parse_quote!(
+				#[doc = r"
+				The background tasks of this pallet, which anyone can execute through
+				`frame_system::Pallet::do_task` while they are valid.
+				"]
+			));
+		}
+
+		// `frame_system::Config::RuntimeTask` requires Clone, FullCodec, Eq, PartialEq, Debug
+		// and TypeInfo.
+		task_item.attrs.push(syn::parse_quote!(
+			#[derive(
+				#frame_support::CloneNoBound,
+				#frame_support::EqNoBound,
+				#frame_support::PartialEqNoBound,
+				#frame_support::RuntimeDebugNoBound,
+				#frame_support::codec::Encode,
+				#frame_support::codec::Decode,
+				#frame_support::scale_info::TypeInfo,
+			)]
+		));
+
+		let capture_docs = if cfg!(feature = "no-metadata-docs") { "never" } else { "always" };
+
+		task_item.attrs.push(syn::parse_quote!(
+			#[scale_info(skip_type_params(#type_use_gen), capture_docs = #capture_docs)]
+		));
+	}
+
+	quote! {
+		#pallet_tasks_impl
+
+		#[doc(hidden)]
+		pub mod __substrate_task_check {
+			#[macro_export]
+			#[doc(hidden)]
+			macro_rules! #macro_ident {
+				($pallet_name:ident) => {
+					#maybe_compile_error
+				}
+			}
+
+			#[doc(hidden)]
+			pub use #macro_ident as is_task_part_defined;
+		}
+	}
+}
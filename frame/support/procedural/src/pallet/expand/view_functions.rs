@@ -0,0 +1,230 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	pallet::{parse::view_functions::ViewFunctionDef, Def},
+	COUNTER,
+};
+use inflector::Inflector;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{spanned::Spanned, Ident};
+
+/// * generate a struct implementing `ViewFunction` for each view function,
+/// * impl `ViewFunctionIdPrefix` and `DispatchViewFunction` on the pallet,
+/// * add the `pallet_view_functions_metadata` function to the pallet,
+/// * generate the `is_view_functions_part_defined` macro used by `construct_runtime`.
+pub fn expand_view_functions(def: &mut Def) -> TokenStream {
+	let count = COUNTER.with(|counter| counter.borrow_mut().inc());
+	let macro_ident =
+		Ident::new(&format!("__is_view_functions_part_defined_{}", count), def.item.span());
+
+	let maybe_compile_error = if def.view_functions.is_none() {
+		quote! {
+			compile_error!(concat!(
+				"`",
+				stringify!($pallet_name),
+				"` does not have #[pallet::view_functions] defined, perhaps you should \
+				remove `ViewFunctions` from construct_runtime?",
+			));
+		}
+	} else {
+		TokenStream::new()
+	};
+
+	let view_functions_impls = def.view_functions.as_ref().map(|view_functions| {
+		let frame_support = &def.frame_support;
+		let span = view_functions.attr_span;
+		let type_impl_gen = &def.type_impl_generics(span);
+		let type_use_gen = &def.type_use_generics(span);
+		let where_clause =
+			super::merge_where_clauses(&[&def.config.where_clause, &view_functions.where_clause]);
+
+		let structs = view_functions
+			.view_functions
+			.iter()
+			.map(|view_function| expand_view_function(def, view_function, &where_clause));
+
+		let struct_idents =
+			view_functions.view_functions.iter().map(struct_ident).collect::<Vec<_>>();
+
+		let metadata = view_functions.view_functions.iter().zip(&struct_idents).map(
+			|(view_function, struct_ident)| {
+				let name = view_function.name.to_string();
+				let arg_names = view_function.args.iter().map(|(name, _)| name.to_string());
+				let arg_types = view_function.args.iter().map(|(_, ty)| ty);
+				let return_type = &view_function.return_type;
+				let no_docs = vec![];
+				let docs =
+					if cfg!(feature = "no-metadata-docs") { &no_docs } else { &view_function.docs };
+
+				quote::quote_spanned!(span =>
+					#frame_support::view_functions::ViewFunctionMetadata {
+						name: #name,
+						id: <
+							#struct_ident<#type_use_gen>
+							as #frame_support::view_functions::ViewFunction
+						>::id(),
+						args: #frame_support::sp_std::vec![ #(
+							#frame_support::view_functions::ViewFunctionArgMetadata {
+								name: #arg_names,
+								ty: #frame_support::scale_info::meta_type::<#arg_types>(),
+							}
+						),* ],
+						output: #frame_support::scale_info::meta_type::<#return_type>(),
+						docs: #frame_support::sp_std::vec![ #( #docs ),* ],
+					}
+				)
+			},
+		);
+
+		quote::quote_spanned!(span =>
+			#( #structs )*
+
+			impl<#type_impl_gen> #frame_support::view_functions::ViewFunctionIdPrefix
+				for Pallet<#type_use_gen> #where_clause
+			{
+				fn prefix() -> [u8; 16] {
+					#frame_support::sp_io::hashing::twox_128(
+						<Self as #frame_support::traits::PalletInfoAccess>::name().as_bytes()
+					)
+				}
+			}
+
+			impl<#type_impl_gen> #frame_support::view_functions::DispatchViewFunction
+				for Pallet<#type_use_gen> #where_clause
+			{
+				fn dispatch_view_function<O: #frame_support::codec::Output>(
+					id: &#frame_support::view_functions::ViewFunctionId,
+					input: &mut &[u8],
+					output: &mut O,
+				) -> Result<(), #frame_support::view_functions::ViewFunctionDispatchError> {
+					#(
+						if *id == <
+							#struct_idents<#type_use_gen>
+							as #frame_support::view_functions::ViewFunction
+						>::id() {
+							return <
+								#struct_idents<#type_use_gen>
+								as #frame_support::view_functions::ViewFunction
+							>::execute(input, output)
+						}
+					)*
+					Err(#frame_support::view_functions::ViewFunctionDispatchError::NotFound(
+						id.clone(),
+					))
+				}
+			}
+
+			impl<#type_impl_gen> Pallet<#type_use_gen> #where_clause {
+				#[doc(hidden)]
+				pub fn pallet_view_functions_metadata() -> #frame_support::sp_std::vec::Vec<
+					#frame_support::view_functions::ViewFunctionMetadata
+				> {
+					#frame_support::sp_std::vec![ #( #metadata ),* ]
+				}
+			}
+		)
+	});
+
+	quote! {
+		#view_functions_impls
+
+		#[doc(hidden)]
+		pub mod __substrate_view_functions_check {
+			#[macro_export]
+			#[doc(hidden)]
+			macro_rules! #macro_ident {
+				($pallet_name:ident) => {
+					#maybe_compile_error
+				}
+			}
+
+			#[doc(hidden)]
+			pub use #macro_ident as is_view_functions_part_defined;
+		}
+	}
+}
+
+/// The name of the struct of the view function, i.e. its name in PascalCase suffixed with
+/// `ViewFunction`, so that it doesn't collide with the storages of the pallet.
+fn struct_ident(view_function: &ViewFunctionDef) -> Ident {
+	Ident::new(
+		&format!("{}ViewFunction", view_function.name.to_string().to_pascal_case()),
+		view_function.name.span(),
+	)
+}
+
+fn expand_view_function(
+	def: &Def,
+	view_function: &ViewFunctionDef,
+	where_clause: &Option<syn::WhereClause>,
+) -> TokenStream {
+	let frame_support = &def.frame_support;
+	let span = view_function.name.span();
+	let type_impl_gen = &def.type_impl_generics(span);
+	let type_decl_bounded_gen = &def.type_decl_bounded_generics(span);
+	let type_use_gen = &def.type_use_generics(span);
+	let struct_ident = struct_ident(view_function);
+	let fn_name = &view_function.name;
+	let arg_names = view_function.args.iter().map(|(name, _)| name).collect::<Vec<_>>();
+	let arg_types = view_function.args.iter().map(|(_, ty)| ty).collect::<Vec<_>>();
+	let return_type = &view_function.return_type;
+	let docs = &view_function.docs;
+	let signature = syn::LitByteStr::new(view_function.signature().as_bytes(), span);
+
+	quote::quote_spanned!(span =>
+		#( #[doc = #docs] )*
+		///
+		/// The arguments of the view function, see
+		#[doc = concat!("[`Pallet::", stringify!(#fn_name), "`].")]
+		#[derive(#frame_support::codec::Encode, #frame_support::codec::Decode)]
+		pub struct #struct_ident<#type_decl_bounded_gen> #where_clause {
+			#( pub #arg_names: #arg_types, )*
+			#[codec(skip)]
+			_marker: #frame_support::sp_std::marker::PhantomData<(#type_use_gen,)>,
+		}
+
+		impl<#type_impl_gen> #struct_ident<#type_use_gen> #where_clause {
+			/// Create the view function with its arguments.
+			#[allow(clippy::new_without_default)]
+			pub fn new(#( #arg_names: #arg_types ),*) -> Self {
+				Self { #( #arg_names, )* _marker: Default::default() }
+			}
+		}
+
+		impl<#type_impl_gen> #frame_support::view_functions::ViewFunction
+			for #struct_ident<#type_use_gen> #where_clause
+		{
+			fn id() -> #frame_support::view_functions::ViewFunctionId {
+				#frame_support::view_functions::ViewFunctionId {
+					prefix: <
+						Pallet<#type_use_gen> as #frame_support::view_functions::ViewFunctionIdPrefix
+					>::prefix(),
+					suffix: #frame_support::sp_core_hashing_proc_macro::twox_128!(#signature),
+				}
+			}
+
+			type ReturnType = #return_type;
+
+			fn invoke(self) -> Self::ReturnType {
+				let Self { #( #arg_names, )* _marker } = self;
+				Pallet::<#type_use_gen>::#fn_name(#( #arg_names ),*)
+			}
+		}
+	)
+}
@@ -31,6 +31,8 @@ mod keyword {
 	syn::custom_keyword!(RuntimeEvent);
 	syn::custom_keyword!(Event);
 	syn::custom_keyword!(constant);
+	syn::custom_keyword!(no_default);
+	syn::custom_keyword!(no_default_bounds);
 	syn::custom_keyword!(frame_system);
 	syn::custom_keyword!(disable_frame_system_supertrait_check);
 }
@@ -52,6 +54,19 @@ pub struct ConfigDef {
 	pub where_clause: Option<syn::WhereClause>,
 	/// The span of the pallet::config attribute.
 	pub attr_span: proc_macro2::Span,
+	/// The `DefaultConfig` trait to generate, i.e. `Some` when the trait is annotated with
+	/// `#[pallet::config(with_default)]`.
+	pub default_sub_trait: Option<DefaultTrait>,
+}
+
+/// Input definition for the `DefaultConfig` trait generated by
+/// `#[pallet::config(with_default)]`.
+pub struct DefaultTrait {
+	/// The items of the trait, along with whether their bounds must be removed, i.e. whether they
+	/// are annotated with `#[pallet::no_default_bounds]`.
+	pub items: Vec<(syn::TraitItem, bool)>,
+	/// Whether the trait has `frame_system::DefaultConfig` as supertrait.
+	pub has_system: bool,
 }
 
 /// Input definition for a constant in pallet config.
@@ -123,16 +138,22 @@ impl syn::parse::Parse for DisableFrameSystemSupertraitCheck {
 	}
 }
 
-/// Parse for `#[pallet::constant]`
-pub struct TypeAttrConst(proc_macro2::Span);
+/// Parse for `#[pallet::constant]`, `#[pallet::no_default]` or `#[pallet::no_default_bounds]`
+pub enum PalletAttrType {
+	Constant(proc_macro2::Span),
+	NoDefault(proc_macro2::Span),
+	NoDefaultBounds(proc_macro2::Span),
+}
 
-impl Spanned for TypeAttrConst {
+impl Spanned for PalletAttrType {
 	fn span(&self) -> proc_macro2::Span {
-		self.0
+		match self {
+			Self::Constant(span) | Self::NoDefault(span) | Self::NoDefaultBounds(span) => *span,
+		}
 	}
 }
 
-impl syn::parse::Parse for TypeAttrConst {
+impl syn::parse::Parse for PalletAttrType {
 	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
 		input.parse::<syn::Token![#]>()?;
 		let content;
@@ -140,7 +161,16 @@ impl syn::parse::Parse for TypeAttrConst {
 		content.parse::<syn::Ident>()?;
 		content.parse::<syn::Token![::]>()?;
 
-		Ok(TypeAttrConst(content.parse::<keyword::constant>()?.span()))
+		let lookahead = content.lookahead1();
+		if lookahead.peek(keyword::constant) {
+			Ok(Self::Constant(content.parse::<keyword::constant>()?.span()))
+		} else if lookahead.peek(keyword::no_default_bounds) {
+			Ok(Self::NoDefaultBounds(content.parse::<keyword::no_default_bounds>()?.span()))
+		} else if lookahead.peek(keyword::no_default) {
+			Ok(Self::NoDefault(content.parse::<keyword::no_default>()?.span()))
+		} else {
+			Err(lookahead.error())
+		}
 	}
 }
 
@@ -295,6 +325,7 @@ impl ConfigDef {
 		attr_span: proc_macro2::Span,
 		index: usize,
 		item: &mut syn::Item,
+		enable_default: bool,
 	) -> syn::Result<Self> {
 		let item = if let syn::Item::Trait(item) = item {
 			item
@@ -332,33 +363,74 @@ impl ConfigDef {
 
 		let mut has_event_type = false;
 		let mut consts_metadata = vec![];
+		let mut default_items = vec![];
 		for trait_item in &mut item.items {
 			// Parse for event
-			has_event_type =
-				has_event_type || check_event_type(frame_system, trait_item, has_instance)?;
-
-			// Parse for constant
-			let type_attrs_const: Vec<TypeAttrConst> = helper::take_item_pallet_attrs(trait_item)?;
-
-			if type_attrs_const.len() > 1 {
-				let msg = "Invalid attribute in pallet::config, only one attribute is expected";
-				return Err(syn::Error::new(type_attrs_const[1].span(), msg))
-			}
-
-			if type_attrs_const.len() == 1 {
-				match trait_item {
-					syn::TraitItem::Type(ref type_) => {
-						let constant = ConstMetadataDef::try_from(type_)?;
-						consts_metadata.push(constant);
+			let is_event = check_event_type(frame_system, trait_item, has_instance)?;
+			has_event_type = has_event_type || is_event;
+
+			let mut already_constant = false;
+			let mut already_no_default = false;
+			let mut already_no_default_bounds = false;
+
+			for attr in helper::take_item_pallet_attrs::<PalletAttrType>(trait_item)? {
+				match attr {
+					PalletAttrType::Constant(span) if already_constant => {
+						let msg = "Invalid duplicated attribute for `#[pallet::constant]`";
+						return Err(syn::Error::new(span, msg))
+					},
+					PalletAttrType::Constant(_) => match trait_item {
+						syn::TraitItem::Type(ref type_) => {
+							let constant = ConstMetadataDef::try_from(type_)?;
+							consts_metadata.push(constant);
+							already_constant = true;
+						},
+						_ => {
+							let msg =
+								"Invalid pallet::constant in pallet::config, expected type trait \
+								item";
+							return Err(syn::Error::new(trait_item.span(), msg))
+						},
+					},
+					PalletAttrType::NoDefault(span) if !enable_default => {
+						let msg = "`#[pallet::no_default]` can only be used on a config trait \
+							annotated with `#[pallet::config(with_default)]`";
+						return Err(syn::Error::new(span, msg))
 					},
-					_ => {
+					PalletAttrType::NoDefault(span) if already_no_default => {
+						let msg = "Invalid duplicated attribute for `#[pallet::no_default]`";
+						return Err(syn::Error::new(span, msg))
+					},
+					PalletAttrType::NoDefault(_) => already_no_default = true,
+					PalletAttrType::NoDefaultBounds(span) if !enable_default => {
+						let msg = "`#[pallet::no_default_bounds]` can only be used on a config \
+							trait annotated with `#[pallet::config(with_default)]`";
+						return Err(syn::Error::new(span, msg))
+					},
+					PalletAttrType::NoDefaultBounds(span) if already_no_default_bounds => {
 						let msg =
-							"Invalid pallet::constant in pallet::config, expected type trait \
-							item";
-						return Err(syn::Error::new(trait_item.span(), msg))
+							"Invalid duplicated attribute for `#[pallet::no_default_bounds]`";
+						return Err(syn::Error::new(span, msg))
 					},
+					PalletAttrType::NoDefaultBounds(_) => already_no_default_bounds = true,
 				}
 			}
+
+			if already_no_default && already_no_default_bounds {
+				let msg = "`#[pallet::no_default]` and `#[pallet::no_default_bounds]` are \
+					mutually exclusive";
+				return Err(syn::Error::new(trait_item.span(), msg))
+			}
+
+			// Only the associated types are part of the `DefaultConfig` trait. The bounds of
+			// `RuntimeEvent` refer to the pallet `Event` which requires `Config`, so they are
+			// always removed.
+			if enable_default &&
+				!already_no_default &&
+				matches!(trait_item, syn::TraitItem::Type(_))
+			{
+				default_items.push((trait_item.clone(), already_no_default_bounds || is_event));
+			}
 		}
 
 		let attr: Option<DisableFrameSystemSupertraitCheck> =
@@ -395,6 +467,19 @@ impl ConfigDef {
 			return Err(syn::Error::new(item.span(), msg))
 		}
 
-		Ok(Self { index, has_instance, consts_metadata, has_event_type, where_clause, attr_span })
+		let default_sub_trait = enable_default.then(|| DefaultTrait {
+			items: default_items,
+			has_system: has_frame_system_supertrait,
+		});
+
+		Ok(Self {
+			index,
+			has_instance,
+			consts_metadata,
+			has_event_type,
+			where_clause,
+			attr_span,
+			default_sub_trait,
+		})
 	}
 }
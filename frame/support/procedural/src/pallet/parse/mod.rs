@@ -32,8 +32,10 @@ pub mod inherent;
 pub mod origin;
 pub mod pallet_struct;
 pub mod storage;
+pub mod tasks;
 pub mod type_value;
 pub mod validate_unsigned;
+pub mod view_functions;
 
 use frame_support_procedural_tools::generate_crate_access_2018;
 use syn::spanned::Spanned;
@@ -55,6 +57,8 @@ pub struct Def {
 	pub genesis_config: Option<genesis_config::GenesisConfigDef>,
 	pub genesis_build: Option<genesis_build::GenesisBuildDef>,
 	pub validate_unsigned: Option<validate_unsigned::ValidateUnsignedDef>,
+	pub tasks: Option<tasks::TasksDef>,
+	pub view_functions: Option<view_functions::ViewFunctionsDef>,
 	pub extra_constants: Option<extra_constants::ExtraConstantsDef>,
 	pub type_values: Vec<type_value::TypeValueDef>,
 	pub frame_system: syn::Ident,
@@ -88,6 +92,8 @@ impl Def {
 		let mut genesis_config = None;
 		let mut genesis_build = None;
 		let mut validate_unsigned = None;
+		let mut tasks = None;
+		let mut view_functions = None;
 		let mut extra_constants = None;
 		let mut storages = vec![];
 		let mut type_values = vec![];
@@ -96,8 +102,14 @@ impl Def {
 			let pallet_attr: Option<PalletAttr> = helper::take_first_item_pallet_attr(item)?;
 
 			match pallet_attr {
-				Some(PalletAttr::Config(span)) if config.is_none() =>
-					config = Some(config::ConfigDef::try_from(&frame_system, span, index, item)?),
+				Some(PalletAttr::Config(span, with_default)) if config.is_none() =>
+					config = Some(config::ConfigDef::try_from(
+						&frame_system,
+						span,
+						index,
+						item,
+						with_default,
+					)?),
 				Some(PalletAttr::Pallet(span)) if pallet_struct.is_none() => {
 					let p = pallet_struct::PalletStructDef::try_from(span, index, item)?;
 					pallet_struct = Some(p);
@@ -130,6 +142,12 @@ impl Def {
 					let v = validate_unsigned::ValidateUnsignedDef::try_from(index, item)?;
 					validate_unsigned = Some(v);
 				},
+				Some(PalletAttr::Tasks(span)) if tasks.is_none() =>
+					tasks = Some(tasks::TasksDef::try_from(span, index, item)?),
+				Some(PalletAttr::ViewFunctions(span)) if view_functions.is_none() => {
+					let v = view_functions::ViewFunctionsDef::try_from(span, index, item)?;
+					view_functions = Some(v);
+				},
 				Some(PalletAttr::TypeValue(span)) =>
 					type_values.push(type_value::TypeValueDef::try_from(span, index, item)?),
 				Some(PalletAttr::ExtraConstants(_)) =>
@@ -166,6 +184,8 @@ impl Def {
 			genesis_config,
 			genesis_build,
 			validate_unsigned,
+			tasks,
+			view_functions,
 			error,
 			event,
 			origin,
@@ -236,6 +256,12 @@ impl Def {
 		if let Some(extra_constants) = &self.extra_constants {
 			instances.extend_from_slice(&extra_constants.instances[..]);
 		}
+		if let Some(tasks) = &self.tasks {
+			instances.extend_from_slice(&tasks.instances[..]);
+		}
+		if let Some(view_functions) = &self.view_functions {
+			instances.extend_from_slice(&view_functions.instances[..]);
+		}
 
 		let mut errors = instances.into_iter().filter_map(|instances| {
 			if instances.has_instance == self.config.has_instance {
@@ -385,12 +411,15 @@ mod keyword {
 	syn::custom_keyword!(generate_store);
 	syn::custom_keyword!(Store);
 	syn::custom_keyword!(extra_constants);
+	syn::custom_keyword!(tasks);
+	syn::custom_keyword!(view_functions);
+	syn::custom_keyword!(with_default);
 }
 
 /// Parse attributes for item in pallet module
 /// syntax must be `pallet::` (e.g. `#[pallet::config]`)
 enum PalletAttr {
-	Config(proc_macro2::Span),
+	Config(proc_macro2::Span, bool),
 	Pallet(proc_macro2::Span),
 	Hooks(proc_macro2::Span),
 	RuntimeCall(proc_macro2::Span),
@@ -404,12 +433,14 @@ enum PalletAttr {
 	ValidateUnsigned(proc_macro2::Span),
 	TypeValue(proc_macro2::Span),
 	ExtraConstants(proc_macro2::Span),
+	Tasks(proc_macro2::Span),
+	ViewFunctions(proc_macro2::Span),
 }
 
 impl PalletAttr {
 	fn span(&self) -> proc_macro2::Span {
 		match self {
-			Self::Config(span) => *span,
+			Self::Config(span, _) => *span,
 			Self::Pallet(span) => *span,
 			Self::Hooks(span) => *span,
 			Self::RuntimeCall(span) => *span,
@@ -423,6 +454,8 @@ impl PalletAttr {
 			Self::ValidateUnsigned(span) => *span,
 			Self::TypeValue(span) => *span,
 			Self::ExtraConstants(span) => *span,
+			Self::Tasks(span) => *span,
+			Self::ViewFunctions(span) => *span,
 		}
 	}
 }
@@ -437,7 +470,14 @@ impl syn::parse::Parse for PalletAttr {
 
 		let lookahead = content.lookahead1();
 		if lookahead.peek(keyword::config) {
-			Ok(PalletAttr::Config(content.parse::<keyword::config>()?.span()))
+			let span = content.parse::<keyword::config>()?.span();
+			let with_default = content.peek(syn::token::Paren);
+			if with_default {
+				let inside_config;
+				let _paren = syn::parenthesized!(inside_config in content);
+				inside_config.parse::<keyword::with_default>()?;
+			}
+			Ok(PalletAttr::Config(span, with_default))
 		} else if lookahead.peek(keyword::pallet) {
 			Ok(PalletAttr::Pallet(content.parse::<keyword::pallet>()?.span()))
 		} else if lookahead.peek(keyword::hooks) {
@@ -464,6 +504,10 @@ impl syn::parse::Parse for PalletAttr {
 			Ok(PalletAttr::TypeValue(content.parse::<keyword::type_value>()?.span()))
 		} else if lookahead.peek(keyword::extra_constants) {
 			Ok(PalletAttr::ExtraConstants(content.parse::<keyword::extra_constants>()?.span()))
+		} else if lookahead.peek(keyword::tasks) {
+			Ok(PalletAttr::Tasks(content.parse::<keyword::tasks>()?.span()))
+		} else if lookahead.peek(keyword::view_functions) {
+			Ok(PalletAttr::ViewFunctions(content.parse::<keyword::view_functions>()?.span()))
 		} else {
 			Err(lookahead.error())
 		}
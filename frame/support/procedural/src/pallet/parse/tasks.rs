@@ -0,0 +1,67 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::helper;
+use syn::spanned::Spanned;
+
+/// Definition of the pallet task enum.
+pub struct TasksDef {
+	/// The index of the task enum in pallet module.
+	pub index: usize,
+	/// A set of usage of instance, must be check for consistency with trait.
+	pub instances: Vec<helper::InstanceUsage>,
+	/// The span of the pallet::tasks attribute.
+	pub attr_span: proc_macro2::Span,
+}
+
+impl TasksDef {
+	pub fn try_from(
+		attr_span: proc_macro2::Span,
+		index: usize,
+		item: &mut syn::Item,
+	) -> syn::Result<Self> {
+		let item = if let syn::Item::Enum(item) = item {
+			item
+		} else {
+			return Err(syn::Error::new(item.span(), "Invalid pallet::tasks, expected item enum"))
+		};
+
+		if !matches!(item.vis, syn::Visibility::Public(_)) {
+			let msg = "Invalid pallet::tasks, `Task` must be public";
+			return Err(syn::Error::new(item.span(), msg))
+		}
+
+		if item.ident != "Task" {
+			let msg = "Invalid pallet::tasks, ident must be `Task`";
+			return Err(syn::Error::new(item.ident.span(), msg))
+		}
+
+		if item.generics.where_clause.is_some() {
+			let msg = "Invalid pallet::tasks, where clause is not allowed on pallet task item";
+			return Err(syn::Error::new(item.generics.where_clause.as_ref().unwrap().span(), msg))
+		}
+
+		let instances = vec![helper::check_type_def_gen(&item.generics, item.ident.span())?];
+
+		if item.variants.is_empty() {
+			let msg = "Invalid pallet::tasks, expected at least one task variant";
+			return Err(syn::Error::new(item.ident.span(), msg))
+		}
+
+		Ok(TasksDef { index, instances, attr_span })
+	}
+}
@@ -0,0 +1,147 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::helper;
+use frame_support_procedural_tools::get_doc_literals;
+use syn::spanned::Spanned;
+
+/// Definition of the view functions, i.e. `impl<T: Config> Pallet<T> { ... }`.
+pub struct ViewFunctionsDef {
+	/// The index of the impl item in pallet module.
+	pub index: usize,
+	/// A set of usage of instance, must be check for consistency with trait.
+	pub instances: Vec<helper::InstanceUsage>,
+	/// The where_clause used.
+	pub where_clause: Option<syn::WhereClause>,
+	/// The span of the pallet::view_functions attribute.
+	pub attr_span: proc_macro2::Span,
+	/// The view functions.
+	pub view_functions: Vec<ViewFunctionDef>,
+}
+
+/// Definition of a view function.
+pub struct ViewFunctionDef {
+	/// Name of the function.
+	pub name: syn::Ident,
+	/// The name and type of each argument.
+	pub args: Vec<(syn::Ident, syn::Type)>,
+	/// The type returned by the function.
+	pub return_type: syn::Type,
+	/// The doc associated.
+	pub docs: Vec<syn::Lit>,
+}
+
+impl ViewFunctionDef {
+	/// The signature which identifies the view function within the pallet.
+	pub fn signature(&self) -> String {
+		let name = &self.name;
+		let arg_types = self.args.iter().map(|(_, ty)| ty);
+		let return_type = &self.return_type;
+		quote::quote!(#name(#( #arg_types ),*) -> #return_type).to_string()
+	}
+}
+
+impl ViewFunctionsDef {
+	pub fn try_from(
+		attr_span: proc_macro2::Span,
+		index: usize,
+		item: &mut syn::Item,
+	) -> syn::Result<Self> {
+		let item = if let syn::Item::Impl(item) = item {
+			item
+		} else {
+			let msg = "Invalid pallet::view_functions, expected item impl";
+			return Err(syn::Error::new(item.span(), msg))
+		};
+
+		let instances = vec![
+			helper::check_impl_gen(&item.generics, item.impl_token.span())?,
+			helper::check_pallet_struct_usage(&item.self_ty)?,
+		];
+
+		if let Some((_, _, for_)) = item.trait_ {
+			let msg = "Invalid pallet::view_functions, expected no trait ident as in \
+				`impl<..> Pallet<..> { .. }`";
+			return Err(syn::Error::new(for_.span(), msg))
+		}
+
+		let mut view_functions = vec![];
+		for impl_item in &item.items {
+			let method = if let syn::ImplItem::Method(method) = impl_item {
+				method
+			} else {
+				let msg = "Invalid pallet::view_functions, only method accepted";
+				return Err(syn::Error::new(impl_item.span(), msg))
+			};
+
+			if !matches!(method.vis, syn::Visibility::Public(_)) {
+				let msg = "Invalid pallet::view_functions, view function must be public";
+				return Err(syn::Error::new(method.sig.span(), msg))
+			}
+
+			if !method.sig.generics.params.is_empty() || method.sig.generics.where_clause.is_some()
+			{
+				let msg = "Invalid pallet::view_functions, view function must have no generics";
+				return Err(syn::Error::new(method.sig.generics.span(), msg))
+			}
+
+			let mut args = vec![];
+			for input in &method.sig.inputs {
+				let arg = match input {
+					syn::FnArg::Typed(arg) => arg,
+					syn::FnArg::Receiver(_) => {
+						let msg = "Invalid pallet::view_functions, view function must not take \
+							`self`";
+						return Err(syn::Error::new(input.span(), msg))
+					},
+				};
+				let ident = match &*arg.pat {
+					syn::Pat::Ident(pat) => pat.ident.clone(),
+					_ => {
+						let msg = "Invalid pallet::view_functions, argument must be an ident";
+						return Err(syn::Error::new(arg.pat.span(), msg))
+					},
+				};
+				args.push((ident, *arg.ty.clone()));
+			}
+
+			let return_type = match &method.sig.output {
+				syn::ReturnType::Default => {
+					let msg = "Invalid pallet::view_functions, view function must have a return \
+						type";
+					return Err(syn::Error::new(method.sig.span(), msg))
+				},
+				syn::ReturnType::Type(_, type_) => *type_.clone(),
+			};
+
+			view_functions.push(ViewFunctionDef {
+				name: method.sig.ident.clone(),
+				args,
+				return_type,
+				docs: get_doc_literals(&method.attrs),
+			});
+		}
+
+		Ok(Self {
+			index,
+			instances,
+			where_clause: item.generics.where_clause.clone(),
+			attr_span,
+			view_functions,
+		})
+	}
+}
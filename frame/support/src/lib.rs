@@ -81,6 +81,7 @@ pub mod crypto;
 pub mod instances;
 pub mod migrations;
 pub mod traits;
+pub mod view_functions;
 pub mod weights;
 
 #[doc(hidden)]
@@ -539,7 +540,71 @@ pub use frame_support_procedural::{
 };
 
 #[doc(hidden)]
-pub use frame_support_procedural::{__create_tt_macro, __generate_dummy_part_checker};
+pub use frame_support_procedural::{
+	__create_tt_macro, __derive_impl_expand, __generate_dummy_part_checker,
+};
+
+/// Implement a trait for a type by only specifying the associated types which differ from a
+/// default impl.
+///
+/// The default impl must be registered with [`register_default_impl`]. The associated types
+/// missing from the annotated impl are defined as the ones of the default impl, i.e.
+/// `type Foo = <DefaultImpl as DefaultTrait>::Foo;`, except the types marked with
+/// `#[inject_runtime_type]` in the default impl which are defined as the types of the same
+/// name in the scope of the impl, i.e. the types aggregated by `construct_runtime!`:
+///
+/// ```ignore
+/// #[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+/// impl frame_system::Config for Test {
+/// 	type AccountData = pallet_balances::AccountData<u64>;
+/// }
+/// ```
+///
+/// The default trait, e.g. `as frame_system::DefaultConfig`, is optional and defaults to the
+/// implemented trait with its last segment prefixed by `Default`, e.g. `frame_system::Config`
+/// implies `frame_system::DefaultConfig`.
+///
+/// `#[derive_impl(.., no_aggregated_types)]` defines the types marked with
+/// `#[inject_runtime_type]` as the ones of the default impl as well. This is used to implement
+/// a `DefaultConfig` for a default impl outside of a runtime, e.g. the `frame_system` part of
+/// the default config of a pallet:
+///
+/// ```ignore
+/// #[derive_impl(
+/// 	frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig,
+/// 	no_aggregated_types
+/// )]
+/// impl frame_system::DefaultConfig for TestDefaultConfig {}
+/// ```
+pub use frame_support_procedural::derive_impl;
+
+/// Register a default impl to be used with [`derive_impl`].
+///
+/// The attribute takes the name of the type the trait is implemented for, and must be used in
+/// the module defining this type so that `path::to::Type` refers to both the type and the
+/// registered impl. Only associated types are supported. The types which can only be provided
+/// by a runtime, i.e. the ones aggregated by `construct_runtime!`, are marked with
+/// `#[inject_runtime_type]`:
+///
+/// ```ignore
+/// pub mod config_preludes {
+/// 	use super::*;
+///
+/// 	pub struct TestDefaultConfig;
+///
+/// 	#[register_default_impl(TestDefaultConfig)]
+/// 	impl DefaultConfig for TestDefaultConfig {
+/// 		type Index = u64;
+/// 		#[inject_runtime_type]
+/// 		type RuntimeCall = ();
+/// 	}
+/// }
+/// ```
+pub use frame_support_procedural::register_default_impl;
+
+/// Mark an associated type of an impl registered with [`register_default_impl`] as provided by
+/// the runtime.
+pub use frame_support_procedural::inject_runtime_type;
 
 /// Derive [`Clone`] but do not bound any generic.
 ///
@@ -1626,10 +1691,63 @@ pub mod pallet_prelude {
 /// Also see
 /// [`pallet::disable_frame_system_supertrait_check`](`frame_support::pallet_macros::disable_frame_system_supertrait_check`)
 ///
+/// ## `pallet::config(with_default)`
+///
+/// `#[pallet::config(with_default)]` generates a `DefaultConfig` trait containing the
+/// associated types of `Config`, with `frame_system::DefaultConfig` as supertrait if `Config`
+/// has `frame_system::Config` as supertrait. The pallet can register default configs
+/// implementing `DefaultConfig` with [`register_default_impl`], which runtimes use with
+/// [`derive_impl`] to only specify the associated types that differ from the defaults, e.g.:
+///
+/// ```ignore
+/// #[pallet::config(with_default)]
+/// pub trait Config: frame_system::Config {
+/// 	#[pallet::no_default_bounds]
+/// 	type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+/// 	#[pallet::constant]
+/// 	type MaxItems: Get<u32>;
+/// 	#[pallet::no_default]
+/// 	type Currency: Currency<Self::AccountId>;
+/// }
+///
+/// pub mod config_preludes {
+/// 	use super::*;
+///
+/// 	pub struct TestDefaultConfig;
+///
+/// 	#[frame_support::derive_impl(
+/// 		frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig,
+/// 		no_aggregated_types
+/// 	)]
+/// 	impl frame_system::DefaultConfig for TestDefaultConfig {}
+///
+/// 	#[frame_support::register_default_impl(TestDefaultConfig)]
+/// 	impl DefaultConfig for TestDefaultConfig {
+/// 		#[inject_runtime_type]
+/// 		type RuntimeEvent = ();
+/// 		type MaxItems = frame_support::traits::ConstU32<10>;
+/// 	}
+/// }
+///
+/// // In the mock runtime:
+/// #[derive_impl(pallet::config_preludes::TestDefaultConfig)]
+/// impl pallet::Config for Test {
+/// 	type Currency = Balances;
+/// }
+/// ```
+///
+/// The attributes on the associated types are:
+/// * `#[pallet::no_default]`: the type is not part of `DefaultConfig`, e.g. because it can't
+///   have a sensible default. The runtime must always specify it.
+/// * `#[pallet::no_default_bounds]`: the type is part of `DefaultConfig` but without its
+///   bounds, e.g. because they require `Config`. The bounds of `RuntimeEvent` are always
+///   removed.
+///
 /// ## Macro expansion:
 ///
 /// The macro expands pallet constant metadata with the information given by
-/// `#[pallet::constant]`.
+/// `#[pallet::constant]`, and the `DefaultConfig` trait if `#[pallet::config(with_default)]`
+/// is used.
 ///
 /// # `pallet::generate_store($vis trait Store)`
 ///
@@ -1687,6 +1805,20 @@ pub mod pallet_prelude {
 ///
 /// If not present, the current storage version is set to the default value.
 ///
+/// The storage version is not part of the metadata. Tools like `frame-storage-layout-diff`
+/// read it from a `StorageVersion` constant, which the pallet can expose with
+/// `#[pallet::extra_constants]`:
+///
+/// ```ignore
+/// #[pallet::extra_constants]
+/// impl<T: Config> Pallet<T> {
+/// 	#[pallet::constant_name(StorageVersion)]
+/// 	fn storage_version() -> StorageVersion {
+/// 		STORAGE_VERSION
+/// 	}
+/// }
+/// ```
+///
 /// Also see [`pallet::storage_version`](`frame_support::pallet_macros::storage_version`)
 ///
 /// # Hooks: `#[pallet::hooks]` (optional)
@@ -2198,6 +2330,81 @@ pub mod pallet_prelude {
 ///
 /// Also see [`pallet::origin`](`frame_support::pallet_macros::origin`)
 ///
+/// # Tasks: `#[pallet::tasks]` (optional)
+///
+/// The `#[pallet::tasks]` attribute allows you to declare background tasks of the pallet,
+/// which anyone can execute through `frame_system::Pallet::do_task` while they are valid, e.g.
+/// cleaning up the storage of a destroyed asset.
+///
+/// Item must be defined as:
+///
+/// ```ignore
+/// #[pallet::tasks]
+/// pub enum Task<T: Config> {
+/// 	// ... the tasks
+/// }
+///
+/// impl<T: Config> frame_support::traits::Task for Task<T> {
+/// 	// ... regular trait implementation
+/// }
+/// ```
+///
+/// The macro derives the traits required by [`traits::Task`], adds a hidden
+/// `__Ignore(PhantomData<T>, Never)` variant which the trait implementation must match, and
+/// adds the `Task` part to the pallet, so `construct_runtime` includes the tasks in the
+/// aggregated `RuntimeTask` enum. Offchain workers can submit the valid tasks with
+/// `frame_system::offchain::submit_valid_tasks`.
+///
+/// Also see [`pallet::tasks`](`frame_support::pallet_macros::tasks`)
+///
+/// # View functions: `#[pallet::view_functions]` (optional)
+///
+/// The `#[pallet::view_functions]` attribute allows you to declare read-only queries of the
+/// pallet, which clients can call through the runtime API
+/// [`view_functions::runtime_api::RuntimeViewFunction`] without a dedicated runtime API.
+///
+/// Item must be defined as:
+///
+/// ```ignore
+/// #[pallet::view_functions]
+/// impl<T: Config> Pallet<T> {
+/// 	/// Get the balance of `who`.
+/// 	pub fn balance_of(who: T::AccountId) -> BalanceOf<T> {
+/// 		// ...
+/// 	}
+/// }
+/// ```
+///
+/// I.e. an inherent implementation of `Pallet` with public functions without `self`, generics
+/// or where clause. Their arguments and return type must implement `Encode`, `Decode` and
+/// `TypeInfo`.
+///
+/// For each function, the macro generates a struct named after the function in PascalCase and
+/// suffixed with `ViewFunction` (e.g. `BalanceOfViewFunction`) with its arguments as fields,
+/// which implements [`view_functions::ViewFunction`]. A view function is identified by the
+/// hash of the pallet name and the hash of its signature, see
+/// [`view_functions::ViewFunctionId`]. The macro also adds the `ViewFunctions` part to the
+/// pallet, so `construct_runtime` includes the view functions in
+/// `Runtime::execute_view_function` and `Runtime::view_functions_metadata`, which
+/// implement the runtime API:
+///
+/// ```ignore
+/// impl frame_support::view_functions::runtime_api::RuntimeViewFunction<Block> for Runtime {
+/// 	fn execute_view_function(
+/// 		id: frame_support::view_functions::ViewFunctionId,
+/// 		input: Vec<u8>,
+/// 	) -> Result<Vec<u8>, frame_support::view_functions::ViewFunctionDispatchError> {
+/// 		Runtime::execute_view_function(id, input)
+/// 	}
+///
+/// 	fn view_functions_metadata() -> OpaqueMetadata {
+/// 		OpaqueMetadata::new(Runtime::view_functions_metadata().encode())
+/// 	}
+/// }
+/// ```
+///
+/// Also see [`pallet::view_functions`](`frame_support::pallet_macros::view_functions`)
+///
 /// # General notes on instantiable pallets
 ///
 /// An instantiable pallet is one where Config is generic, i.e. `Config<I>`. This allows
@@ -2731,7 +2938,8 @@ pub mod pallet_macros {
 	pub use frame_support_procedural::{
 		call_index, compact, config, constant, disable_frame_system_supertrait_check, error, event,
 		extra_constants, generate_deposit, generate_storage_info, generate_store, genesis_build,
-		genesis_config, getter, hooks, inherent, origin, storage, storage_prefix, storage_version,
-		type_value, unbounded, validate_unsigned, weight, whitelist_storage,
+		genesis_config, getter, hooks, inherent, no_default, no_default_bounds, origin, storage,
+		storage_prefix, storage_version, tasks, type_value, unbounded, validate_unsigned,
+		view_functions, weight, whitelist_storage,
 	};
 }
@@ -15,11 +15,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "try-runtime")]
+use crate::ensure;
 use crate::{
-	traits::{GetStorageVersion, PalletInfoAccess},
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, PalletInfoAccess, StorageVersion},
 	weights::{RuntimeDbWeight, Weight},
 };
+#[cfg(feature = "try-runtime")]
+use codec::{Decode, Encode};
 use impl_trait_for_tuples::impl_for_tuples;
+use sp_std::marker::PhantomData;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
 
 /// Trait used by [`migrate_from_pallet_version_to_storage_version`] to do the actual migration.
 pub trait PalletVersionToStorageVersionHelper {
@@ -67,3 +74,118 @@ pub fn migrate_from_pallet_version_to_storage_version<
 ) -> Weight {
 	Pallets::migrate(db_weight)
 }
+
+/// A migration of `Pallet` from the storage version `FROM` to the storage version `TO`.
+///
+/// The `Inner` migration is only executed if the on-chain storage version of `Pallet` is `FROM`,
+/// otherwise it is skipped and only a log message is emitted. The message is a warning if the
+/// on-chain storage version is already `TO` or newer, i.e. the migration can be removed, and an
+/// error otherwise, e.g. if an earlier migration is missing. After executing `Inner`, the
+/// on-chain storage version is set to `TO`, so `Inner` doesn't need to check or set the storage
+/// version itself. The `pre_upgrade` and `post_upgrade` checks of `Inner` are only executed if
+/// `Inner` is executed as well.
+///
+/// `DbWeight` is used to account for reading and writing the storage version.
+///
+/// # Example
+///
+/// ```ignore
+/// pub type MigrateV0ToV1<T> = VersionedMigration<
+/// 	0,
+/// 	1,
+/// 	UncheckedMigrateV0ToV1<T>,
+/// 	Pallet<T>,
+/// 	<T as frame_system::Config>::DbWeight,
+/// >;
+/// ```
+pub struct VersionedMigration<const FROM: u16, const TO: u16, Inner, Pallet, DbWeight>(
+	PhantomData<(Inner, Pallet, DbWeight)>,
+);
+
+/// The state that [`VersionedMigration::pre_upgrade`] passes to
+/// [`VersionedMigration::post_upgrade`].
+#[cfg(feature = "try-runtime")]
+#[derive(Encode, Decode)]
+enum VersionedPostUpgradeData {
+	/// The inner migration is executed, with the state of its `pre_upgrade`.
+	MigrationExecuted(Vec<u8>),
+	/// The inner migration is skipped.
+	Noop,
+}
+
+impl<
+		const FROM: u16,
+		const TO: u16,
+		Inner: OnRuntimeUpgrade,
+		Pallet: GetStorageVersion + PalletInfoAccess,
+		DbWeight: Get<RuntimeDbWeight>,
+	> OnRuntimeUpgrade for VersionedMigration<FROM, TO, Inner, Pallet, DbWeight>
+{
+	fn on_runtime_upgrade() -> Weight {
+		let on_chain_version = Pallet::on_chain_storage_version();
+		if on_chain_version == FROM {
+			log::info!(
+				target: "runtime::frame-support",
+				"🚚 Pallet {:?} migrating storage version from {:?} to {:?}.",
+				Pallet::name(),
+				FROM,
+				TO,
+			);
+
+			let weight = Inner::on_runtime_upgrade();
+			StorageVersion::new(TO).put::<Pallet>();
+
+			weight.saturating_add(DbWeight::get().reads_writes(1, 1))
+		} else if on_chain_version >= TO {
+			log::warn!(
+				target: "runtime::frame-support",
+				"🚚 Pallet {:?} migration {}->{} can be removed; on-chain is already at {:?}.",
+				Pallet::name(),
+				FROM,
+				TO,
+				on_chain_version,
+			);
+
+			DbWeight::get().reads(1)
+		} else {
+			log::error!(
+				target: "runtime::frame-support",
+				"🚚 Pallet {:?} migration {}->{} is skipped; on-chain is at {:?}, an earlier \
+				migration may be missing.",
+				Pallet::name(),
+				FROM,
+				TO,
+				on_chain_version,
+			);
+
+			DbWeight::get().reads(1)
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		let data = if Pallet::on_chain_storage_version() == FROM {
+			VersionedPostUpgradeData::MigrationExecuted(Inner::pre_upgrade()?)
+		} else {
+			VersionedPostUpgradeData::Noop
+		};
+		Ok(data.encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+		let data = VersionedPostUpgradeData::decode(&mut &state[..]).map_err(|_| {
+			"VersionedMigration post_upgrade failed to decode the pre_upgrade state"
+		})?;
+		match data {
+			VersionedPostUpgradeData::MigrationExecuted(inner_state) => {
+				ensure!(
+					Pallet::on_chain_storage_version() == TO,
+					"VersionedMigration did not set the on-chain storage version to TO",
+				);
+				Inner::post_upgrade(inner_state)
+			},
+			VersionedPostUpgradeData::Noop => Ok(()),
+		}
+	}
+}
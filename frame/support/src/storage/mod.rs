@@ -23,6 +23,7 @@ use crate::{
 		EncodeLikeTuple, HasKeyPrefix, HasReversibleKeyPrefix, KeyGenerator,
 		ReversibleKeyGenerator, TupleToEncodedIter,
 	},
+	weights::{Weight, WeightMeter},
 };
 use codec::{Decode, Encode, EncodeLike, FullCodec, FullEncode};
 use sp_core::storage::ChildInfo;
@@ -32,6 +33,7 @@ use sp_std::{collections::btree_set::BTreeSet, marker::PhantomData, prelude::*};
 pub use self::{
 	transactional::{
 		in_storage_layer, with_storage_layer, with_transaction, with_transaction_unchecked,
+		with_transactional_limit, StorageLayer,
 	},
 	types::StorageEntryMetadataBuilder,
 };
@@ -895,6 +897,40 @@ impl<T, OnRemoval> PrefixIterator<T, OnRemoval> {
 	}
 }
 
+impl<T, OnRemoval: PrefixIteratorOnRemoval> PrefixIterator<T, OnRemoval> {
+	/// Take at most `limit` items from the iterator.
+	///
+	/// Returns the items together with the cursor to pass as `starting_raw_key` to continue the
+	/// iteration, or `None` if there are no items left after the returned ones. A `limit` of zero
+	/// is treated as one, so that following the cursor always makes progress.
+	pub fn next_page(&mut self, limit: u32) -> (Vec<T>, Option<Vec<u8>>) {
+		let items = self.by_ref().take(limit.max(1) as usize).collect();
+		let has_more = sp_io::storage::next_key(&self.previous_key)
+			.map_or(false, |next| next.starts_with(&self.prefix));
+
+		(items, has_more.then(|| self.previous_key.clone()))
+	}
+
+	/// Remove items from storage and return them, as long as `meter` can accrue
+	/// `weight_per_item` for each of them.
+	///
+	/// Items are removed regardless of whether the iterator was turned into a draining iterator.
+	pub fn drain_bounded(self, meter: &mut WeightMeter, weight_per_item: Weight) -> Vec<T> {
+		let mut iter = self.drain();
+		let mut items = Vec::new();
+		while meter.can_accrue(weight_per_item) {
+			match iter.next() {
+				Some(item) => {
+					meter.check_accrue(weight_per_item);
+					items.push(item);
+				},
+				None => break,
+			}
+		}
+		items
+	}
+}
+
 impl<T, OnRemoval: PrefixIteratorOnRemoval> Iterator for PrefixIterator<T, OnRemoval> {
 	type Item = T;
 
@@ -25,6 +25,8 @@
 //! modifications.
 //!
 //! [`with_transaction`] provides a way to run a given closure in a transactional context.
+//! [`StorageLayer`] provides the same as a guard, for code that wants to decide explicitly when
+//! to commit or rollback a layer.
 
 use sp_io::storage::{commit_transaction, rollback_transaction, start_transaction};
 use sp_runtime::{DispatchError, TransactionOutcome, TransactionalError};
@@ -33,6 +35,8 @@ use sp_runtime::{DispatchError, TransactionOutcome, TransactionalError};
 pub type Layer = u32;
 /// The key that is holds the current number of active layers.
 pub const TRANSACTION_LEVEL_KEY: &[u8] = b":transaction_level:";
+/// The key that holds the layer limit set by [`with_transactional_limit`].
+pub const TRANSACTION_LIMIT_KEY: &[u8] = b":transaction_limit:";
 /// The maximum number of nested layers.
 pub const TRANSACTIONAL_LIMIT: Layer = 255;
 
@@ -51,12 +55,60 @@ fn kill_transaction_level() {
 	crate::storage::unhashed::kill(TRANSACTION_LEVEL_KEY);
 }
 
+/// Returns the current number of nested transactional layers.
+///
+/// This is `0` outside of any storage layer.
+pub fn transaction_level() -> Layer {
+	get_transaction_level()
+}
+
+/// Returns the maximum number of nested transactional layers.
+///
+/// This is [`TRANSACTIONAL_LIMIT`], unless lowered by [`with_transactional_limit`].
+pub fn transactional_limit() -> Layer {
+	crate::storage::unhashed::get::<Layer>(TRANSACTION_LIMIT_KEY).unwrap_or(TRANSACTIONAL_LIMIT)
+}
+
+/// Returns the number of storage keys changed in the current storage layer.
+///
+/// These are the changes a rollback of the layer would discard, so this is a measure of the
+/// rollback cost. Returns `0` outside of any storage layer.
+pub fn transaction_changes() -> u32 {
+	sp_io::storage::transaction_changes()
+}
+
+/// Execute the supplied function, allowing it to open at most `limit` nested layers.
+///
+/// The limit is relative to the current [`transaction_level`], and can only lower the current
+/// [`transactional_limit`]. Opening a layer past it fails with
+/// [`TransactionalError::LimitReached`]. The previous limit is restored afterwards.
+///
+/// This allows a dispatch to bound the number of layers the calls it dispatches may open.
+pub fn with_transactional_limit<R, F>(limit: Layer, f: F) -> R
+where
+	F: FnOnce() -> R,
+{
+	let previous = crate::storage::unhashed::get::<Layer>(TRANSACTION_LIMIT_KEY);
+	let new_limit = get_transaction_level()
+		.saturating_add(limit)
+		.min(previous.unwrap_or(TRANSACTIONAL_LIMIT));
+	crate::storage::unhashed::put::<Layer>(TRANSACTION_LIMIT_KEY, &new_limit);
+
+	let res = f();
+
+	match previous {
+		Some(previous) => crate::storage::unhashed::put::<Layer>(TRANSACTION_LIMIT_KEY, &previous),
+		None => crate::storage::unhashed::kill(TRANSACTION_LIMIT_KEY),
+	}
+	res
+}
+
 /// Increments the transaction level. Returns an error if levels go past the limit.
 ///
 /// Returns a guard that when dropped decrements the transaction level automatically.
 fn inc_transaction_level() -> Result<StorageLayerGuard, ()> {
 	let existing_levels = get_transaction_level();
-	if existing_levels >= TRANSACTIONAL_LIMIT {
+	if existing_levels >= transactional_limit() {
 		return Err(())
 	}
 	// Cannot overflow because of check above.
@@ -92,13 +144,62 @@ pub fn is_transactional() -> bool {
 	get_transaction_level() > 0
 }
 
+/// A storage layer that is open until it is explicitly committed or rolled back.
+///
+/// This is the guard version of [`with_transaction`]: all changes to storage performed while the
+/// layer is open are discarded by [`StorageLayer::rollback`] and committed to the parent layer by
+/// [`StorageLayer::commit`]. A layer that is dropped without either is rolled back.
+///
+/// Layers must be closed in the reverse order they were started in.
+#[must_use = "a storage layer is rolled back when dropped"]
+pub struct StorageLayer {
+	// Dropped after the transaction is closed in `Drop::drop`, see `StorageLayer::start`.
+	_guard: StorageLayerGuard,
+	closed: bool,
+}
+
+impl StorageLayer {
+	/// Start a new storage layer.
+	///
+	/// Returns [`TransactionalError::LimitReached`] if this would open more layers than the
+	/// [`transactional_limit`].
+	pub fn start() -> Result<Self, TransactionalError> {
+		// This needs to happen before `start_transaction` below.
+		// Otherwise we may rollback the increase, then decrease as the guard goes out of scope
+		// and then end in some bad state.
+		let guard = inc_transaction_level().map_err(|()| TransactionalError::LimitReached)?;
+		start_transaction();
+		Ok(Self { _guard: guard, closed: false })
+	}
+
+	/// Commit all changes of this layer to the parent layer.
+	pub fn commit(mut self) {
+		self.closed = true;
+		commit_transaction();
+	}
+
+	/// Discard all changes of this layer.
+	pub fn rollback(mut self) {
+		self.closed = true;
+		rollback_transaction();
+	}
+}
+
+impl Drop for StorageLayer {
+	fn drop(&mut self) {
+		if !self.closed {
+			rollback_transaction();
+		}
+	}
+}
+
 /// Execute the supplied function in a new storage transaction.
 ///
 /// All changes to storage performed by the supplied function are discarded if the returned
 /// outcome is `TransactionOutcome::Rollback`.
 ///
-/// Transactions can be nested up to `TRANSACTIONAL_LIMIT` times; more than that will result in an
-/// error.
+/// Transactions can be nested up to [`transactional_limit`] times; more than that will result in
+/// an error.
 ///
 /// Commits happen to the parent transaction.
 pub fn with_transaction<T, E, F>(f: F) -> Result<T, E>
@@ -106,20 +207,15 @@ where
 	E: From<DispatchError>,
 	F: FnOnce() -> TransactionOutcome<Result<T, E>>,
 {
-	// This needs to happen before `start_transaction` below.
-	// Otherwise we may rollback the increase, then decrease as the guard goes out of scope
-	// and then end in some bad state.
-	let _guard = inc_transaction_level().map_err(|()| TransactionalError::LimitReached.into())?;
-
-	start_transaction();
+	let layer = StorageLayer::start().map_err(|e| DispatchError::from(e).into())?;
 
 	match f() {
 		TransactionOutcome::Commit(res) => {
-			commit_transaction();
+			layer.commit();
 			res
 		},
 		TransactionOutcome::Rollback(res) => {
-			rollback_transaction();
+			layer.rollback();
 			res
 		},
 	}
@@ -298,4 +394,83 @@ mod tests {
 			assert_noop!(res, "epic fail");
 		});
 	}
+
+	#[test]
+	fn storage_layer_works() {
+		TestExternalities::default().execute_with(|| {
+			let layer = StorageLayer::start().unwrap();
+			assert_eq!(transaction_level(), 1);
+			crate::storage::unhashed::put(b"committed", &1u32);
+
+			let inner = StorageLayer::start().unwrap();
+			assert_eq!(transaction_level(), 2);
+			crate::storage::unhashed::put(b"rolled_back", &2u32);
+			inner.rollback();
+			assert_eq!(transaction_level(), 1);
+
+			{
+				let _dropped = StorageLayer::start().unwrap();
+				crate::storage::unhashed::put(b"dropped", &3u32);
+			}
+			assert_eq!(transaction_level(), 1);
+
+			layer.commit();
+			assert_eq!(transaction_level(), 0);
+
+			assert_eq!(crate::storage::unhashed::get::<u32>(b"committed"), Some(1));
+			assert_eq!(crate::storage::unhashed::get::<u32>(b"rolled_back"), None);
+			assert_eq!(crate::storage::unhashed::get::<u32>(b"dropped"), None);
+		});
+	}
+
+	#[test]
+	fn transactional_limit_works() {
+		TestExternalities::default().execute_with(|| {
+			assert_eq!(transactional_limit(), TRANSACTIONAL_LIMIT);
+
+			assert_ok!(with_transaction(|| -> TransactionOutcome<DispatchResult> {
+				let res = with_transactional_limit(2, || {
+					assert_eq!(transactional_limit(), 3);
+					assert_ok!(recursive_transactional(2));
+					assert_noop!(
+						recursive_transactional(3),
+						sp_runtime::TransactionalError::LimitReached
+					);
+
+					// A nested limit can only lower the current one.
+					with_transactional_limit(10, || assert_eq!(transactional_limit(), 3));
+					with_transactional_limit(1, || assert_eq!(transactional_limit(), 2));
+					assert_eq!(transactional_limit(), 3);
+					Ok(())
+				});
+				TransactionOutcome::Commit(res)
+			}));
+
+			assert_eq!(transactional_limit(), TRANSACTIONAL_LIMIT);
+			assert_eq!(crate::storage::unhashed::get::<Layer>(TRANSACTION_LIMIT_KEY), None);
+		});
+	}
+
+	#[test]
+	fn transaction_changes_works() {
+		TestExternalities::default().execute_with(|| {
+			assert_eq!(transaction_changes(), 0);
+
+			let layer = StorageLayer::start().unwrap();
+			crate::storage::unhashed::put(b"a", &1u32);
+			crate::storage::unhashed::put(b"a", &2u32);
+			assert_eq!(transaction_changes(), 1);
+
+			assert_ok!(with_storage_layer(|| -> DispatchResult {
+				crate::storage::unhashed::put(b"b", &1u32);
+				crate::storage::unhashed::kill(b"c");
+				Ok(())
+			}));
+			// The committed layer also changed the transaction level key.
+			assert_eq!(transaction_changes(), 4);
+
+			layer.rollback();
+			assert_eq!(transaction_changes(), 0);
+		});
+	}
 }
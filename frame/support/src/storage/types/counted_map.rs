@@ -28,6 +28,7 @@ use crate::{
 		StorageAppend, StorageDecodeLength, StorageTryAppend,
 	},
 	traits::{Get, GetDefault, StorageInfo, StorageInfoTrait, StorageInstance},
+	weights::{Weight, WeightMeter},
 	Never,
 };
 use codec::{Decode, Encode, EncodeLike, FullCodec, MaxEncodedLen, Ref};
@@ -437,6 +438,25 @@ where
 		<Self as MapWrapper>::Map::iter_from(starting_raw_key).convert_on_removal()
 	}
 
+	/// Enumerate at most `limit` elements in the counted map after the `cursor` in no particular
+	/// order.
+	///
+	/// Returns the elements and the cursor to continue the enumeration with, or `None` if all
+	/// elements have been enumerated. Pass `None` as `cursor` to start from the beginning.
+	///
+	/// If you alter the map between the calls, you'll get undefined results.
+	pub fn iter_paged(cursor: Option<Vec<u8>>, limit: u32) -> (Vec<(Key, Value)>, Option<Vec<u8>>) {
+		<Self as MapWrapper>::Map::iter_paged(cursor, limit)
+	}
+
+	/// Remove elements from the counted map as long as `meter` can accrue `weight_per_item` for
+	/// each of them, and return them in no particular order.
+	///
+	/// The counter is updated for every removed element.
+	pub fn drain_bounded(meter: &mut WeightMeter, weight_per_item: Weight) -> Vec<(Key, Value)> {
+		Self::iter().drain_bounded(meter, weight_per_item)
+	}
+
 	/// Enumerate all keys in the counted map.
 	///
 	/// If you alter the map while doing this, you'll get undefined results.
@@ -1076,6 +1096,42 @@ mod test {
 		})
 	}
 
+	#[test]
+	fn test_iter_paged_drain_bounded() {
+		type A = CountedStorageMap<Prefix, Twox64Concat, u16, u32>;
+		TestExternalities::default().execute_with(|| {
+			for i in 1..=5 {
+				A::insert(i, i as u32);
+			}
+
+			let mut items = vec![];
+			let mut cursor = None;
+			loop {
+				let (page, next) = A::iter_paged(cursor, 2);
+				assert!(page.len() <= 2);
+				items.extend(page);
+				cursor = next;
+				if cursor.is_none() {
+					break
+				}
+			}
+			assert_eq!(items, A::iter().collect::<Vec<_>>());
+
+			// A full last page doesn't return a cursor.
+			assert_eq!(A::iter_paged(None, 5).1, None);
+
+			let mut meter = WeightMeter::from_limit(Weight::from_ref_time(7));
+			let drained = A::drain_bounded(&mut meter, Weight::from_ref_time(2));
+			assert_eq!(drained, items[..3].to_vec());
+			assert_eq!(meter.remaining(), Weight::from_ref_time(1));
+			assert_eq!(A::count(), 2);
+
+			let mut meter = WeightMeter::max_limit();
+			assert_eq!(A::drain_bounded(&mut meter, Weight::from_ref_time(2)), items[3..].to_vec());
+			assert_eq!(A::count(), 0);
+		})
+	}
+
 	#[test]
 	fn test_metadata() {
 		type A = CountedStorageMap<Prefix, Twox64Concat, u16, u32, ValueQuery, ADefault>;
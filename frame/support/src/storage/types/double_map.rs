@@ -25,6 +25,7 @@ use crate::{
 		KeyLenOf, StorageAppend, StorageDecodeLength, StoragePrefixedMap, StorageTryAppend,
 	},
 	traits::{Get, GetDefault, StorageInfo, StorageInstance},
+	weights::{Weight, WeightMeter},
 	StorageHasher, Twox128,
 };
 use codec::{Decode, Encode, EncodeLike, FullCodec, MaxEncodedLen};
@@ -587,6 +588,36 @@ where
 		<Self as crate::storage::IterableStorageDoubleMap<Key1, Key2, Value>>::drain_prefix(k1)
 	}
 
+	/// Enumerate at most `limit` elements in the map with first key `k1` after the `cursor` in no
+	/// particular order.
+	///
+	/// Returns the elements and the cursor to continue the enumeration with, or `None` if all
+	/// elements have been enumerated. Pass `None` as `cursor` to start from the beginning.
+	///
+	/// If you add or remove values whose first key is `k1` to the map between the calls, you'll
+	/// get undefined results.
+	pub fn iter_prefix_paged(
+		k1: impl EncodeLike<Key1>,
+		cursor: Option<Vec<u8>>,
+		limit: u32,
+	) -> (Vec<(Key2, Value)>, Option<Vec<u8>>) {
+		match cursor {
+			Some(cursor) => Self::iter_prefix_from(k1, cursor),
+			None => Self::iter_prefix(k1),
+		}
+		.next_page(limit)
+	}
+
+	/// Remove elements with first key `k1` from the map as long as `meter` can accrue
+	/// `weight_per_item` for each of them, and return them in no particular order.
+	pub fn drain_prefix_bounded(
+		k1: impl EncodeLike<Key1>,
+		meter: &mut WeightMeter,
+		weight_per_item: Weight,
+	) -> Vec<(Key2, Value)> {
+		Self::iter_prefix(k1).drain_bounded(meter, weight_per_item)
+	}
+
 	/// Enumerate all elements in the map in no particular order.
 	///
 	/// If you add or remove values to the map while doing this, you'll get undefined results.
@@ -632,6 +663,32 @@ where
 		<Self as crate::storage::IterableStorageDoubleMap<Key1, Key2, Value>>::drain()
 	}
 
+	/// Enumerate at most `limit` elements in the map after the `cursor` in no particular order.
+	///
+	/// Returns the elements and the cursor to continue the enumeration with, or `None` if all
+	/// elements have been enumerated. Pass `None` as `cursor` to start from the beginning.
+	///
+	/// If you add or remove values to the map between the calls, you'll get undefined results.
+	pub fn iter_paged(
+		cursor: Option<Vec<u8>>,
+		limit: u32,
+	) -> (Vec<(Key1, Key2, Value)>, Option<Vec<u8>>) {
+		match cursor {
+			Some(cursor) => Self::iter_from(cursor),
+			None => Self::iter(),
+		}
+		.next_page(limit)
+	}
+
+	/// Remove elements from the map as long as `meter` can accrue `weight_per_item` for each of
+	/// them, and return them in no particular order.
+	pub fn drain_bounded(
+		meter: &mut WeightMeter,
+		weight_per_item: Weight,
+	) -> Vec<(Key1, Key2, Value)> {
+		Self::iter().drain_bounded(meter, weight_per_item)
+	}
+
 	/// Translate the values of all elements by a function `f`, in the map in no particular order.
 	///
 	/// By returning `None` from `f` for an element, you'll remove it from the map.
@@ -970,4 +1027,93 @@ mod test {
 			assert_eq!(A::drain_prefix(4).collect::<Vec<_>>(), vec![]);
 		})
 	}
+
+	#[test]
+	fn iter_paged_works() {
+		type A = StorageDoubleMap<Prefix, Twox64Concat, u16, Twox64Concat, u8, u32>;
+		TestExternalities::default().execute_with(|| {
+			assert_eq!(A::iter_paged(None, 10), (vec![], None));
+
+			for i in 0..5 {
+				A::insert(i, i as u8, i as u32 * 10);
+			}
+			let all = A::iter().collect::<Vec<_>>();
+
+			let (page, cursor) = A::iter_paged(None, 3);
+			assert_eq!(page, all[..3].to_vec());
+			assert_eq!(cursor, Some(A::hashed_key_for(all[2].0, all[2].1)));
+
+			let (page, cursor) = A::iter_paged(cursor, 3);
+			assert_eq!(page, all[3..].to_vec());
+			assert_eq!(cursor, None);
+
+			// A limit of zero still makes progress.
+			let (page, cursor) = A::iter_paged(None, 0);
+			assert_eq!(page, all[..1].to_vec());
+			assert_eq!(cursor, Some(A::hashed_key_for(all[0].0, all[0].1)));
+		})
+	}
+
+	#[test]
+	fn iter_prefix_paged_works() {
+		type A = StorageDoubleMap<Prefix, Twox64Concat, u16, Twox64Concat, u8, u32>;
+		TestExternalities::default().execute_with(|| {
+			assert_eq!(A::iter_prefix_paged(1, None, 10), (vec![], None));
+
+			for i in 0..5 {
+				A::insert(1, i, i as u32 * 10);
+				A::insert(2, i, i as u32 * 20);
+			}
+			let all = A::iter_prefix(1).collect::<Vec<_>>();
+
+			let (page, cursor) = A::iter_prefix_paged(1, None, 3);
+			assert_eq!(page, all[..3].to_vec());
+			assert_eq!(cursor, Some(A::hashed_key_for(1, all[2].0)));
+
+			let (page, cursor) = A::iter_prefix_paged(1, cursor, 3);
+			assert_eq!(page, all[3..].to_vec());
+			assert_eq!(cursor, None);
+		})
+	}
+
+	#[test]
+	fn drain_bounded_works() {
+		type A = StorageDoubleMap<Prefix, Twox64Concat, u16, Twox64Concat, u8, u32>;
+		TestExternalities::default().execute_with(|| {
+			for i in 0..5 {
+				A::insert(i, i as u8, i as u32 * 10);
+			}
+			let all = A::iter().collect::<Vec<_>>();
+
+			let mut meter = WeightMeter::from_limit(Weight::from_ref_time(10));
+			assert_eq!(A::drain_bounded(&mut meter, Weight::from_ref_time(3)), all[..3].to_vec());
+			assert_eq!(meter.remaining(), Weight::from_ref_time(1));
+			assert_eq!(A::iter().collect::<Vec<_>>(), all[3..].to_vec());
+
+			// Nothing is drained if the meter can't accrue a single item.
+			assert!(A::drain_bounded(&mut meter, Weight::from_ref_time(3)).is_empty());
+			assert_eq!(A::iter().count(), 2);
+		})
+	}
+
+	#[test]
+	fn drain_prefix_bounded_works() {
+		type A = StorageDoubleMap<Prefix, Twox64Concat, u16, Twox64Concat, u8, u32>;
+		TestExternalities::default().execute_with(|| {
+			for i in 0..5 {
+				A::insert(1, i, i as u32 * 10);
+				A::insert(2, i, i as u32 * 20);
+			}
+			let all = A::iter_prefix(1).collect::<Vec<_>>();
+
+			let mut meter = WeightMeter::from_limit(Weight::from_ref_time(10));
+			assert_eq!(
+				A::drain_prefix_bounded(1, &mut meter, Weight::from_ref_time(3)),
+				all[..3].to_vec()
+			);
+			assert_eq!(meter.remaining(), Weight::from_ref_time(1));
+			assert_eq!(A::iter_prefix(1).collect::<Vec<_>>(), all[3..].to_vec());
+			assert_eq!(A::iter_prefix(2).count(), 5);
+		})
+	}
 }
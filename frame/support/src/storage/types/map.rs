@@ -25,6 +25,7 @@ use crate::{
 		KeyLenOf, StorageAppend, StorageDecodeLength, StoragePrefixedMap, StorageTryAppend,
 	},
 	traits::{Get, GetDefault, StorageInfo, StorageInstance},
+	weights::{Weight, WeightMeter},
 	StorageHasher, Twox128,
 };
 use codec::{Decode, Encode, EncodeLike, FullCodec, MaxEncodedLen};
@@ -388,6 +389,26 @@ where
 		<Self as crate::storage::IterableStorageMap<Key, Value>>::drain()
 	}
 
+	/// Enumerate at most `limit` elements in the map after the `cursor` in no particular order.
+	///
+	/// Returns the elements and the cursor to continue the enumeration with, or `None` if all
+	/// elements have been enumerated. Pass `None` as `cursor` to start from the beginning.
+	///
+	/// If you alter the map between the calls, you'll get undefined results.
+	pub fn iter_paged(cursor: Option<Vec<u8>>, limit: u32) -> (Vec<(Key, Value)>, Option<Vec<u8>>) {
+		match cursor {
+			Some(cursor) => Self::iter_from(cursor),
+			None => Self::iter(),
+		}
+		.next_page(limit)
+	}
+
+	/// Remove elements from the map as long as `meter` can accrue `weight_per_item` for each of
+	/// them, and return them in no particular order.
+	pub fn drain_bounded(meter: &mut WeightMeter, weight_per_item: Weight) -> Vec<(Key, Value)> {
+		Self::iter().drain_bounded(meter, weight_per_item)
+	}
+
 	/// Translate the values of all elements by a function `f`, in the map in no particular order.
 	///
 	/// By returning `None` from `f` for an element, you'll remove it from the map.
@@ -524,6 +545,47 @@ mod test {
 		assert_eq!(KeyLenOf::<C>::get(), size);
 	}
 
+	#[test]
+	fn iter_paged_works() {
+		type A = StorageMap<Prefix, Twox64Concat, u16, u32>;
+		TestExternalities::default().execute_with(|| {
+			assert_eq!(A::iter_paged(None, 10), (vec![], None));
+
+			for i in 0..5 {
+				A::insert(i, i as u32 * 10);
+			}
+			let all = A::iter().collect::<Vec<_>>();
+
+			let (page, cursor) = A::iter_paged(None, 3);
+			assert_eq!(page, all[..3].to_vec());
+			assert_eq!(cursor, Some(A::hashed_key_for(all[2].0)));
+
+			let (page, cursor) = A::iter_paged(cursor, 3);
+			assert_eq!(page, all[3..].to_vec());
+			assert_eq!(cursor, None);
+		})
+	}
+
+	#[test]
+	fn drain_bounded_works() {
+		type A = StorageMap<Prefix, Twox64Concat, u16, u32>;
+		TestExternalities::default().execute_with(|| {
+			for i in 0..5 {
+				A::insert(i, i as u32 * 10);
+			}
+			let all = A::iter().collect::<Vec<_>>();
+
+			let mut meter = WeightMeter::from_limit(Weight::from_ref_time(10));
+			assert_eq!(A::drain_bounded(&mut meter, Weight::from_ref_time(3)), all[..3].to_vec());
+			assert_eq!(meter.remaining(), Weight::from_ref_time(1));
+			assert_eq!(A::iter().collect::<Vec<_>>(), all[3..].to_vec());
+
+			// Nothing is drained if the meter can't accrue a single item.
+			assert!(A::drain_bounded(&mut meter, Weight::from_ref_time(3)).is_empty());
+			assert_eq!(A::iter().count(), 2);
+		})
+	}
+
 	#[test]
 	fn test() {
 		type A = StorageMap<Prefix, Blake2_128Concat, u16, u32, OptionQuery>;
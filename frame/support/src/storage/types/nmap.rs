@@ -28,6 +28,7 @@ use crate::{
 		KeyGenerator, PrefixIterator, StorageAppend, StorageDecodeLength, StoragePrefixedMap,
 	},
 	traits::{Get, GetDefault, StorageInfo, StorageInstance},
+	weights::{Weight, WeightMeter},
 };
 use codec::{Decode, Encode, EncodeLike, FullCodec, MaxEncodedLen};
 use sp_runtime::SaturatedConversion;
@@ -491,6 +492,42 @@ where
 		<Self as crate::storage::IterableStorageNMap<Key, Value>>::drain_prefix(kp)
 	}
 
+	/// Enumerate at most `limit` elements in the map with prefix key `kp` after the `cursor` in no
+	/// particular order.
+	///
+	/// Returns the elements and the cursor to continue the enumeration with, or `None` if all
+	/// elements have been enumerated. Pass `None` as `cursor` to start from the beginning.
+	///
+	/// If you add or remove values whose prefix key is `kp` to the map between the calls, you'll
+	/// get undefined results.
+	pub fn iter_prefix_paged<KP>(
+		kp: KP,
+		cursor: Option<Vec<u8>>,
+		limit: u32,
+	) -> (Vec<(<Key as HasKeyPrefix<KP>>::Suffix, Value)>, Option<Vec<u8>>)
+	where
+		Key: HasReversibleKeyPrefix<KP>,
+	{
+		match cursor {
+			Some(cursor) => Self::iter_prefix_from(kp, cursor),
+			None => Self::iter_prefix(kp),
+		}
+		.next_page(limit)
+	}
+
+	/// Remove elements with prefix key `kp` from the map as long as `meter` can accrue
+	/// `weight_per_item` for each of them, and return them in no particular order.
+	pub fn drain_prefix_bounded<KP>(
+		kp: KP,
+		meter: &mut WeightMeter,
+		weight_per_item: Weight,
+	) -> Vec<(<Key as HasKeyPrefix<KP>>::Suffix, Value)>
+	where
+		Key: HasReversibleKeyPrefix<KP>,
+	{
+		Self::iter_prefix(kp).drain_bounded(meter, weight_per_item)
+	}
+
 	/// Enumerate all elements in the map in no particular order.
 	///
 	/// If you add or remove values to the map while doing this, you'll get undefined results.
@@ -530,6 +567,32 @@ where
 		<Self as crate::storage::IterableStorageNMap<Key, Value>>::drain()
 	}
 
+	/// Enumerate at most `limit` elements in the map after the `cursor` in no particular order.
+	///
+	/// Returns the elements and the cursor to continue the enumeration with, or `None` if all
+	/// elements have been enumerated. Pass `None` as `cursor` to start from the beginning.
+	///
+	/// If you add or remove values to the map between the calls, you'll get undefined results.
+	pub fn iter_paged(
+		cursor: Option<Vec<u8>>,
+		limit: u32,
+	) -> (Vec<(Key::Key, Value)>, Option<Vec<u8>>) {
+		match cursor {
+			Some(cursor) => Self::iter_from(cursor),
+			None => Self::iter(),
+		}
+		.next_page(limit)
+	}
+
+	/// Remove elements from the map as long as `meter` can accrue `weight_per_item` for each of
+	/// them, and return them in no particular order.
+	pub fn drain_bounded(
+		meter: &mut WeightMeter,
+		weight_per_item: Weight,
+	) -> Vec<(Key::Key, Value)> {
+		Self::iter().drain_bounded(meter, weight_per_item)
+	}
+
 	/// Translate the values of all elements by a function `f`, in the map in no particular order.
 	///
 	/// By returning `None` from `f` for an element, you'll remove it from the map.
@@ -1280,4 +1343,93 @@ mod test {
 			assert_eq!(A::iter_prefix_values((4, 40)).collect::<Vec<_>>(), vec![14, 13]);
 		});
 	}
+
+	#[test]
+	fn iter_paged_works() {
+		type A = StorageNMap<Prefix, (NMapKey<Twox64Concat, u16>, NMapKey<Twox64Concat, u8>), u32>;
+		TestExternalities::default().execute_with(|| {
+			assert_eq!(A::iter_paged(None, 10), (vec![], None));
+
+			for i in 0..5 {
+				A::insert((i, i as u8), i as u32 * 10);
+			}
+			let all = A::iter().collect::<Vec<_>>();
+
+			let (page, cursor) = A::iter_paged(None, 3);
+			assert_eq!(page, all[..3].to_vec());
+			assert_eq!(cursor, Some(A::hashed_key_for(all[2].0)));
+
+			let (page, cursor) = A::iter_paged(cursor, 3);
+			assert_eq!(page, all[3..].to_vec());
+			assert_eq!(cursor, None);
+
+			// A limit of zero still makes progress.
+			let (page, cursor) = A::iter_paged(None, 0);
+			assert_eq!(page, all[..1].to_vec());
+			assert_eq!(cursor, Some(A::hashed_key_for(all[0].0)));
+		})
+	}
+
+	#[test]
+	fn iter_prefix_paged_works() {
+		type A = StorageNMap<Prefix, (NMapKey<Twox64Concat, u16>, NMapKey<Twox64Concat, u8>), u32>;
+		TestExternalities::default().execute_with(|| {
+			assert_eq!(A::iter_prefix_paged((1,), None, 10), (vec![], None));
+
+			for i in 0..5 {
+				A::insert((1, i), i as u32 * 10);
+				A::insert((2, i), i as u32 * 20);
+			}
+			let all = A::iter_prefix((1,)).collect::<Vec<_>>();
+
+			let (page, cursor) = A::iter_prefix_paged((1,), None, 3);
+			assert_eq!(page, all[..3].to_vec());
+			assert_eq!(cursor, Some(A::hashed_key_for((1, all[2].0))));
+
+			let (page, cursor) = A::iter_prefix_paged((1,), cursor, 3);
+			assert_eq!(page, all[3..].to_vec());
+			assert_eq!(cursor, None);
+		})
+	}
+
+	#[test]
+	fn drain_bounded_works() {
+		type A = StorageNMap<Prefix, (NMapKey<Twox64Concat, u16>, NMapKey<Twox64Concat, u8>), u32>;
+		TestExternalities::default().execute_with(|| {
+			for i in 0..5 {
+				A::insert((i, i as u8), i as u32 * 10);
+			}
+			let all = A::iter().collect::<Vec<_>>();
+
+			let mut meter = WeightMeter::from_limit(Weight::from_ref_time(10));
+			assert_eq!(A::drain_bounded(&mut meter, Weight::from_ref_time(3)), all[..3].to_vec());
+			assert_eq!(meter.remaining(), Weight::from_ref_time(1));
+			assert_eq!(A::iter().collect::<Vec<_>>(), all[3..].to_vec());
+
+			// Nothing is drained if the meter can't accrue a single item.
+			assert!(A::drain_bounded(&mut meter, Weight::from_ref_time(3)).is_empty());
+			assert_eq!(A::iter().count(), 2);
+		})
+	}
+
+	#[test]
+	fn drain_prefix_bounded_works() {
+		type A = StorageNMap<Prefix, (NMapKey<Twox64Concat, u16>, NMapKey<Twox64Concat, u8>), u32>;
+		TestExternalities::default().execute_with(|| {
+			for i in 0..5 {
+				A::insert((1, i), i as u32 * 10);
+				A::insert((2, i), i as u32 * 20);
+			}
+			let all = A::iter_prefix((1,)).collect::<Vec<_>>();
+
+			let mut meter = WeightMeter::from_limit(Weight::from_ref_time(10));
+			assert_eq!(
+				A::drain_prefix_bounded((1,), &mut meter, Weight::from_ref_time(3)),
+				all[..3].to_vec()
+			);
+			assert_eq!(meter.remaining(), Weight::from_ref_time(1));
+			assert_eq!(A::iter_prefix((1,)).collect::<Vec<_>>(), all[3..].to_vec());
+			assert_eq!(A::iter_prefix((2,)).count(), 5);
+		})
+	}
 }
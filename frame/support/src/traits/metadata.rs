@@ -19,6 +19,7 @@
 
 use codec::{Decode, Encode};
 use impl_trait_for_tuples::impl_for_tuples;
+use scale_info::TypeInfo;
 use sp_runtime::RuntimeDebug;
 use sp_std::prelude::*;
 
@@ -158,7 +159,7 @@ pub const STORAGE_VERSION_STORAGE_KEY_POSTFIX: &[u8] = b":__STORAGE_VERSION__:";
 ///
 /// Each storage version of a pallet is stored in the state under a fixed key. See
 /// [`STORAGE_VERSION_STORAGE_KEY_POSTFIX`] for how this key is built.
-#[derive(Debug, Eq, PartialEq, Encode, Decode, Ord, Clone, Copy, PartialOrd, Default)]
+#[derive(Debug, Eq, PartialEq, Encode, Decode, Ord, Clone, Copy, PartialOrd, Default, TypeInfo)]
 pub struct StorageVersion(u16);
 
 impl StorageVersion {
@@ -289,4 +290,9 @@ mod tests {
 		assert!(version > StorageVersion::new(1));
 		assert!(version < StorageVersion::new(5));
 	}
+	#[test]
+	fn storage_version_is_encoded_as_u16() {
+		// Tools decode the storage version exposed in the metadata as `u16`.
+		assert_eq!(StorageVersion::new(5).encode(), 5u16.encode());
+	}
 }
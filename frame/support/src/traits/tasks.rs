@@ -0,0 +1,80 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Traits for background work that anyone can trigger, declared with `#[pallet::tasks]`.
+
+use crate::weights::Weight;
+use codec::FullCodec;
+use scale_info::TypeInfo;
+use sp_runtime::DispatchResult;
+use sp_std::fmt::Debug;
+
+/// A unit of background work of a pallet, which anyone can execute while it is valid.
+///
+/// Tasks are executed through the `do_task` call of `frame_system`, either by a signed origin or
+/// as an unsigned transaction submitted by an offchain worker.
+pub trait Task: Sized + FullCodec + TypeInfo + Clone + Debug + PartialEq + Eq + 'static {
+	/// An iterator over the tasks returned by [`Task::iter`].
+	type Enumeration: Iterator<Item = Self>;
+
+	/// Enumerate the tasks that may currently be valid.
+	///
+	/// Used to discover the work to do, e.g. in offchain workers. The returned tasks still need to
+	/// be checked with [`Task::is_valid`].
+	fn iter() -> Self::Enumeration;
+
+	/// Whether the task can be executed in the current state.
+	fn is_valid(&self) -> bool;
+
+	/// Execute the task.
+	///
+	/// Only called if [`Task::is_valid`] returned `true`.
+	fn run(&self) -> DispatchResult;
+
+	/// The weight of executing the task, including checking its validity.
+	fn weight(&self) -> Weight;
+}
+
+/// Implemented by the pallets which declare a task type with `#[pallet::tasks]`.
+pub trait PalletTasks {
+	/// The task type of the pallet.
+	type Task: Task;
+}
+
+/// The task type of the pallet `P`.
+pub type TaskFor<P> = <P as PalletTasks>::Task;
+
+/// For runtimes without any tasks.
+impl Task for crate::Never {
+	type Enumeration = sp_std::iter::Empty<Self>;
+
+	fn iter() -> Self::Enumeration {
+		sp_std::iter::empty()
+	}
+
+	fn is_valid(&self) -> bool {
+		match *self {}
+	}
+
+	fn run(&self) -> DispatchResult {
+		match *self {}
+	}
+
+	fn weight(&self) -> Weight {
+		match *self {}
+	}
+}
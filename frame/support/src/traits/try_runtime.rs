@@ -74,7 +74,8 @@ impl sp_std::str::FromStr for Select {
 						.ok_or("failed to parse count")?;
 					Ok(Select::RoundRobin(count))
 				} else {
-					let pallets = s.split(',').map(|x| x.as_bytes().to_vec()).collect::<Vec<_>>();
+					let pallets =
+						s.split(',').map(|x| x.trim().as_bytes().to_vec()).collect::<Vec<_>>();
 					Ok(Select::Only(pallets))
 				},
 		}
@@ -0,0 +1,208 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Read-only queries of pallets, declared with `#[pallet::view_functions]`.
+//!
+//! `construct_runtime!` aggregates the view functions of all the pallets with a `ViewFunctions`
+//! part, which the runtime exposes through the [`runtime_api::RuntimeViewFunction`] API. A view
+//! function is called by its [`ViewFunctionId`] with its SCALE encoded arguments, so clients can
+//! call any of them with the help of [`RuntimeViewFunctionsMetadata`], without a dedicated
+//! runtime API or RPC.
+
+use codec::{Decode, DecodeAll, Encode, Output};
+use scale_info::{
+	form::{Form, MetaForm, PortableForm},
+	IntoPortable, PortableRegistry, Registry, TypeInfo,
+};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// The identifier of a view function.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub struct ViewFunctionId {
+	/// The `twox_128` hash of the name of the pallet in the runtime.
+	pub prefix: [u8; 16],
+	/// The `twox_128` hash of the signature of the view function.
+	pub suffix: [u8; 16],
+}
+
+/// The error of executing a view function.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub enum ViewFunctionDispatchError {
+	/// No view function with this id exists.
+	NotFound(ViewFunctionId),
+	/// The arguments could not be decoded.
+	Codec,
+}
+
+impl From<codec::Error> for ViewFunctionDispatchError {
+	fn from(_: codec::Error) -> Self {
+		Self::Codec
+	}
+}
+
+/// A view function, i.e. a read-only query of a pallet with its arguments.
+pub trait ViewFunction: DecodeAll {
+	/// The identifier of the view function.
+	fn id() -> ViewFunctionId;
+
+	/// The type returned by the view function.
+	type ReturnType: Encode;
+
+	/// Call the view function.
+	fn invoke(self) -> Self::ReturnType;
+
+	/// Decode the view function from `input`, call it and write the encoded result to `output`.
+	fn execute<O: Output>(
+		input: &mut &[u8],
+		output: &mut O,
+	) -> Result<(), ViewFunctionDispatchError> {
+		let view_function = Self::decode_all(input)?;
+		view_function.invoke().encode_to(output);
+		Ok(())
+	}
+}
+
+/// The prefix of the identifiers of the view functions of a pallet.
+pub trait ViewFunctionIdPrefix {
+	/// The `twox_128` hash of the name of the pallet in the runtime.
+	fn prefix() -> [u8; 16];
+}
+
+/// Dispatch a view function of a pallet by its identifier.
+pub trait DispatchViewFunction {
+	/// Execute the view function `id` with the encoded arguments `input`, writing the encoded
+	/// result to `output`.
+	fn dispatch_view_function<O: Output>(
+		id: &ViewFunctionId,
+		input: &mut &[u8],
+		output: &mut O,
+	) -> Result<(), ViewFunctionDispatchError>;
+}
+
+/// The metadata of the view functions of a runtime.
+///
+/// Contains its own type registry, as the V14 metadata doesn't describe the view functions.
+#[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Decode))]
+pub struct RuntimeViewFunctionsMetadata {
+	/// The types of the arguments and results of the view functions.
+	pub types: PortableRegistry,
+	/// The view functions of every pallet with a `ViewFunctions` part.
+	pub pallets: Vec<PalletViewFunctionsMetadata<PortableForm>>,
+}
+
+impl RuntimeViewFunctionsMetadata {
+	/// Register the types of the view functions of `pallets`.
+	pub fn new(pallets: Vec<PalletViewFunctionsMetadata>) -> Self {
+		let mut registry = Registry::new();
+		let pallets = registry.map_into_portable(pallets);
+		Self { types: registry.into(), pallets }
+	}
+}
+
+/// The metadata of the view functions of a pallet.
+#[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Decode))]
+pub struct PalletViewFunctionsMetadata<T: Form = MetaForm> {
+	/// The name of the pallet in the runtime.
+	pub name: T::String,
+	/// The view functions of the pallet.
+	pub view_functions: Vec<ViewFunctionMetadata<T>>,
+}
+
+impl IntoPortable for PalletViewFunctionsMetadata {
+	type Output = PalletViewFunctionsMetadata<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		PalletViewFunctionsMetadata {
+			name: self.name.into_portable(registry),
+			view_functions: registry.map_into_portable(self.view_functions),
+		}
+	}
+}
+
+/// The metadata of a view function.
+#[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Decode))]
+pub struct ViewFunctionMetadata<T: Form = MetaForm> {
+	/// The name of the view function.
+	pub name: T::String,
+	/// The identifier to call the view function with.
+	pub id: ViewFunctionId,
+	/// The arguments of the view function, encoded in this order.
+	pub args: Vec<ViewFunctionArgMetadata<T>>,
+	/// The type returned by the view function.
+	pub output: T::Type,
+	/// The documentation of the view function.
+	pub docs: Vec<T::String>,
+}
+
+impl IntoPortable for ViewFunctionMetadata {
+	type Output = ViewFunctionMetadata<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		ViewFunctionMetadata {
+			name: self.name.into_portable(registry),
+			id: self.id,
+			args: registry.map_into_portable(self.args),
+			output: registry.register_type(&self.output),
+			docs: registry.map_into_portable(self.docs),
+		}
+	}
+}
+
+/// The metadata of an argument of a view function.
+#[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Decode))]
+pub struct ViewFunctionArgMetadata<T: Form = MetaForm> {
+	/// The name of the argument.
+	pub name: T::String,
+	/// The type of the argument.
+	pub ty: T::Type,
+}
+
+impl IntoPortable for ViewFunctionArgMetadata {
+	type Output = ViewFunctionArgMetadata<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		ViewFunctionArgMetadata {
+			name: self.name.into_portable(registry),
+			ty: registry.register_type(&self.ty),
+		}
+	}
+}
+
+/// The runtime API to call the view functions.
+pub mod runtime_api {
+	use super::*;
+
+	sp_api::decl_runtime_apis! {
+		/// Runtime API to call the view functions declared by the pallets.
+		pub trait RuntimeViewFunction {
+			/// Execute the view function `id` with the SCALE encoded arguments `input`, returning
+			/// the SCALE encoded result.
+			fn execute_view_function(
+				id: ViewFunctionId,
+				input: Vec<u8>,
+			) -> Result<Vec<u8>, ViewFunctionDispatchError>;
+
+			/// The SCALE encoded [`RuntimeViewFunctionsMetadata`] of the runtime.
+			fn view_functions_metadata() -> sp_core::OpaqueMetadata;
+		}
+	}
+}
@@ -0,0 +1,141 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for `#[pallet::config(with_default)]` and `#[derive_impl]`.
+
+use frame_support::{derive_impl, traits::Get};
+use sp_io::TestExternalities;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::config(with_default)]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		#[pallet::constant]
+		type MaxItems: Get<u32>;
+
+		type Value: Parameter + Default + MaxEncodedLen;
+
+		#[pallet::no_default_bounds]
+		type OnValue: OnValue<Self>;
+
+		#[pallet::constant]
+		#[pallet::no_default]
+		type Multiplier: Get<Self::Value>;
+	}
+
+	pub trait OnValue<T: Config> {
+		fn on_value(value: &T::Value);
+	}
+
+	impl<T: Config> OnValue<T> for () {
+		fn on_value(_: &T::Value) {}
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::event]
+	pub enum Event<T: Config> {
+		Something(T::Value),
+	}
+}
+
+/// A default config of the pallet defined in this crate, so `register_default_impl` can't be used.
+pub struct PalletDefaultConfig;
+
+#[derive_impl(
+	frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig,
+	no_aggregated_types
+)]
+impl frame_system::DefaultConfig for PalletDefaultConfig {}
+
+// `Multiplier` is not part of `DefaultConfig`, defining it here would fail to compile.
+impl pallet::DefaultConfig for PalletDefaultConfig {
+	type RuntimeEvent = ();
+	type MaxItems = frame_support::traits::ConstU32<3>;
+	type Value = u16;
+	type OnValue = ();
+}
+
+pub type Block = frame_system::mocking::MockBlock<Runtime>;
+pub type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+
+frame_support::construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system,
+		Example: pallet,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Runtime {
+	type AccountData = u32;
+}
+
+impl pallet::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type MaxItems = <PalletDefaultConfig as pallet::DefaultConfig>::MaxItems;
+	type Value = <PalletDefaultConfig as pallet::DefaultConfig>::Value;
+	type OnValue = <PalletDefaultConfig as pallet::DefaultConfig>::OnValue;
+	type Multiplier = frame_support::traits::ConstU16<2>;
+}
+
+#[test]
+fn derive_impl_uses_the_defaults_of_missing_types() {
+	let account_data: <Runtime as frame_system::Config>::AccountData = 1u32;
+	let account_id: <Runtime as frame_system::Config>::AccountId = 1u64;
+	let block_number: <Runtime as frame_system::Config>::BlockNumber = 1u64;
+	let _ = (account_data, account_id, block_number);
+
+	assert_eq!(<Runtime as frame_system::Config>::BlockHashCount::get(), 250);
+	assert_eq!(<Runtime as pallet::Config>::MaxItems::get(), 3);
+	assert_eq!(<Runtime as pallet::Config>::Multiplier::get(), 2u16);
+}
+
+#[test]
+fn derive_impl_injects_the_runtime_types() {
+	TestExternalities::default().execute_with(|| {
+		System::set_block_number(1);
+
+		let origin: <Runtime as frame_system::Config>::RuntimeOrigin = RuntimeOrigin::root();
+		let call: <Runtime as frame_system::Config>::RuntimeCall =
+			RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		assert!(frame_support::dispatch::Dispatchable::dispatch(call, origin).is_ok());
+
+		System::deposit_event(pallet::Event::<Runtime>::Something(5));
+		assert_eq!(
+			System::events()[0].event,
+			<Runtime as frame_system::Config>::RuntimeEvent::from(
+				pallet::Event::<Runtime>::Something(5)
+			),
+		);
+
+		assert_eq!(
+			<<Runtime as frame_system::Config>::PalletInfo as frame_support::traits::PalletInfo>
+				::name::<Example>(),
+			Some("Example"),
+		);
+	});
+}
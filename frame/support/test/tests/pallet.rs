@@ -574,7 +574,9 @@ pub mod pallet4 {
 	impl<T: Config> Pallet<T> {}
 }
 
-/// Test that the supertrait check works when we pass some parameter to the `frame_system::Config`.
+/// Test that the supertrait check works when we pass some parameter to the `frame_system::Config`,
+/// and that a pallet whose module is feature gated can be used in `construct_runtime`.
+#[cfg(feature = "frame-feature-testing-2")]
 #[frame_support::pallet]
 pub mod pallet5 {
 	#[pallet::config]
@@ -598,6 +600,7 @@ impl frame_system::Config for Runtime {
 	type Index = u64;
 	type BlockNumber = u32;
 	type RuntimeCall = RuntimeCall;
+	type RuntimeTask = RuntimeTask;
 	type Hash = sp_runtime::testing::H256;
 	type Hashing = sp_runtime::traits::BlakeTwo256;
 	type AccountId = u64;
@@ -661,7 +664,7 @@ frame_support::construct_runtime!(
 		Example4: pallet4 use_parts { Call },
 
 		#[cfg(feature = "frame-feature-testing-2")]
-		Example5: pallet5,
+		Example5: pallet5::{Pallet},
 	}
 );
 
@@ -131,6 +131,7 @@ mod tests {
 		type BlockNumber = u64;
 		type Hash = sp_core::H256;
 		type RuntimeCall = RuntimeCall;
+		type RuntimeTask = RuntimeTask;
 		type Hashing = sp_runtime::traits::BlakeTwo256;
 		type AccountId = u64;
 		type Lookup = sp_runtime::traits::IdentityLookup<Self::AccountId>;
@@ -0,0 +1,198 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for `#[pallet::tasks]` and the aggregated `RuntimeTask`.
+
+use frame_support::{
+	assert_noop, assert_ok,
+	pallet_prelude::{InvalidTransaction, TransactionSource, ValidateUnsigned},
+	traits::{ConstU32, Task},
+	weights::Weight,
+};
+use sp_io::TestExternalities;
+
+#[frame_support::pallet]
+pub mod dummy_pallet {
+	use frame_support::pallet_prelude::*;
+	use sp_std::vec::Vec;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {}
+
+	/// Numbers waiting to be added into [`Total`].
+	#[pallet::storage]
+	pub type Numbers<T> = StorageMap<_, Twox64Concat, u32, u32>;
+
+	/// The sum of all the processed numbers.
+	#[pallet::storage]
+	pub type Total<T> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::tasks]
+	pub enum Task<T: Config> {
+		/// Move the number at index `i` into [`Total`].
+		AddNumberIntoTotal { i: u32 },
+	}
+
+	impl<T: Config> frame_support::traits::Task for Task<T> {
+		type Enumeration = sp_std::vec::IntoIter<Task<T>>;
+
+		fn iter() -> Self::Enumeration {
+			Numbers::<T>::iter_keys()
+				.map(|i| Task::AddNumberIntoTotal { i })
+				.collect::<Vec<_>>()
+				.into_iter()
+		}
+
+		fn is_valid(&self) -> bool {
+			match self {
+				Task::AddNumberIntoTotal { i } => Numbers::<T>::contains_key(i),
+				Task::__Ignore(_, never) => match *never {},
+			}
+		}
+
+		fn run(&self) -> DispatchResult {
+			match self {
+				Task::AddNumberIntoTotal { i } => {
+					let number = Numbers::<T>::take(i).ok_or(DispatchError::Other("no number"))?;
+					Total::<T>::mutate(|total| *total += number);
+					Ok(())
+				},
+				Task::__Ignore(_, never) => match *never {},
+			}
+		}
+
+		fn weight(&self) -> Weight {
+			Weight::from_ref_time(1_000)
+		}
+	}
+}
+
+pub type Header = sp_runtime::generic::Header<u32, sp_runtime::traits::BlakeTwo256>;
+pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<u32, RuntimeCall, (), ()>;
+
+impl frame_system::Config for Test {
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type BaseCallFilter = frame_support::traits::Everything;
+	type RuntimeOrigin = RuntimeOrigin;
+	type Index = u64;
+	type BlockNumber = u32;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeTask = RuntimeTask;
+	type Hash = sp_runtime::testing::H256;
+	type Hashing = sp_runtime::traits::BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = sp_runtime::traits::IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU32<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl dummy_pallet::Config for Test {}
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system,
+		DummyPallet: dummy_pallet,
+	}
+);
+
+fn add_number_task(i: u32) -> RuntimeTask {
+	dummy_pallet::Task::<Test>::AddNumberIntoTotal { i }.into()
+}
+
+#[test]
+fn runtime_task_enumerates_the_tasks_of_the_pallets() {
+	TestExternalities::default().execute_with(|| {
+		assert!(RuntimeTask::iter().next().is_none());
+
+		dummy_pallet::Numbers::<Test>::insert(0, 10);
+
+		let tasks = RuntimeTask::iter().collect::<Vec<_>>();
+		assert_eq!(tasks, vec![add_number_task(0)]);
+		assert!(tasks[0].is_valid());
+		assert!(!add_number_task(1).is_valid());
+		assert_eq!(tasks[0].weight(), Weight::from_ref_time(1_000));
+	});
+}
+
+#[test]
+fn do_task_runs_valid_tasks() {
+	TestExternalities::default().execute_with(|| {
+		System::set_block_number(1);
+		dummy_pallet::Numbers::<Test>::insert(0, 10);
+		dummy_pallet::Numbers::<Test>::insert(1, 5);
+
+		assert_ok!(System::do_task(RuntimeOrigin::none(), add_number_task(0)));
+		assert_ok!(System::do_task(RuntimeOrigin::signed(1), add_number_task(1)));
+
+		assert_eq!(dummy_pallet::Total::<Test>::get(), 15);
+		assert!(RuntimeTask::iter().next().is_none());
+		System::assert_last_event(
+			frame_system::Event::TaskCompleted { task: add_number_task(1) }.into(),
+		);
+	});
+}
+
+#[test]
+fn do_task_rejects_invalid_tasks() {
+	TestExternalities::default().execute_with(|| {
+		assert_noop!(
+			System::do_task(RuntimeOrigin::none(), add_number_task(0)),
+			frame_system::Error::<Test>::InvalidTask,
+		);
+	});
+}
+
+#[test]
+fn unsigned_tasks_are_only_accepted_from_the_local_node() {
+	TestExternalities::default().execute_with(|| {
+		dummy_pallet::Numbers::<Test>::insert(0, 10);
+		let call = frame_system::Call::<Test>::do_task { task: add_number_task(0) };
+
+		assert_ok!(System::validate_unsigned(TransactionSource::Local, &call));
+		assert_ok!(System::validate_unsigned(TransactionSource::InBlock, &call));
+		assert_eq!(
+			System::validate_unsigned(TransactionSource::External, &call),
+			InvalidTransaction::Call.into(),
+		);
+
+		let invalid_call = frame_system::Call::<Test>::do_task { task: add_number_task(1) };
+		assert_eq!(
+			System::validate_unsigned(TransactionSource::Local, &invalid_call),
+			InvalidTransaction::Call.into(),
+		);
+	});
+}
@@ -0,0 +1,179 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for [`VersionedMigration`].
+
+use frame_support::{
+	migrations::VersionedMigration,
+	traits::{ConstU32, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::{RuntimeDbWeight, Weight},
+};
+use sp_io::TestExternalities;
+
+#[frame_support::pallet]
+pub mod dummy_pallet {
+	use frame_support::pallet_prelude::*;
+
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
+
+	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {}
+
+	/// The number of times that the inner migration was executed.
+	#[pallet::storage]
+	pub type Executions<T> = StorageValue<_, u32, ValueQuery>;
+}
+
+pub type Header = sp_runtime::generic::Header<u32, sp_runtime::traits::BlakeTwo256>;
+pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<u32, RuntimeCall, (), ()>;
+
+frame_support::parameter_types! {
+	pub const DbWeight: RuntimeDbWeight = RuntimeDbWeight { read: 1, write: 10 };
+}
+
+impl frame_system::Config for Test {
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = DbWeight;
+	type BaseCallFilter = frame_support::traits::Everything;
+	type RuntimeOrigin = RuntimeOrigin;
+	type Index = u64;
+	type BlockNumber = u32;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeTask = RuntimeTask;
+	type Hash = sp_runtime::testing::H256;
+	type Hashing = sp_runtime::traits::BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = sp_runtime::traits::IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU32<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl dummy_pallet::Config for Test {}
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system,
+		DummyPallet: dummy_pallet,
+	}
+);
+
+type DummyPallet = dummy_pallet::Pallet<Test>;
+
+/// An inner migration that counts its executions and doesn't touch the storage version.
+pub struct UncheckedCountExecutions;
+
+impl OnRuntimeUpgrade for UncheckedCountExecutions {
+	fn on_runtime_upgrade() -> Weight {
+		dummy_pallet::Executions::<Test>::mutate(|n| *n += 1);
+		Weight::from_ref_time(100)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		use codec::Encode;
+		Ok(dummy_pallet::Executions::<Test>::get().encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+		use codec::Decode;
+		let executions = u32::decode(&mut &state[..]).map_err(|_| "invalid state")?;
+		frame_support::ensure!(
+			dummy_pallet::Executions::<Test>::get() == executions + 1,
+			"migration was not executed"
+		);
+		Ok(())
+	}
+}
+
+type MigrateV0ToV1 = VersionedMigration<0, 1, UncheckedCountExecutions, DummyPallet, DbWeight>;
+type MigrateV1ToV2 = VersionedMigration<1, 2, UncheckedCountExecutions, DummyPallet, DbWeight>;
+
+#[test]
+fn migration_runs_and_bumps_the_version_if_on_chain_version_matches() {
+	TestExternalities::default().execute_with(|| {
+		assert_eq!(DummyPallet::on_chain_storage_version(), 0);
+
+		let weight = MigrateV0ToV1::on_runtime_upgrade();
+
+		assert_eq!(DummyPallet::on_chain_storage_version(), 1);
+		assert_eq!(dummy_pallet::Executions::<Test>::get(), 1);
+		// The inner weight plus reading and writing the storage version.
+		assert_eq!(weight, Weight::from_ref_time(100 + 1 + 10));
+	});
+}
+
+#[test]
+fn migration_is_skipped_if_on_chain_version_does_not_match() {
+	TestExternalities::default().execute_with(|| {
+		StorageVersion::new(2).put::<DummyPallet>();
+
+		let weight = MigrateV0ToV1::on_runtime_upgrade();
+		assert_eq!(weight, Weight::from_ref_time(1));
+		let weight = MigrateV1ToV2::on_runtime_upgrade();
+		assert_eq!(weight, Weight::from_ref_time(1));
+
+		assert_eq!(DummyPallet::on_chain_storage_version(), 2);
+		assert_eq!(dummy_pallet::Executions::<Test>::get(), 0);
+	});
+}
+
+#[test]
+fn consecutive_migrations_are_executed_in_order() {
+	TestExternalities::default().execute_with(|| {
+		<(MigrateV0ToV1, MigrateV1ToV2, MigrateV0ToV1) as OnRuntimeUpgrade>::on_runtime_upgrade();
+
+		assert_eq!(DummyPallet::on_chain_storage_version(), 2);
+		assert_eq!(dummy_pallet::Executions::<Test>::get(), 2);
+	});
+}
+
+#[cfg(feature = "try-runtime")]
+#[test]
+fn upgrade_checks_only_run_with_the_migration() {
+	TestExternalities::default().execute_with(|| {
+		let state = MigrateV0ToV1::pre_upgrade().unwrap();
+		MigrateV0ToV1::on_runtime_upgrade();
+		assert_eq!(MigrateV0ToV1::post_upgrade(state), Ok(()));
+
+		// The skipped migration doesn't run the post upgrade checks of the inner migration, which
+		// would fail otherwise.
+		let state = MigrateV0ToV1::pre_upgrade().unwrap();
+		MigrateV0ToV1::on_runtime_upgrade();
+		assert_eq!(MigrateV0ToV1::post_upgrade(state), Ok(()));
+	});
+}
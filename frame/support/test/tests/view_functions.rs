@@ -0,0 +1,195 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for `#[pallet::view_functions]`.
+
+use codec::{Decode, Encode};
+use frame_support::{
+	traits::ConstU32,
+	view_functions::{ViewFunction, ViewFunctionDispatchError, ViewFunctionId},
+};
+use sp_io::TestExternalities;
+
+#[frame_support::pallet]
+pub mod dummy_pallet {
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {}
+
+	#[pallet::storage]
+	pub type SomeValue<T> = StorageValue<_, u32>;
+
+	#[pallet::storage]
+	pub type SomeMap<T> = StorageMap<_, Twox64Concat, u32, u32>;
+
+	#[pallet::view_functions]
+	impl<T: Config> Pallet<T> {
+		/// Get the value.
+		pub fn get_value() -> Option<u32> {
+			SomeValue::<T>::get()
+		}
+
+		/// Get the value of `key` in the map.
+		pub fn get_value_with_arg(key: u32) -> Option<u32> {
+			SomeMap::<T>::get(key)
+		}
+
+		/// Get the value, the view function is named after the storage.
+		pub fn some_value() -> Option<u32> {
+			SomeValue::<T>::get()
+		}
+	}
+}
+
+pub type Header = sp_runtime::generic::Header<u32, sp_runtime::traits::BlakeTwo256>;
+pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<u32, RuntimeCall, (), ()>;
+
+impl frame_system::Config for Runtime {
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type BaseCallFilter = frame_support::traits::Everything;
+	type RuntimeOrigin = RuntimeOrigin;
+	type Index = u64;
+	type BlockNumber = u32;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeTask = RuntimeTask;
+	type Hash = sp_runtime::testing::H256;
+	type Hashing = sp_runtime::traits::BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = sp_runtime::traits::IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU32<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl dummy_pallet::Config for Runtime {}
+
+frame_support::construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system,
+		DummyPallet: dummy_pallet,
+	}
+);
+
+#[test]
+fn view_functions_are_executed_by_id() {
+	TestExternalities::default().execute_with(|| {
+		dummy_pallet::SomeValue::<Runtime>::put(10);
+		dummy_pallet::SomeMap::<Runtime>::insert(1, 20);
+
+		let id = <dummy_pallet::GetValueViewFunction<Runtime> as ViewFunction>::id();
+		let output = Runtime::execute_view_function(id, vec![]).unwrap();
+		assert_eq!(Option::<u32>::decode(&mut &output[..]).unwrap(), Some(10));
+
+		let id = <dummy_pallet::GetValueWithArgViewFunction<Runtime> as ViewFunction>::id();
+		let input = dummy_pallet::GetValueWithArgViewFunction::<Runtime>::new(1).encode();
+		let output = Runtime::execute_view_function(id.clone(), input).unwrap();
+		assert_eq!(Option::<u32>::decode(&mut &output[..]).unwrap(), Some(20));
+
+		let output = Runtime::execute_view_function(id, 2u32.encode()).unwrap();
+		assert_eq!(Option::<u32>::decode(&mut &output[..]).unwrap(), None);
+
+		let id = <dummy_pallet::SomeValueViewFunction<Runtime> as ViewFunction>::id();
+		let output = Runtime::execute_view_function(id, vec![]).unwrap();
+		assert_eq!(Option::<u32>::decode(&mut &output[..]).unwrap(), Some(10));
+	});
+}
+
+#[test]
+fn view_function_ids_are_prefixed_by_the_pallet_name() {
+	let id = <dummy_pallet::GetValueViewFunction<Runtime> as ViewFunction>::id();
+	assert_eq!(id.prefix, sp_io::hashing::twox_128(b"DummyPallet"));
+	assert_ne!(id, <dummy_pallet::GetValueWithArgViewFunction<Runtime> as ViewFunction>::id());
+}
+
+#[test]
+fn invalid_view_function_calls_are_rejected() {
+	TestExternalities::default().execute_with(|| {
+		let unknown = ViewFunctionId { prefix: [0; 16], suffix: [0; 16] };
+		assert_eq!(
+			Runtime::execute_view_function(unknown.clone(), vec![]),
+			Err(ViewFunctionDispatchError::NotFound(unknown)),
+		);
+
+		let unknown_suffix =
+			ViewFunctionId { prefix: sp_io::hashing::twox_128(b"DummyPallet"), suffix: [0; 16] };
+		assert_eq!(
+			Runtime::execute_view_function(unknown_suffix.clone(), vec![]),
+			Err(ViewFunctionDispatchError::NotFound(unknown_suffix)),
+		);
+
+		// The argument is missing.
+		let id = <dummy_pallet::GetValueWithArgViewFunction<Runtime> as ViewFunction>::id();
+		assert_eq!(
+			Runtime::execute_view_function(id, vec![]),
+			Err(ViewFunctionDispatchError::Codec),
+		);
+	});
+}
+
+#[test]
+fn view_functions_metadata() {
+	let metadata = Runtime::view_functions_metadata();
+
+	assert_eq!(metadata.pallets.len(), 1);
+	let pallet = &metadata.pallets[0];
+	assert_eq!(pallet.name, "DummyPallet");
+	assert_eq!(
+		pallet.view_functions.iter().map(|f| f.name.as_str()).collect::<Vec<_>>(),
+		vec!["get_value", "get_value_with_arg", "some_value"],
+	);
+
+	let get_value_with_arg = &pallet.view_functions[1];
+	assert_eq!(
+		get_value_with_arg.id,
+		<dummy_pallet::GetValueWithArgViewFunction<Runtime> as ViewFunction>::id()
+	);
+	assert_eq!(get_value_with_arg.args.len(), 1);
+	assert_eq!(get_value_with_arg.args[0].name, "key");
+	let arg_type = metadata.types.resolve(get_value_with_arg.args[0].ty.id()).unwrap();
+	assert_eq!(
+		arg_type.type_def(),
+		&scale_info::TypeDef::Primitive(scale_info::TypeDefPrimitive::U32)
+	);
+
+	// The metadata can be decoded by clients.
+	let encoded = metadata.encode();
+	assert_eq!(
+		frame_support::view_functions::RuntimeViewFunctionsMetadata::decode(&mut &encoded[..])
+			.unwrap(),
+		metadata,
+	);
+}
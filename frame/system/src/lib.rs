@@ -91,7 +91,7 @@ use frame_support::{
 	storage,
 	traits::{
 		ConstU32, Contains, EnsureOrigin, Get, HandleLifetime, OnKilledAccount, OnNewAccount,
-		OriginTrait, PalletInfo, SortedMembers, StoredMap, TypedGet,
+		OriginTrait, PalletInfo, SortedMembers, StoredMap, Task, TypedGet,
 	},
 	Parameter,
 };
@@ -199,7 +199,7 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 
 	/// System configuration trait. Implemented by runtime.
-	#[pallet::config]
+	#[pallet::config(with_default)]
 	#[pallet::disable_frame_system_supertrait_check]
 	pub trait Config: 'static + Eq + Clone {
 		/// The basic call filter to use in Origin. All origins are built with this filter as base,
@@ -215,17 +215,23 @@ pub mod pallet {
 		type BlockLength: Get<limits::BlockLength>;
 
 		/// The `RuntimeOrigin` type used by dispatchable calls.
+		#[pallet::no_default_bounds]
 		type RuntimeOrigin: Into<Result<RawOrigin<Self::AccountId>, Self::RuntimeOrigin>>
 			+ From<RawOrigin<Self::AccountId>>
 			+ Clone
 			+ OriginTrait<Call = Self::RuntimeCall>;
 
 		/// The aggregated `RuntimeCall` type.
+		#[pallet::no_default_bounds]
 		type RuntimeCall: Parameter
 			+ Dispatchable<RuntimeOrigin = Self::RuntimeOrigin>
 			+ Debug
 			+ From<Call<Self>>;
 
+		/// The aggregated `RuntimeTask` type, executed by [`Pallet::do_task`].
+		#[pallet::no_default_bounds]
+		type RuntimeTask: Task;
+
 		/// Account index (aka nonce) type. This stores the number of previous transactions
 		/// associated with a sender account.
 		type Index: Parameter
@@ -295,6 +301,7 @@ pub mod pallet {
 		type Header: Parameter + traits::Header<Number = Self::BlockNumber, Hash = Self::Hash>;
 
 		/// The aggregated event type of the runtime.
+		#[pallet::no_default_bounds]
 		type RuntimeEvent: Parameter
 			+ Member
 			+ From<Event<Self>>
@@ -319,6 +326,7 @@ pub mod pallet {
 		/// runtime.
 		///
 		/// For tests it is okay to use `()` as type, however it will provide "useless" data.
+		#[pallet::no_default_bounds]
 		type PalletInfo: PalletInfo;
 
 		/// Data to be associated with an account (other than nonce/transaction counter, which this
@@ -350,12 +358,65 @@ pub mod pallet {
 		/// [`Pallet::update_code_in_storage`]).
 		/// It's unlikely that this needs to be customized, unless you are writing a parachain using
 		/// `Cumulus`, where the actual code change is deferred.
+		#[pallet::no_default_bounds]
 		type OnSetCode: SetCode<Self>;
 
 		/// The maximum number of consumers allowed on a single account.
 		type MaxConsumers: ConsumerLimits;
 	}
 
+	/// Default configs of this pallet, to be used with
+	/// [`derive_impl`](`frame_support::derive_impl`).
+	pub mod config_preludes {
+		use super::DefaultConfig;
+		use frame_support::{
+			inject_runtime_type, register_default_impl,
+			traits::{ConstU32, ConstU64, Everything},
+		};
+		use sp_runtime::{
+			generic,
+			traits::{BlakeTwo256, IdentityLookup},
+		};
+
+		/// A default config for mock runtimes, with `u64` accounts and block numbers and no
+		/// limits on blocks.
+		pub struct TestDefaultConfig;
+
+		#[register_default_impl(TestDefaultConfig)]
+		impl DefaultConfig for TestDefaultConfig {
+			type BaseCallFilter = Everything;
+			type BlockWeights = ();
+			type BlockLength = ();
+			#[inject_runtime_type]
+			type RuntimeOrigin = ();
+			#[inject_runtime_type]
+			type RuntimeCall = ();
+			#[inject_runtime_type]
+			type RuntimeTask = ();
+			type Index = u64;
+			type BlockNumber = u64;
+			type Hash = sp_core::H256;
+			type Hashing = BlakeTwo256;
+			type AccountId = u64;
+			type Lookup = IdentityLookup<u64>;
+			type Header = generic::Header<u64, BlakeTwo256>;
+			#[inject_runtime_type]
+			type RuntimeEvent = ();
+			type BlockHashCount = ConstU64<250>;
+			type DbWeight = ();
+			type Version = ();
+			#[inject_runtime_type]
+			type PalletInfo = ();
+			type AccountData = ();
+			type OnNewAccount = ();
+			type OnKilledAccount = ();
+			type SystemWeightInfo = ();
+			type SS58Prefix = ();
+			type OnSetCode = ();
+			type MaxConsumers = ConstU32<16>;
+		}
+	}
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub (super) trait Store)]
 	pub struct Pallet<T>(_);
@@ -486,6 +547,24 @@ pub mod pallet {
 			Self::deposit_event(Event::Remarked { sender: who, hash });
 			Ok(().into())
 		}
+
+		/// Execute a task of a pallet if it is valid.
+		///
+		/// Can be dispatched by any origin, including as an unsigned transaction submitted by the
+		/// offchain worker of the local node with [`offchain::submit_valid_tasks`].
+		///
+		/// # <weight>
+		/// - The weight of the task.
+		/// - DbReads: `Number`, `EventCount`
+		/// - DbWrites: `Events`, `EventCount`
+		/// # </weight>
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 2).saturating_add(task.weight()))]
+		pub fn do_task(_origin: OriginFor<T>, task: T::RuntimeTask) -> DispatchResultWithPostInfo {
+			ensure!(task.is_valid(), Error::<T>::InvalidTask);
+			task.run()?;
+			Self::deposit_event(Event::TaskCompleted { task });
+			Ok(().into())
+		}
 	}
 
 	/// Event for the System pallet.
@@ -503,6 +582,8 @@ pub mod pallet {
 		KilledAccount { account: T::AccountId },
 		/// On on-chain remark happened.
 		Remarked { sender: T::AccountId, hash: T::Hash },
+		/// A task was executed.
+		TaskCompleted { task: T::RuntimeTask },
 	}
 
 	/// Error for the System pallet
@@ -524,6 +605,36 @@ pub mod pallet {
 		NonZeroRefCount,
 		/// The origin filter prevent the call to be dispatched.
 		CallFiltered,
+		/// The task is not valid in the current state.
+		InvalidTask,
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			if let Call::do_task { task } = call {
+				// Nobody pays for unsigned tasks, so only accept the ones submitted by the local
+				// offchain worker or already included in a block.
+				match source {
+					TransactionSource::Local | TransactionSource::InBlock => { /* allowed */ },
+					_ => return InvalidTransaction::Call.into(),
+				}
+
+				if !task.is_valid() {
+					return InvalidTransaction::Call.into()
+				}
+
+				ValidTransaction::with_tag_prefix("SystemDoTask")
+					.and_provides(task.encode())
+					.longevity(5)
+					.propagate(false)
+					.build()
+			} else {
+				UnknownTransaction::NoUnsignedValidator.into()
+			}
+		}
 	}
 
 	/// Exposed trait-generic origin type.
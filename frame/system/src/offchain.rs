@@ -34,6 +34,11 @@
 //! To submit a raw unsigned transaction, [`SubmitTransaction`](./struct.SubmitTransaction.html)
 //! can be used.
 //!
+//! ### Submit the valid tasks of a pallet
+//!
+//! To execute the tasks declared with `#[pallet::tasks]` which are currently valid,
+//! [`submit_valid_tasks`] can be used.
+//!
 //! ### Signing transactions
 //!
 //! To be able to use signing, the following trait should be implemented:
@@ -57,7 +62,7 @@
 #![warn(missing_docs)]
 
 use codec::Encode;
-use frame_support::RuntimeDebug;
+use frame_support::{traits::Task, RuntimeDebug};
 use sp_runtime::{
 	app_crypto::RuntimeAppPublic,
 	traits::{Extrinsic as ExtrinsicT, IdentifyAccount, One},
@@ -100,6 +105,29 @@ where
 	}
 }
 
+/// Submit an unsigned [`Call::do_task`](crate::Call::do_task) transaction for every task of
+/// `tasks` which is currently valid.
+///
+/// Pallets usually pass their own tasks converted into the `RuntimeTask`, e.g.
+/// `Task::<T>::iter().map(Into::into)`. Returns the number of submitted transactions.
+pub fn submit_valid_tasks<T>(tasks: impl IntoIterator<Item = T::RuntimeTask>) -> u32
+where
+	T: crate::Config + SendTransactionTypes<crate::Call<T>>,
+{
+	let mut submitted = 0u32;
+	for task in tasks.into_iter().filter(Task::is_valid) {
+		let call = crate::Call::<T>::do_task { task };
+		match SubmitTransaction::<T, crate::Call<T>>::submit_unsigned_transaction(call.into()) {
+			Ok(()) => submitted = submitted.saturating_add(1),
+			Err(()) => log::debug!(
+				target: "runtime::system",
+				"failed to submit a `do_task` transaction",
+			),
+		}
+	}
+	submitted
+}
+
 /// Provides an implementation for signing transaction payloads.
 ///
 /// Keys used for signing are defined when instantiating the signer object.
@@ -124,7 +124,7 @@ pub mod pallet {
 	use frame_system::pallet_prelude::*;
 
 	/// The pallet configuration trait
-	#[pallet::config]
+	#[pallet::config(with_default)]
 	pub trait Config: frame_system::Config {
 		/// Type used for expressing timestamp.
 		type Moment: Parameter
@@ -150,6 +150,30 @@ pub mod pallet {
 		type WeightInfo: WeightInfo;
 	}
 
+	/// Default configs of this pallet, to be used with
+	/// [`derive_impl`](`frame_support::derive_impl`).
+	pub mod config_preludes {
+		use super::DefaultConfig;
+		use frame_support::{derive_impl, register_default_impl, traits::ConstU64};
+
+		/// A default config for mock runtimes, with `u64` moments and a minimum period of 1.
+		pub struct TestDefaultConfig;
+
+		#[derive_impl(
+			frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig,
+			no_aggregated_types
+		)]
+		impl frame_system::DefaultConfig for TestDefaultConfig {}
+
+		#[register_default_impl(TestDefaultConfig)]
+		impl DefaultConfig for TestDefaultConfig {
+			type Moment = u64;
+			type OnTimestampSet = ();
+			type MinimumPeriod = ConstU64<1>;
+			type WeightInfo = ();
+		}
+	}
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
 	pub struct Pallet<T>(PhantomData<T>);
@@ -51,6 +51,11 @@
 //! - `tip_new` - Report an item worthy of a tip and declare a specific amount to tip.
 //! - `tip` - Declare or redeclare an amount to tip for a particular reason.
 //! - `close_tip` - Close and pay out a tip.
+//!
+//! ### Tasks
+//!
+//! - `CloseTip` - Close and pay out a tip once its countdown period is over. The offchain worker
+//!   submits these tasks to `frame_system::Pallet::do_task`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -68,6 +73,8 @@ use sp_std::prelude::*;
 
 use codec::{Decode, Encode};
 use frame_support::{
+	dispatch::DispatchResult,
+	ensure,
 	traits::{
 		ContainsLengthBound, Currency, EnsureOrigin, ExistenceRequirement::KeepAlive, Get,
 		OnUnbalanced, ReservableCurrency, SortedMembers,
@@ -113,7 +120,7 @@ pub struct OpenTip<
 pub mod pallet {
 	use super::*;
 	use frame_support::pallet_prelude::*;
-	use frame_system::pallet_prelude::*;
+	use frame_system::{offchain::SendTransactionTypes, pallet_prelude::*};
 
 	/// The current storage version.
 	const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
@@ -124,12 +131,28 @@ pub mod pallet {
 	#[pallet::without_storage_info]
 	pub struct Pallet<T, I = ()>(_);
 
+	#[pallet::extra_constants]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// The current storage version of the pallet.
+		#[pallet::constant_name(StorageVersion)]
+		fn storage_version() -> StorageVersion {
+			STORAGE_VERSION
+		}
+	}
+
 	#[pallet::config]
-	pub trait Config<I: 'static = ()>: frame_system::Config + pallet_treasury::Config<I> {
+	pub trait Config<I: 'static = ()>:
+		frame_system::Config
+		+ pallet_treasury::Config<I>
+		+ SendTransactionTypes<frame_system::Call<Self>>
+	{
 		/// The overarching event type.
 		type RuntimeEvent: From<Event<Self, I>>
 			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
+		/// The overarching task type.
+		type RuntimeTask: From<Task<Self, I>> + IsType<<Self as frame_system::Config>::RuntimeTask>;
+
 		/// Maximum acceptable reason length.
 		///
 		/// Benchmarks depend on this value, be sure to update weights file when changing this value
@@ -214,6 +237,57 @@ pub mod pallet {
 		Premature,
 	}
 
+	#[pallet::tasks]
+	pub enum Task<T: Config<I>, I: 'static = ()> {
+		/// Close and payout the tip `hash` once it has finished its countdown period.
+		CloseTip { hash: T::Hash },
+	}
+
+	impl<T: Config<I>, I: 'static> frame_support::traits::Task for Task<T, I> {
+		type Enumeration = sp_std::vec::IntoIter<Task<T, I>>;
+
+		fn iter() -> Self::Enumeration {
+			Tips::<T, I>::iter()
+				.filter(|(_, tip)| tip.closes.is_some())
+				.map(|(hash, _)| Task::CloseTip { hash })
+				.collect::<Vec<_>>()
+				.into_iter()
+		}
+
+		fn is_valid(&self) -> bool {
+			match self {
+				Task::CloseTip { hash } => Tips::<T, I>::get(hash)
+					.and_then(|tip| tip.closes)
+					.map_or(false, |n| frame_system::Pallet::<T>::block_number() >= n),
+				Task::__Ignore(_, never) => match *never {},
+			}
+		}
+
+		fn run(&self) -> DispatchResult {
+			match self {
+				Task::CloseTip { hash } => Pallet::<T, I>::do_close_tip(*hash),
+				Task::__Ignore(_, never) => match *never {},
+			}
+		}
+
+		fn weight(&self) -> Weight {
+			match self {
+				Task::CloseTip { .. } =>
+					<T as Config<I>>::WeightInfo::close_tip(T::Tippers::max_len() as u32),
+				Task::__Ignore(_, never) => match *never {},
+			}
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		fn offchain_worker(_n: T::BlockNumber) {
+			let tasks = <Task<T, I> as frame_support::traits::Task>::iter()
+				.map(|task| <T as Config<I>>::RuntimeTask::from(task).into());
+			frame_system::offchain::submit_valid_tasks::<T>(tasks);
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		/// Report something `reason` that deserves a tip and claim any eventual the finder's fee.
@@ -421,14 +495,7 @@ pub mod pallet {
 		pub fn close_tip(origin: OriginFor<T>, hash: T::Hash) -> DispatchResult {
 			ensure_signed(origin)?;
 
-			let tip = Tips::<T, I>::get(hash).ok_or(Error::<T, I>::UnknownTip)?;
-			let n = tip.closes.as_ref().ok_or(Error::<T, I>::StillOpen)?;
-			ensure!(frame_system::Pallet::<T>::block_number() >= *n, Error::<T, I>::Premature);
-			// closed.
-			Reasons::<T, I>::remove(&tip.reason);
-			Tips::<T, I>::remove(hash);
-			Self::payout_tip(hash, tip);
-			Ok(())
+			Self::do_close_tip(hash)
 		}
 
 		/// Remove and slash an already-open tip.
@@ -519,6 +586,18 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		});
 	}
 
+	/// Close the tip `hash` and execute its payout, if it has finished its countdown period.
+	fn do_close_tip(hash: T::Hash) -> DispatchResult {
+		let tip = Tips::<T, I>::get(hash).ok_or(Error::<T, I>::UnknownTip)?;
+		let n = tip.closes.as_ref().ok_or(Error::<T, I>::StillOpen)?;
+		ensure!(frame_system::Pallet::<T>::block_number() >= *n, Error::<T, I>::Premature);
+		// closed.
+		Reasons::<T, I>::remove(&tip.reason);
+		Tips::<T, I>::remove(hash);
+		Self::payout_tip(hash, tip);
+		Ok(())
+	}
+
 	/// Execute the payout of a tip.
 	///
 	/// Up to three balance operations.
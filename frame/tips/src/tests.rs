@@ -19,7 +19,10 @@
 
 #![cfg(test)]
 
-use sp_core::H256;
+use sp_core::{
+	offchain::{testing::TestTransactionPoolExt, TransactionPoolExt},
+	H256,
+};
 use sp_runtime::{
 	testing::Header,
 	traits::{BadOrigin, BlakeTwo256, IdentityLookup},
@@ -32,7 +35,7 @@ use frame_support::{
 	pallet_prelude::GenesisBuild,
 	parameter_types,
 	storage::StoragePrefixedMap,
-	traits::{ConstU32, ConstU64, SortedMembers, StorageVersion},
+	traits::{ConstU32, ConstU64, Hooks, SortedMembers, StorageVersion},
 	PalletId,
 };
 
@@ -52,8 +55,8 @@ frame_support::construct_runtime!(
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Treasury: pallet_treasury::{Pallet, Call, Storage, Config, Event<T>},
 		Treasury1: pallet_treasury::<Instance1>::{Pallet, Call, Storage, Config, Event<T>},
-		Tips: pallet_tips::{Pallet, Call, Storage, Event<T>},
-		Tips1: pallet_tips::<Instance1>::{Pallet, Call, Storage, Event<T>},
+		Tips: pallet_tips::{Pallet, Call, Storage, Event<T>, Task},
+		Tips1: pallet_tips::<Instance1>::{Pallet, Call, Storage, Event<T>, Task},
 	}
 );
 
@@ -69,6 +72,7 @@ impl frame_system::Config for Test {
 	type Index = u64;
 	type BlockNumber = u64;
 	type RuntimeCall = RuntimeCall;
+	type RuntimeTask = RuntimeTask;
 	type Hash = H256;
 	type Hashing = BlakeTwo256;
 	type AccountId = u128; // u64 is not enough to hold bytes used to generate bounty account
@@ -177,6 +181,7 @@ impl Config for Test {
 	type TipReportDepositBase = ConstU64<1>;
 	type DataDepositPerByte = ConstU64<1>;
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeTask = RuntimeTask;
 	type WeightInfo = ();
 }
 
@@ -188,9 +193,20 @@ impl Config<Instance1> for Test {
 	type TipReportDepositBase = ConstU64<1>;
 	type DataDepositPerByte = ConstU64<1>;
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeTask = RuntimeTask;
 	type WeightInfo = ();
 }
 
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = Extrinsic;
+}
+
+pub type Extrinsic = sp_runtime::testing::TestXt<RuntimeCall, ()>;
+
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	let mut ext: sp_io::TestExternalities = GenesisConfig {
 		system: frame_system::GenesisConfig::default(),
@@ -321,6 +337,42 @@ fn close_tip_works() {
 	});
 }
 
+#[test]
+fn offchain_worker_submits_tips_to_close() {
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut ext = new_test_ext();
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	ext.execute_with(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_ok!(Tips::tip_new(RuntimeOrigin::signed(10), b"awesome.dot".to_vec(), 3, 10));
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(11), tip_hash(), 10));
+		assert_ok!(Tips::tip(RuntimeOrigin::signed(12), tip_hash(), 10));
+
+		// still in the countdown period.
+		Tips::offchain_worker(1);
+		assert!(pool_state.read().transactions.is_empty());
+
+		System::set_block_number(2);
+		Tips::offchain_worker(2);
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+
+		let task: RuntimeTask = Task::<Test>::CloseTip { hash: tip_hash() }.into();
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, None);
+		assert_eq!(
+			tx.call,
+			RuntimeCall::System(frame_system::Call::do_task { task: task.clone() })
+		);
+
+		assert_ok!(System::do_task(RuntimeOrigin::none(), task));
+		assert_eq!(Balances::free_balance(3), 10);
+		assert_eq!(last_event(), TipEvent::TipClosed { tip_hash: tip_hash(), who: 3, payout: 10 });
+		assert!(Tips::tips(tip_hash()).is_none());
+	});
+}
+
 #[test]
 fn slash_tip_works() {
 	new_test_ext().execute_with(|| {
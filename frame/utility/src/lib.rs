@@ -58,7 +58,9 @@ pub mod weights;
 
 use codec::{Decode, Encode};
 use frame_support::{
-	dispatch::{extract_actual_weight, GetDispatchInfo, PostDispatchInfo},
+	dispatch::{
+		extract_actual_weight, DispatchErrorWithPostInfo, GetDispatchInfo, PostDispatchInfo,
+	},
 	traits::{IsSubType, OriginTrait, UnfilteredDispatchable},
 };
 use sp_core::TypeId;
@@ -99,6 +101,24 @@ pub mod pallet {
 			Into<<Self as frame_system::Config>::RuntimeOrigin> +
 			IsType<<<Self as frame_system::Config>::RuntimeOrigin as frame_support::traits::OriginTrait>::PalletsOrigin>;
 
+		/// The maximum number of storage keys the calls of a `batch_all` may change, or `None`
+		/// for no limit.
+		///
+		/// A failing `batch_all` rolls back the changes of all of its calls, so this bounds the
+		/// cost of that rollback. The limit is checked with the `transaction_changes` host
+		/// function, so it must be `None` as long as the clients executing the runtime don't
+		/// provide it.
+		///
+		/// This is a soft bound. The changes are only counted after each call, so the calls may
+		/// exceed it by the changes of the call that crosses it. The last call is not checked,
+		/// since no later call can fail and roll it back.
+		#[pallet::constant]
+		type MaxBatchAllChanges: Get<Option<u32>>;
+
+		/// The maximum number of nested storage layers each call of a `batch_all` may open.
+		#[pallet::constant]
+		type MaxBatchAllLayers: Get<u32>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -158,6 +178,9 @@ pub mod pallet {
 	pub enum Error<T> {
 		/// Too many calls batched.
 		TooManyCalls,
+		/// The calls of a `batch_all` before the last one changed more storage keys than
+		/// `MaxBatchAllChanges`.
+		TooManyChanges,
 	}
 
 	#[pallet::call]
@@ -337,29 +360,44 @@ pub mod pallet {
 			let mut weight = Weight::zero();
 			for (index, call) in calls.into_iter().enumerate() {
 				let info = call.get_dispatch_info();
-				// If origin is root, bypass any dispatch filter; root can call anything.
-				let result = if is_root {
-					call.dispatch_bypass_filter(origin.clone())
-				} else {
-					let mut filtered_origin = origin.clone();
-					// Don't allow users to nest `batch_all` calls.
-					filtered_origin.add_filter(
-						move |c: &<T as frame_system::Config>::RuntimeCall| {
-							let c = <T as Config>::RuntimeCall::from_ref(c);
-							!matches!(c.is_sub_type(), Some(Call::batch_all { .. }))
-						},
-					);
-					call.dispatch(filtered_origin)
-				};
+				// Bound the number of storage layers the call may open.
+				let max_layers = T::MaxBatchAllLayers::get();
+				let result = storage::transactional::with_transactional_limit(max_layers, || {
+					// If origin is root, bypass any dispatch filter; root can call anything.
+					if is_root {
+						call.dispatch_bypass_filter(origin.clone())
+					} else {
+						let mut filtered_origin = origin.clone();
+						// Don't allow users to nest `batch_all` calls.
+						filtered_origin.add_filter(
+							move |c: &<T as frame_system::Config>::RuntimeCall| {
+								let c = <T as Config>::RuntimeCall::from_ref(c);
+								!matches!(c.is_sub_type(), Some(Call::batch_all { .. }))
+							},
+						);
+						call.dispatch(filtered_origin)
+					}
+				});
 				// Add the weight of this call.
 				weight = weight.saturating_add(extract_actual_weight(&result, &info));
+				// Take the weight of this function itself into account.
+				let base_weight = T::WeightInfo::batch_all(index.saturating_add(1) as u32);
 				result.map_err(|mut err| {
-					// Take the weight of this function itself into account.
-					let base_weight = T::WeightInfo::batch_all(index.saturating_add(1) as u32);
 					// Return the actual used weight + base_weight of this call.
 					err.post_info = Some(base_weight + weight).into();
 					err
 				})?;
+				// Everything changed so far is rolled back if a later call fails, there is no later
+				// call after the last one.
+				let is_last = index.saturating_add(1) == calls_len;
+				if let Some(max_changes) = T::MaxBatchAllChanges::get().filter(|_| !is_last) {
+					if storage::transactional::transaction_changes() > max_changes {
+						return Err(DispatchErrorWithPostInfo {
+							post_info: Some(base_weight + weight).into(),
+							error: Error::<T>::TooManyChanges.into(),
+						})
+					}
+				}
 				Self::deposit_event(Event::ItemCompleted);
 			}
 			Self::deposit_event(Event::BatchCompleted);
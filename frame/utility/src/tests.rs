@@ -35,6 +35,7 @@ use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, Hash, IdentityLookup},
+	TransactionalError,
 };
 
 type BlockNumber = u64;
@@ -155,6 +156,7 @@ impl frame_system::Config for Test {
 	type BlockNumber = u64;
 	type Hash = H256;
 	type RuntimeCall = RuntimeCall;
+	type RuntimeTask = RuntimeTask;
 	type Hashing = BlakeTwo256;
 	type AccountId = u64;
 	type Lookup = IdentityLookup<Self::AccountId>;
@@ -238,10 +240,17 @@ impl mock_democracy::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type ExternalMajorityOrigin = EnsureProportionAtLeast<u64, Instance1, 3, 4>;
 }
+parameter_types! {
+	pub static MaxBatchAllChanges: Option<u32> = Some(100);
+	pub static MaxBatchAllLayers: u32 = 10;
+}
+
 impl Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
 	type PalletsOrigin = OriginCaller;
+	type MaxBatchAllChanges = MaxBatchAllChanges;
+	type MaxBatchAllLayers = MaxBatchAllLayers;
 	type WeightInfo = ();
 }
 
@@ -603,6 +612,55 @@ fn batch_all_revert() {
 	});
 }
 
+#[test]
+fn batch_all_limits_changes() {
+	new_test_ext().execute_with(|| {
+		let calls: Vec<_> = (2..=6).map(|dest| call_transfer(dest, 1)).collect();
+
+		// The transfers alone change the accounts of the sender and of five receivers.
+		MaxBatchAllChanges::set(Some(5));
+		assert_err_ignore_postinfo!(
+			Utility::batch_all(RuntimeOrigin::signed(1), calls.clone()),
+			Error::<Test>::TooManyChanges
+		);
+		assert_eq!(Balances::free_balance(1), 10);
+		assert_eq!(Balances::free_balance(6), 0);
+
+		// The changes of the last call are not checked, as no later call can fail.
+		MaxBatchAllChanges::set(Some(0));
+		assert_ok!(Utility::batch_all(RuntimeOrigin::signed(1), vec![call_transfer(2, 1)]));
+		assert_eq!(Balances::free_balance(2), 11);
+
+		MaxBatchAllChanges::set(None);
+		assert_ok!(Utility::batch_all(RuntimeOrigin::signed(1), calls));
+		assert_eq!(Balances::free_balance(1), 4);
+		assert_eq!(Balances::free_balance(6), 1);
+	});
+}
+
+#[test]
+fn batch_all_limits_storage_layers() {
+	new_test_ext().execute_with(|| {
+		// Dispatching a call opens a storage layer.
+		MaxBatchAllLayers::set(0);
+		assert_err_ignore_postinfo!(
+			Utility::batch_all(RuntimeOrigin::signed(1), vec![call_transfer(2, 1)]),
+			DispatchError::Transactional(TransactionalError::LimitReached)
+		);
+		assert_eq!(Balances::free_balance(1), 10);
+
+		MaxBatchAllLayers::set(1);
+		assert_ok!(Utility::batch_all(RuntimeOrigin::signed(1), vec![call_transfer(2, 1)]));
+		assert_eq!(Balances::free_balance(1), 9);
+
+		// The limit is restored after the batch.
+		assert_eq!(
+			storage::transactional::transactional_limit(),
+			storage::transactional::TRANSACTIONAL_LIMIT
+		);
+	});
+}
+
 #[test]
 fn batch_all_handles_weight_refund() {
 	new_test_ext().execute_with(|| {
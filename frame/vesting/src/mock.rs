@@ -56,6 +56,7 @@ impl frame_system::Config for Test {
 	type BlockNumber = u64;
 	type BlockWeights = ();
 	type RuntimeCall = RuntimeCall;
+	type RuntimeTask = RuntimeTask;
 	type DbWeight = ();
 	type RuntimeEvent = RuntimeEvent;
 	type Hash = H256;
@@ -0,0 +1,33 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2019-2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! BLS12-377 crypto types.
+//!
+//! There are no host functions for BLS12-377 yet, so the keys don't implement
+//! [`RuntimeAppPublic`](crate::RuntimeAppPublic) and can only be used on the client side.
+
+pub use sp_core::bls::bls377::*;
+
+mod app {
+	use sp_core::testing::BLS377;
+
+	crate::app_crypto_without_runtime!(super, BLS377);
+}
+
+#[cfg(feature = "full_crypto")]
+pub use app::Pair as AppPair;
+pub use app::{Public as AppPublic, Signature as AppSignature};
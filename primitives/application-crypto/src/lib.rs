@@ -44,6 +44,12 @@ pub use serde;
 #[doc(hidden)]
 pub use sp_std::{ops::Deref, vec::Vec};
 
+#[cfg(feature = "bandersnatch-experimental")]
+pub mod bandersnatch;
+#[cfg(feature = "bls-experimental")]
+pub mod bls377;
+#[cfg(feature = "bls-experimental")]
+pub mod bls381;
 pub mod ecdsa;
 pub mod ed25519;
 pub mod sr25519;
@@ -60,17 +66,28 @@ pub use traits::*;
 /// // of value `b"fuba"`.
 /// app_crypto!(ed25519, KeyTypeId(*b"_uba"));
 /// ```
-#[cfg(feature = "full_crypto")]
 #[macro_export]
 macro_rules! app_crypto {
 	($module:ident, $key_type:expr) => {
-		$crate::app_crypto_public_full_crypto!($module::Public, $key_type, $module::CRYPTO_ID);
-		$crate::app_crypto_public_common!(
+		$crate::app_crypto_without_runtime!($module, $key_type);
+		$crate::app_crypto_public_runtime!(
 			$module::Public,
 			$module::Signature,
 			$key_type,
 			$module::CRYPTO_ID
 		);
+	};
+}
+
+/// Same as [`app_crypto`], but doesn't implement [`RuntimeAppPublic`] for the public key.
+///
+/// This is for crypto that has no host functions yet, so it can only be used on the client side.
+#[cfg(feature = "full_crypto")]
+#[macro_export]
+macro_rules! app_crypto_without_runtime {
+	($module:ident, $key_type:expr) => {
+		$crate::app_crypto_public_full_crypto!($module::Public, $key_type, $module::CRYPTO_ID);
+		$crate::app_crypto_public_common!($module::Public, $key_type, $module::CRYPTO_ID);
 		$crate::app_crypto_signature_full_crypto!(
 			$module::Signature,
 			$key_type,
@@ -81,26 +98,15 @@ macro_rules! app_crypto {
 	};
 }
 
-/// Declares Public, Pair, Signature types which are functionally equivalent to `$pair`, but are new
-/// Application-specific types whose identifier is `$key_type`.
+/// Same as [`app_crypto`], but doesn't implement [`RuntimeAppPublic`] for the public key.
 ///
-/// ```rust
-/// # use sp_application_crypto::{app_crypto, wrap, ed25519, KeyTypeId};
-/// // Declare a new set of crypto types using Ed25519 logic that identifies as `KeyTypeId`
-/// // of value `b"fuba"`.
-/// app_crypto!(ed25519, KeyTypeId(*b"_uba"));
-/// ```
+/// This is for crypto that has no host functions yet, so it can only be used on the client side.
 #[cfg(not(feature = "full_crypto"))]
 #[macro_export]
-macro_rules! app_crypto {
+macro_rules! app_crypto_without_runtime {
 	($module:ident, $key_type:expr) => {
 		$crate::app_crypto_public_not_full_crypto!($module::Public, $key_type, $module::CRYPTO_ID);
-		$crate::app_crypto_public_common!(
-			$module::Public,
-			$module::Signature,
-			$key_type,
-			$module::CRYPTO_ID
-		);
+		$crate::app_crypto_public_common!($module::Public, $key_type, $module::CRYPTO_ID);
 		$crate::app_crypto_signature_not_full_crypto!(
 			$module::Signature,
 			$key_type,
@@ -289,7 +295,7 @@ macro_rules! app_crypto_public_not_full_crypto {
 #[doc(hidden)]
 #[macro_export]
 macro_rules! app_crypto_public_common {
-	($public:ty, $sig:ty, $key_type:expr, $crypto_type:expr) => {
+	($public:ty, $key_type:expr, $crypto_type:expr) => {
 		$crate::app_crypto_public_common_if_std!();
 
 		impl AsRef<[u8]> for Public {
@@ -317,6 +323,33 @@ macro_rules! app_crypto_public_common {
 			type Generic = $public;
 		}
 
+		impl From<Public> for $crate::CryptoTypePublicPair {
+			fn from(key: Public) -> Self {
+				(&key).into()
+			}
+		}
+
+		impl From<&Public> for $crate::CryptoTypePublicPair {
+			fn from(key: &Public) -> Self {
+				$crate::CryptoTypePublicPair($crypto_type, $crate::ByteArray::to_raw_vec(key))
+			}
+		}
+
+		impl<'a> TryFrom<&'a [u8]> for Public {
+			type Error = ();
+
+			fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+				<$public>::try_from(data).map(Into::into)
+			}
+		}
+	};
+}
+
+/// Implements [`RuntimeAppPublic`] for the Public type declared by `app_crypto_public_common!`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! app_crypto_public_runtime {
+	($public:ty, $sig:ty, $key_type:expr, $crypto_type:expr) => {
 		impl $crate::RuntimeAppPublic for Public
 		where
 			$public: $crate::RuntimePublic<Signature = $sig>,
@@ -350,26 +383,6 @@ macro_rules! app_crypto_public_common {
 				<$public as $crate::RuntimePublic>::to_raw_vec(&self.0)
 			}
 		}
-
-		impl From<Public> for $crate::CryptoTypePublicPair {
-			fn from(key: Public) -> Self {
-				(&key).into()
-			}
-		}
-
-		impl From<&Public> for $crate::CryptoTypePublicPair {
-			fn from(key: &Public) -> Self {
-				$crate::CryptoTypePublicPair($crypto_type, $crate::ByteArray::to_raw_vec(key))
-			}
-		}
-
-		impl<'a> TryFrom<&'a [u8]> for Public {
-			type Error = ();
-
-			fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-				<$public>::try_from(data).map(Into::into)
-			}
-		}
 	};
 }
 
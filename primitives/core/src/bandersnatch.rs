@@ -0,0 +1,784 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bandersnatch Schnorr signatures and VRFs.
+//!
+//! Bandersnatch is a twisted Edwards curve defined over the scalar field of BLS12-381, which
+//! makes its keys usable in ring VRF proofs over BLS12-381. Keys sign with Schnorr signatures and
+//! produce VRF outputs that are proven with a DLEQ proof, see the [`vrf`] module.
+//!
+//! This module is experimental and only available with the `bandersnatch-experimental` feature.
+
+#[cfg(feature = "std")]
+use crate::crypto::Ss58Codec;
+use crate::crypto::{
+	ByteArray, CryptoType, CryptoTypeId, CryptoTypePublicPair, Derive, Public as TraitPublic,
+	UncheckedFrom,
+};
+#[cfg(feature = "full_crypto")]
+use crate::crypto::{DeriveJunction, Pair as TraitPair, SecretStringError};
+
+#[cfg(feature = "full_crypto")]
+use ark_ec::{AffineRepr, CurveGroup};
+#[cfg(feature = "full_crypto")]
+use ark_ed_on_bls12_381_bandersnatch::{EdwardsAffine, Fr};
+#[cfg(feature = "full_crypto")]
+use ark_ff::PrimeField;
+#[cfg(feature = "full_crypto")]
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+#[cfg(feature = "std")]
+use bip39::{Language, Mnemonic, MnemonicType};
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use sp_runtime_interface::pass_by::PassByInner;
+#[cfg(feature = "full_crypto")]
+use sp_std::vec::Vec;
+use sp_std::{convert::TryFrom, ops::Deref};
+#[cfg(feature = "std")]
+use substrate_bip39::seed_from_entropy;
+
+/// An identifier used to match public keys against bandersnatch keys
+pub const CRYPTO_ID: CryptoTypeId = CryptoTypeId(*b"band");
+
+/// The byte length of a public key and of a VRF output.
+pub const PUBLIC_SERIALIZED_LEN: usize = 32;
+
+/// The byte length of a signature and of a VRF proof.
+pub const SIGNATURE_SERIALIZED_LEN: usize = 64;
+
+/// The byte length of a scalar.
+#[cfg(feature = "full_crypto")]
+const SCALAR_SERIALIZED_LEN: usize = 32;
+
+/// A secret seed.
+#[cfg(feature = "full_crypto")]
+type Seed = [u8; 32];
+
+/// A public key.
+#[cfg_attr(feature = "full_crypto", derive(Hash))]
+#[derive(
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Clone,
+	Copy,
+	Encode,
+	Decode,
+	PassByInner,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+pub struct Public(pub [u8; PUBLIC_SERIALIZED_LEN]);
+
+impl AsRef<[u8; PUBLIC_SERIALIZED_LEN]> for Public {
+	fn as_ref(&self) -> &[u8; PUBLIC_SERIALIZED_LEN] {
+		&self.0
+	}
+}
+
+impl AsRef<[u8]> for Public {
+	fn as_ref(&self) -> &[u8] {
+		&self.0[..]
+	}
+}
+
+impl AsMut<[u8]> for Public {
+	fn as_mut(&mut self) -> &mut [u8] {
+		&mut self.0[..]
+	}
+}
+
+impl Deref for Public {
+	type Target = [u8];
+
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl TryFrom<&[u8]> for Public {
+	type Error = ();
+
+	fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+		if data.len() != Self::LEN {
+			return Err(())
+		}
+		let mut r = [0u8; PUBLIC_SERIALIZED_LEN];
+		r.copy_from_slice(data);
+		Ok(Self::unchecked_from(r))
+	}
+}
+
+impl From<Public> for [u8; PUBLIC_SERIALIZED_LEN] {
+	fn from(x: Public) -> Self {
+		x.0
+	}
+}
+
+#[cfg(feature = "full_crypto")]
+impl From<Pair> for Public {
+	fn from(x: Pair) -> Self {
+		x.public()
+	}
+}
+
+impl UncheckedFrom<[u8; PUBLIC_SERIALIZED_LEN]> for Public {
+	fn unchecked_from(x: [u8; PUBLIC_SERIALIZED_LEN]) -> Self {
+		Public(x)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::str::FromStr for Public {
+	type Err = crate::crypto::PublicError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::from_ss58check(s)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for Public {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "{}", self.to_ss58check())
+	}
+}
+
+impl sp_std::fmt::Debug for Public {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		let s = self.to_ss58check();
+		write!(f, "{} ({}...)", crate::hexdisplay::HexDisplay::from(&self.0), &s[0..8])
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+#[cfg(feature = "std")]
+impl Serialize for Public {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_str(&self.to_ss58check())
+	}
+}
+
+#[cfg(feature = "std")]
+impl<'de> Deserialize<'de> for Public {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		Public::from_ss58check(&String::deserialize(deserializer)?)
+			.map_err(|e| de::Error::custom(format!("{:?}", e)))
+	}
+}
+
+impl ByteArray for Public {
+	const LEN: usize = PUBLIC_SERIALIZED_LEN;
+}
+
+impl TraitPublic for Public {
+	fn to_public_crypto_pair(&self) -> CryptoTypePublicPair {
+		CryptoTypePublicPair(CRYPTO_ID, self.to_raw_vec())
+	}
+}
+
+impl Derive for Public {}
+
+impl From<Public> for CryptoTypePublicPair {
+	fn from(key: Public) -> Self {
+		(&key).into()
+	}
+}
+
+impl From<&Public> for CryptoTypePublicPair {
+	fn from(key: &Public) -> Self {
+		CryptoTypePublicPair(CRYPTO_ID, key.to_raw_vec())
+	}
+}
+
+impl CryptoType for Public {
+	#[cfg(feature = "full_crypto")]
+	type Pair = Pair;
+}
+
+/// A Schnorr signature, the commitment point followed by the response scalar.
+#[cfg_attr(feature = "full_crypto", derive(Hash))]
+#[derive(Clone, Encode, Decode, MaxEncodedLen, PassByInner, TypeInfo, PartialEq, Eq)]
+pub struct Signature(pub [u8; SIGNATURE_SERIALIZED_LEN]);
+
+impl TryFrom<&[u8]> for Signature {
+	type Error = ();
+
+	fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+		if data.len() != SIGNATURE_SERIALIZED_LEN {
+			return Err(())
+		}
+		let mut inner = [0u8; SIGNATURE_SERIALIZED_LEN];
+		inner.copy_from_slice(data);
+		Ok(Signature(inner))
+	}
+}
+
+#[cfg(feature = "std")]
+impl Serialize for Signature {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_str(&array_bytes::bytes2hex("", self.as_ref()))
+	}
+}
+
+#[cfg(feature = "std")]
+impl<'de> Deserialize<'de> for Signature {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let signature_hex = array_bytes::hex2bytes(&String::deserialize(deserializer)?)
+			.map_err(|e| de::Error::custom(format!("{:?}", e)))?;
+		Signature::try_from(signature_hex.as_ref())
+			.map_err(|e| de::Error::custom(format!("{:?}", e)))
+	}
+}
+
+impl From<Signature> for [u8; SIGNATURE_SERIALIZED_LEN] {
+	fn from(v: Signature) -> [u8; SIGNATURE_SERIALIZED_LEN] {
+		v.0
+	}
+}
+
+impl AsRef<[u8]> for Signature {
+	fn as_ref(&self) -> &[u8] {
+		&self.0[..]
+	}
+}
+
+impl AsMut<[u8]> for Signature {
+	fn as_mut(&mut self) -> &mut [u8] {
+		&mut self.0[..]
+	}
+}
+
+impl sp_std::fmt::Debug for Signature {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "{}", crate::hexdisplay::HexDisplay::from(&self.0))
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl UncheckedFrom<[u8; SIGNATURE_SERIALIZED_LEN]> for Signature {
+	fn unchecked_from(data: [u8; SIGNATURE_SERIALIZED_LEN]) -> Signature {
+		Signature(data)
+	}
+}
+
+impl CryptoType for Signature {
+	#[cfg(feature = "full_crypto")]
+	type Pair = Pair;
+}
+
+/// Hashes `data` to a scalar, with `domain` as domain separator.
+#[cfg(feature = "full_crypto")]
+fn hash_to_scalar(domain: &[u8], data: impl Encode) -> Fr {
+	(domain, data)
+		.using_encoded(|encoded| Fr::from_le_bytes_mod_order(&sp_core_hashing::blake2_512(encoded)))
+}
+
+#[cfg(feature = "full_crypto")]
+fn point_to_bytes(point: &EdwardsAffine) -> [u8; PUBLIC_SERIALIZED_LEN] {
+	let mut bytes = [0u8; PUBLIC_SERIALIZED_LEN];
+	point
+		.serialize_compressed(&mut bytes[..])
+		.expect("Compressed points are 32 bytes long; qed");
+	bytes
+}
+
+/// Decodes a point, checking that it is in the prime order subgroup.
+#[cfg(feature = "full_crypto")]
+fn point_from_bytes(bytes: &[u8]) -> Option<EdwardsAffine> {
+	EdwardsAffine::deserialize_compressed(bytes).ok()
+}
+
+#[cfg(feature = "full_crypto")]
+fn scalar_to_bytes(scalar: &Fr) -> [u8; SCALAR_SERIALIZED_LEN] {
+	let mut bytes = [0u8; SCALAR_SERIALIZED_LEN];
+	scalar
+		.serialize_compressed(&mut bytes[..])
+		.expect("Scalars are 32 bytes long; qed");
+	bytes
+}
+
+#[cfg(feature = "full_crypto")]
+fn scalar_from_bytes(bytes: &[u8]) -> Option<Fr> {
+	Fr::deserialize_compressed(bytes).ok()
+}
+
+/// A key pair.
+#[cfg(feature = "full_crypto")]
+#[derive(Clone)]
+pub struct Pair {
+	secret: Fr,
+	public: Public,
+	seed: Seed,
+}
+
+/// Derive a single hard junction.
+#[cfg(feature = "full_crypto")]
+fn derive_hard_junction(secret_seed: &Seed, cc: &[u8; 32]) -> Seed {
+	("BandersnatchHDKD", secret_seed, cc).using_encoded(sp_core_hashing::blake2_256)
+}
+
+/// An error when deriving a key.
+#[cfg(feature = "full_crypto")]
+pub enum DeriveError {
+	/// A soft key was found in the path (and is unsupported).
+	SoftKeyInPath,
+}
+
+#[cfg(feature = "full_crypto")]
+impl TraitPair for Pair {
+	type Public = Public;
+	type Seed = Seed;
+	type Signature = Signature;
+	type DeriveError = DeriveError;
+
+	/// Generate new secure (random) key pair and provide the recovery phrase.
+	///
+	/// You can recover the same key later with `from_phrase`.
+	#[cfg(feature = "std")]
+	fn generate_with_phrase(password: Option<&str>) -> (Pair, String, Seed) {
+		let mnemonic = Mnemonic::new(MnemonicType::Words12, Language::English);
+		let phrase = mnemonic.phrase();
+		let (pair, seed) = Self::from_phrase(phrase, password)
+			.expect("All phrases generated by Mnemonic are valid; qed");
+		(pair, phrase.to_owned(), seed)
+	}
+
+	/// Generate key pair from given recovery phrase and password.
+	#[cfg(feature = "std")]
+	fn from_phrase(
+		phrase: &str,
+		password: Option<&str>,
+	) -> Result<(Pair, Seed), SecretStringError> {
+		let big_seed = seed_from_entropy(
+			Mnemonic::from_phrase(phrase, Language::English)
+				.map_err(|_| SecretStringError::InvalidPhrase)?
+				.entropy(),
+			password.unwrap_or(""),
+		)
+		.map_err(|_| SecretStringError::InvalidSeed)?;
+		let mut seed = Seed::default();
+		seed.copy_from_slice(&big_seed[0..32]);
+		Self::from_seed_slice(&big_seed[0..32]).map(|x| (x, seed))
+	}
+
+	/// Make a new key pair from secret seed material.
+	///
+	/// You should never need to use this; generate(), generate_with_phrase
+	fn from_seed(seed: &Seed) -> Pair {
+		Self::from_seed_slice(&seed[..]).expect("seed has valid length; qed")
+	}
+
+	/// Make a new key pair from secret seed material. The slice must be 32 bytes long or it
+	/// will return `None`.
+	///
+	/// You should never need to use this; generate(), generate_with_phrase
+	fn from_seed_slice(seed_slice: &[u8]) -> Result<Pair, SecretStringError> {
+		let seed = Seed::try_from(seed_slice).map_err(|_| SecretStringError::InvalidSeedLength)?;
+		let secret = hash_to_scalar(b"BandersnatchSecret", seed);
+		let public = Public(point_to_bytes(&(EdwardsAffine::generator() * secret).into_affine()));
+		Ok(Pair { secret, public, seed })
+	}
+
+	/// Derive a child key from a series of given junctions.
+	fn derive<Iter: Iterator<Item = DeriveJunction>>(
+		&self,
+		path: Iter,
+		_seed: Option<Seed>,
+	) -> Result<(Pair, Option<Seed>), DeriveError> {
+		let mut acc = self.seed;
+		for j in path {
+			match j {
+				DeriveJunction::Soft(_cc) => return Err(DeriveError::SoftKeyInPath),
+				DeriveJunction::Hard(cc) => acc = derive_hard_junction(&acc, &cc),
+			}
+		}
+		Ok((Self::from_seed(&acc), Some(acc)))
+	}
+
+	/// Get the public key.
+	fn public(&self) -> Public {
+		self.public
+	}
+
+	/// Sign a message.
+	fn sign(&self, message: &[u8]) -> Signature {
+		let nonce = hash_to_scalar(b"BandersnatchNonce", (&self.seed, message));
+		let commitment = point_to_bytes(&(EdwardsAffine::generator() * nonce).into_affine());
+		let challenge = hash_to_scalar(
+			b"BandersnatchSignature",
+			(&commitment[..], &self.public.0[..], message),
+		);
+		let response = nonce + challenge * self.secret;
+
+		let mut signature = [0u8; SIGNATURE_SERIALIZED_LEN];
+		signature[..PUBLIC_SERIALIZED_LEN].copy_from_slice(&commitment[..]);
+		signature[PUBLIC_SERIALIZED_LEN..].copy_from_slice(&scalar_to_bytes(&response));
+		Signature(signature)
+	}
+
+	/// Verify a signature on a message. Returns true if the signature is good.
+	fn verify<M: AsRef<[u8]>>(sig: &Self::Signature, message: M, pubkey: &Self::Public) -> bool {
+		Self::verify_weak(&sig.0[..], message.as_ref(), pubkey)
+	}
+
+	/// Verify a signature on a message. Returns true if the signature is good.
+	///
+	/// This doesn't use the type system to ensure that `sig` and `pubkey` are the correct
+	/// size. Use it only if you're coming from byte buffers and need the speed.
+	fn verify_weak<P: AsRef<[u8]>, M: AsRef<[u8]>>(sig: &[u8], message: M, pubkey: P) -> bool {
+		if sig.len() != SIGNATURE_SERIALIZED_LEN {
+			return false
+		}
+		let (commitment_bytes, response) = sig.split_at(PUBLIC_SERIALIZED_LEN);
+		let (Some(public), Some(commitment), Some(response)) = (
+			point_from_bytes(pubkey.as_ref()),
+			point_from_bytes(commitment_bytes),
+			scalar_from_bytes(response),
+		) else {
+			return false
+		};
+
+		let challenge = hash_to_scalar(
+			b"BandersnatchSignature",
+			(commitment_bytes, pubkey.as_ref(), message.as_ref()),
+		);
+		EdwardsAffine::generator() * response == commitment + public * challenge
+	}
+
+	/// Return a vec filled with raw data.
+	fn to_raw_vec(&self) -> Vec<u8> {
+		self.seed.to_vec()
+	}
+}
+
+#[cfg(feature = "full_crypto")]
+impl Pair {
+	/// Get the seed for this key.
+	pub fn seed(&self) -> Seed {
+		self.seed
+	}
+}
+
+#[cfg(feature = "full_crypto")]
+impl CryptoType for Pair {
+	type Pair = Pair;
+}
+
+/// Verifiable random function with bandersnatch keys.
+///
+/// The VRF output of an input point `I` is `O = sk * I`; the proof is a Chaum-Pedersen DLEQ
+/// proof showing that `O` and the public key have the same discrete logarithm. The proof
+/// additionally signs the `extra` data of the [`VrfSignData`].
+pub mod vrf {
+	use super::*;
+
+	/// The output of a VRF evaluation, a compressed curve point.
+	///
+	/// Use [`VrfOutput::make_bytes`] to derive randomness from it.
+	#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+	pub struct VrfOutput(pub [u8; PUBLIC_SERIALIZED_LEN]);
+
+	impl sp_std::fmt::Debug for VrfOutput {
+		#[cfg(feature = "std")]
+		fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+			write!(f, "{}", crate::hexdisplay::HexDisplay::from(&self.0))
+		}
+
+		#[cfg(not(feature = "std"))]
+		fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+			Ok(())
+		}
+	}
+
+	/// A VRF output together with the proof that it was correctly computed.
+	#[derive(Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(Debug))]
+	pub struct VrfSignature {
+		/// The VRF output.
+		pub output: VrfOutput,
+		/// The DLEQ proof, the challenge scalar followed by the response scalar.
+		pub proof: [u8; SIGNATURE_SERIALIZED_LEN],
+	}
+
+	/// The input of a VRF evaluation, a curve point in the prime order subgroup.
+	#[cfg(feature = "full_crypto")]
+	#[derive(Clone, PartialEq, Eq)]
+	pub struct VrfInput(EdwardsAffine);
+
+	#[cfg(feature = "full_crypto")]
+	impl VrfInput {
+		/// Hashes the `data` to a VRF input, using `domain` as domain separator.
+		pub fn new(domain: impl AsRef<[u8]>, data: impl AsRef<[u8]>) -> Self {
+			// Try and increment: about every second hash is the `y` coordinate of a curve point.
+			(0u32..)
+				.find_map(|counter| {
+					let hash = (b"BandersnatchVrfInput", domain.as_ref(), data.as_ref(), counter)
+						.using_encoded(sp_core_hashing::blake2_256);
+					let point = EdwardsAffine::deserialize_compressed_unchecked(&hash[..])
+						.ok()?
+						.clear_cofactor();
+					(!point.is_zero()).then_some(point)
+				})
+				.map(VrfInput)
+				.expect("A point is found after a few attempts with overwhelming probability; qed")
+		}
+
+		fn to_bytes(&self) -> [u8; PUBLIC_SERIALIZED_LEN] {
+			point_to_bytes(&self.0)
+		}
+	}
+
+	/// Everything that is signed by a VRF signature: the VRF input and some extra data.
+	#[cfg(feature = "full_crypto")]
+	#[derive(Clone)]
+	pub struct VrfSignData {
+		/// The VRF input.
+		pub input: VrfInput,
+		/// Extra data that is signed together with the VRF output.
+		pub extra: Vec<u8>,
+	}
+
+	#[cfg(feature = "full_crypto")]
+	impl VrfSignData {
+		/// Create new data to sign.
+		pub fn new(input: VrfInput, extra: impl Into<Vec<u8>>) -> Self {
+			Self { input, extra: extra.into() }
+		}
+	}
+
+	#[cfg(feature = "full_crypto")]
+	impl VrfOutput {
+		/// Derives 32 bytes of randomness from the output of the VRF evaluation of `input`.
+		///
+		/// The `context` allows to derive independent randomness from the same output.
+		pub fn make_bytes(&self, context: &[u8], input: &VrfInput) -> [u8; 32] {
+			(b"BandersnatchVrfOutput", context, input.to_bytes(), &self.0)
+				.using_encoded(sp_core_hashing::blake2_256)
+		}
+	}
+
+	#[cfg(feature = "full_crypto")]
+	fn challenge(
+		public: &Public,
+		data: &VrfSignData,
+		output: &VrfOutput,
+		commitments: (&EdwardsAffine, &EdwardsAffine),
+	) -> Fr {
+		hash_to_scalar(
+			b"BandersnatchVrfProof",
+			(
+				&public.0,
+				data.input.to_bytes(),
+				&output.0,
+				point_to_bytes(commitments.0),
+				point_to_bytes(commitments.1),
+				&data.extra,
+			),
+		)
+	}
+
+	#[cfg(feature = "full_crypto")]
+	impl Pair {
+		/// Evaluates the VRF on `input`, without proving the output.
+		pub fn vrf_output(&self, input: &VrfInput) -> VrfOutput {
+			VrfOutput(point_to_bytes(&(input.0 * self.secret).into_affine()))
+		}
+
+		/// Evaluates the VRF on the input of `data` and proves the output.
+		pub fn vrf_sign(&self, data: &VrfSignData) -> VrfSignature {
+			let output = self.vrf_output(&data.input);
+			let nonce = hash_to_scalar(
+				b"BandersnatchVrfNonce",
+				(&self.seed, data.input.to_bytes(), &data.extra),
+			);
+			let challenge = challenge(
+				&self.public,
+				data,
+				&output,
+				(
+					&(EdwardsAffine::generator() * nonce).into_affine(),
+					&(data.input.0 * nonce).into_affine(),
+				),
+			);
+			let response = nonce + challenge * self.secret;
+
+			let mut proof = [0u8; SIGNATURE_SERIALIZED_LEN];
+			proof[..SCALAR_SERIALIZED_LEN].copy_from_slice(&scalar_to_bytes(&challenge));
+			proof[SCALAR_SERIALIZED_LEN..].copy_from_slice(&scalar_to_bytes(&response));
+			VrfSignature { output, proof }
+		}
+	}
+
+	#[cfg(feature = "full_crypto")]
+	impl Public {
+		/// Verifies that `signature` is a VRF signature of `data` by this key.
+		pub fn vrf_verify(&self, data: &VrfSignData, signature: &VrfSignature) -> bool {
+			let (challenge_bytes, response) = signature.proof.split_at(SCALAR_SERIALIZED_LEN);
+			let (Some(public), Some(output), Some(challenge), Some(response)) = (
+				point_from_bytes(&self.0),
+				point_from_bytes(&signature.output.0),
+				scalar_from_bytes(challenge_bytes),
+				scalar_from_bytes(response),
+			) else {
+				return false
+			};
+
+			let commitments = (
+				(EdwardsAffine::generator() * response - public * challenge).into_affine(),
+				(data.input.0 * response - output * challenge).into_affine(),
+			);
+			challenge ==
+				super::vrf::challenge(
+					self,
+					data,
+					&signature.output,
+					(&commitments.0, &commitments.1),
+				)
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::{vrf::*, *};
+	use crate::crypto::DEV_PHRASE;
+
+	#[test]
+	fn default_phrase_should_be_used() {
+		assert_eq!(
+			Pair::from_string("//Alice///password", None).unwrap().public(),
+			Pair::from_string(&format!("{}//Alice", DEV_PHRASE), Some("password"))
+				.unwrap()
+				.public(),
+		);
+	}
+
+	#[test]
+	fn seed_and_derive_should_work() {
+		let seed = array_bytes::hex2array_unchecked(
+			"9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+		);
+		let pair = Pair::from_seed(&seed);
+		assert_eq!(pair.seed(), seed);
+		let path = vec![DeriveJunction::Hard([0u8; 32])];
+		let derived = pair.derive(path.into_iter(), None).ok().unwrap();
+		assert_eq!(derived.1, Some(derived.0.seed()));
+		assert_ne!(derived.0.public(), pair.public());
+		assert!(pair.derive(vec![DeriveJunction::soft(1)].into_iter(), None).is_err());
+	}
+
+	#[test]
+	fn generated_pair_should_work() {
+		let (pair, _) = Pair::generate();
+		let public = pair.public();
+		let message = b"Something important";
+		let signature = pair.sign(&message[..]);
+		assert!(Pair::verify(&signature, &message[..], &public));
+		assert!(!Pair::verify(&signature, b"Something else", &public));
+		assert!(!Pair::verify(&signature, &message[..], &Pair::generate().0.public()));
+	}
+
+	#[test]
+	fn generate_with_phrase_recovery_possible() {
+		let (pair1, phrase, _) = Pair::generate_with_phrase(Some("password"));
+		let (pair2, _) = Pair::from_phrase(&phrase, Some("password")).unwrap();
+		let (pair3, _) = Pair::from_phrase(&phrase, None).unwrap();
+
+		assert_eq!(pair1.public(), pair2.public());
+		assert_ne!(pair1.public(), pair3.public());
+	}
+
+	#[test]
+	fn ss58check_roundtrip_works() {
+		let pair = Pair::from_seed(b"12345678901234567890123456789012");
+		let public = pair.public();
+		let s = public.to_ss58check();
+		let cmp = Public::from_ss58check(&s).unwrap();
+		assert_eq!(cmp, public);
+	}
+
+	#[test]
+	fn invalid_public_keys_are_rejected() {
+		let pair = Pair::from_seed(b"12345678901234567890123456789012");
+		let signature = pair.sign(b"message");
+		// Not the `y` coordinate of a point in the prime order subgroup.
+		assert!(!Pair::verify(&signature, b"message", &Public([0xff; 32])));
+		assert!(!Pair::verify_weak(&signature.0[..63], b"message", pair.public()));
+	}
+
+	#[test]
+	fn vrf_sign_and_verify_works() {
+		let pair = Pair::from_seed(b"12345678901234567890123456789012");
+		let public = pair.public();
+		let data = VrfSignData::new(VrfInput::new(b"domain", b"input"), b"extra".to_vec());
+
+		let signature = pair.vrf_sign(&data);
+		assert_eq!(signature.output, pair.vrf_output(&data.input));
+		assert!(public.vrf_verify(&data, &signature));
+
+		let other_extra = VrfSignData::new(data.input.clone(), b"other".to_vec());
+		assert!(!public.vrf_verify(&other_extra, &signature));
+
+		let other_input = VrfSignData::new(VrfInput::new(b"domain", b"other"), b"extra".to_vec());
+		assert!(!public.vrf_verify(&other_input, &signature));
+
+		let other_key = Pair::from_seed(b"12345678901234567890123456789013");
+		assert!(!other_key.public().vrf_verify(&data, &signature));
+
+		let mut forged = signature.clone();
+		forged.output = other_key.vrf_output(&data.input);
+		assert!(!public.vrf_verify(&data, &forged));
+	}
+
+	#[test]
+	fn vrf_output_is_deterministic_and_context_dependent() {
+		let pair = Pair::from_seed(b"12345678901234567890123456789012");
+		let input = VrfInput::new(b"domain", b"input");
+		let output = pair.vrf_output(&input);
+
+		assert_eq!(output, pair.vrf_sign(&VrfSignData::new(input.clone(), Vec::new())).output);
+		assert_eq!(output.make_bytes(b"a", &input), output.make_bytes(b"a", &input));
+		assert_ne!(output.make_bytes(b"a", &input), output.make_bytes(b"b", &input));
+		assert_ne!(output, pair.vrf_output(&VrfInput::new(b"other domain", b"input")));
+	}
+}
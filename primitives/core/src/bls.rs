@@ -0,0 +1,870 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Simple BLS (Boneh–Lynn–Shacham) Signature API.
+//!
+//! The keys and signatures are generic over the curve and come in two flavours,
+//! [`bls377`] (BLS12-377) and [`bls381`] (BLS12-381). Public keys contain the key in both
+//! groups and signatures carry a DLEQ proof, as described in <https://eprint.iacr.org/2022/1611>,
+//! so that single signatures can be verified without a pairing.
+//!
+//! Signatures of several signers on the same message can be aggregated into an
+//! [`AggregateSignature`]. Aggregation is only secure if the proofs of possession of all signing
+//! keys were checked beforehand with [`Public::verify_proof_of_possession`], otherwise it is
+//! vulnerable to rogue key attacks. The proofs are generated with
+//! [`Pair::generate_proof_of_possession`].
+//!
+//! This module is experimental and only available with the `bls-experimental` feature.
+
+use crate::crypto::{
+	ByteArray, CryptoType, CryptoTypeId, CryptoTypePublicPair, Derive, Public as TraitPublic,
+	UncheckedFrom,
+};
+#[cfg(feature = "full_crypto")]
+use crate::crypto::{DeriveJunction, Pair as TraitPair, SecretStringError};
+
+#[cfg(feature = "std")]
+use bip39::{Language, Mnemonic, MnemonicType};
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "full_crypto")]
+use sp_std::vec::Vec;
+use sp_std::{convert::TryFrom, marker::PhantomData, ops::Deref};
+#[cfg(feature = "std")]
+use substrate_bip39::seed_from_entropy;
+
+use w3f_bls::{
+	DoublePublicKey, DoubleSignature, EngineBLS, Message, SerializableToBytes, TinyBLS381,
+};
+#[cfg(feature = "full_crypto")]
+use w3f_bls::{DoublePublicKeyScheme, SecretKeyVT};
+
+/// BLS-377 specialized types
+pub mod bls377 {
+	use crate::crypto::CryptoTypeId;
+	use w3f_bls::TinyBLS377;
+
+	/// An identifier used to match public keys against BLS12-377 keys
+	pub const CRYPTO_ID: CryptoTypeId = <TinyBLS377 as super::BlsBound>::CRYPTO_ID;
+
+	/// BLS12-377 key pair.
+	#[cfg(feature = "full_crypto")]
+	pub type Pair = super::Pair<TinyBLS377>;
+	/// BLS12-377 public key.
+	pub type Public = super::Public<TinyBLS377>;
+	/// BLS12-377 signature.
+	pub type Signature = super::Signature<TinyBLS377>;
+	/// BLS12-377 aggregate signature.
+	pub type AggregateSignature = super::AggregateSignature<TinyBLS377>;
+
+	impl super::BlsBound for TinyBLS377 {
+		const CRYPTO_ID: CryptoTypeId = CryptoTypeId(*b"bls7");
+		const HARD_JUNCTION_ID: &'static str = "BLS12377HDKD";
+	}
+}
+
+/// BLS-381 specialized types
+pub mod bls381 {
+	use crate::crypto::CryptoTypeId;
+	use w3f_bls::TinyBLS381;
+
+	/// An identifier used to match public keys against BLS12-381 keys
+	pub const CRYPTO_ID: CryptoTypeId = <TinyBLS381 as super::BlsBound>::CRYPTO_ID;
+
+	/// BLS12-381 key pair.
+	#[cfg(feature = "full_crypto")]
+	pub type Pair = super::Pair<TinyBLS381>;
+	/// BLS12-381 public key.
+	pub type Public = super::Public<TinyBLS381>;
+	/// BLS12-381 signature.
+	pub type Signature = super::Signature<TinyBLS381>;
+	/// BLS12-381 aggregate signature.
+	pub type AggregateSignature = super::AggregateSignature<TinyBLS381>;
+
+	impl super::BlsBound for TinyBLS381 {
+		const CRYPTO_ID: CryptoTypeId = CryptoTypeId(*b"bls8");
+		const HARD_JUNCTION_ID: &'static str = "BLS12381HDKD";
+	}
+}
+
+/// The size of a secret seed.
+pub const SEED_SERIALIZED_SIZE: usize = 32;
+
+/// The byte length of a public key, containing the key in both groups.
+///
+/// The sizes are the same for all supported curves.
+pub const PUBLIC_KEY_SERIALIZED_SIZE: usize =
+	<DoublePublicKey<TinyBLS381> as SerializableToBytes>::SERIALIZED_BYTES_SIZE;
+
+/// The byte length of a signature, including its DLEQ proof.
+pub const SIGNATURE_SERIALIZED_SIZE: usize =
+	<DoubleSignature<TinyBLS381> as SerializableToBytes>::SERIALIZED_BYTES_SIZE;
+
+/// The byte length of an aggregate signature.
+pub const AGGREGATE_SIGNATURE_SERIALIZED_SIZE: usize =
+	<w3f_bls::Signature<TinyBLS381> as SerializableToBytes>::SERIALIZED_BYTES_SIZE;
+
+/// The curve specific constants of a BLS key type.
+pub trait BlsBound: EngineBLS + Send + Sync + 'static {
+	/// The identifier used to match public keys against keys of this type.
+	const CRYPTO_ID: CryptoTypeId;
+	/// The domain separator used for hard key derivation.
+	const HARD_JUNCTION_ID: &'static str;
+}
+
+/// A secret seed.
+#[cfg(feature = "full_crypto")]
+type Seed = [u8; SEED_SERIALIZED_SIZE];
+
+/// The context of all messages signed with a BLS key.
+const SIGNING_CONTEXT: &[u8] = b"";
+
+/// The context of the proofs of possession, so that no signature on a message can be used as one.
+const PROOF_OF_POSSESSION_CONTEXT: &[u8] = b"POP_";
+
+/// Verifies a signature of `pubkey` on `message` in the given signing `context`.
+fn verify_in_context<T: BlsBound>(
+	sig: &[u8],
+	context: &[u8],
+	message: &[u8],
+	pubkey: &[u8],
+) -> bool {
+	let public_key = match DoublePublicKey::<T>::from_bytes(pubkey) {
+		Ok(pk) => pk,
+		Err(_) => return false,
+	};
+
+	let signature = match DoubleSignature::<T>::from_bytes(sig) {
+		Ok(s) => s,
+		Err(_) => return false,
+	};
+
+	signature.verify(&Message::new(context, message), &public_key)
+}
+
+/// A public key.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct Public<T> {
+	inner: [u8; PUBLIC_KEY_SERIALIZED_SIZE],
+	_phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for Public<T> {
+	fn clone(&self) -> Self {
+		Self { inner: self.inner, _phantom: PhantomData }
+	}
+}
+
+impl<T> Copy for Public<T> {}
+
+impl<T> PartialEq for Public<T> {
+	fn eq(&self, other: &Self) -> bool {
+		self.inner == other.inner
+	}
+}
+
+impl<T> Eq for Public<T> {}
+
+impl<T> PartialOrd for Public<T> {
+	fn partial_cmp(&self, other: &Self) -> Option<sp_std::cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl<T> Ord for Public<T> {
+	fn cmp(&self, other: &Self) -> sp_std::cmp::Ordering {
+		self.inner.cmp(&other.inner)
+	}
+}
+
+#[cfg(feature = "full_crypto")]
+impl<T> sp_std::hash::Hash for Public<T> {
+	fn hash<H: sp_std::hash::Hasher>(&self, state: &mut H) {
+		self.inner.hash(state)
+	}
+}
+
+impl<T> AsRef<[u8; PUBLIC_KEY_SERIALIZED_SIZE]> for Public<T> {
+	fn as_ref(&self) -> &[u8; PUBLIC_KEY_SERIALIZED_SIZE] {
+		&self.inner
+	}
+}
+
+impl<T> AsRef<[u8]> for Public<T> {
+	fn as_ref(&self) -> &[u8] {
+		&self.inner[..]
+	}
+}
+
+impl<T> AsMut<[u8]> for Public<T> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		&mut self.inner[..]
+	}
+}
+
+impl<T> Deref for Public<T> {
+	type Target = [u8];
+
+	fn deref(&self) -> &Self::Target {
+		&self.inner
+	}
+}
+
+impl<T> TryFrom<&[u8]> for Public<T> {
+	type Error = ();
+
+	fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+		if data.len() != PUBLIC_KEY_SERIALIZED_SIZE {
+			return Err(())
+		}
+		let mut r = [0u8; PUBLIC_KEY_SERIALIZED_SIZE];
+		r.copy_from_slice(data);
+		Ok(Self::unchecked_from(r))
+	}
+}
+
+impl<T> From<Public<T>> for [u8; PUBLIC_KEY_SERIALIZED_SIZE] {
+	fn from(x: Public<T>) -> Self {
+		x.inner
+	}
+}
+
+#[cfg(feature = "full_crypto")]
+impl<T: BlsBound> From<Pair<T>> for Public<T> {
+	fn from(x: Pair<T>) -> Self {
+		x.public()
+	}
+}
+
+impl<T> UncheckedFrom<[u8; PUBLIC_KEY_SERIALIZED_SIZE]> for Public<T> {
+	fn unchecked_from(data: [u8; PUBLIC_KEY_SERIALIZED_SIZE]) -> Self {
+		Public { inner: data, _phantom: PhantomData }
+	}
+}
+
+// SS58 decoding is limited to 132 bytes, so public keys are shown and parsed as hex instead.
+#[cfg(feature = "std")]
+impl<T> std::str::FromStr for Public<T> {
+	type Err = crate::crypto::PublicError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let data = array_bytes::hex2bytes(s).map_err(|_| crate::crypto::PublicError::BadBase58)?;
+		Self::try_from(&data[..]).map_err(|_| crate::crypto::PublicError::BadLength)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T> std::fmt::Display for Public<T> {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "{}", crate::hexdisplay::HexDisplay::from(&self.inner))
+	}
+}
+
+impl<T> sp_std::fmt::Debug for Public<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "{}", crate::hexdisplay::HexDisplay::from(&self.inner))
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T> Serialize for Public<T> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_str(&array_bytes::bytes2hex("", self.as_ref()))
+	}
+}
+
+#[cfg(feature = "std")]
+impl<'de, T> Deserialize<'de> for Public<T> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let public_hex = array_bytes::hex2bytes(&String::deserialize(deserializer)?)
+			.map_err(|e| de::Error::custom(format!("{:?}", e)))?;
+		Public::try_from(public_hex.as_ref()).map_err(|e| de::Error::custom(format!("{:?}", e)))
+	}
+}
+
+impl<T> ByteArray for Public<T> {
+	const LEN: usize = PUBLIC_KEY_SERIALIZED_SIZE;
+}
+
+impl<T: BlsBound> TraitPublic for Public<T> {
+	fn to_public_crypto_pair(&self) -> CryptoTypePublicPair {
+		CryptoTypePublicPair(T::CRYPTO_ID, self.to_raw_vec())
+	}
+}
+
+impl<T> Derive for Public<T> {}
+
+impl<T: BlsBound> From<Public<T>> for CryptoTypePublicPair {
+	fn from(key: Public<T>) -> Self {
+		(&key).into()
+	}
+}
+
+impl<T: BlsBound> From<&Public<T>> for CryptoTypePublicPair {
+	fn from(key: &Public<T>) -> Self {
+		CryptoTypePublicPair(T::CRYPTO_ID, key.to_raw_vec())
+	}
+}
+
+impl<T: BlsBound> Public<T> {
+	/// Verifies a proof of possession of the secret key of this public key.
+	///
+	/// The proof must have been generated with [`Pair::generate_proof_of_possession`].
+	pub fn verify_proof_of_possession(&self, proof: &Signature<T>) -> bool {
+		verify_in_context::<T>(&proof.inner, PROOF_OF_POSSESSION_CONTEXT, &self.inner, &self.inner)
+	}
+}
+
+impl<T: BlsBound> CryptoType for Public<T> {
+	#[cfg(feature = "full_crypto")]
+	type Pair = Pair<T>;
+}
+
+/// A signature, containing a DLEQ proof.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct Signature<T> {
+	inner: [u8; SIGNATURE_SERIALIZED_SIZE],
+	_phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for Signature<T> {
+	fn clone(&self) -> Self {
+		Self { inner: self.inner, _phantom: PhantomData }
+	}
+}
+
+impl<T> PartialEq for Signature<T> {
+	fn eq(&self, other: &Self) -> bool {
+		self.inner == other.inner
+	}
+}
+
+impl<T> Eq for Signature<T> {}
+
+#[cfg(feature = "full_crypto")]
+impl<T> sp_std::hash::Hash for Signature<T> {
+	fn hash<H: sp_std::hash::Hasher>(&self, state: &mut H) {
+		self.inner.hash(state)
+	}
+}
+
+impl<T> TryFrom<&[u8]> for Signature<T> {
+	type Error = ();
+
+	fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+		if data.len() != SIGNATURE_SERIALIZED_SIZE {
+			return Err(())
+		}
+		let mut inner = [0u8; SIGNATURE_SERIALIZED_SIZE];
+		inner.copy_from_slice(data);
+		Ok(Signature::unchecked_from(inner))
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T> Serialize for Signature<T> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_str(&array_bytes::bytes2hex("", self.as_ref()))
+	}
+}
+
+#[cfg(feature = "std")]
+impl<'de, T> Deserialize<'de> for Signature<T> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let signature_hex = array_bytes::hex2bytes(&String::deserialize(deserializer)?)
+			.map_err(|e| de::Error::custom(format!("{:?}", e)))?;
+		Signature::try_from(signature_hex.as_ref())
+			.map_err(|e| de::Error::custom(format!("{:?}", e)))
+	}
+}
+
+impl<T> From<Signature<T>> for [u8; SIGNATURE_SERIALIZED_SIZE] {
+	fn from(signature: Signature<T>) -> [u8; SIGNATURE_SERIALIZED_SIZE] {
+		signature.inner
+	}
+}
+
+impl<T> AsRef<[u8; SIGNATURE_SERIALIZED_SIZE]> for Signature<T> {
+	fn as_ref(&self) -> &[u8; SIGNATURE_SERIALIZED_SIZE] {
+		&self.inner
+	}
+}
+
+impl<T> AsRef<[u8]> for Signature<T> {
+	fn as_ref(&self) -> &[u8] {
+		&self.inner[..]
+	}
+}
+
+impl<T> AsMut<[u8]> for Signature<T> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		&mut self.inner[..]
+	}
+}
+
+impl<T> sp_std::fmt::Debug for Signature<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "{}", crate::hexdisplay::HexDisplay::from(&self.inner))
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T> UncheckedFrom<[u8; SIGNATURE_SERIALIZED_SIZE]> for Signature<T> {
+	fn unchecked_from(data: [u8; SIGNATURE_SERIALIZED_SIZE]) -> Self {
+		Signature { inner: data, _phantom: PhantomData }
+	}
+}
+
+impl<T: BlsBound> CryptoType for Signature<T> {
+	#[cfg(feature = "full_crypto")]
+	type Pair = Pair<T>;
+}
+
+/// An aggregate of signatures of several signers on the same message.
+///
+/// Aggregate signatures don't contain the DLEQ proofs of the signatures they are made of and are
+/// only secure if the proofs of possession of all signers were checked beforehand, see
+/// [`Public::verify_proof_of_possession`].
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct AggregateSignature<T> {
+	inner: [u8; AGGREGATE_SIGNATURE_SERIALIZED_SIZE],
+	_phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for AggregateSignature<T> {
+	fn clone(&self) -> Self {
+		Self { inner: self.inner, _phantom: PhantomData }
+	}
+}
+
+impl<T> PartialEq for AggregateSignature<T> {
+	fn eq(&self, other: &Self) -> bool {
+		self.inner == other.inner
+	}
+}
+
+impl<T> Eq for AggregateSignature<T> {}
+
+impl<T> AsRef<[u8]> for AggregateSignature<T> {
+	fn as_ref(&self) -> &[u8] {
+		&self.inner[..]
+	}
+}
+
+impl<T> sp_std::fmt::Debug for AggregateSignature<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "{}", crate::hexdisplay::HexDisplay::from(&self.inner))
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T> UncheckedFrom<[u8; AGGREGATE_SIGNATURE_SERIALIZED_SIZE]> for AggregateSignature<T> {
+	fn unchecked_from(data: [u8; AGGREGATE_SIGNATURE_SERIALIZED_SIZE]) -> Self {
+		AggregateSignature { inner: data, _phantom: PhantomData }
+	}
+}
+
+impl<T: BlsBound> AggregateSignature<T> {
+	/// Aggregates the given signatures on the same message.
+	///
+	/// Returns `None` if there aren't any signatures or if one of them is not a valid encoding.
+	/// The signatures themselves are not verified.
+	pub fn aggregate<'a>(signatures: impl IntoIterator<Item = &'a Signature<T>>) -> Option<Self> {
+		let mut aggregate: Option<T::SignatureGroup> = None;
+		for signature in signatures {
+			let signature = DoubleSignature::<T>::from_bytes(&signature.inner).ok()?.0;
+			aggregate = Some(match aggregate {
+				Some(aggregate) => aggregate + signature,
+				None => signature,
+			});
+		}
+
+		let mut inner = [0u8; AGGREGATE_SIGNATURE_SERIALIZED_SIZE];
+		inner.copy_from_slice(&w3f_bls::Signature::<T>(aggregate?).to_bytes());
+		Some(Self::unchecked_from(inner))
+	}
+
+	/// Verifies the aggregate signature of `signers` on `message`.
+	///
+	/// The caller must have checked the proofs of possession of all `signers` with
+	/// [`Public::verify_proof_of_possession`].
+	pub fn verify<'a, M: AsRef<[u8]>>(
+		&self,
+		message: M,
+		signers: impl IntoIterator<Item = &'a Public<T>>,
+	) -> bool {
+		let signature = match w3f_bls::Signature::<T>::from_bytes(&self.inner) {
+			Ok(signature) => signature,
+			Err(_) => return false,
+		};
+
+		let mut aggregate: Option<T::PublicKeyGroup> = None;
+		for signer in signers {
+			let public = match DoublePublicKey::<T>::from_bytes(&signer.inner) {
+				Ok(public) => public.1,
+				Err(_) => return false,
+			};
+			aggregate = Some(match aggregate {
+				Some(aggregate) => aggregate + public,
+				None => public,
+			});
+		}
+
+		match aggregate {
+			Some(public) => signature.verify(
+				&Message::new(SIGNING_CONTEXT, message.as_ref()),
+				&w3f_bls::PublicKey(public),
+			),
+			None => false,
+		}
+	}
+}
+
+impl<T: BlsBound> CryptoType for AggregateSignature<T> {
+	#[cfg(feature = "full_crypto")]
+	type Pair = Pair<T>;
+}
+
+/// A key pair.
+#[cfg(feature = "full_crypto")]
+pub struct Pair<T: EngineBLS> {
+	secret: SecretKeyVT<T>,
+	public: Public<T>,
+	seed: Seed,
+}
+
+#[cfg(feature = "full_crypto")]
+impl<T: EngineBLS> Clone for Pair<T> {
+	fn clone(&self) -> Self {
+		Pair { secret: self.secret.clone(), public: self.public, seed: self.seed }
+	}
+}
+
+/// Derive a single hard junction.
+#[cfg(feature = "full_crypto")]
+fn derive_hard_junction<T: BlsBound>(secret_seed: &Seed, cc: &[u8; 32]) -> Seed {
+	(T::HARD_JUNCTION_ID, secret_seed, cc).using_encoded(sp_core_hashing::blake2_256)
+}
+
+/// An error when deriving a key.
+#[cfg(feature = "full_crypto")]
+pub enum DeriveError {
+	/// A soft key was found in the path (and is unsupported).
+	SoftKeyInPath,
+}
+
+#[cfg(feature = "full_crypto")]
+impl<T: BlsBound> TraitPair for Pair<T> {
+	type Public = Public<T>;
+	type Seed = Seed;
+	type Signature = Signature<T>;
+	type DeriveError = DeriveError;
+
+	/// Generate new secure (random) key pair and provide the recovery phrase.
+	///
+	/// You can recover the same key later with `from_phrase`.
+	#[cfg(feature = "std")]
+	fn generate_with_phrase(password: Option<&str>) -> (Self, String, Seed) {
+		let mnemonic = Mnemonic::new(MnemonicType::Words12, Language::English);
+		let phrase = mnemonic.phrase();
+		let (pair, seed) = Self::from_phrase(phrase, password)
+			.expect("All phrases generated by Mnemonic are valid; qed");
+		(pair, phrase.to_owned(), seed)
+	}
+
+	/// Generate key pair from given recovery phrase and password.
+	#[cfg(feature = "std")]
+	fn from_phrase(
+		phrase: &str,
+		password: Option<&str>,
+	) -> Result<(Self, Seed), SecretStringError> {
+		let big_seed = seed_from_entropy(
+			Mnemonic::from_phrase(phrase, Language::English)
+				.map_err(|_| SecretStringError::InvalidPhrase)?
+				.entropy(),
+			password.unwrap_or(""),
+		)
+		.map_err(|_| SecretStringError::InvalidSeed)?;
+		let mut seed = Seed::default();
+		seed.copy_from_slice(&big_seed[0..SEED_SERIALIZED_SIZE]);
+		Self::from_seed_slice(&seed).map(|x| (x, seed))
+	}
+
+	/// Make a new key pair from secret seed material.
+	///
+	/// You should never need to use this; generate(), generate_with_phrase
+	fn from_seed(seed: &Seed) -> Self {
+		Self::from_seed_slice(&seed[..]).expect("seed has valid length; qed")
+	}
+
+	/// Make a new key pair from secret seed material. The slice must be 32 bytes long or it
+	/// will return `None`.
+	///
+	/// You should never need to use this; generate(), generate_with_phrase
+	fn from_seed_slice(seed_slice: &[u8]) -> Result<Self, SecretStringError> {
+		let seed = Seed::try_from(seed_slice).map_err(|_| SecretStringError::InvalidSeedLength)?;
+		let secret = SecretKeyVT::<T>::from_seed(&seed);
+		let mut public = [0u8; PUBLIC_KEY_SERIALIZED_SIZE];
+		public.copy_from_slice(&secret.into_double_public_key().to_bytes());
+		Ok(Pair { secret, public: Public::unchecked_from(public), seed })
+	}
+
+	/// Derive a child key from a series of given junctions.
+	fn derive<Iter: Iterator<Item = DeriveJunction>>(
+		&self,
+		path: Iter,
+		_seed: Option<Seed>,
+	) -> Result<(Self, Option<Seed>), DeriveError> {
+		let mut acc = self.seed;
+		for j in path {
+			match j {
+				DeriveJunction::Soft(_cc) => return Err(DeriveError::SoftKeyInPath),
+				DeriveJunction::Hard(cc) => acc = derive_hard_junction::<T>(&acc, &cc),
+			}
+		}
+		Ok((Self::from_seed(&acc), Some(acc)))
+	}
+
+	/// Get the public key.
+	fn public(&self) -> Public<T> {
+		self.public
+	}
+
+	/// Sign a message.
+	fn sign(&self, message: &[u8]) -> Signature<T> {
+		self.sign_in_context(SIGNING_CONTEXT, message)
+	}
+
+	/// Verify a signature on a message. Returns true if the signature is good.
+	fn verify<M: AsRef<[u8]>>(sig: &Self::Signature, message: M, pubkey: &Self::Public) -> bool {
+		Self::verify_weak(&sig.inner[..], message, pubkey)
+	}
+
+	/// Verify a signature on a message. Returns true if the signature is good.
+	///
+	/// This doesn't use the type system to ensure that `sig` and `pubkey` are the correct
+	/// size. Use it only if you're coming from byte buffers and need the speed.
+	fn verify_weak<P: AsRef<[u8]>, M: AsRef<[u8]>>(sig: &[u8], message: M, pubkey: P) -> bool {
+		verify_in_context::<T>(sig, SIGNING_CONTEXT, message.as_ref(), pubkey.as_ref())
+	}
+
+	/// Return a vec filled with raw data.
+	fn to_raw_vec(&self) -> Vec<u8> {
+		self.seed.to_vec()
+	}
+}
+
+#[cfg(feature = "full_crypto")]
+impl<T: BlsBound> Pair<T> {
+	/// Get the seed for this key.
+	pub fn seed(&self) -> Seed {
+		self.seed
+	}
+
+	/// Generate a proof of possession of the secret key of this pair.
+	///
+	/// The proof is a signature on the public key in a dedicated context and has to be checked
+	/// with [`Public::verify_proof_of_possession`] before the public key is used to verify an
+	/// [`AggregateSignature`].
+	pub fn generate_proof_of_possession(&self) -> Signature<T> {
+		self.sign_in_context(PROOF_OF_POSSESSION_CONTEXT, &self.public.inner)
+	}
+
+	fn sign_in_context(&self, context: &[u8], message: &[u8]) -> Signature<T> {
+		let mut secret = self.secret.clone();
+		let signature = DoublePublicKeyScheme::sign(&mut secret, &Message::new(context, message));
+		let mut inner = [0u8; SIGNATURE_SERIALIZED_SIZE];
+		inner.copy_from_slice(&signature.to_bytes());
+		Signature::unchecked_from(inner)
+	}
+}
+
+#[cfg(feature = "full_crypto")]
+impl<T: BlsBound> CryptoType for Pair<T> {
+	type Pair = Pair<T>;
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::crypto::DEV_PHRASE;
+	use bls377::{Pair as Bls377Pair, Signature as Bls377Signature};
+	use bls381::Pair as Bls381Pair;
+
+	#[test]
+	fn serialized_sizes_match_the_curves() {
+		assert_eq!(PUBLIC_KEY_SERIALIZED_SIZE, 144);
+		assert_eq!(SIGNATURE_SERIALIZED_SIZE, 112);
+		assert_eq!(AGGREGATE_SIGNATURE_SERIALIZED_SIZE, 48);
+		assert_eq!(
+			<DoublePublicKey<w3f_bls::TinyBLS377> as SerializableToBytes>::SERIALIZED_BYTES_SIZE,
+			PUBLIC_KEY_SERIALIZED_SIZE,
+		);
+		assert_eq!(
+			<DoubleSignature<w3f_bls::TinyBLS377> as SerializableToBytes>::SERIALIZED_BYTES_SIZE,
+			SIGNATURE_SERIALIZED_SIZE,
+		);
+	}
+
+	#[test]
+	fn default_phrase_should_be_used() {
+		assert_eq!(
+			Bls377Pair::from_string("//Alice///password", None).unwrap().public(),
+			Bls377Pair::from_string(&format!("{}//Alice", DEV_PHRASE), Some("password"))
+				.unwrap()
+				.public(),
+		);
+	}
+
+	#[test]
+	fn seed_and_derive_should_work() {
+		let seed = array_bytes::hex2array_unchecked(
+			"9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+		);
+		let pair = Bls377Pair::from_seed(&seed);
+		assert_eq!(pair.seed(), seed);
+		let path = vec![DeriveJunction::Hard([0u8; 32])];
+		let derived = pair.derive(path.into_iter(), None).ok().unwrap();
+		assert_eq!(derived.1, Some(derived.0.seed()));
+		assert_eq!(derived.0.public(), Bls377Pair::from_seed(&derived.0.seed()).public());
+		assert_ne!(derived.0.public(), pair.public());
+		assert!(pair.derive(vec![DeriveJunction::soft(1)].into_iter(), None).is_err());
+	}
+
+	#[test]
+	fn curves_give_different_keys() {
+		let seed = [7u8; 32];
+		assert_ne!(
+			Bls377Pair::from_seed(&seed).public().to_raw_vec(),
+			Bls381Pair::from_seed(&seed).public().to_raw_vec(),
+		);
+	}
+
+	#[test]
+	fn generated_pair_should_work() {
+		fn check<T: BlsBound>() {
+			let (pair, _) = Pair::<T>::generate();
+			let public = pair.public();
+			let message = b"Something important";
+			let signature = pair.sign(&message[..]);
+			assert!(Pair::verify(&signature, &message[..], &public));
+			assert!(!Pair::verify(&signature, b"Something else", &public));
+			assert!(!Pair::verify(&signature, &message[..], &Pair::<T>::generate().0.public()));
+		}
+		check::<w3f_bls::TinyBLS377>();
+		check::<w3f_bls::TinyBLS381>();
+	}
+
+	#[test]
+	fn generate_with_phrase_recovery_possible() {
+		let (pair1, phrase, _) = Bls381Pair::generate_with_phrase(Some("password"));
+		let (pair2, _) = Bls381Pair::from_phrase(&phrase, Some("password")).unwrap();
+		let (pair3, _) = Bls381Pair::from_phrase(&phrase, None).unwrap();
+
+		assert_eq!(pair1.public(), pair2.public());
+		assert_ne!(pair1.public(), pair3.public());
+	}
+
+	#[test]
+	fn public_serialization_works() {
+		let public = Bls377Pair::from_seed(b"12345678901234567890123456789012").public();
+		let serialized_public = serde_json::to_string(&public).unwrap();
+		// Public key is 144 bytes, so 288 chars + 2 quote chars
+		assert_eq!(serialized_public.len(), 290);
+		assert_eq!(serde_json::from_str::<bls377::Public>(&serialized_public).unwrap(), public);
+		assert_eq!(public.to_string().parse::<bls377::Public>().unwrap(), public);
+	}
+
+	#[test]
+	fn signature_serialization_works() {
+		let pair = Bls377Pair::from_seed(b"12345678901234567890123456789012");
+		let message = b"Something important";
+		let signature = pair.sign(&message[..]);
+		let serialized_signature = serde_json::to_string(&signature).unwrap();
+		// Signature is 112 bytes, so 224 chars + 2 quote chars
+		assert_eq!(serialized_signature.len(), 226);
+		let signature: Bls377Signature = serde_json::from_str(&serialized_signature).unwrap();
+		assert!(Bls377Pair::verify(&signature, &message[..], &pair.public()));
+		assert!(serde_json::from_str::<Bls377Signature>("\"abc123\"").is_err());
+	}
+
+	#[test]
+	fn aggregate_signature_should_work() {
+		let message = b"Something important";
+		let pairs = (0..3u8).map(|i| Bls377Pair::from_seed(&[i; 32])).collect::<Vec<_>>();
+		let publics = pairs.iter().map(|p| p.public()).collect::<Vec<_>>();
+		let signatures = pairs.iter().map(|p| p.sign(&message[..])).collect::<Vec<_>>();
+
+		let aggregate = bls377::AggregateSignature::aggregate(&signatures).unwrap();
+		assert!(aggregate.verify(&message[..], &publics));
+		assert!(!aggregate.verify(b"Something else", &publics));
+		assert!(!aggregate.verify(&message[..], &publics[..2]));
+
+		let partial = bls377::AggregateSignature::aggregate(&signatures[1..]).unwrap();
+		assert!(partial.verify(&message[..], &publics[1..]));
+		assert_ne!(partial, aggregate);
+
+		assert!(bls377::AggregateSignature::aggregate(&[]).is_none());
+	}
+
+	#[test]
+	fn proof_of_possession_should_work() {
+		let pair = Bls377Pair::from_seed(&[1u8; 32]);
+		let other = Bls377Pair::from_seed(&[2u8; 32]);
+		let proof = pair.generate_proof_of_possession();
+
+		assert!(pair.public().verify_proof_of_possession(&proof));
+		assert!(!other.public().verify_proof_of_possession(&proof));
+		// A signature on the public key in the regular signing context is not a proof.
+		let public = pair.public();
+		assert!(!public.verify_proof_of_possession(&pair.sign(public.as_ref())));
+		assert!(!Bls377Pair::verify(&proof, public, &public));
+	}
+}
@@ -239,6 +239,15 @@ pub trait Externalities: ExtensionStore {
 	/// no transaction is open that can be closed.
 	fn storage_commit_transaction(&mut self) -> Result<(), ()>;
 
+	/// Returns the number of keys changed in the last transaction started by
+	/// `storage_start_transaction`.
+	///
+	/// These are the changes that `storage_rollback_transaction` would discard. Returns `0`
+	/// when no transaction is open.
+	fn storage_transaction_changes(&self) -> u32 {
+		unimplemented!("storage_transaction_changes");
+	}
+
 	/// Index specified transaction slice and store it.
 	fn storage_index_transaction(&mut self, _index: u32, _hash: &[u8], _size: u32) {
 		unimplemented!("storage_index_transaction");
@@ -0,0 +1,414 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compact ancestry proofs for GRANDPA-finalized chains, suitable for WASM compilation.
+//!
+//! An [`AncestryProof`] shows that some block `B` is an ancestor of a finalized block `F`
+//! without carrying the headers of the `(B; F]` sub-chain. Instead it relies on the runtime
+//! maintaining a Merkle Mountain Range (`pallet_mmr`) where the leaf added by block `B + 1`
+//! contains the number and hash of its parent `B`. The proof consists of:
+//!
+//! 1) the header of block `F` (and, for convenience, its GRANDPA justification);
+//! 2) a storage proof of the MMR root against the state root of `F`;
+//! 3) the MMR leaf added by block `B + 1` and its inclusion proof against that root.
+//!
+//! The size of the proof is logarithmic in the distance between `B` and `F`.
+//!
+//! This crate does not verify the GRANDPA justification itself. Light clients (e.g. bridge
+//! pallets) already track the finalized chain and should only pass a finalized block hash they
+//! trust to [`AncestryProof::verify`].
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(missing_docs)]
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_mmr_primitives::{DataOrHash, FullLeaf, OpaqueLeaf, Proof};
+use sp_runtime::{
+	traits::{Hash as HashT, Header as HeaderT},
+	RuntimeDebug,
+};
+use sp_std::{marker::PhantomData, prelude::*};
+use sp_trie::{LayoutV1, StorageProof};
+
+/// Name of the `pallet_mmr` storage item holding the latest MMR root.
+pub const MMR_ROOT_STORAGE_NAME: &[u8] = b"RootHash";
+
+/// Compute the storage key of the MMR root for a `pallet_mmr` instance.
+///
+/// `pallet_prefix` is the name given to the pallet in `construct_runtime!` (e.g. `b"Mmr"`).
+pub fn mmr_root_storage_key(pallet_prefix: &[u8]) -> Vec<u8> {
+	let mut key = sp_core::hashing::twox_128(pallet_prefix).to_vec();
+	key.extend_from_slice(&sp_core::hashing::twox_128(MMR_ROOT_STORAGE_NAME));
+	key
+}
+
+/// An MMR leaf which commits to the parent of the block that added it.
+///
+/// Implemented for the leaf types of `pallet_mmr::ParentNumberAndHash` and of
+/// `pallet_beefy_mmr`.
+pub trait AncestryLeaf<Number, Hash>: Decode {
+	/// Number and hash of the parent of the block that added this leaf.
+	fn parent_number_and_hash(&self) -> (Number, Hash);
+}
+
+impl<Number: Decode + Clone, Hash: Decode + Clone> AncestryLeaf<Number, Hash> for (Number, Hash) {
+	fn parent_number_and_hash(&self) -> (Number, Hash) {
+		self.clone()
+	}
+}
+
+/// Leaf layout of `beefy_primitives::mmr::MmrLeaf`, decoding only the fields we need.
+///
+/// The SCALE encoding of the leaf starts with a version byte followed by the parent number and
+/// hash, any trailing data is ignored.
+#[derive(Decode, RuntimeDebug, Clone, PartialEq, Eq)]
+pub struct BeefyMmrLeafPrefix<Number, Hash> {
+	/// Version of the leaf format.
+	pub version: u8,
+	/// Parent number and hash of the block that added this leaf.
+	pub parent_number_and_hash: (Number, Hash),
+}
+
+impl<Number: Decode + Clone, Hash: Decode + Clone> AncestryLeaf<Number, Hash>
+	for BeefyMmrLeafPrefix<Number, Hash>
+{
+	fn parent_number_and_hash(&self) -> (Number, Hash) {
+		self.parent_number_and_hash.clone()
+	}
+}
+
+/// Proof that a block is an ancestor of a finalized block.
+#[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq, TypeInfo)]
+pub struct AncestryProof<Header, MmrHash> {
+	/// Header of the finalized block `F`.
+	pub finalized_header: Header,
+	/// SCALE-encoded GRANDPA justification of `F`.
+	pub justification: Vec<u8>,
+	/// MMR part of the proof, missing when the proven block is `F` itself.
+	pub mmr_proof: Option<MmrAncestryProof<MmrHash>>,
+}
+
+/// MMR part of an [`AncestryProof`].
+#[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq, TypeInfo)]
+pub struct MmrAncestryProof<MmrHash> {
+	/// Storage proof of the MMR root against the state root of the finalized block.
+	pub mmr_root_proof: StorageProof,
+	/// SCALE-encoded MMR leaf added by the child of the proven block.
+	pub leaf: Vec<u8>,
+	/// Proof of inclusion of `leaf` in the MMR.
+	pub leaf_proof: Proof<MmrHash>,
+}
+
+/// Errors that can occur while verifying an [`AncestryProof`].
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+#[derive(RuntimeDebug, PartialEq, Eq)]
+pub enum Error {
+	/// The finalized header in the proof is not the trusted finalized block.
+	#[cfg_attr(feature = "std", error("Finalized header does not match the trusted block"))]
+	FinalizedHeaderMismatch,
+	/// The proven block is not an ancestor of the finalized block.
+	#[cfg_attr(feature = "std", error("Block is not an ancestor of the finalized block"))]
+	NotAnAncestor,
+	/// The proof is missing its MMR part.
+	#[cfg_attr(feature = "std", error("Missing MMR proof"))]
+	MissingMmrProof,
+	/// The storage proof of the MMR root is invalid.
+	#[cfg_attr(feature = "std", error("Invalid MMR root storage proof"))]
+	InvalidMmrRootProof,
+	/// The MMR root could not be found in the finalized block state.
+	#[cfg_attr(feature = "std", error("MMR root not found in finalized state"))]
+	MissingMmrRoot,
+	/// The MMR leaf could not be decoded.
+	#[cfg_attr(feature = "std", error("Invalid MMR leaf"))]
+	InvalidLeaf,
+	/// The MMR leaf is not included in the MMR.
+	#[cfg_attr(feature = "std", error("Invalid MMR leaf proof"))]
+	InvalidLeafProof,
+}
+
+impl<Header, MmrHash> AncestryProof<Header, MmrHash>
+where
+	Header: HeaderT,
+	MmrHash: Decode + Clone + PartialEq + sp_std::fmt::Debug + AsRef<[u8]>,
+{
+	/// Verify that block `(number, hash)` is an ancestor of (or equal to) the finalized block
+	/// `finalized_hash`.
+	///
+	/// `finalized_hash` must come from a trusted source, e.g. a previously verified
+	/// justification. `mmr_root_key` is the storage key of the MMR root, see
+	/// [`mmr_root_storage_key`].
+	pub fn verify<MmrHashing, Leaf>(
+		&self,
+		finalized_hash: &Header::Hash,
+		mmr_root_key: &[u8],
+		number: Header::Number,
+		hash: Header::Hash,
+	) -> Result<(), Error>
+	where
+		MmrHashing: HashT<Output = MmrHash>,
+		Leaf: AncestryLeaf<Header::Number, Header::Hash>,
+	{
+		if self.finalized_header.hash() != *finalized_hash {
+			return Err(Error::FinalizedHeaderMismatch)
+		}
+
+		let finalized_number = *self.finalized_header.number();
+		if number >= finalized_number {
+			return if number == finalized_number && hash == *finalized_hash {
+				Ok(())
+			} else {
+				Err(Error::NotAnAncestor)
+			}
+		}
+
+		let mmr_proof = self.mmr_proof.as_ref().ok_or(Error::MissingMmrProof)?;
+
+		let leaf = Leaf::decode(&mut &mmr_proof.leaf[..]).map_err(|_| Error::InvalidLeaf)?;
+		if leaf.parent_number_and_hash() != (number, hash) {
+			return Err(Error::NotAnAncestor)
+		}
+
+		let db = mmr_proof.mmr_root_proof.to_memory_db::<Header::Hashing>();
+		let encoded_root = sp_trie::read_trie_value::<LayoutV1<Header::Hashing>, _>(
+			&db,
+			self.finalized_header.state_root(),
+			mmr_root_key,
+			None,
+			None,
+		)
+		.map_err(|_| Error::InvalidMmrRootProof)?
+		.ok_or(Error::MissingMmrRoot)?;
+		let mmr_root =
+			MmrHash::decode(&mut &encoded_root[..]).map_err(|_| Error::InvalidMmrRootProof)?;
+
+		verify_leaf_proof::<MmrHashing>(
+			mmr_root,
+			OpaqueLeaf::from_encoded_leaf(mmr_proof.leaf.clone()),
+			mmr_proof.leaf_proof.clone(),
+		)
+	}
+}
+
+/// Hasher merging MMR nodes the same way `pallet_mmr` does.
+struct Hasher<H, L>(PhantomData<(H, L)>);
+
+impl<H: HashT, L: FullLeaf> mmr_lib::Merge for Hasher<H, L> {
+	type Item = DataOrHash<H, L>;
+
+	fn merge(left: &Self::Item, right: &Self::Item) -> mmr_lib::Result<Self::Item> {
+		let mut concat = left.hash().as_ref().to_vec();
+		concat.extend_from_slice(right.hash().as_ref());
+
+		Ok(DataOrHash::Hash(<H as HashT>::hash(&concat)))
+	}
+}
+
+/// Verify the inclusion of a single leaf in an MMR with the given root.
+fn verify_leaf_proof<H: HashT>(
+	root: H::Output,
+	leaf: OpaqueLeaf,
+	proof: Proof<H::Output>,
+) -> Result<(), Error> {
+	let leaf_index = match proof.leaf_indices[..] {
+		[leaf_index] if leaf_index < proof.leaf_count => leaf_index,
+		_ => return Err(Error::InvalidLeafProof),
+	};
+	let size = mmr_lib::leaf_index_to_mmr_size(proof.leaf_count - 1);
+
+	let merkle_proof =
+		mmr_lib::MerkleProof::<DataOrHash<H, OpaqueLeaf>, Hasher<H, OpaqueLeaf>>::new(
+			size,
+			proof.items.into_iter().map(DataOrHash::Hash).collect(),
+		);
+	let is_valid = merkle_proof
+		.verify(
+			DataOrHash::Hash(root),
+			vec![(mmr_lib::leaf_index_to_pos(leaf_index), DataOrHash::Data(leaf))],
+		)
+		.map_err(|_| Error::InvalidLeafProof)?;
+
+	if is_valid {
+		Ok(())
+	} else {
+		Err(Error::InvalidLeafProof)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::H256;
+	use sp_runtime::{
+		testing::{Digest, Header},
+		traits::{BlakeTwo256, Keccak256},
+		StateVersion,
+	};
+	use sp_state_machine::{prove_read, Backend, InMemoryBackend};
+
+	type Leaf = (u64, H256);
+	type Node = DataOrHash<Keccak256, OpaqueLeaf>;
+
+	const MMR_PREFIX: &[u8] = b"Mmr";
+
+	/// Build a chain of `len` headers and an MMR with one leaf per block (starting at block 1),
+	/// returning the headers, the MMR and the encoded leaves.
+	fn chain(len: u64) -> (Vec<Header>, mmr_lib::util::MemStore<Node>, Vec<Vec<u8>>, u64) {
+		let mut headers: Vec<Header> = Vec::new();
+		let store = mmr_lib::util::MemStore::default();
+		let mut mmr = mmr_lib::MMR::<Node, Hasher<Keccak256, OpaqueLeaf>, _>::new(0, &store);
+		let mut leaves = Vec::new();
+
+		for number in 0..len {
+			let parent_hash = headers.last().map(|h| h.hash()).unwrap_or_default();
+			if let Some(parent) = headers.last() {
+				let leaf = (*parent.number(), parent.hash()).encode();
+				mmr.push(DataOrHash::Data(OpaqueLeaf::from_encoded_leaf(leaf.clone()))).unwrap();
+				leaves.push(leaf);
+			}
+			headers.push(Header::new(
+				number,
+				Default::default(),
+				Default::default(),
+				parent_hash,
+				Digest::default(),
+			));
+		}
+
+		let size = mmr.mmr_size();
+		mmr.commit().unwrap();
+		(headers, store, leaves, size)
+	}
+
+	/// Build an ancestry proof of `block` against the last header of a chain of `len` blocks.
+	fn ancestry_proof(len: u64, block: u64) -> (AncestryProof<Header, H256>, Header) {
+		let (mut headers, store, leaves, size) = chain(len);
+		let mmr = mmr_lib::MMR::<Node, Hasher<Keccak256, OpaqueLeaf>, _>::new(size, &store);
+		let root = mmr.get_root().unwrap().hash();
+
+		let key = mmr_root_storage_key(MMR_PREFIX);
+		let backend = InMemoryBackend::<BlakeTwo256>::from((
+			vec![(None, vec![(key.clone(), Some(root.encode()))])],
+			StateVersion::V1,
+		));
+		let state_root = backend.storage_root(std::iter::empty(), StateVersion::V1).0;
+		let mmr_root_proof = prove_read(backend, &[&key]).unwrap();
+
+		let mut finalized_header = headers.pop().unwrap();
+		finalized_header.state_root = state_root;
+
+		// leaf `i` was added by block `i + 1`.
+		let leaf_index = block;
+		let leaf_proof = mmr.gen_proof(vec![mmr_lib::leaf_index_to_pos(leaf_index)]).unwrap();
+		let leaf_proof = Proof {
+			leaf_indices: vec![leaf_index],
+			leaf_count: leaves.len() as u64,
+			items: leaf_proof.proof_items().iter().map(|n| n.hash()).collect(),
+		};
+
+		let proof = AncestryProof {
+			finalized_header,
+			justification: Vec::new(),
+			mmr_proof: Some(MmrAncestryProof {
+				mmr_root_proof,
+				leaf: leaves[leaf_index as usize].clone(),
+				leaf_proof,
+			}),
+		};
+		(proof, headers[block as usize].clone())
+	}
+
+	#[test]
+	fn verifies_ancestry_proof() {
+		let key = mmr_root_storage_key(MMR_PREFIX);
+		for block in [0, 1, 7, 30] {
+			let (proof, header) = ancestry_proof(32, block);
+			let finalized_hash = proof.finalized_header.hash();
+			assert_eq!(
+				proof.verify::<Keccak256, Leaf>(&finalized_hash, &key, block, header.hash()),
+				Ok(())
+			);
+		}
+	}
+
+	#[test]
+	fn finalized_block_is_its_own_ancestor() {
+		let (mut proof, _) = ancestry_proof(8, 3);
+		proof.mmr_proof = None;
+		let finalized_hash = proof.finalized_header.hash();
+		let key = mmr_root_storage_key(MMR_PREFIX);
+
+		assert_eq!(
+			proof.verify::<Keccak256, Leaf>(&finalized_hash, &key, 7, finalized_hash),
+			Ok(())
+		);
+		assert_eq!(
+			proof.verify::<Keccak256, Leaf>(&finalized_hash, &key, 3, H256::zero()),
+			Err(Error::MissingMmrProof)
+		);
+		assert_eq!(
+			proof.verify::<Keccak256, Leaf>(&finalized_hash, &key, 8, H256::zero()),
+			Err(Error::NotAnAncestor)
+		);
+	}
+
+	#[test]
+	fn rejects_invalid_proofs() {
+		let key = mmr_root_storage_key(MMR_PREFIX);
+		let (proof, header) = ancestry_proof(16, 5);
+		let finalized_hash = proof.finalized_header.hash();
+
+		// untrusted finalized block.
+		assert_eq!(
+			proof.verify::<Keccak256, Leaf>(&H256::zero(), &key, 5, header.hash()),
+			Err(Error::FinalizedHeaderMismatch)
+		);
+
+		// leaf for a different block.
+		assert_eq!(
+			proof.verify::<Keccak256, Leaf>(&finalized_hash, &key, 5, H256::zero()),
+			Err(Error::NotAnAncestor)
+		);
+
+		// wrong MMR root storage key.
+		assert_eq!(
+			proof.verify::<Keccak256, Leaf>(
+				&finalized_hash,
+				&mmr_root_storage_key(b"Other"),
+				5,
+				header.hash()
+			),
+			Err(Error::MissingMmrRoot)
+		);
+
+		// tampered leaf proof.
+		let mut tampered = proof.clone();
+		tampered.mmr_proof.as_mut().unwrap().leaf_proof.items[0] = H256::zero();
+		assert_eq!(
+			tampered.verify::<Keccak256, Leaf>(&finalized_hash, &key, 5, header.hash()),
+			Err(Error::InvalidLeafProof)
+		);
+
+		// leaf proven at a different position.
+		let mut tampered = proof;
+		tampered.mmr_proof.as_mut().unwrap().leaf_proof.leaf_indices = vec![4];
+		assert_eq!(
+			tampered.verify::<Keccak256, Leaf>(&finalized_hash, &key, 5, header.hash()),
+			Err(Error::InvalidLeafProof)
+		);
+	}
+}
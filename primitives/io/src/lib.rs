@@ -81,6 +81,9 @@ mod batch_verifier;
 #[cfg(feature = "std")]
 use batch_verifier::BatchVerifier;
 
+#[cfg(all(target_arch = "wasm32", feature = "runtime_allocator", not(feature = "std")))]
+mod runtime_allocator;
+
 pub use sp_externalities::MultiRemovalResults;
 
 #[cfg(feature = "std")]
@@ -321,6 +324,15 @@ pub trait Storage {
 		self.storage_commit_transaction()
 			.expect("No open transaction that can be committed.");
 	}
+
+	/// Returns the number of storage keys changed in the last transaction started by
+	/// `start_transaction`.
+	///
+	/// These are the changes a `rollback_transaction` would discard, including the ones of
+	/// nested transactions that were already committed. Returns `0` when no transaction is open.
+	fn transaction_changes(&mut self) -> u32 {
+		self.storage_transaction_changes()
+	}
 }
 
 /// Interface for accessing the child storage for default child trie,
@@ -1658,14 +1670,19 @@ pub trait Sandbox {
 }
 
 /// Allocator used by Substrate when executing the Wasm runtime.
-#[cfg(all(target_arch = "wasm32", not(feature = "std")))]
+#[cfg(all(target_arch = "wasm32", not(feature = "runtime_allocator"), not(feature = "std")))]
 struct WasmAllocator;
 
-#[cfg(all(target_arch = "wasm32", not(feature = "disable_allocator"), not(feature = "std")))]
+#[cfg(all(
+	target_arch = "wasm32",
+	not(feature = "disable_allocator"),
+	not(feature = "runtime_allocator"),
+	not(feature = "std")
+))]
 #[global_allocator]
 static ALLOCATOR: WasmAllocator = WasmAllocator;
 
-#[cfg(all(target_arch = "wasm32", not(feature = "std")))]
+#[cfg(all(target_arch = "wasm32", not(feature = "runtime_allocator"), not(feature = "std")))]
 mod allocator_impl {
 	use super::*;
 	use core::alloc::{GlobalAlloc, Layout};
@@ -0,0 +1,200 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Allocator managing the heap of the runtime inside of the runtime.
+//!
+//! By default the heap of the runtime is managed by the host, which the runtime calls into through
+//! the [`allocator`](crate::allocator) host functions. With the `runtime_allocator` feature the
+//! runtime manages its heap itself. It exports the functions named
+//! [`RUNTIME_ALLOC_EXPORT`](sp_wasm_interface::RUNTIME_ALLOC_EXPORT) and
+//! [`RUNTIME_FREE_EXPORT`](sp_wasm_interface::RUNTIME_FREE_EXPORT), through which the host
+//! allocates the call data and the buffers it returns from host functions. The host detects
+//! these exports and selects the allocator ABI accordingly, so runtimes built with and without
+//! this feature can be executed by the same node. See
+//! [`RUNTIME_ALLOC_EXPORT`](sp_wasm_interface::RUNTIME_ALLOC_EXPORT) for why the ABI isn't selected
+//! by the runtime version.
+//!
+//! The allocator follows the design of the host's freeing-bump allocator: allocations are
+//! rounded up to a power of two between 8 bytes and 32 MiB, freed allocations are kept in one free
+//! list per size and reused, and new allocations are bumped from `__heap_base`, growing the
+//! linear memory as needed.
+
+use core::{
+	alloc::{GlobalAlloc, Layout},
+	arch::wasm32,
+	cell::UnsafeCell,
+};
+
+/// The size of a wasm page.
+const PAGE_SIZE: usize = 65536;
+
+/// The header preceding every allocation, which stores its order while it is allocated and the
+/// next entry of its free list while it is free.
+const HEADER_SIZE: usize = 8;
+
+/// The smallest possible allocation.
+const MIN_POSSIBLE_ALLOCATION: usize = 8;
+
+/// The number of allocation sizes, 8 bytes to 32 MiB.
+const N_ORDERS: usize = 23;
+
+/// The largest possible allocation.
+const MAX_POSSIBLE_ALLOCATION: usize = MIN_POSSIBLE_ALLOCATION << (N_ORDERS - 1);
+
+extern "C" {
+	/// The start of the heap, provided by the linker.
+	static __heap_base: u8;
+}
+
+struct Heap {
+	/// The address of the next bumped allocation, `0` if not initialized yet.
+	bumper: usize,
+	/// The headers of the first free allocation of each order, `0` if there is none.
+	free_lists: [usize; N_ORDERS],
+}
+
+impl Heap {
+	/// Returns the order of an allocation of `size` bytes.
+	fn order(size: usize) -> Option<usize> {
+		if size > MAX_POSSIBLE_ALLOCATION {
+			return None
+		}
+
+		let size = size.max(MIN_POSSIBLE_ALLOCATION).next_power_of_two();
+		Some((size.trailing_zeros() - MIN_POSSIBLE_ALLOCATION.trailing_zeros()) as usize)
+	}
+
+	/// Allocates `size` bytes, returns a null pointer if the heap is exhausted.
+	unsafe fn allocate(&mut self, size: usize) -> *mut u8 {
+		let order = match Self::order(size) {
+			Some(order) => order,
+			None => return core::ptr::null_mut(),
+		};
+
+		let header = match self.free_lists[order] {
+			0 => match self.bump(HEADER_SIZE + (MIN_POSSIBLE_ALLOCATION << order)) {
+				Some(header) => header,
+				None => return core::ptr::null_mut(),
+			},
+			header => {
+				self.free_lists[order] = *(header as *const usize);
+				header
+			},
+		};
+
+		*(header as *mut usize) = order;
+		(header + HEADER_SIZE) as *mut u8
+	}
+
+	/// Frees the allocation at `ptr`.
+	unsafe fn deallocate(&mut self, ptr: *mut u8) {
+		if ptr.is_null() {
+			return
+		}
+
+		let header = ptr as usize - HEADER_SIZE;
+		let order = *(header as *const usize);
+		if order >= N_ORDERS {
+			// The header is corrupted or the allocation wasn't made by this allocator.
+			wasm32::unreachable()
+		}
+
+		*(header as *mut usize) = self.free_lists[order];
+		self.free_lists[order] = header;
+	}
+
+	/// Bumps `size` bytes from the end of the heap, growing the memory if needed.
+	unsafe fn bump(&mut self, size: usize) -> Option<usize> {
+		if self.bumper == 0 {
+			// Keep allocations 8 byte aligned.
+			self.bumper = (&__heap_base as *const u8 as usize + 7) & !7;
+		}
+
+		let start = self.bumper;
+		let end = start.checked_add(size)?;
+		// The memory can only be too large to be addressed at the 4 GiB limit, in which case it
+		// can't grow anymore, but holds any allocation that fits into the address space.
+		let memory_end = wasm32::memory_size(0).checked_mul(PAGE_SIZE).unwrap_or(usize::MAX);
+		if end > memory_end {
+			let pages = (end - memory_end + PAGE_SIZE - 1) / PAGE_SIZE;
+			if wasm32::memory_grow(0, pages) == usize::MAX {
+				return None
+			}
+		}
+
+		self.bumper = end;
+		Some(start)
+	}
+}
+
+/// The allocator of the runtime's heap.
+struct RuntimeAllocator(UnsafeCell<Heap>);
+
+// The runtime is single threaded.
+unsafe impl Sync for RuntimeAllocator {}
+
+#[cfg_attr(not(feature = "disable_allocator"), global_allocator)]
+static ALLOCATOR: RuntimeAllocator =
+	RuntimeAllocator(UnsafeCell::new(Heap { bumper: 0, free_lists: [0; N_ORDERS] }));
+
+unsafe impl GlobalAlloc for RuntimeAllocator {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		let heap = &mut *self.0.get();
+		if layout.align() <= HEADER_SIZE {
+			return heap.allocate(layout.size())
+		}
+
+		// Allocations are only aligned to the header size. Allocate enough to align the returned
+		// pointer, and store the pointer to the allocation right before it.
+		let size = match layout.size().checked_add(layout.align()) {
+			Some(size) => size,
+			None => return core::ptr::null_mut(),
+		};
+		let ptr = heap.allocate(size);
+		if ptr.is_null() {
+			return ptr
+		}
+		let aligned = (ptr as usize + HEADER_SIZE + layout.align() - 1) & !(layout.align() - 1);
+		*((aligned - HEADER_SIZE) as *mut usize) = ptr as usize;
+		aligned as *mut u8
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		let heap = &mut *self.0.get();
+		if layout.align() <= HEADER_SIZE {
+			heap.deallocate(ptr)
+		} else {
+			heap.deallocate(*((ptr as usize - HEADER_SIZE) as *const usize) as *mut u8)
+		}
+	}
+}
+
+/// Allocates `size` bytes on behalf of the host.
+///
+/// Exported as [`RUNTIME_ALLOC_EXPORT`](sp_wasm_interface::RUNTIME_ALLOC_EXPORT).
+#[no_mangle]
+pub extern "C" fn __runtime_alloc(size: u32) -> u32 {
+	unsafe { (*ALLOCATOR.0.get()).allocate(size as usize) as u32 }
+}
+
+/// Frees the allocation at `ptr` on behalf of the host.
+///
+/// Exported as [`RUNTIME_FREE_EXPORT`](sp_wasm_interface::RUNTIME_FREE_EXPORT).
+#[no_mangle]
+pub extern "C" fn __runtime_free(ptr: u32) {
+	unsafe { (*ALLOCATOR.0.get()).deallocate(ptr as *mut u8) }
+}
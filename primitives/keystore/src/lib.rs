@@ -23,6 +23,8 @@ use crate::vrf::{VRFSignature, VRFTranscriptData};
 use async_trait::async_trait;
 use futures::{executor::block_on, future::join_all};
 use sp_core::{
+	bandersnatch,
+	bls::{bls377, bls381},
 	crypto::{CryptoTypePublicPair, KeyTypeId},
 	ecdsa, ed25519, sr25519,
 };
@@ -207,6 +209,85 @@ pub trait CryptoStore: Send + Sync {
 		public: &ecdsa::Public,
 		msg: &[u8; 32],
 	) -> Result<Option<ecdsa::Signature>, Error>;
+
+	/// Returns all BLS12-377 public keys for the given key type.
+	///
+	/// Keystores without BLS12-377 support don't override this and return no keys.
+	async fn bls377_public_keys(&self, _id: KeyTypeId) -> Vec<bls377::Public> {
+		Vec::new()
+	}
+
+	/// Generate a new BLS12-377 key pair for the given key type and an optional seed.
+	///
+	/// If the given seed is `Some(_)`, the key pair will only be stored in memory.
+	///
+	/// Returns the public key of the generated key pair.
+	/// Returns [`Error::KeyNotSupported`] by default.
+	async fn bls377_generate_new(
+		&self,
+		id: KeyTypeId,
+		_seed: Option<&str>,
+	) -> Result<bls377::Public, Error> {
+		Err(Error::KeyNotSupported(id))
+	}
+
+	/// Returns all BLS12-381 public keys for the given key type.
+	///
+	/// Keystores without BLS12-381 support don't override this and return no keys.
+	async fn bls381_public_keys(&self, _id: KeyTypeId) -> Vec<bls381::Public> {
+		Vec::new()
+	}
+
+	/// Generate a new BLS12-381 key pair for the given key type and an optional seed.
+	///
+	/// If the given seed is `Some(_)`, the key pair will only be stored in memory.
+	///
+	/// Returns the public key of the generated key pair.
+	/// Returns [`Error::KeyNotSupported`] by default.
+	async fn bls381_generate_new(
+		&self,
+		id: KeyTypeId,
+		_seed: Option<&str>,
+	) -> Result<bls381::Public, Error> {
+		Err(Error::KeyNotSupported(id))
+	}
+
+	/// Returns all bandersnatch public keys for the given key type.
+	///
+	/// Keystores without bandersnatch support don't override this and return no keys.
+	async fn bandersnatch_public_keys(&self, _id: KeyTypeId) -> Vec<bandersnatch::Public> {
+		Vec::new()
+	}
+
+	/// Generate a new bandersnatch key pair for the given key type and an optional seed.
+	///
+	/// If the given seed is `Some(_)`, the key pair will only be stored in memory.
+	///
+	/// Returns the public key of the generated key pair.
+	/// Returns [`Error::KeyNotSupported`] by default.
+	async fn bandersnatch_generate_new(
+		&self,
+		id: KeyTypeId,
+		_seed: Option<&str>,
+	) -> Result<bandersnatch::Public, Error> {
+		Err(Error::KeyNotSupported(id))
+	}
+
+	/// Generate a bandersnatch VRF signature for the given data.
+	///
+	/// Receives [`KeyTypeId`] and a [`bandersnatch::Public`] key to be able to map
+	/// them to a private key that exists in the keystore.
+	///
+	/// Returns `None` if the given `key_type` and `public` combination doesn't
+	/// exist in the keystore or an `Err` when something failed. Returns `None` by default.
+	async fn bandersnatch_vrf_sign(
+		&self,
+		_key_type: KeyTypeId,
+		_public: &bandersnatch::Public,
+		_data: &bandersnatch::vrf::VrfSignData,
+	) -> Result<Option<bandersnatch::vrf::VrfSignature>, Error> {
+		Ok(None)
+	}
 }
 
 /// Sync version of the CryptoStore
@@ -382,6 +463,85 @@ pub trait SyncCryptoStore: CryptoStore + Send + Sync {
 		public: &ecdsa::Public,
 		msg: &[u8; 32],
 	) -> Result<Option<ecdsa::Signature>, Error>;
+
+	/// Returns all BLS12-377 public keys for the given key type.
+	///
+	/// Keystores without BLS12-377 support don't override this and return no keys.
+	fn bls377_public_keys(&self, _id: KeyTypeId) -> Vec<bls377::Public> {
+		Vec::new()
+	}
+
+	/// Generate a new BLS12-377 key pair for the given key type and an optional seed.
+	///
+	/// If the given seed is `Some(_)`, the key pair will only be stored in memory.
+	///
+	/// Returns the public key of the generated key pair.
+	/// Returns [`Error::KeyNotSupported`] by default.
+	fn bls377_generate_new(
+		&self,
+		id: KeyTypeId,
+		_seed: Option<&str>,
+	) -> Result<bls377::Public, Error> {
+		Err(Error::KeyNotSupported(id))
+	}
+
+	/// Returns all BLS12-381 public keys for the given key type.
+	///
+	/// Keystores without BLS12-381 support don't override this and return no keys.
+	fn bls381_public_keys(&self, _id: KeyTypeId) -> Vec<bls381::Public> {
+		Vec::new()
+	}
+
+	/// Generate a new BLS12-381 key pair for the given key type and an optional seed.
+	///
+	/// If the given seed is `Some(_)`, the key pair will only be stored in memory.
+	///
+	/// Returns the public key of the generated key pair.
+	/// Returns [`Error::KeyNotSupported`] by default.
+	fn bls381_generate_new(
+		&self,
+		id: KeyTypeId,
+		_seed: Option<&str>,
+	) -> Result<bls381::Public, Error> {
+		Err(Error::KeyNotSupported(id))
+	}
+
+	/// Returns all bandersnatch public keys for the given key type.
+	///
+	/// Keystores without bandersnatch support don't override this and return no keys.
+	fn bandersnatch_public_keys(&self, _id: KeyTypeId) -> Vec<bandersnatch::Public> {
+		Vec::new()
+	}
+
+	/// Generate a new bandersnatch key pair for the given key type and an optional seed.
+	///
+	/// If the given seed is `Some(_)`, the key pair will only be stored in memory.
+	///
+	/// Returns the public key of the generated key pair.
+	/// Returns [`Error::KeyNotSupported`] by default.
+	fn bandersnatch_generate_new(
+		&self,
+		id: KeyTypeId,
+		_seed: Option<&str>,
+	) -> Result<bandersnatch::Public, Error> {
+		Err(Error::KeyNotSupported(id))
+	}
+
+	/// Generate a bandersnatch VRF signature for the given data.
+	///
+	/// Receives [`KeyTypeId`] and a [`bandersnatch::Public`] key to be able to map
+	/// them to a private key that exists in the keystore.
+	///
+	/// Returns `None` if the given `key_type` and `public` combination doesn't
+	/// exist in the keystore or an `Err` when something failed. Returns `None` by default.
+	fn bandersnatch_vrf_sign(
+		&self,
+		_key_type: KeyTypeId,
+		_public: &bandersnatch::Public,
+		_data: &bandersnatch::vrf::VrfSignData,
+	) -> Result<Option<bandersnatch::vrf::VrfSignature>, Error> {
+		Ok(None)
+	}
 }
 
 /// A pointer to a keystore.
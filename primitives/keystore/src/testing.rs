@@ -18,6 +18,8 @@
 //! Types that should only be used for testing!
 
 use sp_core::{
+	bandersnatch,
+	bls::{bls377, bls381},
 	crypto::{ByteArray, CryptoTypePublicPair, KeyTypeId, Pair},
 	ecdsa, ed25519, sr25519,
 };
@@ -69,6 +71,47 @@ impl KeyStore {
 				.map(|s| ecdsa::Pair::from_string(s, None).expect("`ecdsa` seed slice is valid"))
 		})
 	}
+
+	fn key_pair<T: Pair>(&self, id: KeyTypeId, pub_key: &T::Public) -> Option<T> {
+		self.keys.read().get(&id).and_then(|inner| {
+			inner
+				.get(pub_key.as_slice())
+				.map(|s| T::from_string(s, None).expect("seed slice is valid"))
+		})
+	}
+
+	fn public_keys<T: Pair>(&self, id: KeyTypeId) -> Vec<T::Public> {
+		self.keys
+			.read()
+			.get(&id)
+			.map(|keys| {
+				keys.values()
+					.map(|s| T::from_string(s, None).expect("seed slice is valid"))
+					.map(|p| p.public())
+					.collect()
+			})
+			.unwrap_or_default()
+	}
+
+	fn generate_new<T: Pair>(&self, id: KeyTypeId, seed: Option<&str>) -> Result<T::Public, Error> {
+		let (pair, suri) = match seed {
+			Some(seed) => {
+				let pair = T::from_string(seed, None)
+					.map_err(|_| Error::ValidationError("Generates a pair.".to_owned()))?;
+				(pair, seed.to_owned())
+			},
+			None => {
+				let (pair, phrase, _) = T::generate_with_phrase(None);
+				(pair, phrase)
+			},
+		};
+		self.keys
+			.write()
+			.entry(id)
+			.or_default()
+			.insert(pair.public().to_raw_vec(), suri);
+		Ok(pair.public())
+	}
 }
 
 #[async_trait]
@@ -155,6 +198,51 @@ impl CryptoStore for KeyStore {
 	) -> Result<Option<ecdsa::Signature>, Error> {
 		SyncCryptoStore::ecdsa_sign_prehashed(self, id, public, msg)
 	}
+
+	async fn bls377_public_keys(&self, id: KeyTypeId) -> Vec<bls377::Public> {
+		SyncCryptoStore::bls377_public_keys(self, id)
+	}
+
+	async fn bls377_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<bls377::Public, Error> {
+		SyncCryptoStore::bls377_generate_new(self, id, seed)
+	}
+
+	async fn bls381_public_keys(&self, id: KeyTypeId) -> Vec<bls381::Public> {
+		SyncCryptoStore::bls381_public_keys(self, id)
+	}
+
+	async fn bls381_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<bls381::Public, Error> {
+		SyncCryptoStore::bls381_generate_new(self, id, seed)
+	}
+
+	async fn bandersnatch_public_keys(&self, id: KeyTypeId) -> Vec<bandersnatch::Public> {
+		SyncCryptoStore::bandersnatch_public_keys(self, id)
+	}
+
+	async fn bandersnatch_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<bandersnatch::Public, Error> {
+		SyncCryptoStore::bandersnatch_generate_new(self, id, seed)
+	}
+
+	async fn bandersnatch_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &bandersnatch::Public,
+		data: &bandersnatch::vrf::VrfSignData,
+	) -> Result<Option<bandersnatch::vrf::VrfSignature>, Error> {
+		SyncCryptoStore::bandersnatch_vrf_sign(self, key_type, public, data)
+	}
 }
 
 impl SyncCryptoStore for KeyStore {
@@ -167,6 +255,9 @@ impl SyncCryptoStore for KeyStore {
 					v.push(CryptoTypePublicPair(sr25519::CRYPTO_ID, k.clone()));
 					v.push(CryptoTypePublicPair(ed25519::CRYPTO_ID, k.clone()));
 					v.push(CryptoTypePublicPair(ecdsa::CRYPTO_ID, k.clone()));
+					v.push(CryptoTypePublicPair(bls377::CRYPTO_ID, k.clone()));
+					v.push(CryptoTypePublicPair(bls381::CRYPTO_ID, k.clone()));
+					v.push(CryptoTypePublicPair(bandersnatch::CRYPTO_ID, k.clone()));
 					v
 				}))
 			})
@@ -357,6 +448,30 @@ impl SyncCryptoStore for KeyStore {
 
 				key_pair.map(|k| k.sign(msg).encode()).map(Ok).transpose()
 			},
+			bls377::CRYPTO_ID => {
+				let key_pair = self.key_pair::<bls377::Pair>(
+					id,
+					&bls377::Public::from_slice(key.1.as_slice()).unwrap(),
+				);
+
+				key_pair.map(|k| k.sign(msg).encode()).map(Ok).transpose()
+			},
+			bls381::CRYPTO_ID => {
+				let key_pair = self.key_pair::<bls381::Pair>(
+					id,
+					&bls381::Public::from_slice(key.1.as_slice()).unwrap(),
+				);
+
+				key_pair.map(|k| k.sign(msg).encode()).map(Ok).transpose()
+			},
+			bandersnatch::CRYPTO_ID => {
+				let key_pair = self.key_pair::<bandersnatch::Pair>(
+					id,
+					&bandersnatch::Public::from_slice(key.1.as_slice()).unwrap(),
+				);
+
+				key_pair.map(|k| k.sign(msg).encode()).map(Ok).transpose()
+			},
 			_ => Err(Error::KeyNotSupported(id)),
 		}
 	}
@@ -384,6 +499,52 @@ impl SyncCryptoStore for KeyStore {
 		let pair = self.ecdsa_key_pair(id, public);
 		pair.map(|k| k.sign_prehashed(msg)).map(Ok).transpose()
 	}
+
+	fn bls377_public_keys(&self, id: KeyTypeId) -> Vec<bls377::Public> {
+		self.public_keys::<bls377::Pair>(id)
+	}
+
+	fn bls377_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<bls377::Public, Error> {
+		self.generate_new::<bls377::Pair>(id, seed)
+	}
+
+	fn bls381_public_keys(&self, id: KeyTypeId) -> Vec<bls381::Public> {
+		self.public_keys::<bls381::Pair>(id)
+	}
+
+	fn bls381_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<bls381::Public, Error> {
+		self.generate_new::<bls381::Pair>(id, seed)
+	}
+
+	fn bandersnatch_public_keys(&self, id: KeyTypeId) -> Vec<bandersnatch::Public> {
+		self.public_keys::<bandersnatch::Pair>(id)
+	}
+
+	fn bandersnatch_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<bandersnatch::Public, Error> {
+		self.generate_new::<bandersnatch::Pair>(id, seed)
+	}
+
+	fn bandersnatch_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &bandersnatch::Public,
+		data: &bandersnatch::vrf::VrfSignData,
+	) -> Result<Option<bandersnatch::vrf::VrfSignature>, Error> {
+		let pair = self.key_pair::<bandersnatch::Pair>(key_type, public);
+		Ok(pair.map(|k| k.vrf_sign(data)))
+	}
 }
 
 impl Into<SyncCryptoStorePtr> for KeyStore {
@@ -488,4 +649,65 @@ mod tests {
 			SyncCryptoStore::ecdsa_sign_prehashed(&store, ECDSA, &pair.public(), &msg).unwrap();
 		assert!(res.is_some());
 	}
+
+	#[test]
+	fn bls_sign_with_works() {
+		use codec::Decode;
+
+		let store = KeyStore::new();
+
+		let public = SyncCryptoStore::bls381_generate_new(&store, sp_core::testing::BLS381, None)
+			.expect("Generates key");
+		assert_eq!(
+			SyncCryptoStore::bls381_public_keys(&store, sp_core::testing::BLS381),
+			vec![public]
+		);
+
+		let msg = b"this should be a normal unhashed message";
+		let sig = SyncCryptoStore::sign_with(&store, sp_core::testing::BLS381, &public.into(), msg)
+			.unwrap()
+			.map(|s| bls381::Signature::decode(&mut &s[..]).unwrap())
+			.expect("Signs with the generated key");
+		assert!(bls381::Pair::verify(&sig, msg, &public));
+	}
+
+	#[test]
+	fn bandersnatch_vrf_sign_works() {
+		use bandersnatch::vrf::{VrfInput, VrfSignData};
+
+		let store = KeyStore::new();
+
+		let suri = "//Alice";
+		let pair = bandersnatch::Pair::from_string(suri, None).unwrap();
+		let data = VrfSignData::new(VrfInput::new(b"domain", b"input"), b"extra".to_vec());
+
+		// no key in key store
+		let res = SyncCryptoStore::bandersnatch_vrf_sign(
+			&store,
+			sp_core::testing::BANDERSNATCH,
+			&pair.public(),
+			&data,
+		)
+		.unwrap();
+		assert!(res.is_none());
+
+		// insert key, sign again
+		SyncCryptoStore::insert_unknown(
+			&store,
+			sp_core::testing::BANDERSNATCH,
+			suri,
+			pair.public().as_ref(),
+		)
+		.unwrap();
+
+		let signature = SyncCryptoStore::bandersnatch_vrf_sign(
+			&store,
+			sp_core::testing::BANDERSNATCH,
+			&pair.public(),
+			&data,
+		)
+		.unwrap()
+		.expect("Signs with the inserted key");
+		assert!(pair.public().vrf_verify(&data, &signature));
+	}
 }
@@ -373,7 +373,7 @@ fn generate_host_function_implementation(
 			|mut caller: #crate_::sp_wasm_interface::wasmtime::Caller<T::State>, #(#ffi_args_prototype),*|
 				-> std::result::Result<#ffi_return_ty, #crate_::sp_wasm_interface::wasmtime::Trap>
 			{
-				T::with_function_context(caller, move |__function_context__| {
+				T::call_host_function(#name, move || T::with_function_context(caller, move |__function_context__| {
 					let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
 						#struct_name::call(
 							__function_context__,
@@ -394,7 +394,7 @@ fn generate_host_function_implementation(
 							return Err(#crate_::sp_wasm_interface::wasmtime::Trap::new(message));
 						}
 					}
-				})
+				}))
 			}
 		)?;
 	};
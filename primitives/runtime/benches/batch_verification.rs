@@ -0,0 +1,89 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compares verifying the signatures of the extrinsics of a block one after another with
+//! verifying them in a `SignatureBatching` session, as `Executive::execute_block` does with the
+//! `batch-signature-verification` feature.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use sp_core::{ed25519, sr25519, Pair};
+use sp_runtime::{
+	traits::{IdentifyAccount, Verify},
+	AccountId32, MultiSignature, MultiSigner, SignatureBatching,
+};
+
+fn signed_messages(count: usize, sr25519: bool) -> Vec<(Vec<u8>, MultiSignature, AccountId32)> {
+	(0..count)
+		.map(|i| {
+			let seed = [i as u8; 32];
+			let message = format!("extrinsic {}", i).into_bytes();
+			let (signature, signer): (MultiSignature, MultiSigner) = if sr25519 {
+				let pair = sr25519::Pair::from_seed(&seed);
+				(pair.sign(&message).into(), pair.public().into())
+			} else {
+				let pair = ed25519::Pair::from_seed(&seed);
+				(pair.sign(&message).into(), pair.public().into())
+			};
+			(message, signature, signer.into_account())
+		})
+		.collect()
+}
+
+fn bench_batch_verification(c: &mut Criterion) {
+	let mut group = c.benchmark_group("verify signatures");
+
+	for (name, sr25519) in [("sr25519", true), ("ed25519", false)] {
+		for count in [100, 1000] {
+			let signed = signed_messages(count, sr25519);
+
+			group.bench_with_input(
+				BenchmarkId::new(format!("{} sequential", name), count),
+				&signed,
+				|b, signed| {
+					sp_io::TestExternalities::default().execute_with(|| {
+						b.iter(|| {
+							for (message, signature, signer) in signed {
+								assert!(signature.verify(black_box(&message[..]), signer));
+							}
+						})
+					})
+				},
+			);
+
+			group.bench_with_input(
+				BenchmarkId::new(format!("{} batched", name), count),
+				&signed,
+				|b, signed| {
+					sp_io::TestExternalities::default().execute_with(|| {
+						b.iter(|| {
+							let batching = SignatureBatching::start();
+							for (message, signature, signer) in signed {
+								assert!(signature.batch_verify(black_box(&message[..]), signer));
+							}
+							assert!(batching.verify());
+						})
+					})
+				},
+			);
+		}
+	}
+
+	group.finish();
+}
+
+criterion_group!(benches, bench_batch_verification);
+criterion_main!(benches);
@@ -151,7 +151,9 @@ where
 			Some((signed, signature, extra)) => {
 				let signed = lookup.lookup(signed)?;
 				let raw_payload = SignedPayload::new(self.function, extra)?;
-				if !raw_payload.using_encoded(|payload| signature.verify(payload, &signed)) {
+				// Inside of a `SignatureBatching` session, the signature is only verified when the
+				// session is finished.
+				if !raw_payload.using_encoded(|payload| signature.batch_verify(payload, &signed)) {
 					return Err(InvalidTransaction::BadProof.into())
 				}
 
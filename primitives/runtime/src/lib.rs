@@ -423,6 +423,22 @@ impl Verify for MultiSignature {
 			},
 		}
 	}
+
+	fn batch_verify<L: Lazy<[u8]>>(&self, msg: L, signer: &AccountId32) -> bool {
+		match (self, signer) {
+			(Self::Ed25519(ref sig), who) => match ed25519::Public::from_slice(who.as_ref()) {
+				Ok(signer) => sig.batch_verify(msg, &signer),
+				Err(()) => false,
+			},
+			(Self::Sr25519(ref sig), who) => match sr25519::Public::from_slice(who.as_ref()) {
+				Ok(signer) => sig.batch_verify(msg, &signer),
+				Err(()) => false,
+			},
+			// The account only contains the hash of the public key, which has to be recovered
+			// from the signature to verify it.
+			(Self::Ecdsa(_), _) => self.verify(msg, signer),
+		}
+	}
 }
 
 /// Signature verify that can work with any known signature types..
@@ -927,6 +943,10 @@ pub fn print(print: impl traits::Printable) {
 
 /// Batching session.
 ///
+/// While the session is active, signatures verified through [`traits::Verify::batch_verify`], like
+/// the signatures of signed extrinsics, are only verified in parallel when calling
+/// [`Self::verify`].
+///
 /// To be used in runtime only. Outside of runtime, just construct
 /// `BatchVerifier` directly.
 #[must_use = "`verify()` needs to be called to finish batch signature verification!"]
@@ -1066,6 +1086,43 @@ mod tests {
 		assert!(multi_sig.verify(msg, &multi_signer.into_account()));
 	}
 
+	#[test]
+	fn multi_signature_batch_verify_is_deferred_in_batching_session() {
+		let mut ext = sp_state_machine::BasicExternalities::default();
+		ext.register_extension(sp_core::traits::TaskExecutorExt::new(
+			sp_core::testing::TaskExecutor::new(),
+		));
+
+		ext.execute_with(|| {
+			let sr25519_pair = sr25519::Pair::from_seed(&[1; 32]);
+			let ed25519_pair = ed25519::Pair::from_seed(&[2; 32]);
+			let signed = [
+				(
+					MultiSignature::from(sr25519_pair.sign(b"valid")),
+					MultiSigner::from(sr25519_pair.public()).into_account(),
+				),
+				(
+					MultiSignature::from(ed25519_pair.sign(b"valid")),
+					MultiSigner::from(ed25519_pair.public()).into_account(),
+				),
+			];
+
+			for (signature, signer) in &signed {
+				// Without a batching session, the signature is verified immediately.
+				assert!(signature.batch_verify(&b"valid"[..], signer));
+				assert!(!signature.batch_verify(&b"invalid"[..], signer));
+
+				let batching = SignatureBatching::start();
+				assert!(signature.batch_verify(&b"valid"[..], signer));
+				assert!(batching.verify());
+
+				let batching = SignatureBatching::start();
+				assert!(signature.batch_verify(&b"invalid"[..], signer));
+				assert!(!batching.verify());
+			}
+		});
+	}
+
 	#[test]
 	#[should_panic(expected = "Signature verification has not been called")]
 	fn batching_still_finishes_when_not_called_directly() {
@@ -106,6 +106,22 @@ pub trait Verify {
 		msg: L,
 		signer: &<Self::Signer as IdentifyAccount>::AccountId,
 	) -> bool;
+
+	/// Verify a signature as part of the current [`SignatureBatching`](crate::SignatureBatching)
+	/// session.
+	///
+	/// While a batching session is active, the signature may only be queued to be verified when
+	/// the session is finished. Otherwise, or if the signature type doesn't support batching, the
+	/// signature is verified immediately, like with [`Self::verify`].
+	///
+	/// Return `true` if signature is valid for the value or was queued for verification.
+	fn batch_verify<L: Lazy<[u8]>>(
+		&self,
+		msg: L,
+		signer: &<Self::Signer as IdentifyAccount>::AccountId,
+	) -> bool {
+		self.verify(msg, signer)
+	}
 }
 
 impl Verify for sp_core::ed25519::Signature {
@@ -114,6 +130,10 @@ impl Verify for sp_core::ed25519::Signature {
 	fn verify<L: Lazy<[u8]>>(&self, mut msg: L, signer: &sp_core::ed25519::Public) -> bool {
 		sp_io::crypto::ed25519_verify(self, msg.get(), signer)
 	}
+
+	fn batch_verify<L: Lazy<[u8]>>(&self, mut msg: L, signer: &sp_core::ed25519::Public) -> bool {
+		sp_io::crypto::ed25519_batch_verify(self, msg.get(), signer)
+	}
 }
 
 impl Verify for sp_core::sr25519::Signature {
@@ -122,6 +142,10 @@ impl Verify for sp_core::sr25519::Signature {
 	fn verify<L: Lazy<[u8]>>(&self, mut msg: L, signer: &sp_core::sr25519::Public) -> bool {
 		sp_io::crypto::sr25519_verify(self, msg.get(), signer)
 	}
+
+	fn batch_verify<L: Lazy<[u8]>>(&self, mut msg: L, signer: &sp_core::sr25519::Public) -> bool {
+		sp_io::crypto::sr25519_batch_verify(self, msg.get(), signer)
+	}
 }
 
 impl Verify for sp_core::ecdsa::Signature {
@@ -135,6 +159,10 @@ impl Verify for sp_core::ecdsa::Signature {
 			_ => false,
 		}
 	}
+
+	fn batch_verify<L: Lazy<[u8]>>(&self, mut msg: L, signer: &sp_core::ecdsa::Public) -> bool {
+		sp_io::crypto::ecdsa_batch_verify(self, msg.get(), signer)
+	}
 }
 
 /// Means of signature verification of an application key.
@@ -50,10 +50,16 @@ use sp_std::prelude::*;
 
 pub use sp_wasm_interface::{ReturnValue, Value};
 
-#[cfg(not(all(feature = "wasmer-sandbox", not(feature = "std"))))]
+#[cfg(not(all(
+	any(feature = "wasmer-sandbox", feature = "wasmtime-sandbox"),
+	not(feature = "std")
+)))]
 pub use self::embedded_executor as default_executor;
 pub use self::env::HostError;
-#[cfg(all(feature = "wasmer-sandbox", not(feature = "std")))]
+#[cfg(all(
+	any(feature = "wasmer-sandbox", feature = "wasmtime-sandbox"),
+	not(feature = "std")
+))]
 pub use self::host_executor as default_executor;
 
 /// The target used for logging.
@@ -303,6 +303,10 @@ impl Externalities for BasicExternalities {
 		self.overlay.commit_transaction().map_err(drop)
 	}
 
+	fn storage_transaction_changes(&self) -> u32 {
+		u32::try_from(self.overlay.transaction_changes()).unwrap_or(u32::MAX)
+	}
+
 	fn wipe(&mut self) {}
 
 	fn commit(&mut self) {}
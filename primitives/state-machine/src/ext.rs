@@ -674,6 +674,10 @@ where
 		self.overlay.commit_transaction().map_err(|_| ())
 	}
 
+	fn storage_transaction_changes(&self) -> u32 {
+		u32::try_from(self.overlay.transaction_changes()).unwrap_or(u32::MAX)
+	}
+
 	fn wipe(&mut self) {
 		for _ in 0..self.overlay.transaction_depth() {
 			self.overlay.rollback_transaction().expect(BENCHMARKING_FN);
@@ -283,6 +283,14 @@ impl<K: Ord + Hash + Clone, V> OverlayedMap<K, V> {
 		self.dirty_keys.len()
 	}
 
+	/// Returns the number of keys changed in the current transaction.
+	///
+	/// This includes the changes of nested transactions that were committed into it. A value of
+	/// zero is returned when no transaction is open.
+	pub fn transaction_changes(&self) -> usize {
+		self.dirty_keys.last().map_or(0, |keys| keys.len())
+	}
+
 	/// Call this before transfering control to the runtime.
 	///
 	/// This protects all existing transactions from being removed by the runtime.
@@ -812,6 +820,33 @@ mod test {
 		let _ = changeset.drain_commited();
 	}
 
+	#[test]
+	fn transaction_changes_counts_keys_of_current_tx() {
+		let mut changeset = OverlayedChangeSet::default();
+		changeset.set(b"key0".to_vec(), Some(b"val0".to_vec()), None);
+		assert_eq!(changeset.transaction_changes(), 0);
+
+		changeset.start_transaction();
+		changeset.set(b"key0".to_vec(), Some(b"val1".to_vec()), None);
+		changeset.set(b"key0".to_vec(), Some(b"val2".to_vec()), None);
+		assert_eq!(changeset.transaction_changes(), 1);
+
+		changeset.start_transaction();
+		changeset.set(b"key1".to_vec(), Some(b"val1".to_vec()), None);
+		changeset.set(b"key2".to_vec(), None, None);
+		assert_eq!(changeset.transaction_changes(), 2);
+		changeset.commit_transaction().unwrap();
+		assert_eq!(changeset.transaction_changes(), 3);
+
+		changeset.start_transaction();
+		changeset.set(b"key3".to_vec(), Some(b"val3".to_vec()), None);
+		changeset.rollback_transaction().unwrap();
+		assert_eq!(changeset.transaction_changes(), 3);
+
+		changeset.rollback_transaction().unwrap();
+		assert_eq!(changeset.transaction_changes(), 0);
+	}
+
 	#[test]
 	fn runtime_cannot_close_client_tx() {
 		let mut changeset = OverlayedChangeSet::default();
@@ -344,6 +344,18 @@ impl OverlayedChanges {
 		self.top.transaction_depth()
 	}
 
+	/// Returns the number of keys changed in the current transaction.
+	///
+	/// This sums up the changes to the top changeset and to all child changesets. A rollback of
+	/// the current transaction discards exactly these changes.
+	pub fn transaction_changes(&self) -> usize {
+		self.children
+			.values()
+			.fold(self.top.transaction_changes(), |acc, (changeset, _)| {
+				acc.saturating_add(changeset.transaction_changes())
+			})
+	}
+
 	/// Start a new nested transaction.
 	///
 	/// This allows to either commit or roll back all changes that where made while this
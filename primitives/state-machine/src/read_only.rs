@@ -195,6 +195,10 @@ where
 		unimplemented!("Transactions are not supported by ReadOnlyExternalities");
 	}
 
+	fn storage_transaction_changes(&self) -> u32 {
+		unimplemented!("Transactions are not supported by ReadOnlyExternalities");
+	}
+
 	fn wipe(&mut self) {}
 
 	fn commit(&mut self) {}
@@ -287,6 +287,29 @@ impl PartialEq for dyn Function {
 	}
 }
 
+/// The function exported by runtimes that manage their heap themselves, which the host calls to
+/// allocate memory in the runtime, e.g. for the buffers it returns from host functions.
+///
+/// Takes the size of the allocation and returns the pointer to the allocated memory, or `0` if the
+/// allocation failed. Runtimes that don't export it use the allocator of the host.
+///
+/// The allocator ABI is selected by the presence of this export instead of the runtime version.
+/// This is safe because:
+/// - The host needs the allocator to pass the call data of every call, including `Core_version`, so
+///   it has to be known before the runtime version can be read through a call. The
+///   `runtime_version` custom section is optional and can't be relied on.
+/// - Like the runtime version, the exports are part of the code, so every node selects the same ABI
+///   for the same code.
+/// - A runtime only exports this function when built with the `runtime_allocator` feature of
+///   `sp-io`, which removes its global allocator that calls into the host. The two ABIs can't be
+///   mixed by accident: the export must come with [`RUNTIME_FREE_EXPORT`] and both must have the
+///   expected signatures, otherwise the instantiation fails.
+pub const RUNTIME_ALLOC_EXPORT: &str = "__runtime_alloc";
+
+/// The function exported by runtimes that manage their heap themselves, which the host calls to
+/// free memory allocated through [`RUNTIME_ALLOC_EXPORT`].
+pub const RUNTIME_FREE_EXPORT: &str = "__runtime_free";
+
 /// Context used by `Function` to interact with the allocator and the memory of the wasm instance.
 pub trait FunctionContext {
 	/// Read memory from `address` into a vector.
@@ -403,6 +426,15 @@ if_wasmtime_is_enabled! {
 			callback: impl FnOnce(&mut dyn FunctionContext) -> R,
 		) -> R;
 
+		/// Calls the host function `fn_name` through the given `call`.
+		///
+		/// Every call of a statically registered host function goes through this, so it can be
+		/// overridden to e.g. profile the host functions.
+		fn call_host_function<R>(fn_name: &'static str, call: impl FnOnce() -> R) -> R {
+			let _ = fn_name;
+			call()
+		}
+
 		/// Registers a given host function with the WASM executor.
 		///
 		/// The function has to be statically callable, and all of its arguments
@@ -499,6 +531,10 @@ where
 					T::with_function_context(caller, callback)
 				}
 
+				fn call_host_function<R>(fn_name: &'static str, call: impl FnOnce() -> R) -> R {
+					T::call_host_function(fn_name, call)
+				}
+
 				fn register_static<Params, Results>(
 					&mut self,
 					fn_name: &str,
@@ -18,8 +18,8 @@
 use super::{writer, PalletCmd};
 use codec::{Decode, Encode};
 use frame_benchmarking::{
-	Analysis, BenchmarkBatch, BenchmarkBatchSplitResults, BenchmarkList, BenchmarkParameter,
-	BenchmarkResult, BenchmarkSelector,
+	Analysis, Benchmark, BenchmarkBatch, BenchmarkBatchSplitResults, BenchmarkList,
+	BenchmarkMetadata, BenchmarkParameter, BenchmarkResult, BenchmarkSelector,
 };
 use frame_support::traits::StorageInfo;
 use linked_hash_map::LinkedHashMap;
@@ -30,6 +30,7 @@ use sc_client_db::BenchmarkingState;
 use sc_executor::NativeElseWasmExecutor;
 use sc_service::{Configuration, NativeExecutionDispatch};
 use serde::Serialize;
+use sp_api::{RuntimeApiInfo, RuntimeVersion};
 use sp_core::offchain::{
 	testing::{TestOffchainExt, TestTransactionPoolExt},
 	OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
@@ -54,6 +55,45 @@ pub(crate) struct ComponentRange {
 	max: u32,
 }
 
+/// Maps (pallet, benchmark) to the PoV estimation modes set with `#[pov_mode]`, by `ALL` or
+/// `Pallet::Storage` name.
+pub(crate) type PovModesMap =
+	HashMap<(Vec<u8>, Vec<u8>), HashMap<String, frame_benchmarking::PovEstimationMode>>;
+
+/// A [`BenchmarkList`] as returned by version 1 of the `Benchmark` runtime API.
+#[derive(Decode)]
+struct BenchmarkListV1 {
+	pallet: Vec<u8>,
+	instance: Vec<u8>,
+	benchmarks: Vec<BenchmarkMetadataV1>,
+}
+
+/// A [`BenchmarkMetadata`] as returned by version 1 of the `Benchmark` runtime API, which did
+/// not have the PoV estimation modes yet.
+#[derive(Decode)]
+struct BenchmarkMetadataV1 {
+	name: Vec<u8>,
+	components: Vec<(BenchmarkParameter, u32, u32)>,
+}
+
+impl From<BenchmarkListV1> for BenchmarkList {
+	fn from(list: BenchmarkListV1) -> Self {
+		BenchmarkList {
+			pallet: list.pallet,
+			instance: list.instance,
+			benchmarks: list
+				.benchmarks
+				.into_iter()
+				.map(|benchmark| BenchmarkMetadata {
+					name: benchmark.name,
+					components: benchmark.components,
+					pov_modes: Vec::new(),
+				})
+				.collect(),
+		}
+	}
+}
+
 // This takes multiple benchmark batches and combines all the results where the pallet, instance,
 // and benchmark are the same.
 fn combine_batches(
@@ -188,8 +228,28 @@ impl PalletCmd {
 			extensions
 		};
 
-		// Get Benchmark List
+		// Get the version of the `Benchmark` runtime API, which determines the encoding of the
+		// benchmark metadata.
 		let state = &state_without_tracking;
+		let result = StateMachine::new(
+			state,
+			&mut changes,
+			&executor,
+			"Core_version",
+			&[],
+			extensions(),
+			&sp_state_machine::backend::BackendRuntimeCode::new(state).runtime_code()?,
+			sp_core::testing::TaskExecutor::new(),
+		)
+		.execute(strategy.into())
+		.map_err(|e| format!("Failed to get the runtime version: {}", e))?;
+
+		let benchmark_api_version = <RuntimeVersion as Decode>::decode(&mut &result[..])
+			.map_err(|e| format!("Failed to decode the runtime version: {:?}", e))?
+			.api_version(&<dyn Benchmark<BB> as RuntimeApiInfo>::ID)
+			.ok_or(ERROR_METADATA_NOT_FOUND)?;
+
+		// Get Benchmark List
 		let result = StateMachine::new(
 			state,
 			&mut changes,
@@ -203,9 +263,32 @@ impl PalletCmd {
 		.execute(strategy.into())
 		.map_err(|e| format!("{}: {}", ERROR_METADATA_NOT_FOUND, e))?;
 
-		let (list, storage_info) =
+		let (list, storage_info) = if benchmark_api_version >= 2 {
 			<(Vec<BenchmarkList>, Vec<StorageInfo>) as Decode>::decode(&mut &result[..])
-				.map_err(|e| format!("Failed to decode benchmark metadata: {:?}", e))?;
+				.map_err(|e| format!("Failed to decode benchmark metadata: {:?}", e))?
+		} else {
+			let (list, storage_info) =
+				<(Vec<BenchmarkListV1>, Vec<StorageInfo>) as Decode>::decode(&mut &result[..])
+					.map_err(|e| format!("Failed to decode benchmark metadata: {:?}", e))?;
+			(list.into_iter().map(Into::into).collect(), storage_info)
+		};
+
+		// Maps (pallet, benchmark) to the PoV estimation modes of its storage items.
+		let pov_modes: PovModesMap = list
+			.iter()
+			.flat_map(|item| {
+				item.benchmarks.iter().map(|benchmark| {
+					let modes = benchmark
+						.pov_modes
+						.iter()
+						.map(|(storage, mode)| {
+							(String::from_utf8_lossy(storage).into_owned(), *mode)
+						})
+						.collect();
+					((item.pallet.clone(), benchmark.name.clone()), modes)
+				})
+			})
+			.collect();
 
 		// Use the benchmark list and the user input to determine the set of benchmarks to run.
 		let mut benchmarks_to_run = Vec::new();
@@ -429,7 +512,7 @@ impl PalletCmd {
 		// Combine all of the benchmark results, so that benchmarks of the same pallet/function
 		// are together.
 		let batches = combine_batches(batches, batches_db);
-		self.output(&batches, &storage_info, &component_ranges)
+		self.output(&batches, &storage_info, &component_ranges, &pov_modes)
 	}
 
 	fn output(
@@ -437,16 +520,24 @@ impl PalletCmd {
 		batches: &[BenchmarkBatchSplitResults],
 		storage_info: &[StorageInfo],
 		component_ranges: &HashMap<(Vec<u8>, Vec<u8>), Vec<ComponentRange>>,
+		pov_modes: &PovModesMap,
 	) -> Result<()> {
 		// Jsonify the result and write it to a file or stdout if desired.
 		if !self.jsonify(&batches)? {
 			// Print the summary only if `jsonify` did not write to stdout.
-			self.print_summary(&batches, &storage_info)
+			self.print_summary(&batches, &storage_info, pov_modes)
 		}
 
 		// Create the weights.rs file.
 		if let Some(output_path) = &self.output {
-			writer::write_results(&batches, &storage_info, &component_ranges, output_path, self)?;
+			writer::write_results(
+				&batches,
+				&storage_info,
+				&component_ranges,
+				pov_modes,
+				output_path,
+				self,
+			)?;
 		}
 
 		Ok(())
@@ -484,7 +575,16 @@ impl PalletCmd {
 			})
 			.collect();
 
-		self.output(batches, &[], &component_ranges)
+		self.output(batches, &[], &component_ranges, &Default::default())
+	}
+
+	/// The parameters of the proof size estimation as given on the command line.
+	pub(crate) fn pov_estimation_params(&self) -> writer::PovEstimationParams {
+		writer::PovEstimationParams {
+			default_mode: self.default_pov_mode,
+			worst_case_map_values: self.worst_case_map_values,
+			additional_trie_layers: self.additional_trie_layers,
+		}
 	}
 
 	/// Jsonifies the passed batches and writes them to stdout or into a file.
@@ -507,7 +607,12 @@ impl PalletCmd {
 	}
 
 	/// Prints the results as human-readable summary without raw timing data.
-	fn print_summary(&self, batches: &[BenchmarkBatchSplitResults], storage_info: &[StorageInfo]) {
+	fn print_summary(
+		&self,
+		batches: &[BenchmarkBatchSplitResults],
+		storage_info: &[StorageInfo],
+		pov_modes: &PovModesMap,
+	) {
 		for batch in batches.iter() {
 			// Print benchmark metadata
 			println!(
@@ -527,7 +632,17 @@ impl PalletCmd {
 
 			if !self.no_storage_info {
 				let mut comments: Vec<String> = Default::default();
-				writer::add_storage_comments(&mut comments, &batch.db_results, storage_info);
+				let pov_modes = pov_modes
+					.get(&(batch.pallet.clone(), batch.benchmark.clone()))
+					.cloned()
+					.unwrap_or_default();
+				writer::add_storage_comments(
+					&mut comments,
+					&batch.db_results,
+					storage_info,
+					&pov_modes,
+					&self.pov_estimation_params(),
+				);
 				println!("Raw Storage Info\n========");
 				for comment in comments {
 					println!("{}", comment);
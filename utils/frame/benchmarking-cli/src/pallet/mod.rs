@@ -19,6 +19,7 @@ mod command;
 mod writer;
 
 use crate::shared::HostInfoParams;
+use frame_benchmarking::PovEstimationMode;
 use sc_cli::{
 	ExecutionStrategy, WasmExecutionMethod, WasmtimeInstantiationStrategy,
 	DEFAULT_WASMTIME_INSTANTIATION_STRATEGY, DEFAULT_WASM_EXECUTION_METHOD,
@@ -31,6 +32,15 @@ fn parse_pallet_name(pallet: &str) -> std::result::Result<String, String> {
 	Ok(pallet.replace("-", "_"))
 }
 
+fn parse_pov_mode(mode: &str) -> std::result::Result<PovEstimationMode, String> {
+	match mode {
+		"max-encoded-len" => Ok(PovEstimationMode::MaxEncodedLen),
+		"measured" => Ok(PovEstimationMode::Measured),
+		"ignored" => Ok(PovEstimationMode::Ignored),
+		_ => Err(format!("Unknown PoV estimation mode: {}", mode)),
+	}
+}
+
 /// Benchmark the extrinsic weight of FRAME Pallets.
 #[derive(Debug, clap::Parser)]
 pub struct PalletCmd {
@@ -121,6 +131,24 @@ pub struct PalletCmd {
 	#[arg(long)]
 	pub record_proof: bool,
 
+	/// How to estimate the proof size of the storage accesses of a benchmark, unless overridden
+	/// with `#[pov_mode]`:
+	/// * max-encoded-len (default)
+	/// * measured
+	/// * ignored
+	#[arg(long, default_value = "max-encoded-len", value_parser = parse_pov_mode)]
+	pub default_pov_mode: PovEstimationMode,
+
+	/// The number of values that are assumed to be in a storage map without `max_values`, used
+	/// to estimate the depth of its trie.
+	#[arg(long, default_value_t = 1_000_000)]
+	pub worst_case_map_values: u32,
+
+	/// The number of trie layers that are added on top of the estimated trie depth of every
+	/// storage item, to account for the other storage items in the trie.
+	#[arg(long, default_value_t = 2)]
+	pub additional_trie_layers: u8,
+
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub shared_params: sc_cli::SharedParams,
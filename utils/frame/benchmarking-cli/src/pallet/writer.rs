@@ -27,9 +27,14 @@ use inflector::Inflector;
 use itertools::Itertools;
 use serde::Serialize;
 
-use crate::{pallet::command::ComponentRange, shared::UnderscoreHelper, PalletCmd};
+use crate::{
+	pallet::command::{ComponentRange, PovModesMap},
+	shared::UnderscoreHelper,
+	PalletCmd,
+};
 use frame_benchmarking::{
 	Analysis, AnalysisChoice, BenchmarkBatchSplitResults, BenchmarkResult, BenchmarkSelector,
+	PovEstimationMode,
 };
 use frame_support::traits::StorageInfo;
 use sp_core::hexdisplay::HexDisplay;
@@ -67,6 +72,11 @@ struct BenchmarkData {
 	component_weight: Vec<ComponentSlope>,
 	component_reads: Vec<ComponentSlope>,
 	component_writes: Vec<ComponentSlope>,
+	#[serde(serialize_with = "string_serialize")]
+	base_recorded_proof_size: u128,
+	#[serde(serialize_with = "string_serialize")]
+	base_calculated_proof_size: u128,
+	component_calculated_proof_size: Vec<ComponentSlope>,
 	component_ranges: Vec<ComponentRange>,
 	comments: Vec<String>,
 	#[serde(serialize_with = "string_serialize")]
@@ -104,6 +114,95 @@ struct ComponentSlope {
 	error: u128,
 }
 
+/// The parameters of the proof size estimation.
+#[derive(Debug, Clone)]
+pub(crate) struct PovEstimationParams {
+	/// The mode of the storage items that have no `#[pov_mode]`.
+	pub(crate) default_mode: PovEstimationMode,
+	/// The number of values that are assumed to be in a map without `max_values`.
+	pub(crate) worst_case_map_values: u32,
+	/// The number of trie layers that are added to the depth of every storage item.
+	pub(crate) additional_trie_layers: u8,
+}
+
+impl PovEstimationParams {
+	/// The estimation mode of a storage item, or of an unknown one if `info` is `None`.
+	fn mode(
+		&self,
+		pov_modes: &HashMap<String, PovEstimationMode>,
+		info: Option<&StorageInfo>,
+	) -> PovEstimationMode {
+		info.and_then(|info| pov_modes.get(&storage_name(info)))
+			.or_else(|| pov_modes.get("ALL"))
+			.copied()
+			.unwrap_or(self.default_mode)
+	}
+
+	/// The worst case proof size of reading a single value of a storage item, or `None` if the
+	/// storage item is unbounded.
+	fn max_proof_size(&self, info: &StorageInfo) -> Option<u32> {
+		let max_size = info.max_size?;
+		let max_values = info.max_values.unwrap_or(self.worst_case_map_values);
+		// The depth of a trie with a radix of 16 that contains `max_values` leaves.
+		let mut depth = self.additional_trie_layers as u32;
+		let mut leaves = 1u64;
+		while leaves < max_values as u64 {
+			leaves *= 16;
+			depth += 1;
+		}
+		// Every trie layer contains up to 15 sibling hashes with their length prefix.
+		Some(max_size.saturating_add(depth.saturating_mul(15 * 33)))
+	}
+}
+
+// The `Pallet::Storage` name of a storage item, as used by `#[pov_mode]`.
+fn storage_name(info: &StorageInfo) -> String {
+	format!(
+		"{}::{}",
+		String::from_utf8_lossy(&info.pallet_name),
+		String::from_utf8_lossy(&info.storage_name)
+	)
+}
+
+// Calculates the proof size of a benchmark run from the storage items that it accessed.
+//
+// Every key is only included once in the proof, no matter how often it was read or written.
+// Storage items which are unknown, unbounded or estimated with `Measured` add the measured proof
+// size of the whole run, but only once.
+fn calculate_proof_size(
+	result: &BenchmarkResult,
+	storage_info: &[StorageInfo],
+	pov_modes: &HashMap<String, PovEstimationMode>,
+	params: &PovEstimationParams,
+) -> u32 {
+	let mut proof_size = 0u32;
+	let mut add_measured = false;
+
+	let mut accessed = HashSet::new();
+
+	for (key, _, _, whitelisted) in &result.keys {
+		if *whitelisted || !accessed.insert(key) {
+			continue
+		}
+		let prefix = &key[0..key.len().min(32)];
+		let info = storage_info.iter().find(|info| info.prefix == prefix);
+		match params.mode(pov_modes, info) {
+			PovEstimationMode::MaxEncodedLen =>
+				match info.and_then(|info| params.max_proof_size(info)) {
+					Some(size) => proof_size = proof_size.saturating_add(size),
+					None => add_measured = true,
+				},
+			PovEstimationMode::Measured => add_measured = true,
+			PovEstimationMode::Ignored => {},
+		}
+	}
+
+	if add_measured {
+		proof_size = proof_size.saturating_add(result.proof_size);
+	}
+	proof_size
+}
+
 // Small helper to create an `io::Error` from a string.
 fn io_error(s: &str) -> std::io::Error {
 	use std::io::{Error, ErrorKind};
@@ -122,6 +221,8 @@ fn map_results(
 	batches: &[BenchmarkBatchSplitResults],
 	storage_info: &[StorageInfo],
 	component_ranges: &HashMap<(Vec<u8>, Vec<u8>), Vec<ComponentRange>>,
+	pov_modes: &PovModesMap,
+	pov_params: &PovEstimationParams,
 	analysis_choice: &AnalysisChoice,
 ) -> Result<HashMap<(String, String), Vec<BenchmarkData>>, std::io::Error> {
 	// Skip if batches is empty.
@@ -139,8 +240,14 @@ fn map_results(
 
 		let pallet_string = String::from_utf8(batch.pallet.clone()).unwrap();
 		let instance_string = String::from_utf8(batch.instance.clone()).unwrap();
-		let benchmark_data =
-			get_benchmark_data(batch, storage_info, &component_ranges, analysis_choice);
+		let benchmark_data = get_benchmark_data(
+			batch,
+			storage_info,
+			&component_ranges,
+			pov_modes,
+			pov_params,
+			analysis_choice,
+		);
 		let pallet_benchmarks = all_benchmarks.entry((pallet_string, instance_string)).or_default();
 		pallet_benchmarks.push(benchmark_data);
 	}
@@ -164,6 +271,8 @@ fn get_benchmark_data(
 	storage_info: &[StorageInfo],
 	// Per extrinsic component ranges.
 	component_ranges: &HashMap<(Vec<u8>, Vec<u8>), Vec<ComponentRange>>,
+	pov_modes: &PovModesMap,
+	pov_params: &PovEstimationParams,
 	analysis_choice: &AnalysisChoice,
 ) -> BenchmarkData {
 	// You can use this to put any additional comments with the benchmarking output.
@@ -182,6 +291,25 @@ fn get_benchmark_data(
 		.expect("analysis function should return the number of reads for valid inputs");
 	let writes = analysis_function(&batch.db_results, BenchmarkSelector::Writes)
 		.expect("analysis function should return the number of writes for valid inputs");
+	let recorded_proof_size = analysis_function(&batch.db_results, BenchmarkSelector::ProofSize)
+		.expect("analysis function should return the proof size for valid inputs");
+
+	// Replace the measured proof size with the estimation from the storage info.
+	let pov_modes = pov_modes
+		.get(&(batch.pallet.clone(), batch.benchmark.clone()))
+		.cloned()
+		.unwrap_or_default();
+	let calculated_results = batch
+		.db_results
+		.iter()
+		.map(|result| BenchmarkResult {
+			proof_size: calculate_proof_size(result, storage_info, &pov_modes, pov_params),
+			..result.clone()
+		})
+		.collect::<Vec<_>>();
+	let calculated_proof_size =
+		analysis_function(&calculated_results, BenchmarkSelector::ProofSize)
+			.expect("analysis function should return the proof size for valid inputs");
 
 	// Analysis data may include components that are not used, this filters out anything whose value
 	// is zero.
@@ -189,6 +317,7 @@ fn get_benchmark_data(
 	let mut used_extrinsic_time = Vec::new();
 	let mut used_reads = Vec::new();
 	let mut used_writes = Vec::new();
+	let mut used_calculated_proof_size = Vec::new();
 
 	extrinsic_time
 		.slopes
@@ -229,6 +358,23 @@ fn get_benchmark_data(
 				used_writes.push(ComponentSlope { name: name.clone(), slope, error });
 			}
 		});
+	calculated_proof_size
+		.slopes
+		.into_iter()
+		.zip(calculated_proof_size.names.iter())
+		.zip(extract_errors(&calculated_proof_size.errors))
+		.for_each(|((slope, name), error)| {
+			if !slope.is_zero() {
+				if !used_components.contains(&name) {
+					used_components.push(name);
+				}
+				used_calculated_proof_size.push(ComponentSlope {
+					name: name.clone(),
+					slope,
+					error,
+				});
+			}
+		});
 
 	// This puts a marker on any component which is entirely unused in the weight formula.
 	let components = batch.time_results[0]
@@ -242,7 +388,7 @@ fn get_benchmark_data(
 		.collect::<Vec<_>>();
 
 	// We add additional comments showing which storage items were touched.
-	add_storage_comments(&mut comments, &batch.db_results, storage_info);
+	add_storage_comments(&mut comments, &batch.db_results, storage_info, &pov_modes, pov_params);
 	let component_ranges = component_ranges
 		.get(&(batch.pallet.clone(), batch.benchmark.clone()))
 		.map(|c| c.clone())
@@ -257,6 +403,9 @@ fn get_benchmark_data(
 		component_weight: used_extrinsic_time,
 		component_reads: used_reads,
 		component_writes: used_writes,
+		base_recorded_proof_size: recorded_proof_size.base,
+		base_calculated_proof_size: calculated_proof_size.base,
+		component_calculated_proof_size: used_calculated_proof_size,
 		component_ranges,
 		comments,
 		min_execution_time: extrinsic_time.minimum,
@@ -268,6 +417,7 @@ pub(crate) fn write_results(
 	batches: &[BenchmarkBatchSplitResults],
 	storage_info: &[StorageInfo],
 	component_ranges: &HashMap<(Vec<u8>, Vec<u8>), Vec<ComponentRange>>,
+	pov_modes: &PovModesMap,
 	path: &PathBuf,
 	cmd: &PalletCmd,
 ) -> Result<(), std::io::Error> {
@@ -317,7 +467,14 @@ pub(crate) fn write_results(
 	handlebars.register_escape_fn(|s| -> String { s.to_string() });
 
 	// Organize results by pallet into a JSON map
-	let all_results = map_results(batches, storage_info, component_ranges, &analysis_choice)?;
+	let all_results = map_results(
+		batches,
+		storage_info,
+		component_ranges,
+		pov_modes,
+		&cmd.pov_estimation_params(),
+		&analysis_choice,
+	)?;
 	let mut created_files = Vec::new();
 
 	for ((pallet, instance), results) in all_results.iter() {
@@ -372,6 +529,8 @@ pub(crate) fn add_storage_comments(
 	comments: &mut Vec<String>,
 	results: &[BenchmarkResult],
 	storage_info: &[StorageInfo],
+	pov_modes: &HashMap<String, PovEstimationMode>,
+	pov_params: &PovEstimationParams,
 ) {
 	let mut storage_info_map = storage_info
 		.iter()
@@ -427,6 +586,34 @@ pub(crate) fn add_storage_comments(
 						reads,
 						writes,
 					);
+					comments.push(comment);
+
+					// Explain how the proof size of the storage item is estimated.
+					let mode = pov_params.mode(pov_modes, Some(key_info));
+					let comment = match (mode, pov_params.max_proof_size(key_info)) {
+						(PovEstimationMode::MaxEncodedLen, Some(added)) => format!(
+							"Proof: {} {} (max_values: {:?}, max_size: {:?}, added: {}, mode: {:?})",
+							String::from_utf8_lossy(&key_info.pallet_name),
+							String::from_utf8_lossy(&key_info.storage_name),
+							key_info.max_values,
+							key_info.max_size,
+							added,
+							mode,
+						),
+						(mode, _) => format!(
+							"Proof: {} {} (max_values: {:?}, max_size: {:?}, mode: {:?})",
+							String::from_utf8_lossy(&key_info.pallet_name),
+							String::from_utf8_lossy(&key_info.storage_name),
+							key_info.max_values,
+							key_info.max_size,
+							// Unbounded storage items fall back to the measured proof size.
+							if mode == PovEstimationMode::Ignored {
+								mode
+							} else {
+								PovEstimationMode::Measured
+							},
+						),
+					};
 					comments.push(comment)
 				},
 				None => {
@@ -518,6 +705,50 @@ mod test {
 		}
 	}
 
+	fn test_pov_params() -> PovEstimationParams {
+		PovEstimationParams {
+			default_mode: PovEstimationMode::MaxEncodedLen,
+			worst_case_map_values: 1_000_000,
+			additional_trie_layers: 2,
+		}
+	}
+
+	fn test_storage_info(storage_name: &[u8], max_values: Option<u32>) -> StorageInfo {
+		StorageInfo {
+			pallet_name: b"Pallet".to_vec(),
+			storage_name: storage_name.to_vec(),
+			prefix: test_key(storage_name, &[]),
+			max_values,
+			max_size: Some(32),
+		}
+	}
+
+	// A key of a storage item with a 32 byte prefix.
+	fn test_key(storage_name: &[u8], suffix: &[u8]) -> Vec<u8> {
+		let mut key = [b"prefix_".to_vec(), storage_name.to_vec()].concat();
+		key.resize(32, 0);
+		key.extend_from_slice(suffix);
+		key
+	}
+
+	// Reads `n` values of `Map` twice and one value of `Value` and `Unbounded` each.
+	fn test_proof_data(n: u32, measured: u32) -> BenchmarkResult {
+		let mut keys = (0..n)
+			.map(|i| (test_key(b"Map", &i.to_le_bytes()), 2, 0, false))
+			.collect::<Vec<_>>();
+		keys.extend([
+			(test_key(b"Value", &[]), 1, 1, false),
+			(test_key(b"Whitelisted", &[]), 1, 0, true),
+			(test_key(b"Unbounded", &[]), 1, 0, false),
+		]);
+		BenchmarkResult {
+			components: vec![(BenchmarkParameter::n, n), (BenchmarkParameter::z, 0)],
+			proof_size: measured,
+			keys,
+			..Default::default()
+		}
+	}
+
 	fn check_data(benchmark: &BenchmarkData, component: &str, base: u128, slope: u128) {
 		assert_eq!(
 			benchmark.components,
@@ -555,6 +786,8 @@ mod test {
 			],
 			&[],
 			&Default::default(),
+			&Default::default(),
+			&test_pov_params(),
 			&AnalysisChoice::default(),
 		)
 		.unwrap();
@@ -577,4 +810,104 @@ mod test {
 		assert_eq!(second_pallet_benchmark.name, "first_benchmark");
 		check_data(second_pallet_benchmark, "c", 3, 4);
 	}
+
+	#[test]
+	fn max_proof_size_depends_on_trie_depth() {
+		let params = test_pov_params();
+		// A storage value has no siblings apart from the additional trie layers.
+		assert_eq!(
+			params.max_proof_size(&test_storage_info(b"Value", Some(1))),
+			Some(32 + 2 * 495)
+		);
+		assert_eq!(params.max_proof_size(&test_storage_info(b"Map", Some(16))), Some(32 + 3 * 495));
+		assert_eq!(params.max_proof_size(&test_storage_info(b"Map", Some(17))), Some(32 + 4 * 495));
+		// 1_000_000 values need 5 layers.
+		assert_eq!(params.max_proof_size(&test_storage_info(b"Map", None)), Some(32 + 7 * 495));
+		let unbounded = StorageInfo { max_size: None, ..test_storage_info(b"Unbounded", None) };
+		assert_eq!(params.max_proof_size(&unbounded), None);
+	}
+
+	#[test]
+	fn calculate_proof_size_respects_pov_modes() {
+		let storage_info = vec![
+			test_storage_info(b"Map", Some(16)),
+			test_storage_info(b"Value", Some(1)),
+			StorageInfo { max_size: None, ..test_storage_info(b"Unbounded", None) },
+		];
+		let params = test_pov_params();
+		let result = test_proof_data(3, 1000);
+		let map = 32 + 3 * 495;
+		let value = 32 + 2 * 495;
+
+		// The unbounded storage falls back to the measured proof size.
+		let modes = HashMap::new();
+		assert_eq!(
+			calculate_proof_size(&result, &storage_info, &modes, &params),
+			3 * map + value + 1000
+		);
+
+		let modes = [("Pallet::Unbounded".to_string(), PovEstimationMode::Ignored)].into();
+		assert_eq!(calculate_proof_size(&result, &storage_info, &modes, &params), 3 * map + value);
+
+		let modes = [
+			("ALL".to_string(), PovEstimationMode::Ignored),
+			("Pallet::Map".to_string(), PovEstimationMode::MaxEncodedLen),
+		]
+		.into();
+		assert_eq!(calculate_proof_size(&result, &storage_info, &modes, &params), 3 * map);
+
+		let modes = [("ALL".to_string(), PovEstimationMode::Measured)].into();
+		assert_eq!(calculate_proof_size(&result, &storage_info, &modes, &params), 1000);
+	}
+
+	#[test]
+	fn map_results_estimates_proof_size() {
+		let storage_info = vec![
+			test_storage_info(b"Map", Some(16)),
+			test_storage_info(b"Value", Some(1)),
+			StorageInfo { max_size: None, ..test_storage_info(b"Unbounded", None) },
+		];
+		let results = (0..5).map(|n| test_proof_data(n, 100 * n + 1000)).collect::<Vec<_>>();
+		let batch = BenchmarkBatchSplitResults {
+			pallet: b"pallet".to_vec(),
+			instance: b"instance".to_vec(),
+			benchmark: b"benchmark".to_vec(),
+			time_results: results.clone(),
+			db_results: results,
+		};
+		let pov_modes = [(
+			(b"pallet".to_vec(), b"benchmark".to_vec()),
+			[("Pallet::Unbounded".to_string(), PovEstimationMode::Ignored)].into(),
+		)]
+		.into();
+
+		let mapped_results = map_results(
+			&[batch],
+			&storage_info,
+			&Default::default(),
+			&pov_modes,
+			&test_pov_params(),
+			&AnalysisChoice::default(),
+		)
+		.unwrap();
+		let benchmark = &mapped_results[&("pallet".to_string(), "instance".to_string())][0];
+
+		assert_eq!(benchmark.base_recorded_proof_size, 1000);
+		assert_eq!(benchmark.base_calculated_proof_size, 32 + 2 * 495);
+		assert_eq!(
+			benchmark.component_calculated_proof_size,
+			vec![ComponentSlope { name: "n".to_string(), slope: 32 + 3 * 495, error: 0 }]
+		);
+		assert_eq!(
+			benchmark.comments,
+			vec![
+				"Storage: Pallet Value (r:1 w:1)",
+				"Proof: Pallet Value (max_values: Some(1), max_size: Some(32), added: 1022, mode: MaxEncodedLen)",
+				"Storage: Pallet Unbounded (r:1 w:0)",
+				"Proof: Pallet Unbounded (max_values: None, max_size: None, mode: Ignored)",
+				"Storage: Pallet Map (r:2 w:0)",
+				"Proof: Pallet Map (max_values: Some(16), max_size: Some(32), added: 1517, mode: MaxEncodedLen)",
+			],
+		);
+	}
 }
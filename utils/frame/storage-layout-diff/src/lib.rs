@@ -0,0 +1,535 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Detect changes of the storage layout of pallets between two runtimes.
+//!
+//! The storage layout of the pallets is read from the V14 metadata of the runtimes, see
+//! [`runtime_metadata`]. [`diff`] then reports, for every pallet of the new runtime, the changes
+//! that make the existing storage undecodable:
+//!
+//! - a changed storage prefix of the pallet,
+//! - a removed or renamed storage entry,
+//! - a storage value turned into a map or vice versa,
+//! - changed key hashers,
+//! - a changed encoding of the key or value type.
+//!
+//! Types are compared by their encoding, i.e. renaming a type or one of its fields is not a
+//! change, while e.g. reordering the variants of an enum is.
+//!
+//! Each of these changes requires a migration, hence a bump of the storage version of the pallet.
+//! The storage version is read from the `StorageVersion` constant, which the pallet exposes with
+//! `#[pallet::extra_constants]`. If one of the runtimes doesn't have it, the version is unknown
+//! and the bump can't be verified.
+
+use codec::Decode;
+use frame_metadata::{
+	v14::{PalletMetadata, RuntimeMetadataV14, StorageEntryType, StorageHasher},
+	RuntimeMetadata, RuntimeMetadataPrefixed, META_RESERVED,
+};
+use sc_executor::{WasmExecutionMethod, WasmExecutor};
+use scale_info::{form::PortableForm, Field, PortableRegistry, TypeDef};
+use sp_core::traits::{CodeExecutor, RuntimeCode, WrappedRuntimeCode};
+use std::{collections::BTreeMap, fmt};
+
+/// Read the V14 metadata of the runtime `code`, which may be compressed.
+pub fn runtime_metadata(code: &[u8]) -> Result<RuntimeMetadataV14, String> {
+	let mut executor = WasmExecutor::<sp_io::SubstrateHostFunctions>::new(
+		WasmExecutionMethod::Interpreted,
+		None,
+		1,
+		None,
+		1,
+	);
+	// The runtime may import host functions that this node doesn't know about, which are not
+	// needed to build the metadata.
+	executor.allow_missing_host_functions(true);
+
+	let runtime_code = RuntimeCode {
+		code_fetcher: &WrappedRuntimeCode(code.into()),
+		heap_pages: None,
+		hash: sp_core::blake2_256(code).to_vec(),
+	};
+	let mut ext = sp_state_machine::BasicExternalities::default();
+	let encoded = executor
+		.call(&mut ext, &runtime_code, "Metadata_metadata", &[], false)
+		.0
+		.map_err(|e| format!("failed to call `Metadata_metadata`: {}", e))?;
+
+	let bytes = <Vec<u8>>::decode(&mut &encoded[..])
+		.map_err(|e| format!("failed to decode the opaque metadata: {}", e))?;
+	let RuntimeMetadataPrefixed(magic, metadata) = RuntimeMetadataPrefixed::decode(&mut &bytes[..])
+		.map_err(|e| format!("failed to decode the metadata: {}", e))?;
+
+	if magic != META_RESERVED {
+		return Err(format!("invalid metadata magic number: {:#x}", magic))
+	}
+
+	match metadata {
+		RuntimeMetadata::V14(metadata) => Ok(metadata),
+		_ => Err(format!("unsupported metadata version {}, expected 14", metadata.version())),
+	}
+}
+
+/// A change of the storage layout of a pallet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+	/// The storage prefix of the pallet changed.
+	Prefix { old: String, new: String },
+	/// The storage entry was removed or renamed.
+	Removed { entry: String },
+	/// The storage entry changed from a value to a map or vice versa.
+	Kind { entry: String },
+	/// The key hashers of the storage map changed.
+	Hashers { entry: String, old: Vec<StorageHasher>, new: Vec<StorageHasher> },
+	/// The encoding of the key of the storage map changed.
+	Key { entry: String, old: String, new: String },
+	/// The encoding of the stored value changed.
+	Value { entry: String, old: String, new: String },
+}
+
+impl fmt::Display for Change {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Change::Prefix { old, new } =>
+				write!(f, "storage prefix changed from `{}` to `{}`", old, new),
+			Change::Removed { entry } => write!(f, "`{}` was removed or renamed", entry),
+			Change::Kind { entry } =>
+				write!(f, "`{}` changed from a storage value to a map or vice versa", entry),
+			Change::Hashers { entry, old, new } =>
+				write!(f, "hashers of `{}` changed from {:?} to {:?}", entry, old, new),
+			Change::Key { entry, old, new } =>
+				write!(f, "key of `{}` changed from `{}` to `{}`", entry, old, new),
+			Change::Value { entry, old, new } =>
+				write!(f, "value of `{}` changed from `{}` to `{}`", entry, old, new),
+		}
+	}
+}
+
+/// The storage layout changes of a pallet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PalletDiff {
+	/// The name of the pallet.
+	pub pallet: String,
+	/// The storage version of the pallet in the old runtime, if known.
+	pub old_version: Option<u16>,
+	/// The storage version of the pallet in the new runtime, if known.
+	pub new_version: Option<u16>,
+	/// The changes of the storage layout, never empty.
+	pub changes: Vec<Change>,
+}
+
+impl PalletDiff {
+	/// Whether the storage version was bumped along with the changes.
+	///
+	/// Always `false` if one of the versions is unknown.
+	pub fn is_version_bumped(&self) -> bool {
+		match (self.old_version, self.new_version) {
+			(Some(old), Some(new)) => new > old,
+			_ => false,
+		}
+	}
+}
+
+fn fmt_version(version: Option<u16>) -> String {
+	version.map_or_else(|| "unknown".into(), |v| v.to_string())
+}
+
+impl fmt::Display for PalletDiff {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"{} (storage version {} -> {}{}):",
+			self.pallet,
+			fmt_version(self.old_version),
+			fmt_version(self.new_version),
+			if self.is_version_bumped() { "" } else { ", NOT BUMPED" },
+		)?;
+		for change in &self.changes {
+			write!(f, "\n  - {}", change)?;
+		}
+		Ok(())
+	}
+}
+
+/// Diff the storage layout of the pallets of two runtimes.
+///
+/// Pallets are matched by name. Pallets which are only in one of the runtimes are ignored, as
+/// well as new storage entries. Only the pallets with changes are returned.
+pub fn diff(old: &RuntimeMetadataV14, new: &RuntimeMetadataV14) -> Vec<PalletDiff> {
+	new.pallets
+		.iter()
+		.filter_map(|new_pallet| {
+			let old_pallet = old.pallets.iter().find(|p| p.name == new_pallet.name)?;
+			let changes = diff_pallet(&old.types, old_pallet, &new.types, new_pallet);
+
+			(!changes.is_empty()).then(|| PalletDiff {
+				pallet: new_pallet.name.clone(),
+				old_version: storage_version(old_pallet),
+				new_version: storage_version(new_pallet),
+				changes,
+			})
+		})
+		.collect()
+}
+
+/// The storage version exposed by the pallet, if any.
+fn storage_version(pallet: &PalletMetadata<PortableForm>) -> Option<u16> {
+	pallet
+		.constants
+		.iter()
+		.find(|c| c.name == "StorageVersion")
+		.and_then(|c| u16::decode(&mut &c.value[..]).ok())
+}
+
+fn diff_pallet(
+	old_types: &PortableRegistry,
+	old: &PalletMetadata<PortableForm>,
+	new_types: &PortableRegistry,
+	new: &PalletMetadata<PortableForm>,
+) -> Vec<Change> {
+	let (old_storage, new_storage) = match (&old.storage, &new.storage) {
+		(Some(old), Some(new)) => (old, new),
+		(Some(old), None) =>
+			return old
+				.entries
+				.iter()
+				.map(|entry| Change::Removed { entry: entry.name.clone() })
+				.collect(),
+		(None, _) => return vec![],
+	};
+
+	let mut changes = vec![];
+	if old_storage.prefix != new_storage.prefix {
+		changes.push(Change::Prefix {
+			old: old_storage.prefix.clone(),
+			new: new_storage.prefix.clone(),
+		});
+	}
+
+	let new_entries =
+		new_storage.entries.iter().map(|e| (&e.name, &e.ty)).collect::<BTreeMap<_, _>>();
+
+	for old_entry in &old_storage.entries {
+		let entry = old_entry.name.clone();
+		let new_ty = match new_entries.get(&old_entry.name) {
+			Some(ty) => ty,
+			None => {
+				changes.push(Change::Removed { entry });
+				continue
+			},
+		};
+
+		match (&old_entry.ty, new_ty) {
+			(StorageEntryType::Plain(old), StorageEntryType::Plain(new)) => {
+				let (old, new) = (encoding(old_types, old.id()), encoding(new_types, new.id()));
+				if old != new {
+					changes.push(Change::Value { entry, old, new });
+				}
+			},
+			(
+				StorageEntryType::Map { hashers: old_hashers, key: old_key, value: old_value },
+				StorageEntryType::Map { hashers: new_hashers, key: new_key, value: new_value },
+			) => {
+				if old_hashers != new_hashers {
+					changes.push(Change::Hashers {
+						entry: entry.clone(),
+						old: old_hashers.clone(),
+						new: new_hashers.clone(),
+					});
+				}
+				let (old, new) =
+					(encoding(old_types, old_key.id()), encoding(new_types, new_key.id()));
+				if old != new {
+					changes.push(Change::Key { entry: entry.clone(), old, new });
+				}
+				let (old, new) =
+					(encoding(old_types, old_value.id()), encoding(new_types, new_value.id()));
+				if old != new {
+					changes.push(Change::Value { entry, old, new });
+				}
+			},
+			_ => changes.push(Change::Kind { entry }),
+		}
+	}
+
+	changes
+}
+
+/// Describe the encoding of the type `id`, independently of the registry and the names of the
+/// types and fields.
+fn encoding(registry: &PortableRegistry, id: u32) -> String {
+	describe(registry, id, &mut vec![])
+}
+
+fn describe(registry: &PortableRegistry, id: u32, stack: &mut Vec<u32>) -> String {
+	let ty = match registry.resolve(id) {
+		Some(ty) => ty,
+		None => return format!("<unknown type {}>", id),
+	};
+	// Refer to recursive types by their depth, which doesn't depend on the registry.
+	if let Some(depth) = stack.iter().position(|i| *i == id) {
+		return format!("<recursive {}>", depth)
+	}
+
+	stack.push(id);
+	let description = match ty.type_def() {
+		TypeDef::Composite(composite) =>
+			format!("{{{}}}", describe_fields(registry, composite.fields(), stack)),
+		TypeDef::Variant(variant) => variant
+			.variants()
+			.iter()
+			.map(|v| format!("#{}({})", v.index(), describe_fields(registry, v.fields(), stack)))
+			.collect::<Vec<_>>()
+			.join(" | "),
+		TypeDef::Sequence(sequence) =>
+			format!("Vec<{}>", describe(registry, sequence.type_param().id(), stack)),
+		TypeDef::Array(array) =>
+			format!("[{}; {}]", describe(registry, array.type_param().id(), stack), array.len()),
+		TypeDef::Tuple(tuple) => format!(
+			"({})",
+			tuple
+				.fields()
+				.iter()
+				.map(|ty| describe(registry, ty.id(), stack))
+				.collect::<Vec<_>>()
+				.join(", ")
+		),
+		TypeDef::Primitive(primitive) => format!("{:?}", primitive).to_lowercase(),
+		TypeDef::Compact(compact) =>
+			format!("Compact<{}>", describe(registry, compact.type_param().id(), stack)),
+		TypeDef::BitSequence(bits) => format!(
+			"BitVec<{}, {}>",
+			describe(registry, bits.bit_store_type().id(), stack),
+			describe(registry, bits.bit_order_type().id(), stack),
+		),
+	};
+	stack.pop();
+
+	description
+}
+
+fn describe_fields(
+	registry: &PortableRegistry,
+	fields: &[Field<PortableForm>],
+	stack: &mut Vec<u32>,
+) -> String {
+	fields
+		.iter()
+		.map(|field| describe(registry, field.ty().id(), stack))
+		.collect::<Vec<_>>()
+		.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_metadata::v14::{
+		ExtrinsicMetadata, PalletConstantMetadata, PalletStorageMetadata, StorageEntryMetadata,
+		StorageEntryModifier,
+	};
+	use scale_info::{meta_type, MetaType, TypeInfo};
+
+	#[derive(TypeInfo)]
+	#[allow(dead_code)]
+	struct Account {
+		free: u64,
+		reserved: u64,
+	}
+
+	#[derive(TypeInfo)]
+	#[allow(dead_code)]
+	struct RenamedAccount {
+		balance: u64,
+		locked: u64,
+	}
+
+	#[derive(TypeInfo)]
+	#[allow(dead_code)]
+	struct AccountWithNonce {
+		free: u64,
+		reserved: u64,
+		nonce: u32,
+	}
+
+	fn entry(
+		name: &'static str,
+		ty: StorageEntryType<scale_info::form::MetaForm>,
+	) -> StorageEntryMetadata {
+		StorageEntryMetadata {
+			name,
+			modifier: StorageEntryModifier::Optional,
+			ty,
+			default: vec![0],
+			docs: vec![],
+		}
+	}
+
+	fn map<K: TypeInfo + 'static, V: TypeInfo + 'static>(
+		hasher: StorageHasher,
+	) -> StorageEntryType<scale_info::form::MetaForm> {
+		StorageEntryType::Map {
+			hashers: vec![hasher],
+			key: meta_type::<K>(),
+			value: meta_type::<V>(),
+		}
+	}
+
+	fn metadata(
+		prefix: &'static str,
+		version: Option<u16>,
+		entries: Vec<StorageEntryMetadata>,
+	) -> RuntimeMetadataV14 {
+		let constants = version
+			.map(|v| PalletConstantMetadata {
+				name: "StorageVersion",
+				ty: meta_type::<u16>(),
+				value: codec::Encode::encode(&v),
+				docs: vec![],
+			})
+			.into_iter()
+			.collect();
+		let pallet = PalletMetadata {
+			name: "Balances",
+			storage: Some(PalletStorageMetadata { prefix, entries }),
+			calls: None,
+			event: None,
+			constants,
+			error: None,
+			index: 0,
+		};
+		let extrinsic =
+			ExtrinsicMetadata { ty: meta_type::<()>(), version: 4, signed_extensions: vec![] };
+
+		RuntimeMetadataV14::new(vec![pallet], extrinsic, MetaType::new::<()>())
+	}
+
+	#[test]
+	fn renaming_types_and_adding_entries_is_not_a_change() {
+		let old = metadata(
+			"Balances",
+			None,
+			vec![
+				entry("TotalIssuance", StorageEntryType::Plain(meta_type::<u64>())),
+				entry("Account", map::<u64, Account>(StorageHasher::Blake2_128Concat)),
+			],
+		);
+		let new = metadata(
+			"Balances",
+			None,
+			vec![
+				entry("TotalIssuance", StorageEntryType::Plain(meta_type::<u64>())),
+				entry("Account", map::<u64, RenamedAccount>(StorageHasher::Blake2_128Concat)),
+				entry("Locks", map::<u64, Vec<u8>>(StorageHasher::Blake2_128Concat)),
+			],
+		);
+
+		assert_eq!(diff(&old, &new), vec![]);
+	}
+
+	#[test]
+	fn layout_changes_are_reported() {
+		let old = metadata(
+			"Balances",
+			Some(1),
+			vec![
+				entry("TotalIssuance", StorageEntryType::Plain(meta_type::<u64>())),
+				entry("Account", map::<u64, Account>(StorageHasher::Blake2_128Concat)),
+				entry("Locks", map::<u64, Vec<u8>>(StorageHasher::Twox64Concat)),
+				entry("Reserves", StorageEntryType::Plain(meta_type::<u64>())),
+				entry("Removed", StorageEntryType::Plain(meta_type::<u64>())),
+			],
+		);
+		let new = metadata(
+			"NewBalances",
+			Some(1),
+			vec![
+				entry("TotalIssuance", StorageEntryType::Plain(meta_type::<u128>())),
+				entry("Account", map::<u32, AccountWithNonce>(StorageHasher::Blake2_128Concat)),
+				entry("Locks", map::<u64, Vec<u8>>(StorageHasher::Blake2_128Concat)),
+				entry("Reserves", map::<u64, u64>(StorageHasher::Twox64Concat)),
+			],
+		);
+
+		let diffs = diff(&old, &new);
+		assert_eq!(diffs.len(), 1);
+		assert_eq!(diffs[0].pallet, "Balances");
+		assert!(!diffs[0].is_version_bumped());
+		assert_eq!(
+			diffs[0].changes,
+			vec![
+				Change::Prefix { old: "Balances".into(), new: "NewBalances".into() },
+				Change::Value {
+					entry: "TotalIssuance".into(),
+					old: "u64".into(),
+					new: "u128".into()
+				},
+				Change::Key { entry: "Account".into(), old: "u64".into(), new: "u32".into() },
+				Change::Value {
+					entry: "Account".into(),
+					old: "{u64, u64}".into(),
+					new: "{u64, u64, u32}".into(),
+				},
+				Change::Hashers {
+					entry: "Locks".into(),
+					old: vec![StorageHasher::Twox64Concat],
+					new: vec![StorageHasher::Blake2_128Concat],
+				},
+				Change::Kind { entry: "Reserves".into() },
+				Change::Removed { entry: "Removed".into() },
+			],
+		);
+	}
+
+	#[test]
+	fn storage_version_bump_is_detected() {
+		let old = metadata(
+			"Balances",
+			Some(1),
+			vec![entry("TotalIssuance", StorageEntryType::Plain(meta_type::<u64>()))],
+		);
+		let new = metadata(
+			"Balances",
+			Some(2),
+			vec![entry("TotalIssuance", StorageEntryType::Plain(meta_type::<u128>()))],
+		);
+
+		let diffs = diff(&old, &new);
+		assert_eq!((diffs[0].old_version, diffs[0].new_version), (Some(1), Some(2)));
+		assert!(diffs[0].is_version_bumped());
+	}
+
+	#[test]
+	fn unknown_storage_version_is_not_a_bump() {
+		let old = metadata(
+			"Balances",
+			None,
+			vec![entry("TotalIssuance", StorageEntryType::Plain(meta_type::<u64>()))],
+		);
+		let new = metadata(
+			"Balances",
+			Some(1),
+			vec![entry("TotalIssuance", StorageEntryType::Plain(meta_type::<u128>()))],
+		);
+
+		let diffs = diff(&old, &new);
+		assert_eq!((diffs[0].old_version, diffs[0].new_version), (None, Some(1)));
+		assert!(!diffs[0].is_version_bumped());
+		assert!(diffs[0]
+			.to_string()
+			.starts_with("Balances (storage version unknown -> 1, NOT BUMPED)"));
+	}
+}
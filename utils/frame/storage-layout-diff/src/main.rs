@@ -0,0 +1,70 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Check that the pallets of a runtime upgrade bump their storage version when they change their
+//! storage layout.
+
+use clap::Parser;
+use frame_storage_layout_diff::{diff, runtime_metadata};
+use std::{
+	path::{Path, PathBuf},
+	process::ExitCode,
+};
+
+#[derive(Debug, Parser)]
+struct Opt {
+	/// The wasm blob of the current runtime.
+	old: PathBuf,
+
+	/// The wasm blob of the upgraded runtime.
+	new: PathBuf,
+
+	/// Only report the pallets which didn't bump their storage version.
+	#[arg(long)]
+	only_unversioned: bool,
+}
+
+fn read_metadata(path: &Path) -> Result<frame_metadata::v14::RuntimeMetadataV14, String> {
+	let code =
+		std::fs::read(path).map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+	runtime_metadata(&code).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn main() -> Result<ExitCode, String> {
+	let Opt { old, new, only_unversioned } = Opt::parse();
+	let diffs = diff(&read_metadata(&old)?, &read_metadata(&new)?);
+
+	let mut unversioned = 0;
+	for pallet_diff in &diffs {
+		if !pallet_diff.is_version_bumped() {
+			unversioned += 1;
+		} else if only_unversioned {
+			continue
+		}
+		println!("{}", pallet_diff);
+	}
+
+	if unversioned > 0 {
+		eprintln!(
+			"{} pallet(s) changed their storage layout without bumping their storage version",
+			unversioned,
+		);
+		return Ok(ExitCode::FAILURE)
+	}
+
+	Ok(ExitCode::SUCCESS)
+}
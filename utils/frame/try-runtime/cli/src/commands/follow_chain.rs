@@ -25,7 +25,7 @@ use sc_executor::NativeExecutionDispatch;
 use sc_service::Configuration;
 use serde::{de::DeserializeOwned, Serialize};
 use sp_core::H256;
-use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor, Zero};
 use std::{fmt::Debug, str::FromStr};
 use substrate_rpc_client::{ws_client, ChainApi, FinalizedHeaders, Subscription, WsClient};
 
@@ -55,6 +55,11 @@ pub struct FollowChainCmd {
 	#[arg(long, default_value = "none")]
 	try_state: frame_try_runtime::TryStateSelect,
 
+	/// Only execute the try-state targets on one block out of this many, i.e. on the blocks
+	/// whose number is a multiple of it.
+	#[arg(long, default_value = "1", value_parser = clap::value_parser!(u32).range(1..))]
+	try_state_every: u32,
+
 	/// If present, a single connection to a node will be kept and reused for fetching blocks.
 	#[arg(long)]
 	keep_connection: bool,
@@ -155,12 +160,17 @@ where
 		let (state_ext, spec_state_version) =
 			maybe_state_ext.as_mut().expect("state_ext either existed or was just created");
 
+		let try_state = if (*number % command.try_state_every.into()).is_zero() {
+			command.try_state.clone()
+		} else {
+			frame_try_runtime::TryStateSelect::None
+		};
 		let (mut changes, encoded_result) = state_machine_call_with_proof::<Block, ExecDispatch>(
 			state_ext,
 			&executor,
 			execution,
 			"TryRuntime_execute_block",
-			(block, command.state_root_check, command.try_state.clone()).encode().as_ref(),
+			(block, command.state_root_check, try_state).encode().as_ref(),
 			full_extensions(),
 		)?;
 